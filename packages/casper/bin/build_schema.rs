@@ -1,17 +1,43 @@
-use veil_attestation::veil_attestation::VeilAttestationContractRef;
+//! Writes the contract schemas
+//!
+//! Usage:
+//!   ODRA_MODULE=<Module|all> cargo run --bin veil_attestation_build_schema -- [--out-dir <dir>]
+//!
+//! Each module's schemas go to `<out-dir>/<Module>/` (default: resources/);
+//! see `veil_attestation::schema` for the file names and module registry.
 
-fn main() {
-    let module = std::env::var("ODRA_MODULE").unwrap_or_default();
+use std::path::PathBuf;
+
+use veil_attestation::schema::{module_names, select_modules, DEFAULT_OUT_DIR};
+
+fn exit_with(message: &str) -> ! {
+    eprintln!("{}", message);
+    std::process::exit(1);
+}
 
-    match module.as_str() {
-        "VeilAttestation" => {
-            let legacy = VeilAttestationContractRef::schema();
-            let schema = VeilAttestationContractRef::casper_contract_schema();
-            odra_build::schema(legacy, schema);
+fn main() {
+    let mut out_dir = PathBuf::from(DEFAULT_OUT_DIR);
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--out-dir" => {
+                out_dir = args.next().map(PathBuf::from).unwrap_or_else(|| exit_with("--out-dir requires a value"))
+            }
+            other => exit_with(&format!("Unknown argument: {}", other)),
         }
-        _ => {
-            eprintln!("ODRA_MODULE not set or unknown. Use: VeilAttestation");
-            std::process::exit(1);
+    }
+
+    let selector = std::env::var("ODRA_MODULE").unwrap_or_default();
+    let modules = select_modules(&selector).unwrap_or_else(|| {
+        exit_with(&format!("ODRA_MODULE not set or unknown. Use one of: {}", module_names()))
+    });
+
+    for module in modules {
+        let written = module
+            .write(&out_dir)
+            .unwrap_or_else(|e| exit_with(&format!("{}: {}", out_dir.display(), e)));
+        for path in written {
+            println!("Wrote {}", path.display());
         }
     }
 }
@@ -0,0 +1,141 @@
+//! Veil Indexer
+//!
+//! Tails the VeilAttestation contract's events and persists them to SQLite
+//! so questions like "attestations created this week per chain" are one
+//! query away.
+//!
+//! Usage:
+//!   cargo run --bin veil_indexer --features livenet -- [options]
+//!
+//! Options:
+//!   --db <path>            SQLite database (default: veil-index.sqlite); see
+//!                          `veil_attestation::indexer` for the tables
+//!   --start-event <index>  First event to index on a fresh database (default: 0);
+//!                          ignored once the database has a cursor
+//!   --interval <secs>      Poll interval (default: 10)
+//!   --once                 Index pending events once and exit
+//!   --backfill             Rebuild from the contract's paginated export view
+//!
+//! The contract hash is read from deployed-addresses.json; see
+//! `veil_attestation::config` for the overrides. Log verbosity follows
+//! `RUST_LOG` (default: info). Ctrl-C finishes the current poll and exits.
+
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use log::{error, info};
+use odra::host::HostRefLoader;
+use odra::prelude::*;
+use veil_attestation::config::{ConfigKey, VeilConfig};
+use veil_attestation::indexer::{ContractEvents, IndexDb, Indexer, DEFAULT_DB_PATH};
+use veil_attestation::veil_attestation::VeilAttestation;
+
+/// Command-line options specific to this binary
+struct Options {
+    db: PathBuf,
+    start_event: u32,
+    interval: Duration,
+    once: bool,
+    backfill: bool,
+}
+
+impl Options {
+    fn from_args(args: &[String]) -> Self {
+        let mut options = Options {
+            db: PathBuf::from(DEFAULT_DB_PATH),
+            start_event: 0,
+            interval: Duration::from_secs(10),
+            once: false,
+            backfill: false,
+        };
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--db" => {
+                    options.db = args.next().map(PathBuf::from).unwrap_or_else(|| exit_with("--db requires a value"))
+                }
+                "--start-event" => options.start_event = parse_number(arg, args.next()),
+                "--interval" => options.interval = Duration::from_secs(parse_number(arg, args.next())),
+                "--once" => options.once = true,
+                "--backfill" => options.backfill = true,
+                other => exit_with(&format!("Unknown argument: {}", other)),
+            }
+        }
+        options
+    }
+}
+
+fn parse_number<T: FromStr>(flag: &str, value: Option<&String>) -> T {
+    value
+        .and_then(|value| value.parse().ok())
+        .unwrap_or_else(|| exit_with(&format!("{} requires a number", flag)))
+}
+
+fn exit_with(message: &str) -> ! {
+    eprintln!("Configuration error: {}", message);
+    std::process::exit(2);
+}
+
+fn main() {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
+    let (config, rest) = VeilConfig::from_args_or_exit(&[ConfigKey::CasperContract]);
+    let options = Options::from_args(&rest);
+    let db = IndexDb::open(&options.db).unwrap_or_else(|e| exit_with(&e.to_string()));
+
+    let shutdown = Arc::new(AtomicBool::new(false));
+    {
+        let shutdown = shutdown.clone();
+        ctrlc::set_handler(move || shutdown.store(true, Ordering::SeqCst))
+            .expect("Failed to install Ctrl-C handler");
+    }
+
+    let env = odra_casper_livenet_env::env();
+    let contract_hash = config.casper_contract_hash().expect("Contract hash checked above");
+    let contract_address = Address::from_str(&contract_hash).expect("Invalid contract address");
+    let contract = VeilAttestation::load(&env, contract_address);
+    info!("network={} contract={} db={}", config.network, contract_hash, options.db.display());
+
+    let source = ContractEvents { env: &env, contract: &contract };
+    let mut indexer = Indexer::new(source, db, &contract_hash, options.start_event)
+        .unwrap_or_else(|e| exit_with(&e.to_string()));
+
+    if options.backfill {
+        match indexer.backfill() {
+            Ok(summary) => info!("backfill indexed {:?}", summary),
+            Err(e) => {
+                error!("backfill failed: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    loop {
+        match indexer.poll_once() {
+            Ok(summary) if summary.created + summary.revoked + summary.other > 0 => info!(
+                "indexed created={} revoked={} other={} next_event_index={}",
+                summary.created, summary.revoked, summary.other, summary.next_event_index
+            ),
+            Ok(_) => {}
+            // The cursor was not advanced past the failing event; retry next poll
+            Err(e) => error!("poll failed, will retry: {}", e),
+        }
+
+        if options.once {
+            break;
+        }
+
+        let next_poll = Instant::now() + options.interval;
+        while Instant::now() < next_poll && !shutdown.load(Ordering::SeqCst) {
+            std::thread::sleep(Duration::from_millis(200));
+        }
+        if shutdown.load(Ordering::SeqCst) {
+            break;
+        }
+    }
+
+    info!("indexer stopped");
+}
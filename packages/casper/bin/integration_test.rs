@@ -6,81 +6,123 @@
 //! 3. (Manual) Submit to EVM
 //!
 //! Usage:
-//!   cargo run --bin veil_attestation_integration_test --features livenet
+//!   cargo run --bin veil_attestation_integration_test --features livenet -- [--network <name>] [--json]
+//!
+//! The contract hash is read from deployed-addresses.json; see `veil_attestation::config`
+//! for the available overrides. With `--json` (or `VEIL_OUTPUT=json`) prints a
+//! single document with per-step status and durations instead.
+//! `--deploy-timeout <secs>` bounds the wait for each deploy's execution
+//! result (default: 300); deploys are recorded in `.veil/run-<timestamp>.json`.
 
 use std::str::FromStr;
 use odra::prelude::*;
 use odra::host::HostRefLoader;
+use veil_attestation::config::{ConfigKey, VeilConfig};
+use veil_attestation::deploys::{DeployTracker, TrackerOptions};
+use veil_attestation::output::{run_steps, AttestOutput, OutputMode};
 use veil_attestation::veil_attestation::VeilAttestation;
 
 fn main() {
-    println!("=== Veil Identity Bridge Integration Test ===\n");
-
-    // Load the Casper livenet environment
-    let env = odra_casper_livenet_env::env();
-
-    // Get the deployed contract (use hash- prefix for Odra Address parsing)
-    let contract_hash = "hash-add1c58f0a9878d0050bc1178c369ef62623d86b15e2e657567f54dfe5a2fc67";
-    println!("Connecting to VeilAttestation at: {}", contract_hash);
-
-    // Parse contract address
-    let contract_address = Address::from_str(contract_hash)
-        .expect("Invalid contract address");
-
-    // Load existing contract
-    let mut contract = VeilAttestation::load(&env, contract_address);
-
-    // Test 1: Get signer address
-    println!("\n[Test 1] Getting signer address...");
-    let signer_addr = contract.get_signer_address();
-    println!("Signer address: 0x{}", hex::encode(signer_addr));
-    assert_eq!(
-        hex::encode(signer_addr).to_lowercase(),
-        "f39fd6e51aad88f6f4ce6ab8827279cfffb92266",
-        "Signer address mismatch!"
-    );
-    println!("✅ Signer address matches expected");
-
-    // Test 2: Create attestation
-    println!("\n[Test 2] Creating attestation...");
-    let target_chain = "base-sepolia".to_string();
-    // Use a test EVM address (the deployer wallet)
-    let target_address = "0xff528c955a9b70e5edfdd65643163f93d72cdc38".to_string();
-
-    env.set_gas(50_000_000_000u64); // 50 CSPR for the call
-    let (attestation_id, signature) = contract.create_attestation(
-        target_chain.clone(),
-        target_address.clone(),
-    );
-
-    println!("Attestation ID: 0x{}", hex::encode(attestation_id));
-    println!("Signature ({} bytes): 0x{}", signature.len(), hex::encode(&signature));
-    assert_eq!(signature.len(), 65, "Signature should be 65 bytes");
-    println!("✅ Attestation created successfully");
-
-    // Test 3: Verify attestation stored
-    println!("\n[Test 3] Verifying attestation stored...");
-    let attestation = contract.get_attestation(attestation_id)
-        .expect("Attestation should exist");
-
-    println!("  Target chain: {}", attestation.target_chain);
-    println!("  Target address: {}", attestation.target_address);
-    println!("  Tier: {:?}", attestation.tier);
-    println!("  Revoked: {}", attestation.revoked);
-    assert!(!attestation.revoked, "Attestation should not be revoked");
-    println!("✅ Attestation verified");
-
-    // Test 4: Get user tier
-    println!("\n[Test 4] Getting user tier...");
-    let caller = env.caller();
-    let tier = contract.get_user_tier(caller);
-    println!("User tier: {:?}", tier);
-    println!("✅ User tier retrieved");
-
-    // Summary for EVM submission
-    println!("\n=== EVM Submission Data ===");
-    println!("To verify on Base Sepolia VeilVerifier:");
-    println!("  Attestation ID: 0x{}", hex::encode(attestation_id));
-    println!("  Signature: 0x{}", hex::encode(&signature));
-    println!("\n=== All Tests Passed ===");
+    let (config, rest) = VeilConfig::from_args_or_exit(&[ConfigKey::CasperContract]);
+    let (out, rest) = OutputMode::from_args(rest);
+    let (tracker_options, rest) = TrackerOptions::from_args(rest).unwrap_or_else(|e| panic!("{}", e));
+    if let Some(arg) = rest.first() {
+        panic!("Unknown argument: {}", arg);
+    }
+
+    out.say("=== Veil Identity Bridge Integration Test ===\n");
+    let mut tracker = DeployTracker::livenet("integration", tracker_options);
+
+    let run = run_steps(out, "integration", |steps, exported| {
+        // Load the Casper livenet environment
+        let env = odra_casper_livenet_env::env();
+
+        // Get the deployed contract (hash- prefix for Odra Address parsing)
+        let contract_hash = config.casper_contract_hash().expect("Contract hash checked above");
+        out.say(format!("Network: {}", config.network));
+        out.say(format!("Connecting to VeilAttestation at: {}", contract_hash));
+
+        // Parse contract address
+        let contract_address = Address::from_str(&contract_hash)
+            .expect("Invalid contract address");
+
+        // Load existing contract
+        let mut contract = VeilAttestation::load(&env, contract_address);
+
+        // Test 1: Get signer address
+        steps.start(1, "Getting signer address");
+        let signer_addr = contract.get_signer_address();
+        out.say(format!("Signer address: 0x{}", hex::encode(signer_addr)));
+        assert_eq!(
+            hex::encode(signer_addr).to_lowercase(),
+            "f39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+            "Signer address mismatch!"
+        );
+        out.say("✅ Signer address matches expected");
+
+        // Test 2: Create attestation
+        steps.start(2, "Creating attestation");
+        let target_chain = "base-sepolia".to_string();
+        // Use a test EVM address (the deployer wallet)
+        let target_address = "0xff528c955a9b70e5edfdd65643163f93d72cdc38".to_string();
+
+        env.set_gas(50_000_000_000u64); // 50 CSPR for the call
+        let (attestation_id, signature) = tracker.track("create_attestation", || {
+            contract.create_attestation(
+                target_chain.clone(),
+                target_address.clone(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+        });
+
+        out.say(format!("Attestation ID: 0x{}", hex::encode(attestation_id)));
+        out.say(format!("Signature ({} bytes): 0x{}", signature.len(), hex::encode(&signature)));
+        assert_eq!(signature.len(), 65, "Signature should be 65 bytes");
+        out.say("✅ Attestation created successfully");
+
+        // Test 3: Verify attestation stored
+        steps.start(3, "Verifying attestation stored");
+        let attestation = contract.get_attestation(attestation_id)
+            .expect("Attestation should exist");
+
+        out.say(format!("  Target chain: {}", attestation.target_chain));
+        out.say(format!("  Target address: {}", attestation.target_address));
+        out.say(format!("  Tier: {:?}", attestation.tier));
+        out.say(format!("  Revoked: {}", attestation.revoked));
+        assert!(!attestation.revoked, "Attestation should not be revoked");
+        let (encoded, _) = contract.get_attestation_for_evm(attestation_id)
+            .expect("Attestation should export for EVM");
+        *exported = Some(AttestOutput {
+            attestation_id: format!("0x{}", hex::encode(attestation_id)),
+            signature: format!("0x{}", hex::encode(&signature)),
+            expires_at: attestation.expires_at,
+            encoded: format!("0x{}", hex::encode(&encoded)),
+        });
+        out.say("✅ Attestation verified");
+
+        // Test 4: Get user tier
+        steps.start(4, "Getting user tier");
+        let caller = env.caller();
+        let tier = contract.get_user_tier(caller);
+        out.say(format!("User tier: {:?}", tier));
+        out.say("✅ User tier retrieved");
+
+        // Summary for EVM submission
+        out.say("\n=== EVM Submission Data ===");
+        out.say("To verify on Base Sepolia VeilVerifier:");
+        out.say(format!("  Attestation ID: 0x{}", hex::encode(attestation_id)));
+        out.say(format!("  Signature: 0x{}", hex::encode(&signature)));
+    });
+
+    out.say(format!("Run artifact: {}", tracker.artifact_path().display()));
+    out.emit(&run);
+    if !run.passed {
+        std::process::exit(1);
+    }
+    out.say("\n=== All Tests Passed ===");
 }
@@ -1,12 +1,46 @@
 //! Livenet deployment script for Veil Attestation contract
 //!
 //! Usage:
-//!   cargo run --bin veil_attestation_livenet --features livenet
+//!   cargo run --bin veil_attestation_livenet --features livenet -- [options]
+//!
+//! Options:
+//!   --gas <CSPR>   Gas limit (default: 450)
+//!   --dry-run      Print the estimated cost and exit without deploying; needs
+//!                  the gas-metering local backend (`cargo odra build`, then
+//!                  `ODRA_BACKEND=casper`)
+//!   --json         Print only `{contract_hash, signer_address, gas_used}`
+//!                  (also `VEIL_OUTPUT=json`)
+//!   --deploy-timeout <secs>
+//!                  How long to wait for the deploy's execution result
+//!                  (default: 300); see `veil_attestation::deploys`
+//!   --mock-auction Also deploy a `MockAuction` and make it the stake source,
+//!                  so tiers can be exercised on localnet. Never use on a
+//!                  shared network: anyone can set any account's stake.
+//!
+//! Init config (applied at deployment; defaults otherwise):
+//!   --validity-secs <secs>     Validity of new attestations
+//!   --tier-thresholds <b,s,g,p>
+//!                              Bronze, silver, gold and platinum minimums in whole CSPR
+//!   --attestation-fee <motes>  Fee each attestation creation pays to the treasury
+//!   --require-checksum         Require EIP-55 checksummed target addresses
+//!   --allowlist                Only allow allowlisted target chains
+//!   --millis-timestamps        Sign payload timestamps in milliseconds rather
+//!                              than seconds; deploy VeilVerifier to match
+//!
+//! The deploy and its result are recorded in `.veil/run-<timestamp>.json`.
+//! Built with `no-onchain-signing`, only the signer's public key is deployed.
 
-use odra::host::Deployer;
+use odra::host::{Deployer, NoArgs};
 use odra::prelude::Addressable;
 
-use veil_attestation::veil_attestation::{VeilAttestation, VeilAttestationInitArgs};
+use veil_attestation::cli::{
+    cost_report, parse_gas_cspr, signer_init_args, CostEstimator, Output, SimulatedEstimator, CALL_GAS, DEPLOY_GAS,
+};
+use veil_attestation::deploys::{DeployTracker, TrackerOptions};
+use veil_attestation::mock_auction::MockAuction;
+use veil_attestation::output::{DeployOutput, OutputMode};
+use veil_attestation::types::{InitConfig, TierThresholds};
+use veil_attestation::veil_attestation::{VeilAttestation, TIME_UNIT_MILLIS};
 
 // Signer private key for attestation signing (secp256k1)
 // This should match the expected signer in the EVM verifier contract
@@ -18,33 +52,108 @@ const SIGNER_PRIVATE_KEY: [u8; 32] = [
 ];
 
 fn main() {
-    println!("=== Veil Attestation Livenet Deployment ===\n");
+    let (out, rest) = OutputMode::from_args(std::env::args().skip(1).collect());
+    let (tracker_options, rest) = TrackerOptions::from_args(rest).unwrap_or_else(|e| panic!("{}", e));
+    let mut gas = DEPLOY_GAS;
+    let mut dry_run = false;
+    let mut mock_auction = false;
+    let mut config = InitConfig::default();
+    let mut args = rest.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--gas" => {
+                let value = args.next().expect("--gas requires a value");
+                gas = parse_gas_cspr(&value).unwrap_or_else(|e| panic!("Invalid --gas: {}", e));
+            }
+            "--dry-run" => dry_run = true,
+            "--mock-auction" => mock_auction = true,
+            "--validity-secs" => {
+                let value = args.next().expect("--validity-secs requires a value");
+                config.validity_secs = Some(value.parse().unwrap_or_else(|e| panic!("Invalid --validity-secs: {}", e)));
+            }
+            "--tier-thresholds" => {
+                let value = args.next().expect("--tier-thresholds requires a value");
+                config.tier_thresholds = Some(parse_tier_thresholds(&value));
+            }
+            "--attestation-fee" => {
+                let value = args.next().expect("--attestation-fee requires a value");
+                let motes: u64 = value.parse().unwrap_or_else(|e| panic!("Invalid --attestation-fee: {}", e));
+                config.attestation_fee = Some(motes.into());
+            }
+            "--require-checksum" => config.require_checksum = Some(true),
+            "--allowlist" => config.allowlist_enabled = Some(true),
+            "--millis-timestamps" => config.time_unit = Some(TIME_UNIT_MILLIS),
+            other => panic!("Unknown argument: {}", other),
+        }
+    }
+
+    if dry_run {
+        let estimate = SimulatedEstimator::new(odra_test::env())
+            .estimate_deploy(SIGNER_PRIVATE_KEY)
+            .unwrap_or_else(|e| panic!("Cost estimation failed: {}", e));
+        let report = cost_report("deploy", estimate, gas);
+        out.emit(&report);
+        out.say(Output::CostEstimate(report));
+        return;
+    }
+
+    out.say("=== Veil Attestation Livenet Deployment ===\n");
+    let mut tracker = DeployTracker::livenet("deploy", tracker_options);
 
     // Load the Casper livenet environment
     let env = odra_casper_livenet_env::env();
 
     // Get deployer account (from secret key in Odra.toml)
     let deployer = env.caller();
-    println!("Deployer account: {:?}", deployer);
+    out.say(format!("Deployer account: {:?}", deployer));
 
     // Deploy VeilAttestation
-    println!("\nDeploying VeilAttestation...");
-    let init_args = VeilAttestationInitArgs {
-        admin: deployer.clone(),
-        signer_private_key: SIGNER_PRIVATE_KEY,
-    };
-
-    env.set_gas(450_000_000_000u64); // 450 CSPR gas
-    let contract = VeilAttestation::deploy(&env, init_args);
+    out.say("\nDeploying VeilAttestation...");
+    let config = (config != InitConfig::default()).then_some(config);
+    let init_args = signer_init_args(deployer.clone(), SIGNER_PRIVATE_KEY, config).unwrap_or_else(|e| panic!("{}", e));
+
+    let balance_before = env.balance_of(&deployer);
+    env.set_gas(gas);
+    let mut contract = tracker.track("deploy VeilAttestation", || VeilAttestation::deploy(&env, init_args));
+    let gas_used = balance_before.saturating_sub(env.balance_of(&deployer));
     let contract_address = contract.address();
-    println!("VeilAttestation deployed at: {:?}", contract_address);
+    out.say(format!("VeilAttestation deployed at: {:?}", contract_address));
 
     // Get signer address
     let signer_address = contract.get_signer_address();
-    println!("Signer Ethereum address: 0x{}", hex::encode(signer_address));
+    out.say(format!("Signer Ethereum address: 0x{}", hex::encode(signer_address)));
+
+    if mock_auction {
+        out.say("\nDeploying MockAuction...");
+        env.set_gas(gas);
+        let auction = tracker.track("deploy MockAuction", || MockAuction::deploy(&env, NoArgs));
+        env.set_gas(CALL_GAS);
+        tracker.track("set_stake_source", || contract.set_stake_source(Some(auction.address())));
+        out.say(format!("MockAuction deployed at: {:?} (stake source)", auction.address()));
+    }
 
     // Summary
-    println!("\n=== Deployment Complete ===");
-    println!("Contract: {:?}", contract_address);
-    println!("\nUpdate deployed-addresses.json with this address!");
+    out.say("\n=== Deployment Complete ===");
+    out.say(format!("Contract: {:?}", contract_address));
+    out.say(format!("Gas used: {} motes", gas_used));
+    out.say(format!("Run artifact: {}", tracker.artifact_path().display()));
+    out.say("\nUpdate deployed-addresses.json with this address!");
+
+    out.emit(&DeployOutput {
+        contract_hash: contract_address.to_string(),
+        signer_address: format!("0x{}", hex::encode(signer_address)),
+        gas_used: gas_used.to_string(),
+    });
+}
+
+/// Parse `bronze,silver,gold,platinum` whole-CSPR minimums
+fn parse_tier_thresholds(value: &str) -> TierThresholds {
+    let minimums: Vec<u64> = value
+        .split(',')
+        .map(|part| part.trim().parse().unwrap_or_else(|e| panic!("Invalid --tier-thresholds: {}", e)))
+        .collect();
+    match minimums[..] {
+        [bronze, silver, gold, platinum] => TierThresholds { bronze, silver, gold, platinum },
+        _ => panic!("--tier-thresholds takes four comma-separated values"),
+    }
 }
@@ -0,0 +1,59 @@
+//! Migrate a deployed Veil Attestation contract to the current storage layout
+//!
+//! Usage:
+//!   cargo run --bin veil_migrate --features livenet -- <contract-hash> [options]
+//!
+//! Options:
+//!   --json         Print only the migration report (also `VEIL_OUTPUT=json`)
+//!   --deploy-timeout <secs>
+//!                  How long to wait for the deploy's execution result
+//!                  (default: 300); see `veil_attestation::deploys`
+//!
+//! Sends `migrate_v1_to_v2` from the account in Odra.toml, which must be the
+//! contract admin, and checks `get_contract_version` afterwards. Contracts
+//! already at the current version are left alone. The deploy is recorded in
+//! `.veil/run-<timestamp>.json`.
+
+use std::str::FromStr;
+use odra::host::HostRefLoader;
+use odra::prelude::Address;
+
+use veil_attestation::cli::CALL_GAS;
+use veil_attestation::deploys::{DeployTracker, TrackerOptions};
+use veil_attestation::migrate::migrate;
+use veil_attestation::output::OutputMode;
+use veil_attestation::veil_attestation::VeilAttestation;
+
+fn main() {
+    let (out, rest) = OutputMode::from_args(std::env::args().skip(1).collect());
+    let (tracker_options, rest) = TrackerOptions::from_args(rest).unwrap_or_else(|e| panic!("{}", e));
+    let contract_hash = match rest.as_slice() {
+        [hash] => hash.clone(),
+        [] => panic!("Usage: veil_migrate <contract-hash> [--json] [--deploy-timeout <secs>]"),
+        [_, other, ..] => panic!("Unknown argument: {}", other),
+    };
+
+    out.say("=== Veil Attestation Storage Migration ===\n");
+    let mut tracker = DeployTracker::livenet("migrate", tracker_options);
+
+    let env = odra_casper_livenet_env::env();
+    out.say(format!("Admin account: {:?}", env.caller()));
+    let address = Address::from_str(&contract_hash).expect("Invalid contract address");
+    let mut contract = VeilAttestation::load(&env, address);
+
+    let report = migrate(&mut contract, &contract_hash, |contract| {
+        env.set_gas(CALL_GAS);
+        tracker.track("migrate_v1_to_v2", || contract.migrate_v1_to_v2());
+    });
+    out.say(format!("Run artifact: {}", tracker.artifact_path().display()));
+    match report {
+        Ok(report) => {
+            out.say(format!("\n{}", report));
+            out.emit(&report);
+        }
+        Err(e) => {
+            eprintln!("Migration failed: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,182 @@
+//! Veil Expiry Monitor
+//!
+//! Flags attestations that expire within a lookahead window (warning) or
+//! have already expired while still active (critical), reading the database
+//! kept up to date by `veil_indexer`.
+//!
+//! Usage:
+//!   cargo run --bin veil_monitor -- [options]
+//!
+//! Options:
+//!   --db <path>          Indexer database (default: veil-index.sqlite)
+//!   --lookahead <secs>   Warn about attestations expiring within this window (default: 86400)
+//!   --webhook <url>      POST each new finding as JSON to this URL (repeatable; needs `curl`)
+//!   --once               Check once and exit: 1 if anything critical was found
+//!   --interval <secs>    Check every this many seconds until Ctrl-C (default: 300)
+//!
+//! The contract hash is read from deployed-addresses.json; see
+//! `veil_attestation::config` for the overrides. The database must already
+//! hold that contract's events. Log verbosity follows `RUST_LOG` (default: info).
+
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use log::{error, info, warn};
+use veil_attestation::config::{ConfigKey, VeilConfig};
+use veil_attestation::indexer::{IndexDb, DEFAULT_DB_PATH};
+use veil_attestation::monitor::{
+    has_critical, post_webhook, select_findings, webhook_payload, Finding, MonitorDb, MonitorError, Severity,
+    DEFAULT_LOOKAHEAD_SECS,
+};
+
+/// Command-line options specific to this binary
+struct Options {
+    db: PathBuf,
+    lookahead_secs: u64,
+    webhooks: Vec<String>,
+    once: bool,
+    interval: Duration,
+}
+
+impl Options {
+    fn from_args(args: &[String]) -> Self {
+        let mut options = Options {
+            db: PathBuf::from(DEFAULT_DB_PATH),
+            lookahead_secs: DEFAULT_LOOKAHEAD_SECS,
+            webhooks: Vec::new(),
+            once: false,
+            interval: Duration::from_secs(300),
+        };
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--db" => {
+                    options.db = args.next().map(PathBuf::from).unwrap_or_else(|| exit_with("--db requires a value"))
+                }
+                "--lookahead" => options.lookahead_secs = parse_number(arg, args.next()),
+                "--webhook" => options
+                    .webhooks
+                    .push(args.next().cloned().unwrap_or_else(|| exit_with("--webhook requires a URL"))),
+                "--once" => options.once = true,
+                "--interval" => options.interval = Duration::from_secs(parse_number(arg, args.next())),
+                other => exit_with(&format!("Unknown argument: {}", other)),
+            }
+        }
+        options
+    }
+}
+
+fn parse_number<T: FromStr>(flag: &str, value: Option<&String>) -> T {
+    value
+        .and_then(|value| value.parse().ok())
+        .unwrap_or_else(|| exit_with(&format!("{} requires a number", flag)))
+}
+
+fn exit_with(message: &str) -> ! {
+    eprintln!("Configuration error: {}", message);
+    std::process::exit(2);
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|elapsed| elapsed.as_millis() as u64).unwrap_or_default()
+}
+
+/// One scan: report new findings, deliver them to every webhook and mark
+/// the ones that were delivered everywhere
+fn check(monitor: &MonitorDb, options: &Options) -> Result<Vec<Finding>, MonitorError> {
+    let now = now_ms();
+    let findings = select_findings(&monitor.attestations()?, now, options.lookahead_secs, &monitor.marked()?);
+
+    for finding in &findings {
+        let attestation = &finding.attestation;
+        let message = format!(
+            "{} attestation_id={} chain={} target={} expires_in_secs={}",
+            finding.kind.as_str(),
+            attestation.attestation_id,
+            attestation.target_chain,
+            attestation.target_address,
+            finding.expires_in_secs(now)
+        );
+        match finding.kind.severity() {
+            Severity::Critical => error!("{}", message),
+            Severity::Warning => warn!("{}", message),
+        }
+
+        if options.webhooks.is_empty() {
+            continue;
+        }
+        let payload = webhook_payload(finding, now);
+        let mut delivered = true;
+        for url in &options.webhooks {
+            if let Err(e) = post_webhook(url, &payload) {
+                error!("{}", e);
+                delivered = false;
+            }
+        }
+        if delivered {
+            monitor.mark(finding, now)?;
+        }
+    }
+
+    Ok(findings)
+}
+
+fn main() {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
+    let (config, rest) = VeilConfig::from_args_or_exit(&[ConfigKey::CasperContract]);
+    let options = Options::from_args(&rest);
+    let contract_hash = config.casper_contract_hash().expect("Contract hash checked above");
+
+    let db = IndexDb::open(&options.db).unwrap_or_else(|e| exit_with(&e.to_string()));
+    if db.cursor(&contract_hash).unwrap_or_else(|e| exit_with(&e.to_string())).is_none() {
+        exit_with(&format!("{} has not indexed {} yet; run veil_indexer first", options.db.display(), contract_hash));
+    }
+    let monitor = MonitorDb::new(&db).unwrap_or_else(|e| exit_with(&e.to_string()));
+    info!(
+        "network={} contract={} lookahead_secs={} webhooks={}",
+        config.network,
+        contract_hash,
+        options.lookahead_secs,
+        options.webhooks.len()
+    );
+
+    if options.once {
+        match check(&monitor, &options) {
+            Ok(findings) => {
+                info!("{} finding(s)", findings.len());
+                std::process::exit(if has_critical(&findings) { 1 } else { 0 });
+            }
+            Err(e) => {
+                error!("check failed: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let shutdown = Arc::new(AtomicBool::new(false));
+    {
+        let shutdown = shutdown.clone();
+        ctrlc::set_handler(move || shutdown.store(true, Ordering::SeqCst))
+            .expect("Failed to install Ctrl-C handler");
+    }
+
+    loop {
+        if let Err(e) = check(&monitor, &options) {
+            error!("check failed, will retry: {}", e);
+        }
+
+        let next_check = Instant::now() + options.interval;
+        while Instant::now() < next_check && !shutdown.load(Ordering::SeqCst) {
+            std::thread::sleep(Duration::from_millis(200));
+        }
+        if shutdown.load(Ordering::SeqCst) {
+            break;
+        }
+    }
+
+    info!("monitor stopped");
+}
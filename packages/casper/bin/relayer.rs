@@ -0,0 +1,125 @@
+//! Veil Relayer
+//!
+//! Polls the Casper node for `AttestationCreatedV2` events from the
+//! VeilAttestation contract and submits each new attestation to the
+//! VeilVerifier configured for its target chain.
+//!
+//! Usage:
+//!   cargo run --bin veil_relayer --features livenet -- [options]
+//!
+//! Options:
+//!   --relayer-config <path>   Relayer config (default: relayer.toml); see `veil_attestation::relayer`
+//!   --once                    Process pending events once and exit
+//!   --json                    On exit, print this run's outcomes as one JSON document
+//!                             (also `VEIL_OUTPUT=json`)
+//!
+//! The contract hash is read from deployed-addresses.json; see
+//! `veil_attestation::config` for the overrides. Log verbosity follows
+//! `RUST_LOG` (default: info). Ctrl-C finishes the current poll, saves the
+//! cursor and exits.
+
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use log::{error, info};
+use odra::host::HostRefLoader;
+use odra::prelude::*;
+use veil_attestation::config::{ConfigKey, VeilConfig};
+use veil_attestation::output::{OutputMode, RelayerOutput};
+use veil_attestation::relayer::{Relayer, RelayerConfig, DEFAULT_RELAYER_CONFIG_PATH};
+use veil_attestation::veil_attestation::VeilAttestation;
+
+/// Command-line options specific to this binary
+struct Options {
+    relayer_config: PathBuf,
+    once: bool,
+}
+
+impl Options {
+    fn from_args(args: &[String]) -> Self {
+        let mut options = Options { relayer_config: PathBuf::from(DEFAULT_RELAYER_CONFIG_PATH), once: false };
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--relayer-config" => {
+                    options.relayer_config = args
+                        .next()
+                        .map(PathBuf::from)
+                        .unwrap_or_else(|| exit_with("--relayer-config requires a value"));
+                }
+                "--once" => options.once = true,
+                other => exit_with(&format!("Unknown argument: {}", other)),
+            }
+        }
+        options
+    }
+}
+
+fn exit_with(message: &str) -> ! {
+    eprintln!("Configuration error: {}", message);
+    std::process::exit(2);
+}
+
+fn main() {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
+    let (config, rest) = VeilConfig::from_args_or_exit(&[ConfigKey::CasperContract]);
+    let (out, rest) = OutputMode::from_args(rest);
+    let options = Options::from_args(&rest);
+    let relayer_config = RelayerConfig::load(&options.relayer_config)
+        .unwrap_or_else(|e| exit_with(&e.to_string()));
+    let relayer_key = std::env::var(&relayer_config.relayer_key_env)
+        .unwrap_or_else(|_| exit_with(&format!("{} is not set", relayer_config.relayer_key_env)));
+
+    let shutdown = Arc::new(AtomicBool::new(false));
+    {
+        let shutdown = shutdown.clone();
+        ctrlc::set_handler(move || shutdown.store(true, Ordering::SeqCst))
+            .expect("Failed to install Ctrl-C handler");
+    }
+
+    let env = odra_casper_livenet_env::env();
+    let contract_hash = config.casper_contract_hash().expect("Contract hash checked above");
+    let contract_address = Address::from_str(&contract_hash).expect("Invalid contract address");
+    let contract = VeilAttestation::load(&env, contract_address);
+    info!("network={} contract={}", config.network, contract_hash);
+
+    let mut relayer = Relayer::new(&env, &contract, relayer_config, relayer_key)
+        .unwrap_or_else(|e| exit_with(&e.to_string()));
+    let poll_interval = Duration::from_secs(relayer.config().poll_interval_secs);
+    let mut run_outcomes = Vec::new();
+
+    loop {
+        match relayer.poll_once() {
+            Ok(outcomes) if !outcomes.is_empty() => {
+                info!("poll processed {} attestation(s)", outcomes.len());
+                run_outcomes.extend(outcomes);
+            }
+            Ok(_) => {}
+            // The cursor was not advanced past the failing event; retry next poll
+            Err(e) => error!("poll failed, will retry: {}", e),
+        }
+
+        if options.once {
+            break;
+        }
+
+        let next_poll = Instant::now() + poll_interval;
+        while Instant::now() < next_poll && !shutdown.load(Ordering::SeqCst) {
+            std::thread::sleep(Duration::from_millis(200));
+        }
+        if shutdown.load(Ordering::SeqCst) {
+            break;
+        }
+    }
+
+    info!(
+        "relayer stopped next_event_index={} relayed={}",
+        relayer.state().next_event_index,
+        relayer.state().relayed.len()
+    );
+    out.emit(&RelayerOutput { next_event_index: relayer.state().next_event_index, outcomes: run_outcomes });
+}
@@ -0,0 +1,55 @@
+//! Test-vector generator for the EVM verifier's Foundry tests
+//!
+//! Writes attestation encodings and signatures produced by the contract's own
+//! encoder and signer, so the Solidity suite cannot drift from Rust.
+//!
+//! Usage:
+//!   cargo run --bin veil_vectors -- [options]
+//!
+//! Options:
+//!   --seed-key <hex>   Signer private key (default: Anvil account[0])
+//!   --out <path>       Output file (default: ../evm/test/fixtures/attestation_vectors.json)
+//!   --json             Print `{count, signer_address, out}` (also `VEIL_OUTPUT=json`)
+
+use std::path::PathBuf;
+use veil_attestation::output::{OutputMode, VectorsOutput};
+use veil_attestation::vectors::{generate_vectors, DEFAULT_SEED_KEY, DEFAULT_VECTORS_PATH};
+
+fn exit_with(message: &str) -> ! {
+    eprintln!("Error: {}", message);
+    std::process::exit(2);
+}
+
+fn parse_key(value: &str) -> [u8; 32] {
+    hex::decode(value.trim_start_matches("0x"))
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .unwrap_or_else(|| exit_with("--seed-key must be 32 bytes of hex"))
+}
+
+fn main() {
+    let mut seed_key = DEFAULT_SEED_KEY;
+    let mut out = PathBuf::from(DEFAULT_VECTORS_PATH);
+
+    let (out_mode, args) = OutputMode::from_args(std::env::args().skip(1).collect());
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().unwrap_or_else(|| exit_with(&format!("{} requires a value", arg)));
+        match arg.as_str() {
+            "--seed-key" => seed_key = parse_key(&value()),
+            "--out" => out = PathBuf::from(value()),
+            other => exit_with(&format!("Unknown argument: {}", other)),
+        }
+    }
+
+    let file = generate_vectors(&seed_key).unwrap_or_else(|| exit_with("--seed-key is not a valid secp256k1 key"));
+    let json = serde_json::to_string_pretty(&file).expect("Vectors serialize to JSON");
+
+    if let Some(dir) = out.parent() {
+        std::fs::create_dir_all(dir).unwrap_or_else(|e| exit_with(&format!("{}: {}", dir.display(), e)));
+    }
+    std::fs::write(&out, json + "\n").unwrap_or_else(|e| exit_with(&format!("{}: {}", out.display(), e)));
+
+    out_mode.say(format!("Wrote {} vectors signed by {} to {}", file.count, file.signer_address, out.display()));
+    out_mode.emit(&VectorsOutput { count: file.count, signer_address: file.signer_address, out: out.display().to_string() });
+}
@@ -0,0 +1,80 @@
+//! Veil CLI
+//!
+//! One entry point for the contract operations the separate livenet
+//! binaries used to cover.
+//!
+//! Usage:
+//!   cargo run --bin veil --features livenet -- [global options] <command>
+//!
+//! Commands:
+//!   deploy [--signer-key-env <var>]                     Deploy VeilAttestation (key from VEIL_SIGNER_PRIVATE_KEY)
+//!   attest --chain <chain> --address <addr> [--validity-secs <n>]
+//!   revoke --id <attestation id>
+//!   get --id <attestation id>
+//!   list --user <casper address>
+//!   export-evm --id <attestation id>                   Encoded payload and signature for the EVM verifier
+//!   signer                                             Signer's Ethereum address
+//!
+//! Global options: --config, --network and --contract (see
+//! `veil_attestation::config`) and --json (or VEIL_OUTPUT=json) for
+//! machine-readable output.
+//!
+//! `deploy` and `attest` take `--gas <CSPR>` to override the default gas
+//! limit (450 / 50 CSPR) and `--dry-run` to print a cost estimate without
+//! submitting. Estimates run on a local Odra environment, which must be the
+//! gas-metering Casper backend: `cargo odra build`, then `ODRA_BACKEND=casper`.
+//!
+//! Exits 1 if the command fails and 2 on a configuration error.
+
+use std::str::FromStr;
+
+use clap::Parser;
+use odra::prelude::Address;
+use veil_attestation::cli::{dry_run, render, run, Cli, CliError, HostBackend, Output, SimulatedEstimator};
+use veil_attestation::config::VeilConfig;
+use veil_attestation::output::OutputMode;
+
+fn main() {
+    let cli = Cli::parse();
+    let mode = cli.output_mode();
+
+    if cli.command.is_dry_run() {
+        let result = dry_run(&cli.command, &mut SimulatedEstimator::new(odra_test::env()));
+        print(&result, mode);
+        std::process::exit(if result.is_ok() { 0 } else { 1 });
+    }
+
+    let contract = match resolve_contract(&cli) {
+        Ok(contract) => contract,
+        Err(e) => {
+            print(&Err(e), mode);
+            std::process::exit(2);
+        }
+    };
+
+    let mut backend = HostBackend::new(odra_casper_livenet_env::env(), contract);
+    let result = run(&cli.command, &mut backend);
+    print(&result, mode);
+    if result.is_err() {
+        std::process::exit(1);
+    }
+}
+
+/// JSON always goes to stdout so scripts get exactly one document
+fn print(result: &Result<Output, CliError>, mode: OutputMode) {
+    match (result, mode) {
+        (Err(_), OutputMode::Human) => eprintln!("{}", render(result, mode)),
+        _ => println!("{}", render(result, mode)),
+    }
+}
+
+fn resolve_contract(cli: &Cli) -> Result<Option<Address>, CliError> {
+    let config = VeilConfig::load(&cli.overrides())?;
+    config
+        .casper_contract_hash()
+        .map(|hash| {
+            Address::from_str(&hash)
+                .map_err(|_| CliError::InvalidArgument(format!("Invalid contract hash: {}", hash)))
+        })
+        .transpose()
+}
@@ -0,0 +1,132 @@
+//! Offline attestation verifier
+//!
+//! Answers "is this blob + signature valid and who signed it" without
+//! touching any chain.
+//!
+//! Usage:
+//!   cargo run --bin veil_verify -- --encoded 0x... --signature 0x... [--expected-signer 0x...]
+//!   cargo run --bin veil_verify -- --file <path> [--expected-signer 0x...] [--millis-timestamps]
+//!
+//! Payload timestamps are read in seconds, the Casper contract's default;
+//! pass `--millis-timestamps` for millisecond payloads, such as the
+//! `veil_vectors` fixture or deployments that call `set_time_unit(0)`.
+//!
+//! `--file` accepts a JSON object with `encoded` and `signature` fields (plus an
+//! optional `signer_address`), or a `veil_vectors` fixture with a `vectors` array.
+//!
+//! With `--json` (or `VEIL_OUTPUT=json`) prints one document with a `valid`
+//! flag and the checks for every attestation.
+//!
+//! Exit code: 0 if every attestation is valid, 1 if any is not, 2 on bad input.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde_json::Value;
+use veil_attestation::output::{OutputMode, VerificationOutput, VerifyOutput};
+use veil_attestation::veil_attestation::{TIME_UNIT_MILLIS, TIME_UNIT_SECONDS};
+use veil_attestation::verify::verify_attestation;
+
+/// One attestation to check
+struct Input {
+    encoded: Vec<u8>,
+    signature: Vec<u8>,
+    expected_signer: Option<[u8; 20]>,
+}
+
+fn exit_with(message: &str) -> ! {
+    eprintln!("Error: {}", message);
+    std::process::exit(2);
+}
+
+fn parse_hex(name: &str, value: &str) -> Vec<u8> {
+    hex::decode(value.trim_start_matches("0x"))
+        .unwrap_or_else(|e| exit_with(&format!("{} is not valid hex: {}", name, e)))
+}
+
+fn parse_address(name: &str, value: &str) -> [u8; 20] {
+    parse_hex(name, value)
+        .try_into()
+        .unwrap_or_else(|_| exit_with(&format!("{} must be a 20-byte address", name)))
+}
+
+fn field<'a>(object: &'a Value, name: &str) -> Option<&'a str> {
+    object.get(name).and_then(Value::as_str)
+}
+
+/// Attestations in a JSON file: a single object or a `veil_vectors` fixture
+fn inputs_from_file(path: &str) -> Vec<Input> {
+    let contents = std::fs::read_to_string(path).unwrap_or_else(|e| exit_with(&format!("{}: {}", path, e)));
+    let json: Value = serde_json::from_str(&contents).unwrap_or_else(|e| exit_with(&format!("{}: {}", path, e)));
+
+    let objects = match json.get("vectors").and_then(Value::as_array) {
+        Some(vectors) => vectors.iter().collect(),
+        None => vec![&json],
+    };
+
+    objects
+        .into_iter()
+        .map(|object| {
+            let (Some(encoded), Some(signature)) = (field(object, "encoded"), field(object, "signature")) else {
+                exit_with(&format!("{}: expected `encoded` and `signature` fields", path));
+            };
+            Input {
+                encoded: parse_hex("encoded", encoded),
+                signature: parse_hex("signature", signature),
+                expected_signer: field(object, "signer_address").map(|signer| parse_address("signer_address", signer)),
+            }
+        })
+        .collect()
+}
+
+fn main() {
+    let mut encoded = None;
+    let mut signature = None;
+    let mut expected_signer = None;
+    let mut file = None;
+    let mut time_unit = TIME_UNIT_SECONDS;
+
+    let (out, args) = OutputMode::from_args(std::env::args().skip(1).collect());
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().unwrap_or_else(|| exit_with(&format!("{} requires a value", arg)));
+        match arg.as_str() {
+            "--encoded" => encoded = Some(parse_hex("--encoded", &value())),
+            "--signature" => signature = Some(parse_hex("--signature", &value())),
+            "--expected-signer" => expected_signer = Some(parse_address("--expected-signer", &value())),
+            "--file" => file = Some(value()),
+            "--millis-timestamps" => time_unit = TIME_UNIT_MILLIS,
+            other => exit_with(&format!("Unknown argument: {}", other)),
+        }
+    }
+
+    let mut inputs = match (file, encoded, signature) {
+        (Some(path), None, None) => inputs_from_file(&path),
+        (None, Some(encoded), Some(signature)) => vec![Input { encoded, signature, expected_signer: None }],
+        _ => exit_with("pass either --file <path> or both --encoded and --signature"),
+    };
+    if let Some(expected) = expected_signer {
+        for input in &mut inputs {
+            input.expected_signer = Some(expected);
+        }
+    }
+
+    let now_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or_default();
+
+    let mut all_valid = true;
+    let mut attestations = Vec::new();
+    for (index, input) in inputs.iter().enumerate() {
+        let report = verify_attestation(&input.encoded, &input.signature, input.expected_signer, now_ms, time_unit);
+        if inputs.len() > 1 {
+            out.say(format!("Attestation #{}", index));
+        }
+        out.say(format!("{}\n", report));
+        all_valid &= report.is_valid();
+        attestations.push(VerificationOutput::from(&report));
+    }
+    out.emit(&VerifyOutput { valid: all_valid, attestations });
+
+    std::process::exit(if all_valid { 0 } else { 1 });
+}
@@ -0,0 +1,17 @@
+#!/usr/bin/env sh
+# Lint and test both signing models, then check the contract wasm builds:
+#   default             the contract holds the signer key and signs on chain
+#   no-onchain-signing  an external signer submits signatures; the suite that
+#                       needs on-chain signing is compiled out
+#
+# Usage: scripts/test-features.sh (from anywhere)
+set -eu
+
+cd "$(dirname "$0")/.."
+
+for features in "" "no-onchain-signing"; do
+    echo "==> features: ${features:-default}"
+    cargo clippy --all-targets --features "$features" -- -D warnings
+    cargo test --features "$features"
+    cargo build --lib --release --target wasm32-unknown-unknown --features "$features"
+done
@@ -0,0 +1,212 @@
+//! Soulbound badge NFTs marking attestations on Casper
+//!
+//! `VeilBadge` is a minimal non-transferable collection: one token per
+//! attestation, keyed by the attestation ID, minted and burned only by its
+//! minter. `VeilAttestation` is made the minter and mints through the
+//! `BadgeCollection` interface, so another collection exposing the same
+//! entry points can stand in for it.
+
+use alloc::format;
+use alloc::string::String;
+use odra::prelude::*;
+use crate::errors::VeilError;
+use crate::types::{BadgeBurned, BadgeMinted, Tier};
+
+/// A collection `VeilAttestation` can mint badges on
+#[odra::external_contract]
+pub trait BadgeCollection {
+    /// Mint `token_id` to `to` with JSON `metadata`
+    fn mint(&mut self, to: Address, token_id: [u8; 32], metadata: String);
+    /// Burn `token_id`; a no-op if it does not exist
+    fn burn(&mut self, token_id: [u8; 32]);
+}
+
+/// Badge metadata: `{"attestation_id":"0x…","tier":N,"target_chain":"…","expires_at":N}`
+pub fn badge_metadata(attestation_id: &[u8; 32], tier: Tier, target_chain: &str, expires_at: u64) -> String {
+    format!(
+        "{{\"attestation_id\":\"0x{}\",\"tier\":{},\"target_chain\":\"{}\",\"expires_at\":{}}}",
+        hex::encode(attestation_id),
+        tier as u8,
+        json_escape(target_chain),
+        expires_at
+    )
+}
+
+/// `value` escaped for use inside a JSON string
+fn json_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if u32::from(c) < 0x20 => escaped.push_str(&format!("\\u{:04x}", u32::from(c))),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[odra::module]
+pub struct VeilBadge {
+    admin: Var<Address>,
+    /// The only account or contract allowed to mint and burn
+    minter: Var<Option<Address>>,
+    owners: Mapping<[u8; 32], Option<Address>>,
+    metadata: Mapping<[u8; 32], String>,
+    balances: Mapping<Address, u64>,
+    total_supply: Var<u64>,
+}
+
+#[odra::module]
+impl VeilBadge {
+    /// Initialize the collection; set the minter before minting
+    pub fn init(&mut self, admin: Address) {
+        self.admin.set(admin);
+    }
+
+    /// Mint `token_id` to `to` (minter only)
+    pub fn mint(&mut self, to: Address, token_id: [u8; 32], metadata: String) {
+        self.assert_minter();
+        if self.owner_of(token_id).is_some() {
+            self.env().revert(VeilError::BadgeAlreadyMinted);
+        }
+
+        self.owners.set(&token_id, Some(to));
+        self.metadata.set(&token_id, metadata);
+        self.balances.set(&to, self.balance_of(to) + 1);
+        self.total_supply.set(self.total_supply() + 1);
+
+        self.env().emit_event(BadgeMinted { token_id, owner: to });
+    }
+
+    /// Burn `token_id`; a no-op if it does not exist (minter only)
+    pub fn burn(&mut self, token_id: [u8; 32]) {
+        self.assert_minter();
+        let Some(owner) = self.owner_of(token_id) else {
+            return;
+        };
+
+        self.owners.set(&token_id, None);
+        self.metadata.set(&token_id, String::new());
+        self.balances.set(&owner, self.balance_of(owner) - 1);
+        self.total_supply.set(self.total_supply() - 1);
+
+        self.env().emit_event(BadgeBurned { token_id, owner });
+    }
+
+    // ============ ADMIN FUNCTIONS ============
+
+    /// Set or clear the minter (admin only)
+    pub fn set_minter(&mut self, minter: Option<Address>) {
+        if Some(self.env().caller()) != self.admin.get() {
+            self.env().revert(VeilError::NotAdmin);
+        }
+        self.minter.set(minter);
+    }
+
+    // ============ VIEW FUNCTIONS ============
+
+    /// Get the minter, if set
+    pub fn get_minter(&self) -> Option<Address> {
+        self.minter.get().flatten()
+    }
+
+    /// Owner of `token_id`, or `None` if it was never minted or was burned
+    pub fn owner_of(&self, token_id: [u8; 32]) -> Option<Address> {
+        self.owners.get(&token_id).flatten()
+    }
+
+    /// Metadata of an existing `token_id`
+    pub fn token_metadata(&self, token_id: [u8; 32]) -> Option<String> {
+        self.owner_of(token_id)?;
+        self.metadata.get(&token_id)
+    }
+
+    /// Number of badges `owner` holds
+    pub fn balance_of(&self, owner: Address) -> u64 {
+        self.balances.get(&owner).unwrap_or_default()
+    }
+
+    /// Number of badges in existence
+    pub fn total_supply(&self) -> u64 {
+        self.total_supply.get_or_default()
+    }
+
+    fn assert_minter(&self) {
+        if Some(self.env().caller()) != self.get_minter() {
+            self.env().revert(VeilError::NotBadgeMinter);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+    use alloc::string::ToString;
+    use odra::host::{Deployer, HostEnv};
+    use super::{badge_metadata, VeilBadge, VeilBadgeHostRef, VeilBadgeInitArgs};
+    use crate::errors::VeilError;
+    use crate::types::{BadgeBurned, BadgeMinted, Tier};
+
+    const TOKEN: [u8; 32] = [0xab; 32];
+
+    /// Collection with account 0 as admin and account 1 as minter, called by the minter
+    fn setup() -> (HostEnv, VeilBadgeHostRef) {
+        let env = odra_test::env();
+        let mut badge = VeilBadge::deploy(&env, VeilBadgeInitArgs { admin: env.get_account(0) });
+        badge.set_minter(Some(env.get_account(1)));
+        env.set_caller(env.get_account(1));
+        (env, badge)
+    }
+
+    #[test]
+    fn test_badge_metadata_escapes_chain() {
+        let metadata = badge_metadata(&[0x01; 32], Tier::Gold, "odd\"chain\\\n", 42);
+        let expected_chain = r#""target_chain":"odd\"chain\\\u000a""#;
+        assert!(metadata.starts_with(&format!(r#"{{"attestation_id":"0x{}","tier":3,"#, "01".repeat(32))));
+        assert!(metadata.ends_with(&format!(r#"{},"expires_at":42}}"#, expected_chain)));
+    }
+
+    #[test]
+    fn test_mint_and_burn_by_minter() {
+        let (env, mut badge) = setup();
+        let owner = env.get_account(2);
+
+        badge.mint(owner, TOKEN, "{}".to_string());
+        assert_eq!(badge.owner_of(TOKEN), Some(owner));
+        assert_eq!(badge.token_metadata(TOKEN), Some("{}".to_string()));
+        assert_eq!((badge.balance_of(owner), badge.total_supply()), (1, 1));
+        assert!(env.emitted_event(&badge, BadgeMinted { token_id: TOKEN, owner }));
+        assert_eq!(badge.try_mint(owner, TOKEN, "{}".to_string()), Err(VeilError::BadgeAlreadyMinted.into()));
+
+        badge.burn(TOKEN);
+        assert_eq!(badge.owner_of(TOKEN), None);
+        assert_eq!(badge.token_metadata(TOKEN), None);
+        assert_eq!((badge.balance_of(owner), badge.total_supply()), (0, 0));
+        assert!(env.emitted_event(&badge, BadgeBurned { token_id: TOKEN, owner }));
+
+        // Burning again is a no-op
+        let events_before = env.events_count(&badge);
+        badge.burn(TOKEN);
+        assert_eq!(env.events_count(&badge), events_before);
+    }
+
+    #[test]
+    fn test_only_minter_mints_and_burns() {
+        let (env, mut badge) = setup();
+        badge.mint(env.get_account(2), TOKEN, "{}".to_string());
+
+        env.set_caller(env.get_account(2));
+        assert_eq!(
+            badge.try_mint(env.get_account(2), [0xcd; 32], "{}".to_string()),
+            Err(VeilError::NotBadgeMinter.into())
+        );
+        assert_eq!(badge.try_burn(TOKEN), Err(VeilError::NotBadgeMinter.into()));
+        assert_eq!(badge.try_set_minter(None), Err(VeilError::NotAdmin.into()));
+
+        env.set_caller(env.get_account(0));
+        badge.set_minter(None);
+        assert_eq!(badge.get_minter(), None);
+        assert_eq!(badge.try_burn(TOKEN), Err(VeilError::NotBadgeMinter.into()));
+    }
+}
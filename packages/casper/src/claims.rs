@@ -0,0 +1,144 @@
+//! Provider-signed claims attached to attestations
+//!
+//! A submodule of `VeilAttestation`, which checks who may manage providers,
+//! finds the attestation and re-signs its payload; this module keeps the
+//! provider registry, verifies provider signatures and keeps the claim
+//! records and each attestation's claims hash.
+
+use alloc::vec::Vec;
+use odra::casper_types::bytesrepr::Bytes;
+use odra::prelude::*;
+use crate::encoding::{self, eth_signed_message_hash, public_key_to_address};
+use crate::errors::VeilError;
+use crate::types::Claim;
+
+/// Maximum length of a claim's data in bytes
+pub const MAX_CLAIM_DATA_LEN: usize = 256;
+
+#[odra::module]
+pub struct Claims {
+    /// Public key of each provider, keyed by its Ethereum address; `None` once removed
+    provider_keys: Mapping<[u8; 20], Option<[u8; 64]>>,
+    /// Registered providers, in registration order
+    providers: Var<Vec<[u8; 20]>>,
+    /// ID of the next claim attached
+    next_id: Var<u64>,
+    claims: Mapping<u64, Claim>,
+    /// Claim IDs per attestation, in attachment order
+    by_attestation: Mapping<[u8; 32], Vec<u64>>,
+    /// Claim IDs per provider, in attachment order
+    by_provider: Mapping<[u8; 20], Vec<u64>>,
+    /// `encoding::fold_claims_hash` over each attestation's claims
+    claims_hashes: Mapping<[u8; 32], [u8; 32]>,
+    /// Digests of attached claims, so each signed claim is attached once
+    attached_digests: Mapping<[u8; 32], bool>,
+}
+
+#[odra::module]
+impl Claims {
+    /// Register a provider by its uncompressed public key (without the 0x04
+    /// prefix), returning its Ethereum address. Re-registering is a no-op.
+    pub fn register_provider(&mut self, public_key: [u8; 64]) -> [u8; 20] {
+        let provider = public_key_to_address(&public_key);
+        if self.provider_key(provider).is_none() {
+            let mut providers = self.providers.get_or_default();
+            providers.push(provider);
+            self.providers.set(providers);
+            self.provider_keys.set(&provider, Some(public_key));
+        }
+        provider
+    }
+
+    /// Remove a provider, returning whether it was registered. Its claims
+    /// stay attached.
+    pub fn remove_provider(&mut self, provider: [u8; 20]) -> bool {
+        if self.provider_key(provider).is_none() {
+            return false;
+        }
+        let mut providers = self.providers.get_or_default();
+        providers.retain(|registered| *registered != provider);
+        self.providers.set(providers);
+        self.provider_keys.set(&provider, None);
+        true
+    }
+
+    /// Verify `provider_signature`, an EIP-191 signature over
+    /// `encoding::claim_digest`, and record the claim. Returns it with the
+    /// attestation's new claims hash.
+    pub fn attach(
+        &mut self,
+        attestation_id: [u8; 32],
+        claim_type: u8,
+        claim_data: Bytes,
+        provider_signature: Bytes,
+    ) -> (Claim, [u8; 32]) {
+        if claim_data.len() > MAX_CLAIM_DATA_LEN {
+            self.env().revert(VeilError::ClaimDataTooLong);
+        }
+        let digest = encoding::claim_digest(&attestation_id, claim_type, &claim_data);
+        let provider = encoding::recover_signer(&eth_signed_message_hash(&digest), &provider_signature)
+            .unwrap_or_else(|| self.env().revert(VeilError::InvalidClaimSignature));
+        if self.provider_key(provider).is_none() {
+            self.env().revert(VeilError::UnknownClaimProvider);
+        }
+        if self.attached_digests.get(&digest).unwrap_or_default() {
+            self.env().revert(VeilError::ClaimAlreadyAttached);
+        }
+
+        let id = self.next_id.get_or_default();
+        self.next_id.set(id + 1);
+        let claim = Claim {
+            id,
+            attestation_id,
+            provider,
+            claim_type,
+            claim_data,
+            provider_signature,
+            attached_at: self.env().get_block_time(),
+        };
+        self.claims.set(&id, claim.clone());
+        self.attached_digests.set(&digest, true);
+
+        let mut ids = self.by_attestation.get(&attestation_id).unwrap_or_default();
+        ids.push(id);
+        self.by_attestation.set(&attestation_id, ids);
+        let mut ids = self.by_provider.get(&provider).unwrap_or_default();
+        ids.push(id);
+        self.by_provider.set(&provider, ids);
+
+        let claims_hash = encoding::fold_claims_hash(&self.claims_hash(attestation_id), &digest);
+        self.claims_hashes.set(&attestation_id, claims_hash);
+        (claim, claims_hash)
+    }
+
+    /// Public key of a registered provider
+    pub fn provider_key(&self, provider: [u8; 20]) -> Option<[u8; 64]> {
+        self.provider_keys.get(&provider).flatten()
+    }
+
+    /// Registered providers, in registration order
+    pub fn providers(&self) -> Vec<[u8; 20]> {
+        self.providers.get_or_default()
+    }
+
+    pub fn get(&self, id: u64) -> Option<Claim> {
+        self.claims.get(&id)
+    }
+
+    /// Claims attached to an attestation, in attachment order
+    pub fn for_attestation(&self, attestation_id: [u8; 32]) -> Vec<Claim> {
+        let ids = self.by_attestation.get(&attestation_id).unwrap_or_default();
+        ids.iter().filter_map(|id| self.get(*id)).collect()
+    }
+
+    /// Claims signed by a provider, in attachment order
+    pub fn for_provider(&self, provider: [u8; 20]) -> Vec<Claim> {
+        let ids = self.by_provider.get(&provider).unwrap_or_default();
+        ids.iter().filter_map(|id| self.get(*id)).collect()
+    }
+
+    /// Claims hash of an attestation; zero without claims
+    pub fn claims_hash(&self, attestation_id: [u8; 32]) -> [u8; 32] {
+        self.claims_hashes.get(&attestation_id).unwrap_or_default()
+    }
+}
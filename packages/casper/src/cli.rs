@@ -0,0 +1,865 @@
+//! `veil` command-line interface
+//!
+//! Argument parsing, config resolution and output formatting for the `veil`
+//! binary. Network calls go through [`VeilBackend`], so the command layer
+//! runs against `odra_test` in unit tests and against a node in the binary.
+
+use std::fmt;
+use std::format;
+use std::path::PathBuf;
+use std::prelude::v1::*;
+use std::str::FromStr;
+
+use clap::{Args, Parser, Subcommand};
+use odra::host::{Deployer, HostEnv, HostRefLoader};
+use odra::casper_types::U512;
+use odra::prelude::{Address, Addressable};
+use serde::Serialize;
+
+use crate::config::{CliOverrides, ConfigError};
+#[cfg(feature = "no-onchain-signing")]
+use crate::encoding::derive_public_key;
+use crate::output::{AttestOutput, CostReport, DeployOutput, OutputMode, OUTPUT_ENV};
+use crate::types::{motes_to_cspr_string, Attestation, InitConfig, MOTES_PER_CSPR};
+use crate::veil_attestation::{VeilAttestation, VeilAttestationHostRef, VeilAttestationInitArgs};
+
+/// Env var read by `veil deploy` for the attestation signer key
+pub const DEFAULT_SIGNER_KEY_ENV: &str = "VEIL_SIGNER_PRIVATE_KEY";
+
+/// Gas for deploying the contract (450 CSPR)
+pub const DEPLOY_GAS: u64 = 450_000_000_000;
+/// Gas for state-changing calls (50 CSPR)
+pub const CALL_GAS: u64 = 50_000_000_000;
+/// Largest `--gas` accepted, in CSPR, to catch typos before they burn funds
+pub const MAX_GAS_CSPR: u64 = 5_000;
+
+/// Signer key for simulated deploys; any valid key costs the same
+const SIMULATION_SIGNER_KEY: [u8; 32] = [0x11; 32];
+
+#[derive(Debug, PartialEq, Parser)]
+#[command(name = "veil", about = "Manage Veil attestations on Casper")]
+pub struct Cli {
+    /// Config file (default: ../../deployed-addresses.json)
+    #[arg(long, global = true)]
+    pub config: Option<PathBuf>,
+    /// Network section of the config file (default: casper-localnet)
+    #[arg(long, global = true)]
+    pub network: Option<String>,
+    /// VeilAttestation contract hash, overriding the config file
+    #[arg(long, global = true)]
+    pub contract: Option<String>,
+    /// Print a single JSON document instead of human-readable output
+    #[arg(long, global = true)]
+    pub json: bool,
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, PartialEq, Subcommand)]
+pub enum Command {
+    /// Deploy a new VeilAttestation contract
+    Deploy {
+        /// Env var holding the secp256k1 signer key (hex)
+        #[arg(long, default_value = DEFAULT_SIGNER_KEY_ENV)]
+        signer_key_env: String,
+        #[command(flatten)]
+        gas: GasArgs,
+    },
+    /// Create an attestation for the caller
+    Attest {
+        #[arg(long)]
+        chain: String,
+        #[arg(long)]
+        address: String,
+        /// Shorter validity than the contract default, in seconds
+        #[arg(long)]
+        validity_secs: Option<u64>,
+        /// Proof-of-work nonce, when the contract sets a difficulty
+        #[arg(long)]
+        pow_nonce: Option<u64>,
+        #[command(flatten)]
+        gas: GasArgs,
+    },
+    /// Revoke one of the caller's attestations
+    Revoke {
+        #[arg(long)]
+        id: String,
+    },
+    /// Show an attestation
+    Get {
+        #[arg(long)]
+        id: String,
+    },
+    /// List a user's attestations
+    List {
+        /// Casper address (`account-hash-...` or `hash-...`)
+        #[arg(long)]
+        user: String,
+    },
+    /// Print the ABI-encoded attestation and signature for EVM submission
+    ExportEvm {
+        #[arg(long)]
+        id: String,
+    },
+    /// Show the signer's Ethereum address
+    Signer,
+}
+
+/// Gas options for the commands that submit a deploy
+#[derive(Debug, Default, Clone, PartialEq, Args)]
+pub struct GasArgs {
+    /// Gas limit in CSPR (default: 450 for deploy, 50 for attest)
+    #[arg(long, value_parser = parse_gas_cspr)]
+    pub gas: Option<u64>,
+    /// Estimate the cost and exit without submitting anything
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// Parse a `--gas` amount in CSPR (up to 9 decimals) into motes
+pub fn parse_gas_cspr(value: &str) -> Result<u64, String> {
+    let invalid = || format!("'{}' is not a CSPR amount (e.g. 450 or 12.5)", value);
+    let (whole, fraction) = value.split_once('.').unwrap_or((value, ""));
+    if whole.is_empty() || !whole.bytes().all(|b| b.is_ascii_digit()) || fraction.len() > 9
+        || !fraction.bytes().all(|b| b.is_ascii_digit())
+    {
+        return Err(invalid());
+    }
+
+    let whole: u64 = whole.parse().map_err(|_| invalid())?;
+    if whole > MAX_GAS_CSPR {
+        return Err(format!("gas above {} CSPR is refused as a likely typo", MAX_GAS_CSPR));
+    }
+    let fraction: u64 = format!("{:0<9}", fraction).parse().map_err(|_| invalid())?;
+    let motes = whole * MOTES_PER_CSPR + fraction;
+    if motes == 0 || motes > MAX_GAS_CSPR * MOTES_PER_CSPR {
+        return Err(format!("gas must be greater than 0 and at most {} CSPR", MAX_GAS_CSPR));
+    }
+    Ok(motes)
+}
+
+impl Command {
+    /// Gas limit the command would be submitted with, in motes
+    pub fn gas_limit(&self) -> u64 {
+        match self {
+            Command::Deploy { gas, .. } => gas.gas.unwrap_or(DEPLOY_GAS),
+            Command::Attest { gas, .. } => gas.gas.unwrap_or(CALL_GAS),
+            _ => CALL_GAS,
+        }
+    }
+
+    pub fn is_dry_run(&self) -> bool {
+        matches!(self, Command::Deploy { gas, .. } | Command::Attest { gas, .. } if gas.dry_run)
+    }
+}
+
+impl Cli {
+    /// Config overrides given by the global flags
+    pub fn overrides(&self) -> CliOverrides {
+        CliOverrides {
+            config_path: self.config.clone(),
+            network: self.network.clone(),
+            casper_contract: self.contract.clone(),
+            ..CliOverrides::default()
+        }
+    }
+
+    /// `--json`, or `VEIL_OUTPUT=json`
+    pub fn output_mode(&self) -> OutputMode {
+        OutputMode::resolve(self.json, std::env::var(OUTPUT_ENV).ok().as_deref())
+    }
+}
+
+/// Errors surfaced to the user
+#[derive(Debug, PartialEq)]
+pub enum CliError {
+    Config(ConfigError),
+    InvalidArgument(String),
+    NotFound(String),
+    /// The node rejected or failed a call
+    Backend(String),
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Config(e) => write!(f, "{}", e),
+            CliError::InvalidArgument(message) | CliError::NotFound(message) | CliError::Backend(message) => {
+                write!(f, "{}", message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+impl From<ConfigError> for CliError {
+    fn from(e: ConfigError) -> Self {
+        CliError::Config(e)
+    }
+}
+
+/// Contract operations used by the commands
+pub trait VeilBackend {
+    /// Deploy a new contract, returning its address and the motes charged for it
+    fn deploy(&mut self, signer_private_key: [u8; 32], gas: u64) -> Result<(String, U512), CliError>;
+    fn create_attestation(
+        &mut self,
+        target_chain: String,
+        target_address: String,
+        custom_validity_secs: Option<u64>,
+        pow_nonce: Option<u64>,
+        gas: u64,
+    ) -> Result<([u8; 32], Vec<u8>), CliError>;
+    fn revoke_attestation(&mut self, id: [u8; 32]) -> Result<(), CliError>;
+    fn get_attestation(&self, id: [u8; 32]) -> Result<Option<Attestation>, CliError>;
+    fn get_user_attestations(&self, user: Address) -> Result<Vec<Attestation>, CliError>;
+    fn get_attestation_for_evm(&self, id: [u8; 32]) -> Result<Option<(Vec<u8>, Vec<u8>)>, CliError>;
+    fn get_signer_address(&self) -> Result<[u8; 20], CliError>;
+}
+
+/// [`VeilBackend`] over an Odra host environment (livenet or `odra_test`)
+pub struct HostBackend {
+    env: HostEnv,
+    contract: Option<VeilAttestationHostRef>,
+}
+
+impl HostBackend {
+    pub fn new(env: HostEnv, contract: Option<Address>) -> Self {
+        let contract = contract.map(|address| VeilAttestation::load(&env, address));
+        HostBackend { env, contract }
+    }
+
+    fn contract(&self) -> Result<&VeilAttestationHostRef, CliError> {
+        self.contract.as_ref().ok_or_else(no_contract)
+    }
+
+    fn contract_mut(&mut self) -> Result<&mut VeilAttestationHostRef, CliError> {
+        self.contract.as_mut().ok_or_else(no_contract)
+    }
+}
+
+/// Init args making `admin` the admin and `signer_private_key`'s owner the signer
+#[cfg(not(feature = "no-onchain-signing"))]
+pub fn signer_init_args(
+    admin: Address,
+    signer_private_key: [u8; 32],
+    config: Option<InitConfig>,
+) -> Result<VeilAttestationInitArgs, CliError> {
+    Ok(VeilAttestationInitArgs { admin, signer_private_key, initial_lock_secs: None, config })
+}
+
+/// Init args making `admin` the admin and `signer_private_key`'s owner the
+/// external signer; only the public key goes on chain
+#[cfg(feature = "no-onchain-signing")]
+pub fn signer_init_args(
+    admin: Address,
+    signer_private_key: [u8; 32],
+    config: Option<InitConfig>,
+) -> Result<VeilAttestationInitArgs, CliError> {
+    let signer_public_key = derive_public_key(&signer_private_key)
+        .ok_or_else(|| CliError::InvalidArgument("Signer key is not a valid secp256k1 key".to_string()))?;
+    Ok(VeilAttestationInitArgs { admin, signer_public_key, initial_lock_secs: None, config })
+}
+
+fn no_contract() -> CliError {
+    CliError::InvalidArgument("No contract configured; set VeilAttestation in the config file or pass --contract".to_string())
+}
+
+fn backend_error(e: impl fmt::Debug) -> CliError {
+    CliError::Backend(format!("{:?}", e))
+}
+
+impl VeilBackend for HostBackend {
+    fn deploy(&mut self, signer_private_key: [u8; 32], gas: u64) -> Result<(String, U512), CliError> {
+        let deployer = self.env.caller();
+        let balance_before = self.env.balance_of(&deployer);
+        let init_args = signer_init_args(deployer, signer_private_key, None)?;
+        self.env.set_gas(gas);
+        let contract = VeilAttestation::try_deploy(&self.env, init_args).map_err(backend_error)?;
+        let gas_used = balance_before.saturating_sub(self.env.balance_of(&deployer));
+        let address = contract.address().to_string();
+        self.contract = Some(contract);
+        Ok((address, gas_used))
+    }
+
+    fn create_attestation(
+        &mut self,
+        target_chain: String,
+        target_address: String,
+        custom_validity_secs: Option<u64>,
+        pow_nonce: Option<u64>,
+        gas: u64,
+    ) -> Result<([u8; 32], Vec<u8>), CliError> {
+        self.env.set_gas(gas);
+        let (id, signature) = self
+            .contract_mut()?
+            .try_create_attestation(
+                target_chain,
+                target_address,
+                custom_validity_secs,
+                pow_nonce,
+                None,
+                None,
+                None,
+                None,
+            )
+            .map_err(backend_error)?;
+        Ok((id, signature.to_vec()))
+    }
+
+    fn revoke_attestation(&mut self, id: [u8; 32]) -> Result<(), CliError> {
+        self.env.set_gas(CALL_GAS);
+        self.contract_mut()?.try_revoke_attestation(id).map_err(backend_error)
+    }
+
+    fn get_attestation(&self, id: [u8; 32]) -> Result<Option<Attestation>, CliError> {
+        self.contract()?.try_get_attestation(id).map_err(backend_error)
+    }
+
+    fn get_user_attestations(&self, user: Address) -> Result<Vec<Attestation>, CliError> {
+        self.contract()?.try_get_user_attestations(user).map_err(backend_error)
+    }
+
+    fn get_attestation_for_evm(&self, id: [u8; 32]) -> Result<Option<(Vec<u8>, Vec<u8>)>, CliError> {
+        let exported = self.contract()?.try_get_attestation_for_evm(id).map_err(backend_error)?;
+        Ok(exported.map(|(encoded, signature)| (encoded.to_vec(), signature.to_vec())))
+    }
+
+    fn get_signer_address(&self) -> Result<[u8; 20], CliError> {
+        self.contract()?.try_get_signer_address().map_err(backend_error)
+    }
+}
+
+/// Expected cost of a command that was not submitted
+#[derive(Debug, Clone, PartialEq)]
+pub struct CostEstimate {
+    pub gas_motes: U512,
+    /// Contract hash the deploy would produce, if the estimator can tell
+    pub contract_hash: Option<String>,
+}
+
+/// Estimates commands without submitting them
+pub trait CostEstimator {
+    fn estimate_deploy(&mut self, signer_private_key: [u8; 32]) -> Result<CostEstimate, CliError>;
+    fn estimate_attest(
+        &mut self,
+        target_chain: String,
+        target_address: String,
+        custom_validity_secs: Option<u64>,
+    ) -> Result<CostEstimate, CliError>;
+}
+
+/// [`CostEstimator`] that runs the command on a throwaway local Odra
+/// environment. Only a gas-metering backend (`ODRA_BACKEND=casper` after
+/// `cargo odra build`) gives figures; the contract hash on the network
+/// depends on the real deploy, so none is reported.
+pub struct SimulatedEstimator {
+    env: HostEnv,
+}
+
+impl SimulatedEstimator {
+    pub fn new(env: HostEnv) -> Self {
+        SimulatedEstimator { env }
+    }
+
+    fn metered(&self) -> Result<CostEstimate, CliError> {
+        let gas_motes = U512::from(self.env.last_call_gas_cost());
+        if gas_motes.is_zero() {
+            return Err(CliError::Backend(
+                "The local Odra backend does not meter gas; run `cargo odra build` and set ODRA_BACKEND=casper"
+                    .to_string(),
+            ));
+        }
+        Ok(CostEstimate { gas_motes, contract_hash: None })
+    }
+}
+
+impl CostEstimator for SimulatedEstimator {
+    fn estimate_deploy(&mut self, signer_private_key: [u8; 32]) -> Result<CostEstimate, CliError> {
+        let mut backend = HostBackend::new(self.env.clone(), None);
+        backend.deploy(signer_private_key, MAX_GAS_CSPR * MOTES_PER_CSPR)?;
+        self.metered()
+    }
+
+    fn estimate_attest(
+        &mut self,
+        target_chain: String,
+        target_address: String,
+        custom_validity_secs: Option<u64>,
+    ) -> Result<CostEstimate, CliError> {
+        let mut backend = HostBackend::new(self.env.clone(), None);
+        backend.deploy(SIMULATION_SIGNER_KEY, MAX_GAS_CSPR * MOTES_PER_CSPR)?;
+        let gas = MAX_GAS_CSPR * MOTES_PER_CSPR;
+        // The simulated contract sets no proof-of-work difficulty
+        backend.create_attestation(target_chain, target_address, custom_validity_secs, None, gas)?;
+        self.metered()
+    }
+}
+
+/// Compare an estimate against the gas limit the command would use
+pub fn cost_report(operation: &str, estimate: CostEstimate, gas_limit: u64) -> CostReport {
+    CostReport {
+        operation: operation.to_string(),
+        estimated_gas_motes: estimate.gas_motes.to_string(),
+        estimated_cost_cspr: motes_to_cspr_string(estimate.gas_motes),
+        gas_limit_motes: gas_limit.to_string(),
+        gas_limit_sufficient: estimate.gas_motes <= U512::from(gas_limit),
+        contract_hash: estimate.contract_hash,
+    }
+}
+
+/// Attestation fields as shown to users
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AttestationView {
+    pub attestation_id: String,
+    pub casper_address: String,
+    pub target_chain: String,
+    pub target_address: String,
+    pub stake_motes: String,
+    pub stake_cspr: String,
+    pub tier: String,
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub attestation_validity_secs: u64,
+    pub nonce: u64,
+    pub revoked: bool,
+}
+
+impl From<&Attestation> for AttestationView {
+    fn from(attestation: &Attestation) -> Self {
+        AttestationView {
+            attestation_id: hex0x(&attestation.id),
+            casper_address: attestation.casper_address.to_string(),
+            target_chain: attestation.target_chain.clone(),
+            target_address: attestation.target_address.clone(),
+            stake_motes: attestation.stake_amount.to_string(),
+            stake_cspr: motes_to_cspr_string(attestation.stake_amount),
+            tier: format!("{:?}", attestation.tier),
+            created_at: attestation.created_at,
+            expires_at: attestation.expires_at,
+            attestation_validity_secs: attestation.attestation_validity_secs,
+            nonce: attestation.nonce,
+            revoked: attestation.revoked,
+        }
+    }
+}
+
+/// Result of one command; serialized as-is for `--json`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum Output {
+    Deployed(DeployOutput),
+    Attested(AttestOutput),
+    Revoked { attestation_id: String, revoked: bool },
+    Attestation(AttestationView),
+    Attestations(Vec<AttestationView>),
+    ExportedEvm { attestation_id: String, encoded: String, signature: String },
+    Signer { signer_address: String },
+    CostEstimate(CostReport),
+}
+
+impl fmt::Display for Output {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Output::Deployed(deployed) => {
+                writeln!(f, "VeilAttestation deployed at: {}", deployed.contract_hash)?;
+                writeln!(f, "Signer Ethereum address:     {}", deployed.signer_address)?;
+                write!(f, "Gas used:                    {} motes", deployed.gas_used)
+            }
+            Output::Attested(attested) => {
+                writeln!(f, "Attestation ID: {}", attested.attestation_id)?;
+                writeln!(f, "Expires At:     {} ms", attested.expires_at)?;
+                writeln!(f, "Signature:      {}", attested.signature)?;
+                write!(f, "Encoded:        {}", attested.encoded)
+            }
+            Output::Revoked { attestation_id, .. } => write!(f, "Revoked {}", attestation_id),
+            Output::Attestation(view) => write_view(f, view),
+            Output::Attestations(views) if views.is_empty() => write!(f, "No attestations"),
+            Output::Attestations(views) => {
+                for (index, view) in views.iter().enumerate() {
+                    if index > 0 {
+                        writeln!(f)?;
+                        writeln!(f)?;
+                    }
+                    write_view(f, view)?;
+                }
+                Ok(())
+            }
+            Output::ExportedEvm { attestation_id, encoded, signature } => {
+                writeln!(f, "Attestation ID: {}", attestation_id)?;
+                writeln!(f, "Encoded:        {}", encoded)?;
+                write!(f, "Signature:      {}", signature)
+            }
+            Output::Signer { signer_address } => write!(f, "{}", signer_address),
+            Output::CostEstimate(report) => {
+                let gas_limit = U512::from_dec_str(&report.gas_limit_motes).unwrap_or_default();
+                writeln!(f, "Dry run of {}; nothing was submitted", report.operation)?;
+                writeln!(
+                    f,
+                    "Estimated cost: {} CSPR ({} motes)",
+                    report.estimated_cost_cspr, report.estimated_gas_motes
+                )?;
+                write!(f, "Gas limit:      {} CSPR", motes_to_cspr_string(gas_limit))?;
+                if !report.gas_limit_sufficient {
+                    write!(f, " (insufficient, raise --gas)")?;
+                }
+                if let Some(contract_hash) = &report.contract_hash {
+                    write!(f, "\nContract hash:  {}", contract_hash)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+fn write_view(f: &mut fmt::Formatter<'_>, view: &AttestationView) -> fmt::Result {
+    writeln!(f, "Attestation ID: {}", view.attestation_id)?;
+    writeln!(f, "Casper Address: {}", view.casper_address)?;
+    writeln!(f, "Target:         {} on {}", view.target_address, view.target_chain)?;
+    writeln!(f, "Stake:          {} CSPR ({})", view.stake_cspr, view.tier)?;
+    writeln!(f, "Created At:     {} ms", view.created_at)?;
+    writeln!(f, "Expires At:     {} ms", view.expires_at)?;
+    writeln!(f, "Nonce:          {}", view.nonce)?;
+    write!(f, "Revoked:        {}", view.revoked)
+}
+
+/// Render a command result for the terminal
+pub fn render(result: &Result<Output, CliError>, mode: OutputMode) -> String {
+    match (result, mode) {
+        (Ok(output), OutputMode::Json) => serde_json::to_string_pretty(output).expect("Output serializes to JSON"),
+        (Ok(output), OutputMode::Human) => output.to_string(),
+        (Err(e), OutputMode::Json) => serde_json::json!({ "error": e.to_string() }).to_string(),
+        (Err(e), OutputMode::Human) => format!("Error: {}", e),
+    }
+}
+
+fn hex0x(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+/// Parse a 32-byte hex value, with or without `0x`
+pub fn parse_bytes32(name: &str, value: &str) -> Result<[u8; 32], CliError> {
+    hex::decode(value.trim_start_matches("0x"))
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| CliError::InvalidArgument(format!("{} must be 32 bytes of hex", name)))
+}
+
+/// Run one command against `backend`
+pub fn run(command: &Command, backend: &mut dyn VeilBackend) -> Result<Output, CliError> {
+    match command {
+        Command::Deploy { signer_key_env, .. } => {
+            let (contract_hash, gas_used) = backend.deploy(signer_key(signer_key_env)?, command.gas_limit())?;
+            let signer_address = hex0x(&backend.get_signer_address()?);
+            Ok(Output::Deployed(DeployOutput { contract_hash, signer_address, gas_used: gas_used.to_string() }))
+        }
+        Command::Attest { chain, address, validity_secs, pow_nonce, .. } => {
+            let (id, signature) = backend.create_attestation(
+                chain.clone(),
+                address.clone(),
+                *validity_secs,
+                *pow_nonce,
+                command.gas_limit(),
+            )?;
+            let attestation = backend.get_attestation(id)?.ok_or_else(|| not_found(&id))?;
+            let (encoded, _) = backend.get_attestation_for_evm(id)?.ok_or_else(|| not_found(&id))?;
+            Ok(Output::Attested(AttestOutput {
+                attestation_id: hex0x(&id),
+                signature: hex0x(&signature),
+                expires_at: attestation.expires_at,
+                encoded: hex0x(&encoded),
+            }))
+        }
+        Command::Revoke { id } => {
+            let id = parse_bytes32("--id", id)?;
+            backend.revoke_attestation(id)?;
+            Ok(Output::Revoked { attestation_id: hex0x(&id), revoked: true })
+        }
+        Command::Get { id } => {
+            let id = parse_bytes32("--id", id)?;
+            let attestation = backend.get_attestation(id)?.ok_or_else(|| not_found(&id))?;
+            Ok(Output::Attestation(AttestationView::from(&attestation)))
+        }
+        Command::List { user } => {
+            let user = Address::from_str(user)
+                .map_err(|_| CliError::InvalidArgument(format!("Invalid Casper address: {}", user)))?;
+            let attestations = backend.get_user_attestations(user)?;
+            Ok(Output::Attestations(attestations.iter().map(AttestationView::from).collect()))
+        }
+        Command::ExportEvm { id } => {
+            let id = parse_bytes32("--id", id)?;
+            let (encoded, signature) = backend.get_attestation_for_evm(id)?.ok_or_else(|| not_found(&id))?;
+            Ok(Output::ExportedEvm { attestation_id: hex0x(&id), encoded: hex0x(&encoded), signature: hex0x(&signature) })
+        }
+        Command::Signer => Ok(Output::Signer { signer_address: hex0x(&backend.get_signer_address()?) }),
+    }
+}
+
+/// Estimate `command` instead of running it (`--dry-run`)
+pub fn dry_run(command: &Command, estimator: &mut dyn CostEstimator) -> Result<Output, CliError> {
+    let (operation, estimate) = match command {
+        Command::Deploy { signer_key_env, .. } => ("deploy", estimator.estimate_deploy(signer_key(signer_key_env)?)?),
+        Command::Attest { chain, address, validity_secs, .. } => {
+            ("attest", estimator.estimate_attest(chain.clone(), address.clone(), *validity_secs)?)
+        }
+        _ => return Err(CliError::InvalidArgument("Only deploy and attest support --dry-run".to_string())),
+    };
+    Ok(Output::CostEstimate(cost_report(operation, estimate, command.gas_limit())))
+}
+
+fn signer_key(env_var: &str) -> Result<[u8; 32], CliError> {
+    let key = std::env::var(env_var).map_err(|_| CliError::InvalidArgument(format!("{} is not set", env_var)))?;
+    parse_bytes32(env_var, &key)
+}
+
+fn not_found(id: &[u8; 32]) -> CliError {
+    CliError::NotFound(format!("Attestation {} not found", hex0x(id)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::vec;
+    use crate::config::{parse_networks, VeilConfig};
+
+    const SIGNER_KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    const TARGET: &str = "0x70997970c51812dc3a010c7d01b50e0d17dc79c8";
+
+    fn parse(args: &[&str]) -> Cli {
+        Cli::try_parse_from(std::iter::once("veil").chain(args.iter().copied())).unwrap()
+    }
+
+    /// Backend with a freshly deployed contract; account 1 is the caller
+    fn deployed() -> (HostEnv, HostBackend, String) {
+        let env = odra_test::env();
+        let mut backend = HostBackend::new(env.clone(), None);
+        let (contract_hash, _) = backend.deploy(parse_bytes32("key", SIGNER_KEY).unwrap(), DEPLOY_GAS).unwrap();
+        env.set_caller(env.get_account(1));
+        (env, backend, contract_hash)
+    }
+
+    #[test]
+    fn test_parses_subcommands_and_global_flags() {
+        let cli = parse(&["attest", "--chain", "base-sepolia", "--address", TARGET, "--json", "--network", "casper-testnet"]);
+        assert!(cli.json);
+        assert_eq!(cli.network.as_deref(), Some("casper-testnet"));
+        assert_eq!(
+            cli.command,
+            Command::Attest {
+                chain: "base-sepolia".to_string(),
+                address: TARGET.to_string(),
+                validity_secs: None,
+                pow_nonce: None,
+                gas: GasArgs::default(),
+            }
+        );
+
+        assert_eq!(parse(&["export-evm", "--id", "0x01"]).command, Command::ExportEvm { id: "0x01".to_string() });
+        assert_eq!(
+            parse(&["deploy"]).command,
+            Command::Deploy { signer_key_env: DEFAULT_SIGNER_KEY_ENV.to_string(), gas: GasArgs::default() }
+        );
+        assert!(Cli::try_parse_from(["veil", "attest", "--chain", "base"]).is_err());
+    }
+
+    #[test]
+    fn test_global_flags_resolve_config() {
+        let networks = parse_networks(
+            std::path::Path::new("deployed-addresses.json"),
+            r#"{ "casper-testnet": { "VeilAttestation": "contract-package-aa" } }"#,
+        )
+        .unwrap();
+
+        let cli = parse(&["--network", "casper-testnet", "signer"]);
+        let config = VeilConfig::resolve(&networks, &cli.overrides());
+        assert_eq!(config.casper_contract_hash().as_deref(), Some("hash-aa"));
+
+        let cli = parse(&["--network", "casper-testnet", "--contract", "hash-bb", "signer"]);
+        let config = VeilConfig::resolve(&networks, &cli.overrides());
+        assert_eq!(config.casper_contract_hash().as_deref(), Some("hash-bb"));
+    }
+
+    #[test]
+    fn test_attest_get_export_and_revoke() {
+        let (_env, mut backend, _) = deployed();
+
+        let Output::Attested(AttestOutput { attestation_id, encoded, .. }) =
+            run(&parse(&["attest", "--chain", "base-sepolia", "--address", TARGET]).command, &mut backend).unwrap()
+        else {
+            panic!("expected an attestation");
+        };
+
+        let Output::Attestation(view) = run(&Command::Get { id: attestation_id.clone() }, &mut backend).unwrap() else {
+            panic!("expected an attestation view");
+        };
+        assert_eq!(view.target_address, TARGET);
+        assert!(!view.revoked);
+
+        let Output::ExportedEvm { encoded: exported, .. } =
+            run(&Command::ExportEvm { id: attestation_id.clone() }, &mut backend).unwrap()
+        else {
+            panic!("expected an EVM export");
+        };
+        assert_eq!(exported, encoded);
+
+        run(&Command::Revoke { id: attestation_id.clone() }, &mut backend).unwrap();
+        let Output::Attestation(view) = run(&Command::Get { id: attestation_id }, &mut backend).unwrap() else {
+            panic!("expected an attestation view");
+        };
+        assert!(view.revoked);
+    }
+
+    #[test]
+    fn test_list_and_signer() {
+        let (env, mut backend, _) = deployed();
+        run(&parse(&["attest", "--chain", "base", "--address", TARGET]).command, &mut backend).unwrap();
+
+        let user = env.get_account(1).to_string();
+        let Output::Attestations(views) = run(&Command::List { user }, &mut backend).unwrap() else {
+            panic!("expected a list");
+        };
+        assert_eq!(views.len(), 1);
+
+        assert_eq!(
+            run(&Command::Signer, &mut backend),
+            Ok(Output::Signer { signer_address: "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".to_string() })
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let (_env, mut backend, _) = deployed();
+
+        assert!(matches!(
+            run(&Command::Get { id: "0x1234".to_string() }, &mut backend),
+            Err(CliError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            run(&Command::Get { id: hex0x(&[7u8; 32]) }, &mut backend),
+            Err(CliError::NotFound(_))
+        ));
+        assert!(matches!(
+            run(&Command::List { user: "alice".to_string() }, &mut backend),
+            Err(CliError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            run(&Command::Signer, &mut HostBackend::new(odra_test::env(), None)),
+            Err(CliError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_render_human_and_json() {
+        let output = Output::Attested(AttestOutput {
+            attestation_id: "0x01".to_string(),
+            signature: "0x02".to_string(),
+            expires_at: 604_800_000,
+            encoded: "0x03".to_string(),
+        });
+
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&render(&Ok(output.clone()), OutputMode::Json)).unwrap(),
+            serde_json::json!({
+                "attestation_id": "0x01",
+                "signature": "0x02",
+                "expires_at": 604_800_000u64,
+                "encoded": "0x03",
+            })
+        );
+        assert!(render(&Ok(output), OutputMode::Human).starts_with("Attestation ID: 0x01\n"));
+
+        let error = Err(CliError::NotFound("Attestation 0x01 not found".to_string()));
+        assert_eq!(render(&error, OutputMode::Json), r#"{"error":"Attestation 0x01 not found"}"#);
+        assert_eq!(render(&error, OutputMode::Human), "Error: Attestation 0x01 not found");
+        assert_eq!(render(&Ok(Output::Attestations(vec![])), OutputMode::Human), "No attestations");
+    }
+
+    /// Estimator returning a fixed cost
+    struct FixedEstimator(u64);
+
+    impl CostEstimator for FixedEstimator {
+        fn estimate_deploy(&mut self, _: [u8; 32]) -> Result<CostEstimate, CliError> {
+            Ok(CostEstimate { gas_motes: U512::from(self.0), contract_hash: Some("hash-aa".to_string()) })
+        }
+
+        fn estimate_attest(&mut self, _: String, _: String, _: Option<u64>) -> Result<CostEstimate, CliError> {
+            Ok(CostEstimate { gas_motes: U512::from(self.0), contract_hash: None })
+        }
+    }
+
+    #[test]
+    fn test_gas_and_dry_run_flags() {
+        let command = parse(&["deploy", "--gas", "500", "--dry-run"]).command;
+        assert!(command.is_dry_run());
+        assert_eq!(command.gas_limit(), 500_000_000_000);
+
+        let command = parse(&["attest", "--chain", "base", "--address", TARGET, "--gas", "12.5"]).command;
+        assert!(!command.is_dry_run());
+        assert_eq!(command.gas_limit(), 12_500_000_000);
+
+        assert_eq!(parse(&["deploy"]).command.gas_limit(), DEPLOY_GAS);
+        assert_eq!(parse(&["attest", "--chain", "base", "--address", TARGET]).command.gas_limit(), CALL_GAS);
+        assert!(!parse(&["signer"]).command.is_dry_run());
+        assert!(Cli::try_parse_from(["veil", "revoke", "--id", "0x01", "--dry-run"]).is_err());
+    }
+
+    #[test]
+    fn test_gas_validation() {
+        assert_eq!(parse_gas_cspr("450"), Ok(450_000_000_000));
+        assert_eq!(parse_gas_cspr("0.000000001"), Ok(1));
+        assert_eq!(parse_gas_cspr("5000"), Ok(5_000_000_000_000));
+
+        for invalid in ["", "0", "0.0", "-5", "1e3", "1.0000000001", "5000.1", "50000", ".5", "abc"] {
+            assert!(parse_gas_cspr(invalid).is_err(), "{} should be rejected", invalid);
+        }
+    }
+
+    #[test]
+    fn test_dry_run_cost_report() {
+        std::env::set_var("VEIL_TEST_DRY_RUN_KEY", SIGNER_KEY);
+        let deploy = parse(&["deploy", "--signer-key-env", "VEIL_TEST_DRY_RUN_KEY", "--dry-run"]).command;
+
+        let output = dry_run(&deploy, &mut FixedEstimator(412_000_000_000)).unwrap();
+        assert_eq!(
+            serde_json::to_value(&output).unwrap(),
+            serde_json::json!({
+                "operation": "deploy",
+                "estimated_gas_motes": "412000000000",
+                "estimated_cost_cspr": "412.000000",
+                "gas_limit_motes": "450000000000",
+                "gas_limit_sufficient": true,
+                "contract_hash": "hash-aa",
+            })
+        );
+        assert_eq!(
+            output.to_string(),
+            "Dry run of deploy; nothing was submitted\n\
+             Estimated cost: 412.000000 CSPR (412000000000 motes)\n\
+             Gas limit:      450.000000 CSPR\n\
+             Contract hash:  hash-aa"
+        );
+
+        let attest = parse(&["attest", "--chain", "base", "--address", TARGET, "--gas", "1", "--dry-run"]).command;
+        let Output::CostEstimate(report) = dry_run(&attest, &mut FixedEstimator(2_500_000_000)).unwrap() else {
+            panic!("expected a cost estimate");
+        };
+        assert!(!report.gas_limit_sufficient);
+        assert!(Output::CostEstimate(report).to_string().contains("(insufficient, raise --gas)"));
+
+        assert!(matches!(
+            dry_run(&Command::Signer, &mut FixedEstimator(1)),
+            Err(CliError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_simulated_estimator_requires_metering_backend() {
+        // The default OdraVM backend reports no gas, which must not pass as "free"
+        let mut estimator = SimulatedEstimator::new(odra_test::env());
+        assert!(matches!(
+            estimator.estimate_attest("base".to_string(), TARGET.to_string(), None),
+            Err(CliError::Backend(_))
+        ));
+    }
+}
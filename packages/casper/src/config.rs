@@ -0,0 +1,406 @@
+//! Shared configuration for the livenet binaries
+//!
+//! Settings are read per network from `deployed-addresses.json` (or a
+//! `veil-config.toml`) and can be overridden on the command line:
+//!
+//! ```text
+//! --config <path>         Config file (default: ../../deployed-addresses.json)
+//! --network <name>        Network section to use (default: casper-localnet)
+//! --contract <hash>       VeilAttestation contract hash
+//! --evm-rpc-url <url>     EVM JSON-RPC endpoint
+//! --verifier <address>    VeilVerifier address
+//! --user-key-env <var>    Env var holding the EVM user's private key
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::{eprintln, format};
+use std::path::{Path, PathBuf};
+use std::prelude::v1::*;
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+/// Default config file, relative to `packages/casper`
+pub const DEFAULT_CONFIG_PATH: &str = "../../deployed-addresses.json";
+/// Default network section
+pub const DEFAULT_NETWORK: &str = "casper-localnet";
+
+/// Configuration keys a binary can require
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigKey {
+    CasperContract,
+    EvmRpcUrl,
+    VerifierAddress,
+    UserKeyEnv,
+}
+
+impl ConfigKey {
+    /// Key name in the config file
+    pub fn file_key(&self) -> &'static str {
+        match self {
+            ConfigKey::CasperContract => "VeilAttestation",
+            ConfigKey::EvmRpcUrl => "evmRpcUrl",
+            ConfigKey::VerifierAddress => "VeilVerifier",
+            ConfigKey::UserKeyEnv => "evmUserKeyEnv",
+        }
+    }
+
+    /// Command-line flag overriding the key
+    pub fn flag(&self) -> &'static str {
+        match self {
+            ConfigKey::CasperContract => "--contract",
+            ConfigKey::EvmRpcUrl => "--evm-rpc-url",
+            ConfigKey::VerifierAddress => "--verifier",
+            ConfigKey::UserKeyEnv => "--user-key-env",
+        }
+    }
+}
+
+/// Errors raised while loading or resolving configuration
+#[derive(Debug, PartialEq)]
+pub enum ConfigError {
+    /// The config file exists but could not be read
+    Read { path: PathBuf, message: String },
+    /// The config file could not be parsed
+    Parse { path: PathBuf, message: String },
+    /// A command-line flag was malformed
+    InvalidArgument(String),
+    /// Required keys are not set for the selected network
+    MissingKeys { network: String, keys: Vec<ConfigKey> },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Read { path, message } => {
+                write!(f, "could not read {}: {}", path.display(), message)
+            }
+            ConfigError::Parse { path, message } => {
+                write!(f, "could not parse {}: {}", path.display(), message)
+            }
+            ConfigError::InvalidArgument(message) => write!(f, "{}", message),
+            ConfigError::MissingKeys { network, keys } => {
+                writeln!(f, "network '{}' is missing required settings:", network)?;
+                for key in keys {
+                    writeln!(f, "  - {} (set it in the config file or pass {})", key.file_key(), key.flag())?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// One network section of the config file
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+pub struct NetworkEntry {
+    #[serde(rename = "VeilAttestation")]
+    pub casper_contract: Option<String>,
+    #[serde(rename = "evmRpcUrl")]
+    pub evm_rpc_url: Option<String>,
+    #[serde(rename = "VeilVerifier")]
+    pub verifier_address: Option<String>,
+    #[serde(rename = "evmUserKeyEnv")]
+    pub user_key_env: Option<String>,
+}
+
+/// Values given on the command line; these win over the config file
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CliOverrides {
+    pub config_path: Option<PathBuf>,
+    pub network: Option<String>,
+    pub casper_contract: Option<String>,
+    pub evm_rpc_url: Option<String>,
+    pub verifier_address: Option<String>,
+    pub user_key_env: Option<String>,
+}
+
+impl CliOverrides {
+    /// Parse the shared flags, returning the overrides and any arguments
+    /// left for the binary itself
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<(Self, Vec<String>), ConfigError> {
+        let mut overrides = CliOverrides::default();
+        let mut rest = Vec::new();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let slot = match arg.as_str() {
+                "--config" => {
+                    overrides.config_path = Some(PathBuf::from(next_value(&arg, &mut args)?));
+                    continue;
+                }
+                "--network" => &mut overrides.network,
+                "--contract" => &mut overrides.casper_contract,
+                "--evm-rpc-url" => &mut overrides.evm_rpc_url,
+                "--verifier" => &mut overrides.verifier_address,
+                "--user-key-env" => &mut overrides.user_key_env,
+                _ => {
+                    rest.push(arg);
+                    continue;
+                }
+            };
+            *slot = Some(next_value(&arg, &mut args)?);
+        }
+
+        Ok((overrides, rest))
+    }
+}
+
+fn next_value(flag: &str, args: &mut impl Iterator<Item = String>) -> Result<String, ConfigError> {
+    args.next()
+        .filter(|value| !value.starts_with("--"))
+        .ok_or_else(|| ConfigError::InvalidArgument(format!("{} requires a value", flag)))
+}
+
+/// Fully resolved configuration for one network
+#[derive(Debug, Clone, PartialEq)]
+pub struct VeilConfig {
+    pub network: String,
+    pub casper_contract: Option<String>,
+    pub evm_rpc_url: Option<String>,
+    pub verifier_address: Option<String>,
+    pub user_key_env: Option<String>,
+}
+
+impl VeilConfig {
+    /// Load the config file named by the overrides (or the default) and apply the overrides.
+    /// A missing file is treated as empty so everything can be passed on the command line.
+    pub fn load(overrides: &CliOverrides) -> Result<Self, ConfigError> {
+        let path = overrides
+            .config_path
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_CONFIG_PATH));
+
+        let networks = match std::fs::read_to_string(&path) {
+            Ok(contents) => parse_networks(&path, &contents)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound && overrides.config_path.is_none() => {
+                BTreeMap::new()
+            }
+            Err(e) => {
+                return Err(ConfigError::Read { path, message: e.to_string() });
+            }
+        };
+
+        Ok(Self::resolve(&networks, overrides))
+    }
+
+    /// Parse the process arguments, load the config and check `required` keys,
+    /// exiting with a helpful message on any error. Returns the config and the
+    /// arguments not consumed by the shared flags.
+    pub fn from_args_or_exit(required: &[ConfigKey]) -> (Self, Vec<String>) {
+        let result = CliOverrides::parse(std::env::args().skip(1)).and_then(|(overrides, rest)| {
+            let config = Self::load(&overrides)?;
+            config.require(required)?;
+            Ok((config, rest))
+        });
+
+        result.unwrap_or_else(|e| {
+            eprintln!("Configuration error: {}", e);
+            std::process::exit(2);
+        })
+    }
+
+    /// Merge a network section with the command-line overrides
+    pub fn resolve(networks: &BTreeMap<String, NetworkEntry>, overrides: &CliOverrides) -> Self {
+        let network = overrides
+            .network
+            .clone()
+            .unwrap_or_else(|| DEFAULT_NETWORK.to_string());
+        let entry = networks.get(&network).cloned().unwrap_or_default();
+
+        // Empty strings in the file mean "not deployed yet"
+        let pick = |cli: &Option<String>, file: Option<String>| {
+            cli.clone().or(file).filter(|value| !value.is_empty())
+        };
+
+        VeilConfig {
+            casper_contract: pick(&overrides.casper_contract, entry.casper_contract),
+            evm_rpc_url: pick(&overrides.evm_rpc_url, entry.evm_rpc_url),
+            verifier_address: pick(&overrides.verifier_address, entry.verifier_address),
+            user_key_env: pick(&overrides.user_key_env, entry.user_key_env),
+            network,
+        }
+    }
+
+    /// Check that every key in `keys` is set, listing all missing ones at once
+    pub fn require(&self, keys: &[ConfigKey]) -> Result<(), ConfigError> {
+        let missing: Vec<ConfigKey> = keys
+            .iter()
+            .copied()
+            .filter(|key| self.get(*key).is_none())
+            .collect();
+
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError::MissingKeys { network: self.network.clone(), keys: missing })
+        }
+    }
+
+    /// Value of a single key, if set
+    pub fn get(&self, key: ConfigKey) -> Option<&str> {
+        match key {
+            ConfigKey::CasperContract => self.casper_contract.as_deref(),
+            ConfigKey::EvmRpcUrl => self.evm_rpc_url.as_deref(),
+            ConfigKey::VerifierAddress => self.verifier_address.as_deref(),
+            ConfigKey::UserKeyEnv => self.user_key_env.as_deref(),
+        }
+    }
+
+    /// Casper contract hash in the `hash-...` form Odra's `Address` parser expects
+    pub fn casper_contract_hash(&self) -> Option<String> {
+        self.casper_contract.as_deref().map(|contract| {
+            match contract.strip_prefix("contract-package-") {
+                Some(hex) => format!("hash-{}", hex),
+                None => contract.to_string(),
+            }
+        })
+    }
+}
+
+/// Parse a config file as TOML or JSON depending on its extension
+pub fn parse_networks(path: &Path, contents: &str) -> Result<BTreeMap<String, NetworkEntry>, ConfigError> {
+    parse_file(path, contents)
+}
+
+/// Deserialize TOML or JSON depending on the file extension
+pub(crate) fn parse_file<T: DeserializeOwned>(path: &Path, contents: &str) -> Result<T, ConfigError> {
+    let is_toml = path.extension().and_then(|ext| ext.to_str()) == Some("toml");
+    let parsed = if is_toml {
+        toml::from_str(contents).map_err(|e| e.to_string())
+    } else {
+        serde_json::from_str(contents).map_err(|e| e.to_string())
+    };
+    parsed.map_err(|message| ConfigError::Parse { path: path.to_path_buf(), message })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::vec;
+
+    const JSON: &str = r#"{
+        "casper-localnet": {
+            "VeilAttestation": "contract-package-51c2d7ae1adb41abeab6ea975d376c5a3c95323cb514c08112adbbea95b5501f",
+            "signerAddress": "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+            "evmRpcUrl": "http://127.0.0.1:8545",
+            "evmUserKeyEnv": "EVM_USER_PRIVATE_KEY"
+        },
+        "casper-testnet": {
+            "VeilAttestation": ""
+        }
+    }"#;
+
+    fn networks() -> BTreeMap<String, NetworkEntry> {
+        parse_networks(Path::new("deployed-addresses.json"), JSON).unwrap()
+    }
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_loads_default_network_and_ignores_unknown_keys() {
+        let config = VeilConfig::resolve(&networks(), &CliOverrides::default());
+
+        assert_eq!(config.network, DEFAULT_NETWORK);
+        assert_eq!(config.evm_rpc_url.as_deref(), Some("http://127.0.0.1:8545"));
+        assert_eq!(config.user_key_env.as_deref(), Some("EVM_USER_PRIVATE_KEY"));
+        assert_eq!(config.verifier_address, None);
+        assert_eq!(
+            config.casper_contract_hash().as_deref(),
+            Some("hash-51c2d7ae1adb41abeab6ea975d376c5a3c95323cb514c08112adbbea95b5501f")
+        );
+    }
+
+    #[test]
+    fn test_cli_overrides_take_precedence() {
+        let (overrides, rest) = CliOverrides::parse(args(&[
+            "--evm-rpc-url", "http://10.0.0.1:8545",
+            "--verifier", "0x5FbDB2315678afecb367f032d93F642f64180aa3",
+            "--external-anvil",
+        ]))
+        .unwrap();
+        let config = VeilConfig::resolve(&networks(), &overrides);
+
+        assert_eq!(config.evm_rpc_url.as_deref(), Some("http://10.0.0.1:8545"));
+        assert_eq!(config.verifier_address.as_deref(), Some("0x5FbDB2315678afecb367f032d93F642f64180aa3"));
+        // Untouched keys still come from the file
+        assert_eq!(config.user_key_env.as_deref(), Some("EVM_USER_PRIVATE_KEY"));
+        // Binary-specific flags are passed through
+        assert_eq!(rest, args(&["--external-anvil"]));
+    }
+
+    #[test]
+    fn test_flag_without_value_is_rejected() {
+        let err = CliOverrides::parse(args(&["--network"])).unwrap_err();
+        assert_eq!(err, ConfigError::InvalidArgument("--network requires a value".to_string()));
+
+        let err = CliOverrides::parse(args(&["--contract", "--network", "x"])).unwrap_err();
+        assert_eq!(err, ConfigError::InvalidArgument("--contract requires a value".to_string()));
+    }
+
+    #[test]
+    fn test_missing_keys_are_all_listed() {
+        let overrides = CliOverrides { network: Some("casper-testnet".to_string()), ..Default::default() };
+        let config = VeilConfig::resolve(&networks(), &overrides);
+
+        let err = config
+            .require(&[ConfigKey::CasperContract, ConfigKey::EvmRpcUrl])
+            .unwrap_err();
+
+        assert_eq!(
+            err,
+            ConfigError::MissingKeys {
+                network: "casper-testnet".to_string(),
+                keys: vec![ConfigKey::CasperContract, ConfigKey::EvmRpcUrl],
+            }
+        );
+        let message = err.to_string();
+        assert!(message.contains("network 'casper-testnet'"));
+        assert!(message.contains("VeilAttestation (set it in the config file or pass --contract)"));
+        assert!(message.contains("evmRpcUrl (set it in the config file or pass --evm-rpc-url)"));
+    }
+
+    #[test]
+    fn test_unknown_network_resolves_to_empty() {
+        let overrides = CliOverrides { network: Some("nowhere".to_string()), ..Default::default() };
+        let config = VeilConfig::resolve(&networks(), &overrides);
+
+        assert!(config.require(&[ConfigKey::CasperContract]).is_err());
+    }
+
+    #[test]
+    fn test_parses_toml() {
+        let contents = r#"
+            [casper-localnet]
+            VeilAttestation = "hash-abc"
+            evmRpcUrl = "http://127.0.0.1:8545"
+        "#;
+
+        let networks = parse_networks(Path::new("veil-config.toml"), contents).unwrap();
+        let config = VeilConfig::resolve(&networks, &CliOverrides::default());
+
+        assert_eq!(config.casper_contract_hash().as_deref(), Some("hash-abc"));
+        assert!(config.require(&[ConfigKey::CasperContract, ConfigKey::EvmRpcUrl]).is_ok());
+    }
+
+    #[test]
+    fn test_parse_error_names_the_file() {
+        let err = parse_networks(Path::new("broken.json"), "{ not json").unwrap_err();
+
+        assert!(err.to_string().starts_with("could not parse broken.json"));
+    }
+
+    #[test]
+    fn test_missing_explicit_config_file_is_an_error() {
+        let overrides = CliOverrides {
+            config_path: Some(PathBuf::from("/nonexistent/veil-config.toml")),
+            ..Default::default()
+        };
+
+        assert!(matches!(VeilConfig::load(&overrides), Err(ConfigError::Read { .. })));
+    }
+}
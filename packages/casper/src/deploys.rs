@@ -0,0 +1,650 @@
+//! Deploy tracking for the livenet binaries
+//!
+//! Odra's livenet env sends one deploy per state-changing call but only
+//! reports its hash in the log. [`DeployHashLog`] captures those hashes from
+//! the `log` records; [`DeployTracker`] then polls the node for each deploy's
+//! execution result (through the `casper-client` CLI, like `evm` drives
+//! Foundry), classifies it and records it in a per-run artifact at
+//! `.veil/run-<timestamp>.json`:
+//!
+//! ```text
+//! { "name": "integration", "started_at": 1760000000000, "node_address": "...",
+//!   "deploys": [{ "label": "create_attestation", "deploy_hash": "...",
+//!                 "cost_motes": "2500000000", "outcome": { "status": "success" } }] }
+//! ```
+//!
+//! The artifact is rewritten after every deploy, so a run that dies halfway
+//! still leaves one behind.
+
+use std::fmt;
+use std::format;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::prelude::v1::*;
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use log::{Log, Metadata, Record};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::errors::VeilError;
+use crate::output::panic_message;
+
+/// Where run artifacts are written, relative to the working directory
+pub const ARTIFACT_DIR: &str = ".veil";
+/// Env var Odra's livenet env reads the node address from
+pub const NODE_ADDRESS_ENV: &str = "ODRA_CASPER_LIVENET_NODE_ADDRESS";
+/// Node address used when `NODE_ADDRESS_ENV` is unset (nctl localnet)
+pub const DEFAULT_NODE_ADDRESS: &str = "http://localhost:11101";
+/// How long to wait for a deploy's execution result by default
+pub const DEFAULT_DEPLOY_TIMEOUT: Duration = Duration::from_secs(300);
+/// Wait between two polls of the same deploy
+pub const POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// Flag overriding the deploy timeout, in seconds
+pub const DEPLOY_TIMEOUT_FLAG: &str = "--deploy-timeout";
+
+/// Errors while querying a deploy
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeployError {
+    /// `casper-client` is not installed or not on PATH
+    ToolMissing,
+    /// `casper-client` ran but exited unsuccessfully
+    CommandFailed(String),
+    /// The node's answer could not be interpreted
+    UnexpectedOutput(String),
+    /// No execution result within the timeout
+    Timeout { deploy_hash: String, waited: Duration },
+}
+
+impl fmt::Display for DeployError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeployError::ToolMissing => write!(f, "`casper-client` not found on PATH"),
+            DeployError::CommandFailed(stderr) => write!(f, "casper-client failed: {}", stderr.trim()),
+            DeployError::UnexpectedOutput(output) => write!(f, "unexpected node response: {}", output),
+            DeployError::Timeout { deploy_hash, waited } => {
+                write!(f, "deploy {} not executed after {}s", deploy_hash, waited.as_secs())
+            }
+        }
+    }
+}
+
+impl std::error::Error for DeployError {}
+
+/// Execution result of a processed deploy
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecutionResult {
+    /// Motes charged, as a decimal string
+    pub cost_motes: String,
+    /// Set when execution failed
+    pub error_message: Option<String>,
+}
+
+/// How a deploy ended
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum DeployOutcome {
+    Success,
+    /// The contract reverted; `error` names the `VeilError` if the code is ours
+    UserError { code: u16, error: Option<String> },
+    OutOfGas,
+    /// Any other execution failure
+    Failed { message: String },
+    /// No result within the timeout, or the node could not be queried
+    Unknown { message: String },
+}
+
+impl DeployOutcome {
+    /// Classify a node error message (`None` = success)
+    pub fn classify(error_message: Option<&str>) -> Self {
+        let Some(message) = error_message else {
+            return DeployOutcome::Success;
+        };
+        if let Some(code) = message.strip_prefix("User error: ").and_then(|code| code.trim().parse::<u16>().ok()) {
+            let error = VeilError::from_code(code).map(|error| format!("{:?}", error));
+            return DeployOutcome::UserError { code, error };
+        }
+        if message.to_ascii_lowercase().contains("out of gas") {
+            return DeployOutcome::OutOfGas;
+        }
+        DeployOutcome::Failed { message: message.to_string() }
+    }
+
+    /// Whether the node reported the deploy as failed (an `Unknown` outcome
+    /// is not: the call itself decides then)
+    pub fn is_failure(&self) -> bool {
+        !matches!(self, DeployOutcome::Success | DeployOutcome::Unknown { .. })
+    }
+}
+
+impl fmt::Display for DeployOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeployOutcome::Success => write!(f, "success"),
+            DeployOutcome::UserError { code, error: Some(error) } => write!(f, "reverted with {} ({})", error, code),
+            DeployOutcome::UserError { code, error: None } => write!(f, "reverted with user error {}", code),
+            DeployOutcome::OutOfGas => write!(f, "out of gas"),
+            DeployOutcome::Failed { message } => write!(f, "failed: {}", message),
+            DeployOutcome::Unknown { message } => write!(f, "unknown: {}", message),
+        }
+    }
+}
+
+/// Parse `casper-client get-deploy` output: `Ok(None)` while the deploy is
+/// still pending. Understands both Casper 2.x (`execution_info`) and 1.x
+/// (`execution_results`) responses.
+pub fn parse_execution_result(response: &Value) -> Result<Option<ExecutionResult>, DeployError> {
+    let unexpected = || DeployError::UnexpectedOutput(response.to_string());
+    let result = response.get("result").ok_or_else(unexpected)?;
+
+    let execution = if let Some(info) = result.get("execution_info") {
+        match info.get("execution_result") {
+            None | Some(Value::Null) => return Ok(None),
+            Some(execution) => execution,
+        }
+    } else if let Some(results) = result.get("execution_results").and_then(Value::as_array) {
+        match results.first().and_then(|entry| entry.get("result")) {
+            None => return Ok(None),
+            Some(execution) => execution,
+        }
+    } else {
+        return Err(unexpected());
+    };
+
+    let body = execution
+        .get("Version2")
+        .or_else(|| execution.get("Version1"))
+        .unwrap_or(execution);
+    let body = body.get("Success").or_else(|| body.get("Failure")).unwrap_or(body);
+
+    let cost_motes = match body.get("cost") {
+        Some(Value::String(cost)) => cost.clone(),
+        Some(Value::Number(cost)) => cost.to_string(),
+        _ => return Err(unexpected()),
+    };
+    let error_message = body.get("error_message").and_then(Value::as_str).map(str::to_string);
+    Ok(Some(ExecutionResult { cost_motes, error_message }))
+}
+
+/// Where execution results come from
+pub trait DeployStatusSource {
+    /// The deploy's execution result, or `None` while it is pending
+    fn execution_result(&self, deploy_hash: &str) -> Result<Option<ExecutionResult>, DeployError>;
+}
+
+/// Queries the node with the `casper-client` CLI
+pub struct CasperClient {
+    pub node_address: String,
+}
+
+impl CasperClient {
+    /// Node address from `NODE_ADDRESS_ENV`, falling back to the localnet
+    pub fn from_env() -> Self {
+        let node_address = std::env::var(NODE_ADDRESS_ENV).unwrap_or_else(|_| DEFAULT_NODE_ADDRESS.to_string());
+        CasperClient { node_address }
+    }
+}
+
+impl DeployStatusSource for CasperClient {
+    fn execution_result(&self, deploy_hash: &str) -> Result<Option<ExecutionResult>, DeployError> {
+        let output = Command::new("casper-client")
+            .args(["get-deploy", "--node-address", &self.node_address, deploy_hash])
+            .output()
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::NotFound => DeployError::ToolMissing,
+                _ => DeployError::CommandFailed(e.to_string()),
+            })?;
+        if !output.status.success() {
+            return Err(DeployError::CommandFailed(String::from_utf8_lossy(&output.stderr).to_string()));
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let response = serde_json::from_str(&stdout).map_err(|_| DeployError::UnexpectedOutput(stdout.to_string()))?;
+        parse_execution_result(&response)
+    }
+}
+
+/// Poll `source` every `interval` until the deploy has a result or `timeout`
+/// passes. `sleep` is injected so tests do not wait.
+pub fn poll_deploy(
+    source: &dyn DeployStatusSource,
+    deploy_hash: &str,
+    timeout: Duration,
+    interval: Duration,
+    sleep: &mut dyn FnMut(Duration),
+) -> Result<ExecutionResult, DeployError> {
+    let mut waited = Duration::ZERO;
+    loop {
+        if let Some(result) = source.execution_result(deploy_hash)? {
+            return Ok(result);
+        }
+        if waited >= timeout {
+            return Err(DeployError::Timeout { deploy_hash: deploy_hash.to_string(), waited });
+        }
+        sleep(interval);
+        waited += interval;
+    }
+}
+
+/// 64-character hex tokens (deploy hashes) in a log message, in order
+pub fn extract_deploy_hashes(message: &str) -> Vec<String> {
+    message
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|token| token.len() == 64 && token.bytes().all(|b| b.is_ascii_hexdigit()))
+        .map(str::to_ascii_lowercase)
+        .collect()
+}
+
+/// Deploy hashes seen in the log since the last `take`
+#[derive(Debug, Clone, Default)]
+pub struct DeployHashes(Arc<Mutex<Vec<String>>>);
+
+impl DeployHashes {
+    /// Record `hash` unless it is already pending (Odra logs it when sending
+    /// and again when processed)
+    pub fn push(&self, hash: String) {
+        let mut hashes = self.0.lock().expect("Deploy hash log poisoned");
+        if !hashes.contains(&hash) {
+            hashes.push(hash);
+        }
+    }
+
+    pub fn take(&self) -> Vec<String> {
+        std::mem::take(&mut *self.0.lock().expect("Deploy hash log poisoned"))
+    }
+}
+
+/// Logger that picks deploy hashes out of Odra's log records and forwards
+/// everything to `env_logger`
+pub struct DeployHashLog {
+    inner: env_logger::Logger,
+    hashes: DeployHashes,
+}
+
+impl DeployHashLog {
+    /// Install as the global logger (default level: info)
+    pub fn install() -> DeployHashes {
+        let inner = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).build();
+        let hashes = DeployHashes::default();
+        // Odra reports deploy hashes at info level, so see those even when
+        // RUST_LOG is quieter; `log` only forwards what the inner filter allows
+        log::set_max_level(inner.filter().max(log::LevelFilter::Info));
+        log::set_boxed_logger(Box::new(DeployHashLog { inner, hashes: hashes.clone() }))
+            .expect("Logger already installed");
+        hashes
+    }
+}
+
+impl Log for DeployHashLog {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.target().starts_with("odra") || self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if record.target().starts_with("odra") {
+            for hash in extract_deploy_hashes(&format!("{}", record.args())) {
+                self.hashes.push(hash);
+            }
+        }
+        if self.inner.enabled(record.metadata()) {
+            self.inner.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+/// One tracked deploy
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DeployRecord {
+    pub label: String,
+    pub deploy_hash: String,
+    pub cost_motes: Option<String>,
+    pub outcome: DeployOutcome,
+}
+
+/// Everything a run sent to the node
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RunArtifact {
+    pub name: String,
+    /// Unix time in ms; also names the file
+    pub started_at: u64,
+    pub node_address: String,
+    pub deploys: Vec<DeployRecord>,
+}
+
+impl RunArtifact {
+    pub fn new(name: &str, node_address: &str) -> Self {
+        let started_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis() as u64)
+            .unwrap_or_default();
+        RunArtifact { name: name.to_string(), started_at, node_address: node_address.to_string(), deploys: Vec::new() }
+    }
+
+    /// `<dir>/run-<started_at>.json`
+    pub fn path(&self, dir: &Path) -> PathBuf {
+        dir.join(format!("run-{}.json", self.started_at))
+    }
+
+    /// Write (or rewrite) the artifact, returning its path
+    pub fn write(&self, dir: &Path) -> std::io::Result<PathBuf> {
+        std::fs::create_dir_all(dir)?;
+        let path = self.path(dir);
+        let contents = serde_json::to_string_pretty(self).expect("Artifact serializes to JSON");
+        std::fs::write(&path, contents + "\n")?;
+        Ok(path)
+    }
+}
+
+/// Tracker settings shared by the livenet binaries
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackerOptions {
+    pub timeout: Duration,
+    pub artifact_dir: PathBuf,
+}
+
+impl Default for TrackerOptions {
+    fn default() -> Self {
+        TrackerOptions { timeout: DEFAULT_DEPLOY_TIMEOUT, artifact_dir: PathBuf::from(ARTIFACT_DIR) }
+    }
+}
+
+impl TrackerOptions {
+    /// Strip `--deploy-timeout <secs>` from `args`, returning the rest
+    pub fn from_args(args: Vec<String>) -> Result<(Self, Vec<String>), String> {
+        let mut options = TrackerOptions::default();
+        let mut rest = Vec::new();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            if arg != DEPLOY_TIMEOUT_FLAG {
+                rest.push(arg);
+                continue;
+            }
+            let secs = args
+                .next()
+                .and_then(|value| value.parse::<u64>().ok())
+                .filter(|secs| *secs > 0)
+                .ok_or_else(|| format!("{} requires a positive number of seconds", DEPLOY_TIMEOUT_FLAG))?;
+            options.timeout = Duration::from_secs(secs);
+        }
+        Ok((options, rest))
+    }
+}
+
+/// Confirms every deploy a state-changing call sends and keeps the run artifact
+pub struct DeployTracker<S: DeployStatusSource> {
+    source: S,
+    hashes: DeployHashes,
+    options: TrackerOptions,
+    artifact: RunArtifact,
+    sleep: Box<dyn FnMut(Duration)>,
+}
+
+impl DeployTracker<CasperClient> {
+    /// Tracker for a livenet run: installs the logger and queries the node
+    /// Odra is configured for
+    pub fn livenet(name: &str, options: TrackerOptions) -> Self {
+        let source = CasperClient::from_env();
+        let artifact = RunArtifact::new(name, &source.node_address);
+        DeployTracker::new(source, DeployHashLog::install(), options, artifact, Box::new(std::thread::sleep))
+    }
+}
+
+impl<S: DeployStatusSource> DeployTracker<S> {
+    pub fn new(
+        source: S,
+        hashes: DeployHashes,
+        options: TrackerOptions,
+        artifact: RunArtifact,
+        sleep: Box<dyn FnMut(Duration)>,
+    ) -> Self {
+        DeployTracker { source, hashes, options, artifact, sleep }
+    }
+
+    /// Run a state-changing call, then confirm the deploys it sent and
+    /// update the artifact. Panics with the decoded outcome if the call or
+    /// any of its deploys failed; deploys that could not be confirmed (no
+    /// `casper-client`, timeout) are recorded as `unknown` with a warning.
+    pub fn track<T>(&mut self, label: &str, call: impl FnOnce() -> T) -> T {
+        let result = panic::catch_unwind(AssertUnwindSafe(call));
+
+        let mut failure = None;
+        for deploy_hash in self.hashes.take() {
+            let record = self.confirm(label, deploy_hash);
+            if let DeployOutcome::Unknown { message } = &record.outcome {
+                log::warn!("Could not confirm {} deploy {}: {}", label, record.deploy_hash, message);
+            } else if record.outcome.is_failure() && failure.is_none() {
+                failure = Some(format!("{} deploy {} {}", label, record.deploy_hash, record.outcome));
+            }
+            self.artifact.deploys.push(record);
+        }
+        if let Err(e) = self.artifact.write(&self.options.artifact_dir) {
+            log::warn!("Could not write run artifact: {}", e);
+        }
+
+        match (result, failure) {
+            (Ok(value), None) => value,
+            (_, Some(failure)) => panic!("{}", failure),
+            (Err(payload), None) => panic!("{} failed: {}", label, panic_message(payload.as_ref())),
+        }
+    }
+
+    fn confirm(&mut self, label: &str, deploy_hash: String) -> DeployRecord {
+        let polled = poll_deploy(&self.source, &deploy_hash, self.options.timeout, POLL_INTERVAL, &mut *self.sleep);
+        let (cost_motes, outcome) = match polled {
+            Ok(result) => (Some(result.cost_motes), DeployOutcome::classify(result.error_message.as_deref())),
+            Err(e) => (None, DeployOutcome::Unknown { message: e.to_string() }),
+        };
+        DeployRecord { label: label.to_string(), deploy_hash, cost_motes, outcome }
+    }
+
+    pub fn artifact(&self) -> &RunArtifact {
+        &self.artifact
+    }
+
+    /// Where the artifact is (or will be) written
+    pub fn artifact_path(&self) -> PathBuf {
+        self.artifact.path(&self.options.artifact_dir)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::collections::VecDeque;
+    use std::vec;
+
+    use serde_json::json;
+
+    const HASH_A: &str = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+    const HASH_B: &str = "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
+
+    /// Replays canned node responses per deploy, then reports pending
+    #[derive(Default)]
+    struct MockNode {
+        responses: RefCell<Vec<(String, VecDeque<Result<Option<ExecutionResult>, DeployError>>)>>,
+    }
+
+    impl MockNode {
+        fn respond(self, hash: &str, responses: Vec<Result<Option<ExecutionResult>, DeployError>>) -> Self {
+            self.responses.borrow_mut().push((hash.to_string(), responses.into()));
+            self
+        }
+    }
+
+    impl DeployStatusSource for MockNode {
+        fn execution_result(&self, deploy_hash: &str) -> Result<Option<ExecutionResult>, DeployError> {
+            let mut responses = self.responses.borrow_mut();
+            let queue = responses.iter_mut().find(|(hash, _)| hash == deploy_hash).map(|(_, queue)| queue);
+            queue.and_then(|queue| queue.pop_front()).unwrap_or(Ok(None))
+        }
+    }
+
+    fn executed(cost: &str, error_message: Option<&str>) -> Result<Option<ExecutionResult>, DeployError> {
+        Ok(Some(ExecutionResult { cost_motes: cost.to_string(), error_message: error_message.map(str::to_string) }))
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("veil-deploys-{}-{}", name, std::process::id()))
+    }
+
+    fn tracker(node: MockNode, hashes: &DeployHashes, dir: &Path) -> DeployTracker<MockNode> {
+        let options = TrackerOptions { timeout: Duration::from_secs(10), artifact_dir: dir.to_path_buf() };
+        let artifact = RunArtifact::new("test", "http://node");
+        DeployTracker::new(node, hashes.clone(), options, artifact, Box::new(|_| {}))
+    }
+
+    #[test]
+    fn test_classify_outcomes() {
+        assert_eq!(DeployOutcome::classify(None), DeployOutcome::Success);
+        assert_eq!(
+            DeployOutcome::classify(Some("User error: 13")),
+            DeployOutcome::UserError { code: 13, error: Some("TooManyTags".to_string()) }
+        );
+        assert_eq!(
+            DeployOutcome::classify(Some("User error: 64000")),
+            DeployOutcome::UserError { code: 64000, error: None }
+        );
+        assert_eq!(DeployOutcome::classify(Some("Out of gas error")), DeployOutcome::OutOfGas);
+        assert_eq!(
+            DeployOutcome::classify(Some("Invalid context")),
+            DeployOutcome::Failed { message: "Invalid context".to_string() }
+        );
+    }
+
+    #[test]
+    fn test_parse_execution_result_versions() {
+        let pending_v2 = json!({ "result": { "deploy": {}, "execution_info": null } });
+        assert_eq!(parse_execution_result(&pending_v2), Ok(None));
+        let pending_v1 = json!({ "result": { "deploy": {}, "execution_results": [] } });
+        assert_eq!(parse_execution_result(&pending_v1), Ok(None));
+
+        let failed_v2 = json!({ "result": { "execution_info": { "block_hash": "00", "execution_result": {
+            "Version2": { "error_message": "User error: 2", "cost": "2500000000", "consumed": "10" }
+        } } } });
+        assert_eq!(
+            parse_execution_result(&failed_v2),
+            Ok(Some(ExecutionResult { cost_motes: "2500000000".to_string(), error_message: Some("User error: 2".to_string()) }))
+        );
+
+        let success_v1 = json!({ "result": { "execution_results": [{ "block_hash": "00", "result": {
+            "Success": { "cost": "100", "effect": {}, "transfers": [] }
+        } }] } });
+        assert_eq!(
+            parse_execution_result(&success_v1),
+            Ok(Some(ExecutionResult { cost_motes: "100".to_string(), error_message: None }))
+        );
+
+        assert!(matches!(parse_execution_result(&json!({ "error": "nope" })), Err(DeployError::UnexpectedOutput(_))));
+    }
+
+    #[test]
+    fn test_poll_deploy_waits_then_times_out() {
+        let node = MockNode::default().respond(HASH_A, vec![Ok(None), Ok(None), executed("7", None)]);
+        let mut sleeps = 0;
+        let result = poll_deploy(&node, HASH_A, Duration::from_secs(10), POLL_INTERVAL, &mut |_| sleeps += 1);
+        assert_eq!(result.unwrap().cost_motes, "7");
+        assert_eq!(sleeps, 2);
+
+        let result = poll_deploy(&MockNode::default(), HASH_B, Duration::from_secs(5), POLL_INTERVAL, &mut |_| {});
+        assert_eq!(result, Err(DeployError::Timeout { deploy_hash: HASH_B.to_string(), waited: Duration::from_secs(6) }));
+
+        let node = MockNode::default().respond(HASH_A, vec![Err(DeployError::ToolMissing)]);
+        assert_eq!(poll_deploy(&node, HASH_A, Duration::from_secs(5), POLL_INTERVAL, &mut |_| {}), Err(DeployError::ToolMissing));
+    }
+
+    #[test]
+    fn test_extract_deploy_hashes() {
+        let message = format!("Deploy \"{}\" sent; waiting for {}...", HASH_A.to_uppercase(), HASH_B);
+        assert_eq!(extract_deploy_hashes(&message), vec![HASH_A.to_string(), HASH_B.to_string()]);
+        assert!(extract_deploy_hashes("hash-0123 account-hash-abcdef").is_empty());
+
+        let hashes = DeployHashes::default();
+        hashes.push(HASH_A.to_string());
+        hashes.push(HASH_A.to_string());
+        assert_eq!(hashes.take(), vec![HASH_A.to_string()]);
+        assert!(hashes.take().is_empty());
+    }
+
+    #[test]
+    fn test_tracker_records_deploys_in_artifact() {
+        let dir = temp_dir("success");
+        let node = MockNode::default().respond(HASH_A, vec![Ok(None), executed("2500000000", None)]);
+        let hashes = DeployHashes::default();
+        let mut tracker = tracker(node, &hashes, &dir);
+
+        let value = tracker.track("create_attestation", || {
+            hashes.push(HASH_A.to_string());
+            42
+        });
+        assert_eq!(value, 42);
+
+        let written: Value = serde_json::from_str(&std::fs::read_to_string(tracker.artifact_path()).unwrap()).unwrap();
+        assert_eq!(tracker.artifact_path(), dir.join(format!("run-{}.json", tracker.artifact().started_at)));
+        assert_eq!(written["name"], "test");
+        assert_eq!(
+            written["deploys"],
+            json!([{
+                "label": "create_attestation",
+                "deploy_hash": HASH_A,
+                "cost_motes": "2500000000",
+                "outcome": { "status": "success" }
+            }])
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_tracker_reports_decoded_failure() {
+        let dir = temp_dir("failure");
+        let node = MockNode::default()
+            .respond(HASH_A, vec![executed("50000000000", Some("Out of gas error"))])
+            .respond(HASH_B, vec![executed("100", Some("User error: 2"))]);
+        let hashes = DeployHashes::default();
+        let mut tracker = tracker(node, &hashes, &dir);
+
+        let out_of_gas = panic::catch_unwind(AssertUnwindSafe(|| {
+            tracker.track("deploy", || {
+                hashes.push(HASH_A.to_string());
+                panic!("odra: deploy failed");
+            })
+        }));
+        let message = panic_message(out_of_gas.unwrap_err().as_ref());
+        assert!(message.contains("out of gas"), "{}", message);
+
+        let reverted = panic::catch_unwind(AssertUnwindSafe(|| {
+            tracker.track("set_admin_contact", || hashes.push(HASH_B.to_string()))
+        }));
+        let message = panic_message(reverted.unwrap_err().as_ref());
+        assert!(message.contains("reverted with NotAdmin (2)"), "{}", message);
+
+        // Both failures made it into the artifact
+        let outcomes: Vec<_> = tracker.artifact().deploys.iter().map(|record| record.outcome.clone()).collect();
+        assert_eq!(
+            outcomes,
+            vec![DeployOutcome::OutOfGas, DeployOutcome::UserError { code: 2, error: Some("NotAdmin".to_string()) }]
+        );
+        assert!(tracker.artifact_path().exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_tracker_options_from_args() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+        let (options, rest) = TrackerOptions::from_args(args(&["--json", "--deploy-timeout", "30"])).unwrap();
+        assert_eq!(options.timeout, Duration::from_secs(30));
+        assert_eq!(rest, args(&["--json"]));
+
+        let (options, _) = TrackerOptions::from_args(Vec::new()).unwrap();
+        assert_eq!(options, TrackerOptions::default());
+        assert!(TrackerOptions::from_args(args(&["--deploy-timeout", "0"])).is_err());
+        assert!(TrackerOptions::from_args(args(&["--deploy-timeout"])).is_err());
+    }
+}
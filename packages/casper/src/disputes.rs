@@ -0,0 +1,84 @@
+//! Bonded challenges against attestations
+//!
+//! A submodule of `VeilAttestation`, which takes the bond, checks who may
+//! resolve and revokes upheld attestations; this module only keeps the
+//! dispute records.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use odra::casper_types::U512;
+use odra::prelude::*;
+use crate::errors::VeilError;
+use crate::types::{Dispute, DisputeStatus};
+
+#[odra::module]
+pub struct Disputes {
+    /// ID of the next dispute opened
+    next_id: Var<u64>,
+    disputes: Mapping<u64, Dispute>,
+    /// IDs of disputes not yet resolved, oldest first
+    open_ids: Var<Vec<u64>>,
+    /// Open dispute against each attestation
+    open_by_attestation: Mapping<[u8; 32], Option<u64>>,
+}
+
+#[odra::module]
+impl Disputes {
+    /// Record a dispute against `attestation_id`, returning its ID
+    pub fn open(&mut self, attestation_id: [u8; 32], challenger: Address, bond: U512, reason: String) -> u64 {
+        if self.open_dispute_for(attestation_id).is_some() {
+            self.env().revert(VeilError::DisputeAlreadyOpen);
+        }
+
+        let id = self.next_id.get_or_default();
+        self.next_id.set(id + 1);
+        self.disputes.set(
+            &id,
+            Dispute {
+                id,
+                attestation_id,
+                challenger,
+                bond,
+                reason,
+                opened_at: self.env().get_block_time(),
+                status: DisputeStatus::Open,
+            },
+        );
+        let mut open_ids = self.open_ids.get_or_default();
+        open_ids.push(id);
+        self.open_ids.set(open_ids);
+        self.open_by_attestation.set(&attestation_id, Some(id));
+        id
+    }
+
+    /// Close an open dispute as upheld or rejected, returning it
+    pub fn close(&mut self, id: u64, upheld: bool) -> Dispute {
+        let mut dispute = self.get(id).unwrap_or_else(|| self.env().revert(VeilError::DisputeNotFound));
+        if dispute.status != DisputeStatus::Open {
+            self.env().revert(VeilError::DisputeNotOpen);
+        }
+
+        dispute.status = if upheld { DisputeStatus::Upheld } else { DisputeStatus::Rejected };
+        self.disputes.set(&id, dispute.clone());
+        let mut open_ids = self.open_ids.get_or_default();
+        open_ids.retain(|open_id| *open_id != id);
+        self.open_ids.set(open_ids);
+        self.open_by_attestation.set(&dispute.attestation_id, None);
+        dispute
+    }
+
+    /// Get a dispute, open or resolved
+    pub fn get(&self, id: u64) -> Option<Dispute> {
+        self.disputes.get(&id)
+    }
+
+    /// Disputes not yet resolved, oldest first
+    pub fn open_disputes(&self) -> Vec<Dispute> {
+        self.open_ids.get_or_default().iter().filter_map(|id| self.get(*id)).collect()
+    }
+
+    /// ID of the open dispute against `attestation_id`, if any
+    pub fn open_dispute_for(&self, attestation_id: [u8; 32]) -> Option<u64> {
+        self.open_by_attestation.get(&attestation_id).flatten()
+    }
+}
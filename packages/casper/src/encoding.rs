@@ -0,0 +1,978 @@
+//! ABI encoding and secp256k1 signing shared by the contract and the off-chain tools
+//!
+//! Everything here is pure so binaries (e.g. the test-vector generator) produce
+//! byte-for-byte what the contract signs.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use odra::casper_types::U512;
+use sha3::{Keccak256, Sha3_256, Digest};
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+// Signing stays available to off-chain tools in every build, but is left out
+// of the contract wasm under `no-onchain-signing`
+#[cfg(any(not(feature = "no-onchain-signing"), not(target_arch = "wasm32")))]
+use k256::ecdsa::SigningKey;
+
+use crate::types::{AttestationPayload, EvmLinkPayload};
+
+// Helper: left-pad bytes to 32 bytes
+fn pad_left_32(data: &[u8]) -> [u8; 32] {
+    let mut padded = [0u8; 32];
+    let start = 32 - data.len();
+    padded[start..].copy_from_slice(data);
+    padded
+}
+
+// Helper: convert U512 to 32-byte big-endian array
+fn u512_to_bytes32(value: &U512) -> [u8; 32] {
+    let mut bytes = [0u8; 64];
+    value.to_big_endian(&mut bytes);
+    // Take the lower 32 bytes (U512 is 64 bytes but stake fits in U256)
+    let mut result = [0u8; 32];
+    result.copy_from_slice(&bytes[32..64]);
+    result
+}
+
+/// Keccak256 hash of arbitrary bytes
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(data);
+    let result = hasher.finalize();
+    let mut output = [0u8; 32];
+    output.copy_from_slice(&result);
+    output
+}
+
+/// 4-byte ABI selector of a Solidity function signature such as
+/// `verifyAndStore(bytes,bytes)`: the first 4 bytes of its Keccak256
+pub fn compute_function_selector(function_signature: &str) -> [u8; 4] {
+    let hash = keccak256(function_signature.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// Selector of `VeilVerifier.verifyAndStore(bytes,bytes)`, i.e.
+/// `compute_function_selector("verifyAndStore(bytes,bytes)")`
+pub const VERIFY_AND_STORE_SELECTOR: [u8; 4] = [0xa8, 0x25, 0xc6, 0xf8];
+
+/// Proof-of-work hash for `create_attestation`:
+/// `keccak256(caller_bytes || target_chain || pow_nonce as big-endian u64)`,
+/// where `caller_bytes` is the caller's bytesrepr-serialized `Address`
+pub fn proof_of_work_hash(caller_bytes: &[u8], target_chain: &str, pow_nonce: u64) -> [u8; 32] {
+    let mut data = Vec::with_capacity(caller_bytes.len() + target_chain.len() + 8);
+    data.extend_from_slice(caller_bytes);
+    data.extend_from_slice(target_chain.as_bytes());
+    data.extend_from_slice(&pow_nonce.to_be_bytes());
+    keccak256(&data)
+}
+
+/// `AttestationPayload::hash_algorithm_version` of payloads identified by their Keccak256
+pub const HASH_ALGORITHM_KECCAK256: u8 = 0;
+
+/// `AttestationPayload::hash_algorithm_version` of payloads identified by their SHA3-256 (FIPS 202)
+pub const HASH_ALGORITHM_SHA3_256: u8 = 1;
+
+/// Payload slot holding the hash algorithm, see `abi_encode_payload`
+const HASH_ALGORITHM_SLOT: usize = 15;
+
+/// SHA3-256 hash of arbitrary bytes; not the Keccak256 Ethereum uses
+pub fn sha3_256(data: &[u8]) -> [u8; 32] {
+    let mut output = [0u8; 32];
+    output.copy_from_slice(&Sha3_256::digest(data));
+    output
+}
+
+/// Hash `data` with the algorithm `hash_algorithm` numbers, or `None` if
+/// the number is unknown
+pub fn hash(hash_algorithm: u8, data: &[u8]) -> Option<[u8; 32]> {
+    match hash_algorithm {
+        HASH_ALGORITHM_KECCAK256 => Some(keccak256(data)),
+        HASH_ALGORITHM_SHA3_256 => Some(sha3_256(data)),
+        _ => None,
+    }
+}
+
+/// Attestation ID of an encoded payload: its hash under the algorithm the
+/// payload names. `None` if the encoding is too short to name one or names
+/// an unknown one.
+pub fn attestation_id(encoded: &[u8]) -> Option<[u8; 32]> {
+    let word = encoded.get(HASH_ALGORITHM_SLOT * 32..(HASH_ALGORITHM_SLOT + 1) * 32)?;
+    if word[..31].iter().any(|b| *b != 0) {
+        return None;
+    }
+    hash(word[31], encoded)
+}
+
+/// Commitment a payload carries instead of its nonce:
+/// `keccak256(nonce as big-endian u64 || user_secret)`, Solidity's
+/// `keccak256(abi.encodePacked(uint64 nonce, bytes32 userSecret))`
+pub fn nonce_commitment(nonce: u64, user_secret: &[u8; 32]) -> [u8; 32] {
+    let mut data = [0u8; 40];
+    data[..8].copy_from_slice(&nonce.to_be_bytes());
+    data[8..].copy_from_slice(user_secret);
+    keccak256(&data)
+}
+
+/// Digest a claim provider signs (EIP-191) to vouch for `claim_data` about
+/// an attestation: `keccak256(attestation_id || claim_type || claim_data)`
+pub fn claim_digest(attestation_id: &[u8; 32], claim_type: u8, claim_data: &[u8]) -> [u8; 32] {
+    let mut data = Vec::with_capacity(33 + claim_data.len());
+    data.extend_from_slice(attestation_id);
+    data.push(claim_type);
+    data.extend_from_slice(claim_data);
+    keccak256(&data)
+}
+
+/// Digest the confirmation signer signs (EIP-191) to report that an EVM
+/// transaction consumed an attestation: `keccak256(attestation_id || evm_tx_hash)`
+pub fn confirmation_digest(attestation_id: &[u8; 32], evm_tx_hash: &[u8; 32]) -> [u8; 32] {
+    let mut data = [0u8; 64];
+    data[..32].copy_from_slice(attestation_id);
+    data[32..].copy_from_slice(evm_tx_hash);
+    keccak256(&data)
+}
+
+/// Fold one more claim into an attestation's claims hash:
+/// `keccak256(claims_hash || claim_digest)`, starting from zero
+pub fn fold_claims_hash(claims_hash: &[u8; 32], claim_digest: &[u8; 32]) -> [u8; 32] {
+    let mut data = [0u8; 64];
+    data[..32].copy_from_slice(claims_hash);
+    data[32..].copy_from_slice(claim_digest);
+    keccak256(&data)
+}
+
+/// Number of leading zero bits in `hash`
+pub fn leading_zero_bits(hash: &[u8; 32]) -> u32 {
+    let mut bits = 0;
+    for byte in hash {
+        bits += byte.leading_zeros();
+        if *byte != 0 {
+            break;
+        }
+    }
+    bits
+}
+
+/// Smallest nonce whose proof-of-work hash has at least `difficulty` leading
+/// zero bits. Expect around `2^difficulty` hashes.
+pub fn find_proof_of_work(caller_bytes: &[u8], target_chain: &str, difficulty: u8) -> u64 {
+    (0..=u64::MAX)
+        .find(|nonce| {
+            leading_zero_bits(&proof_of_work_hash(caller_bytes, target_chain, *nonce)) >= u32::from(difficulty)
+        })
+        .expect("a nonce exists for any difficulty up to 256 bits")
+}
+
+/// Solidity `abi.encode` of an AttestationPayload, as decoded by VeilVerifier
+pub fn abi_encode_payload(payload: &AttestationPayload) -> Vec<u8> {
+    // Layout:
+    // [0]    bytes32 casperAddressHash   - 32 bytes
+    // [1]    offset to targetChain       - 32 bytes (pointer)
+    // [2]    offset to targetAddress     - 32 bytes (pointer)
+    // [3]    uint256 stake               - 32 bytes
+    // [4]    uint8 tier (as uint256)     - 32 bytes
+    // [5]    uint64 accountAgeDays       - 32 bytes
+    // [6]    uint64 createdAt            - 32 bytes
+    // [7]    uint64 expiresAt            - 32 bytes
+    // [8]    uint64 nonce                - 32 bytes
+    // [9]    bytes20 trustedVerifier     - 32 bytes (left-aligned)
+    // [10]   uint64 casperEraId          - 32 bytes
+    // [11]   uint64 reputationScore      - 32 bytes
+    // [12]   offset to attestationType   - 32 bytes (pointer)
+    // [13]   uint64 templateId           - 32 bytes
+    // [14]   bytes32 nonceCommitment     - 32 bytes
+    // [15]   uint8 hashAlgorithmVersion  - 32 bytes
+    // [16]   bytes32 claimsHash          - 32 bytes
+    // [17]   offset to regionHint        - 32 bytes (pointer)
+    // [18]   uint8 stakeSource           - 32 bytes
+    // [19]   uint64 stakeEpoch           - 32 bytes
+    // [20+]  dynamic data for strings
+
+    let mut encoded = Vec::new();
+
+    // [0] bytes32 casperAddressHash
+    encoded.extend_from_slice(&payload.casper_address_hash);
+
+    // Calculate offsets for dynamic data
+    // Head size = 20 slots × 32 bytes = 640 bytes
+    let head_size = 20 * 32;
+    let chain_offset = head_size;
+    let chain_len = payload.target_chain.len();
+    let chain_padded = ((chain_len + 31) / 32) * 32;
+    let address_offset = chain_offset + 32 + chain_padded; // length slot + padded data
+    let addr_padded = ((payload.target_address.len() + 31) / 32) * 32;
+    let type_offset = address_offset + 32 + addr_padded;
+    let type_padded = ((payload.attestation_type.len() + 31) / 32) * 32;
+    let region_offset = type_offset + 32 + type_padded;
+
+    // [1] offset to targetChain
+    encoded.extend_from_slice(&pad_left_32(&chain_offset.to_be_bytes()));
+
+    // [2] offset to targetAddress
+    encoded.extend_from_slice(&pad_left_32(&address_offset.to_be_bytes()));
+
+    // [3] uint256 stake - convert U512 to 32 bytes big-endian
+    let stake_bytes = u512_to_bytes32(&payload.stake_amount);
+    encoded.extend_from_slice(&stake_bytes);
+
+    // [4] uint8 tier (encoded as uint256)
+    encoded.extend_from_slice(&pad_left_32(&[payload.tier]));
+
+    // [5] uint64 accountAgeDays
+    encoded.extend_from_slice(&pad_left_32(&payload.account_age_days.to_be_bytes()));
+
+    // [6] uint64 createdAt
+    encoded.extend_from_slice(&pad_left_32(&payload.created_at.to_be_bytes()));
+
+    // [7] uint64 expiresAt
+    encoded.extend_from_slice(&pad_left_32(&payload.expires_at.to_be_bytes()));
+
+    // [8] uint64 nonce
+    encoded.extend_from_slice(&pad_left_32(&payload.nonce.to_be_bytes()));
+
+    // [9] bytes20 trustedVerifier - fixed-size bytes are padded on the right
+    let mut verifier_word = [0u8; 32];
+    verifier_word[..20].copy_from_slice(&payload.trusted_verifier);
+    encoded.extend_from_slice(&verifier_word);
+
+    // [10] uint64 casperEraId
+    encoded.extend_from_slice(&pad_left_32(&payload.casper_era_id.to_be_bytes()));
+
+    // [11] uint64 reputationScore
+    encoded.extend_from_slice(&pad_left_32(&payload.reputation_score.to_be_bytes()));
+
+    // [12] offset to attestationType
+    encoded.extend_from_slice(&pad_left_32(&type_offset.to_be_bytes()));
+
+    // [13] uint64 templateId
+    encoded.extend_from_slice(&pad_left_32(&payload.template_id.to_be_bytes()));
+
+    // [14] bytes32 nonceCommitment
+    encoded.extend_from_slice(&payload.nonce_commitment);
+
+    // [15] uint8 hashAlgorithmVersion
+    encoded.extend_from_slice(&pad_left_32(&[payload.hash_algorithm_version]));
+
+    // [16] bytes32 claimsHash
+    encoded.extend_from_slice(&payload.claims_hash);
+
+    // [17] offset to regionHint
+    encoded.extend_from_slice(&pad_left_32(&region_offset.to_be_bytes()));
+
+    // [18] uint8 stakeSource
+    encoded.extend_from_slice(&pad_left_32(&[payload.stake_source]));
+
+    // [19] uint64 stakeEpoch
+    encoded.extend_from_slice(&pad_left_32(&payload.stake_epoch.to_be_bytes()));
+
+    // Dynamic data: targetChain
+    let chain_bytes = payload.target_chain.as_bytes();
+    encoded.extend_from_slice(&pad_left_32(&chain_bytes.len().to_be_bytes()));
+    encoded.extend_from_slice(chain_bytes);
+    // Pad to 32-byte boundary
+    let padding = chain_padded - chain_len;
+    encoded.extend_from_slice(&alloc::vec![0u8; padding]);
+
+    // Dynamic data: targetAddress
+    let addr_bytes = payload.target_address.as_bytes();
+    encoded.extend_from_slice(&pad_left_32(&addr_bytes.len().to_be_bytes()));
+    encoded.extend_from_slice(addr_bytes);
+    let addr_padding = addr_padded - addr_bytes.len();
+    encoded.extend_from_slice(&alloc::vec![0u8; addr_padding]);
+
+    // Dynamic data: attestationType
+    let type_bytes = payload.attestation_type.as_bytes();
+    encoded.extend_from_slice(&pad_left_32(&type_bytes.len().to_be_bytes()));
+    encoded.extend_from_slice(type_bytes);
+    encoded.extend_from_slice(&alloc::vec![0u8; type_padded - type_bytes.len()]);
+
+    // Dynamic data: regionHint
+    let region_bytes = payload.region_hint.as_bytes();
+    let region_padded = ((region_bytes.len() + 31) / 32) * 32;
+    encoded.extend_from_slice(&pad_left_32(&region_bytes.len().to_be_bytes()));
+    encoded.extend_from_slice(region_bytes);
+    encoded.extend_from_slice(&alloc::vec![0u8; region_padded - region_bytes.len()]);
+
+    encoded
+}
+
+/// Inverse of `abi_encode_payload`; `None` if `data` is not a well-formed encoding
+pub fn abi_decode_payload(data: &[u8]) -> Option<AttestationPayload> {
+    let word = |slot: usize| data.get(slot * 32..(slot + 1) * 32);
+    // Big-endian integer in the low `size` bytes of a word whose high bytes are zero
+    let uint = |word: &[u8], size: usize| -> Option<u64> {
+        let (high, low) = word.split_at(32 - size);
+        if high.iter().any(|b| *b != 0) {
+            return None;
+        }
+        Some(low.iter().fold(0u64, |acc, b| (acc << 8) | u64::from(*b)))
+    };
+    let string_at = |offset_slot: usize| -> Option<String> {
+        let offset = usize::try_from(uint(word(offset_slot)?, 8)?).ok()?;
+        let len_end = offset.checked_add(32)?;
+        let len = usize::try_from(uint(data.get(offset..len_end)?, 8)?).ok()?;
+        let bytes = data.get(len_end..len_end.checked_add(len)?)?;
+        String::from_utf8(bytes.to_vec()).ok()
+    };
+
+    let mut casper_address_hash = [0u8; 32];
+    casper_address_hash.copy_from_slice(word(0)?);
+    let mut nonce_commitment = [0u8; 32];
+    nonce_commitment.copy_from_slice(word(14)?);
+    let mut claims_hash = [0u8; 32];
+    claims_hash.copy_from_slice(word(16)?);
+    let (verifier, verifier_padding) = word(9)?.split_at(20);
+    if verifier_padding.iter().any(|b| *b != 0) {
+        return None;
+    }
+
+    Some(AttestationPayload {
+        casper_address_hash,
+        target_chain: string_at(1)?,
+        target_address: string_at(2)?,
+        stake_amount: U512::from_big_endian(word(3)?),
+        tier: uint(word(4)?, 1)? as u8,
+        account_age_days: uint(word(5)?, 8)?,
+        created_at: uint(word(6)?, 8)?,
+        expires_at: uint(word(7)?, 8)?,
+        nonce: uint(word(8)?, 8)?,
+        trusted_verifier: verifier.try_into().ok()?,
+        casper_era_id: uint(word(10)?, 8)?,
+        reputation_score: uint(word(11)?, 8)?,
+        attestation_type: string_at(12)?,
+        template_id: uint(word(13)?, 8)?,
+        nonce_commitment,
+        hash_algorithm_version: uint(word(HASH_ALGORITHM_SLOT)?, 1)? as u8,
+        claims_hash,
+        region_hint: string_at(17)?,
+        stake_source: uint(word(18)?, 1)? as u8,
+        stake_epoch: uint(word(19)?, 8)?,
+    })
+}
+
+/// ABI-encode an EVM link payload: four static words, `(address evmAddress,
+/// bytes32 casperAccountHash, uint64 expiresAt, uint64 nonce)`
+pub fn abi_encode_evm_link_payload(payload: &EvmLinkPayload) -> Vec<u8> {
+    let mut encoded = Vec::with_capacity(4 * 32);
+    encoded.extend_from_slice(&pad_left_32(&payload.evm_address));
+    encoded.extend_from_slice(&payload.casper_account_hash);
+    encoded.extend_from_slice(&pad_left_32(&payload.expires_at.to_be_bytes()));
+    encoded.extend_from_slice(&pad_left_32(&payload.nonce.to_be_bytes()));
+    encoded
+}
+
+/// Inverse of `abi_encode_evm_link_payload`; `None` unless `data` is exactly
+/// four words with zero padding
+pub fn abi_decode_evm_link_payload(data: &[u8]) -> Option<EvmLinkPayload> {
+    if data.len() != 4 * 32 {
+        return None;
+    }
+    let word = |slot: usize| &data[slot * 32..(slot + 1) * 32];
+    // Low `size` bytes of a word whose high bytes are zero
+    let low = |word: &[u8], size: usize| -> Option<Vec<u8>> {
+        let (high, low) = word.split_at(32 - size);
+        high.iter().all(|b| *b == 0).then(|| low.to_vec())
+    };
+
+    Some(EvmLinkPayload {
+        evm_address: low(word(0), 20)?.try_into().ok()?,
+        casper_account_hash: word(1).try_into().ok()?,
+        expires_at: u64::from_be_bytes(low(word(2), 8)?.try_into().ok()?),
+        nonce: u64::from_be_bytes(low(word(3), 8)?.try_into().ok()?),
+    })
+}
+
+/// Hash of `message_hash` with the Ethereum personal_sign prefix, as recovered by the verifier
+pub fn eth_signed_message_hash(message_hash: &[u8; 32]) -> [u8; 32] {
+    // Ethereum personal_sign prefix
+    let prefix = b"\x19Ethereum Signed Message:\n32";
+    let mut prefixed = Vec::with_capacity(prefix.len() + 32);
+    prefixed.extend_from_slice(prefix);
+    prefixed.extend_from_slice(message_hash);
+
+    keccak256(&prefixed)
+}
+
+/// Uncompressed public key (64 bytes, no 0x04 prefix), or `None` for an invalid private key
+#[cfg(any(not(feature = "no-onchain-signing"), not(target_arch = "wasm32")))]
+pub fn derive_public_key(private_key: &[u8; 32]) -> Option<[u8; 64]> {
+    // Derive public key from private key using k256
+    let signing_key = SigningKey::from_bytes(&(*private_key).into()).ok()?;
+    let verifying_key = signing_key.verifying_key();
+    let public_key_point = verifying_key.to_encoded_point(false);
+
+    // Take 64 bytes (skip 0x04 prefix)
+    let mut pubkey = [0u8; 64];
+    pubkey.copy_from_slice(&public_key_point.as_bytes()[1..65]);
+    Some(pubkey)
+}
+
+/// Whether `pubkey` (64 bytes, no 0x04 prefix) is a point on secp256k1
+pub fn is_valid_public_key(pubkey: &[u8; 64]) -> bool {
+    let mut sec1 = [0u8; 65];
+    sec1[0] = 0x04;
+    sec1[1..].copy_from_slice(pubkey);
+    VerifyingKey::from_sec1_bytes(&sec1).is_ok()
+}
+
+/// Ethereum address of an uncompressed public key
+pub fn public_key_to_address(pubkey: &[u8; 64]) -> [u8; 20] {
+    let hash = keccak256(pubkey);
+    let mut addr = [0u8; 20];
+    addr.copy_from_slice(&hash[12..32]);
+    addr
+}
+
+/// Whether `address` is a `0x`-prefixed 20-byte hex address in its EIP-55
+/// mixed-case form. Addresses without letters are trivially checksummed.
+pub fn is_eip55_checksummed(address: &str) -> bool {
+    let Some(hex_digits) = address.strip_prefix("0x") else {
+        return false;
+    };
+    if hex_digits.len() != 40 || !hex_digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return false;
+    }
+    let hash = keccak256(hex_digits.to_ascii_lowercase().as_bytes());
+    hex_digits.bytes().enumerate().all(|(i, digit)| {
+        let nibble = if i % 2 == 0 { hash[i / 2] >> 4 } else { hash[i / 2] & 0x0f };
+        !digit.is_ascii_alphabetic() || digit.is_ascii_uppercase() == (nibble >= 8)
+    })
+}
+
+/// Sign `message_hash` personal_sign style, returning r (32) + s (32) + v (1)
+#[cfg(any(not(feature = "no-onchain-signing"), not(target_arch = "wasm32")))]
+pub fn sign_message(private_key: &[u8; 32], message_hash: &[u8; 32]) -> [u8; 65] {
+    sign_prehash(private_key, &eth_signed_message_hash(message_hash))
+}
+
+/// Sign `prehash` as-is (no EIP-191 prefix), returning r (32) + s (32) + v (1)
+#[cfg(any(not(feature = "no-onchain-signing"), not(target_arch = "wasm32")))]
+pub fn sign_prehash(private_key: &[u8; 32], prehash: &[u8; 32]) -> [u8; 65] {
+    let signing_key = SigningKey::from_bytes(&(*private_key).into()).expect("Invalid key");
+
+    // Sign with recoverable signature
+    let (signature, recovery_id) = signing_key
+        .sign_prehash_recoverable(prehash)
+        .expect("Signing failed");
+
+    let mut sig_bytes = [0u8; 65];
+    sig_bytes[..64].copy_from_slice(&signature.to_bytes());
+    sig_bytes[64] = recovery_id.to_byte() + 27; // v = recovery_id + 27
+    sig_bytes
+}
+
+/// Recover the Ethereum address that produced a 65-byte r || s || v signature over `prehash`
+pub fn recover_signer(prehash: &[u8; 32], signature: &[u8]) -> Option<[u8; 20]> {
+    if signature.len() != 65 {
+        return None;
+    }
+    let recovery_id = RecoveryId::from_byte(signature[64].checked_sub(27)?)?;
+    let signature = Signature::from_slice(&signature[..64]).ok()?;
+    let key = VerifyingKey::recover_from_prehash(prehash, &signature, recovery_id).ok()?;
+
+    let point = key.to_encoded_point(false);
+    let mut pubkey = [0u8; 64];
+    pubkey.copy_from_slice(&point.as_bytes()[1..65]);
+    Some(public_key_to_address(&pubkey))
+}
+
+/// Split a 65-byte r || s || v signature into `(r, s, v)`
+pub fn signature_components(signature: &[u8]) -> Option<([u8; 32], [u8; 32], u8)> {
+    if signature.len() != 65 {
+        return None;
+    }
+    Some((signature[..32].try_into().ok()?, signature[32..64].try_into().ok()?, signature[64]))
+}
+
+/// EIP-2098 compact form of a 65-byte r || s || v signature:
+/// `r || (yParity << 255 | s)`. `None` if v is not 27 or 28, or s is not
+/// low (top bit set), as the form has no room for either.
+pub fn compact_signature(signature: &[u8]) -> Option<[u8; 64]> {
+    let (r, mut s, v) = signature_components(signature)?;
+    let y_parity = v.checked_sub(27).filter(|parity| *parity <= 1)?;
+    if s[0] & 0x80 != 0 {
+        return None;
+    }
+    s[0] |= y_parity << 7;
+
+    let mut compact = [0u8; 64];
+    compact[..32].copy_from_slice(&r);
+    compact[32..].copy_from_slice(&s);
+    Some(compact)
+}
+
+/// Expand an EIP-2098 compact signature back to r || s || v
+pub fn expand_compact_signature(compact: &[u8; 64]) -> [u8; 65] {
+    let mut signature = [0u8; 65];
+    signature[..64].copy_from_slice(compact);
+    signature[32] &= 0x7f;
+    signature[64] = 27 + (compact[32] >> 7);
+    signature
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeMap;
+    use alloc::format;
+    use alloc::string::ToString;
+    use proptest::prelude::*;
+    use proptest::test_runner::{Config, RngAlgorithm, TestRng, TestRunner};
+    use super::*;
+
+    // Known-good encodings, one 32-byte word per line: the twenty head words
+    // (hash, the two string offsets, stake, tier, account age, created,
+    // expires, nonce, trusted verifier, era, reputation score, the type
+    // offset, template ID, nonce commitment, hash algorithm, claims hash, the
+    // region offset, stake source, stake epoch), then each string as a length
+    // word and padded bytes.
+    // Produced outside this crate from the Solidity ABI spec; re-check with `cast abi-encode` using
+    // "f(bytes32,string,string,uint256,uint8,uint64,uint64,uint64,uint64,bytes20,uint64,uint64,string,uint64,bytes32,uint8,bytes32,string,uint8,uint64)"
+    // and `cast keccak` (any SHA3-256 tool for the max-values vector, which names SHA3-256).
+    // A layout change must update these deliberately.
+
+    const EMPTY_CHAIN_ENCODED: &str = concat!(
+        "1111111111111111111111111111111111111111111111111111111111111111",
+        "0000000000000000000000000000000000000000000000000000000000000280",
+        "00000000000000000000000000000000000000000000000000000000000002a0",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000300",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000340",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "000000000000000000000000000000000000000000000000000000000000002a",
+        "3078313233343536373839306162636465663132333435363738393061626364",
+        "6566313233343536373800000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000008",
+        "6964656e74697479000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+    );
+    const EMPTY_CHAIN_ID: &str = "db373b6b8f9643c8744dfc8a881b3329704a01987efdaead97e207724dac29e9";
+
+    const CHAIN_ONE_FULL_SLOT_ENCODED: &str = concat!(
+        "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+        "0000000000000000000000000000000000000000000000000000000000000280",
+        "00000000000000000000000000000000000000000000000000000000000002c0",
+        "0000000000000000000000000000000000000000000000000000704857068000",
+        "0000000000000000000000000000000000000000000000000000000000000002",
+        "000000000000000000000000000000000000000000000000000000000000001e",
+        "0000000000000000000000000000000000000000000000000000018bcfe56800",
+        "0000000000000000000000000000000000000000000000000000018bf3f1ec00",
+        "0000000000000000000000000000000000000000000000000000000000000007",
+        "5fbdb2315678afecb367f032d93f642f64180aa3000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000003c7a",
+        "000000000000000000000000000000000000000000000000000000000000008c",
+        "0000000000000000000000000000000000000000000000000000000000000320",
+        "0000000000000000000000000000000000000000000000000000000000000003",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000360",
+        "0000000000000000000000000000000000000000000000000000000000000001",
+        "0000000000000000000000000000000000000000000000000000000000003c7a",
+        "0000000000000000000000000000000000000000000000000000000000000020",
+        "6162636465666768696a6b6c6d6e6f707172737475767778797a303132333435",
+        "000000000000000000000000000000000000000000000000000000000000002a",
+        "3078373039393739373063353138313264633361303130633764303162353065",
+        "3064313764633739633800000000000000000000000000000000000000000000",
+        "000000000000000000000000000000000000000000000000000000000000000d",
+        "646566692d736561736f6e2d3100000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000002",
+        "4555000000000000000000000000000000000000000000000000000000000000",
+    );
+    const CHAIN_ONE_FULL_SLOT_ID: &str = "f63e6364682507aff800f7c6de7b75de457ce8946aa45b64d987b2812a5a6b2e";
+
+    const MAX_VALUES_ENCODED: &str = concat!(
+        "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+        "0000000000000000000000000000000000000000000000000000000000000280",
+        "00000000000000000000000000000000000000000000000000000000000002c0",
+        "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+        "0000000000000000000000000000000000000000000000000000000000000005",
+        "000000000000000000000000000000000000000000000000ffffffffffffffff",
+        "000000000000000000000000000000000000000000000000ffffffffffffffff",
+        "000000000000000000000000000000000000000000000000ffffffffffffffff",
+        "000000000000000000000000000000000000000000000000ffffffffffffffff",
+        "ffffffffffffffffffffffffffffffffffffffff000000000000000000000000",
+        "000000000000000000000000000000000000000000000000ffffffffffffffff",
+        "000000000000000000000000000000000000000000000000ffffffffffffffff",
+        "0000000000000000000000000000000000000000000000000000000000000320",
+        "000000000000000000000000000000000000000000000000ffffffffffffffff",
+        "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+        "0000000000000000000000000000000000000000000000000000000000000001",
+        "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+        "0000000000000000000000000000000000000000000000000000000000000380",
+        "0000000000000000000000000000000000000000000000000000000000000003",
+        "000000000000000000000000000000000000000000000000ffffffffffffffff",
+        "000000000000000000000000000000000000000000000000000000000000000c",
+        "626173652d7365706f6c69610000000000000000000000000000000000000000",
+        "000000000000000000000000000000000000000000000000000000000000002a",
+        "3078663339666436653531616164383866366634636536616238383237323739",
+        "6366666662393232363600000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000040",
+        "7474747474747474747474747474747474747474747474747474747474747474",
+        "7474747474747474747474747474747474747474747474747474747474747474",
+        "000000000000000000000000000000000000000000000000000000000000000a",
+        "7272727272727272727200000000000000000000000000000000000000000000",
+    );
+    const MAX_VALUES_ID: &str = "9888f5718a34091db9af1d0d9510a66372909ec3d83f1ec92448ee8b3c13806d";
+
+
+    fn assert_vector(payload: AttestationPayload, encoded_hex: &str, id_hex: &str) {
+        let encoded = abi_encode_payload(&payload);
+        assert_eq!(hex::encode(&encoded), encoded_hex);
+        assert_eq!(hex::encode(attestation_id(&encoded).unwrap()), id_hex);
+        assert_eq!(abi_decode_payload(&encoded), Some(payload));
+    }
+
+    #[test]
+    fn test_leading_zero_bits() {
+        let mut hash = [0xff; 32];
+        assert_eq!(leading_zero_bits(&hash), 0);
+        hash[0] = 0x00;
+        hash[1] = 0x0f;
+        assert_eq!(leading_zero_bits(&hash), 12);
+        assert_eq!(leading_zero_bits(&[0; 32]), 256);
+    }
+
+    #[test]
+    fn test_function_selectors() {
+        // Values from `cast sig`
+        assert_eq!(compute_function_selector("transfer(address,uint256)"), [0xa9, 0x05, 0x9c, 0xbb]);
+        assert_eq!(compute_function_selector("verifyAndStore(bytes,bytes)"), VERIFY_AND_STORE_SELECTOR);
+        assert_eq!(hex::encode(VERIFY_AND_STORE_SELECTOR), "a825c6f8");
+    }
+
+    #[test]
+    fn test_eip55_checksum() {
+        // Examples from EIP-55
+        for address in [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ] {
+            assert!(is_eip55_checksummed(address), "{}", address);
+            assert!(!is_eip55_checksummed(&address.to_ascii_lowercase()), "{}", address);
+        }
+        assert!(!is_eip55_checksummed("0x5aaeb6053F3E94C9b9A09f33669435E7Ef1BeAed"));
+        assert!(is_eip55_checksummed("0x1111111111111111111111111111111111111111"));
+        assert!(!is_eip55_checksummed("5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"));
+        assert!(!is_eip55_checksummed("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeA"));
+    }
+
+    #[test]
+    fn test_nonce_commitment() {
+        let secret = [0x5e; 32];
+        let commitment = nonce_commitment(7, &secret);
+        assert_eq!(commitment, nonce_commitment(7, &secret), "commitments are deterministic");
+        assert_ne!(commitment, nonce_commitment(7, &[0x5f; 32]));
+        assert_ne!(commitment, nonce_commitment(8, &secret));
+
+        let mut preimage = 7u64.to_be_bytes().to_vec();
+        preimage.extend_from_slice(&secret);
+        assert_eq!(commitment, keccak256(&preimage));
+    }
+
+    #[test]
+    fn test_public_key_validation() {
+        let pubkey = derive_public_key(&[0x42; 32]).unwrap();
+        assert!(is_valid_public_key(&pubkey));
+        let mut off_curve = pubkey;
+        off_curve[63] ^= 1;
+        assert!(!is_valid_public_key(&off_curve));
+        assert!(!is_valid_public_key(&[0; 64]));
+    }
+
+    #[test]
+    fn test_compact_signature_round_trips() {
+        for v in [27u8, 28] {
+            let mut signature = [0x11u8; 65];
+            signature[32] = 0x7f;
+            signature[64] = v;
+
+            let compact = compact_signature(&signature).unwrap();
+            assert_eq!(compact[..32], signature[..32]);
+            assert_eq!(compact[32] >> 7, v - 27, "top bit of s carries yParity");
+            assert_eq!(compact[33..], signature[33..64]);
+            assert_eq!(expand_compact_signature(&compact), signature);
+            assert_eq!(signature_components(&signature), Some(([0x11; 32], signature[32..64].try_into().unwrap(), v)));
+        }
+
+        let mut signature = [0x11u8; 65];
+        signature[64] = 29;
+        assert_eq!(compact_signature(&signature), None, "v outside 27/28");
+        signature[64] = 27;
+        signature[32] = 0x80;
+        assert_eq!(compact_signature(&signature), None, "high s");
+        assert_eq!(signature_components(&signature[..64]), None);
+    }
+
+    #[test]
+    fn test_claim_digest_and_fold() {
+        let id = [0x1d; 32];
+        let digest = claim_digest(&id, 2, b"kyc-level-2");
+        let mut preimage = id.to_vec();
+        preimage.push(2);
+        preimage.extend_from_slice(b"kyc-level-2");
+        assert_eq!(digest, keccak256(&preimage));
+        assert_ne!(digest, claim_digest(&id, 3, b"kyc-level-2"));
+        assert_ne!(digest, claim_digest(&[0x1e; 32], 2, b"kyc-level-2"));
+
+        // Folding is order-sensitive, so the hash pins the claim sequence
+        let other = claim_digest(&id, 1, b"");
+        let both = fold_claims_hash(&fold_claims_hash(&[0; 32], &digest), &other);
+        assert_eq!(both, keccak256(&[fold_claims_hash(&[0; 32], &digest), other].concat()));
+        assert_ne!(both, fold_claims_hash(&fold_claims_hash(&[0; 32], &other), &digest));
+    }
+
+    #[test]
+    fn test_hash_algorithms() {
+        // FIPS 202 known answers; Keccak256 of the same input differs only by padding
+        assert_eq!(
+            hex::encode(sha3_256(b"")),
+            "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a"
+        );
+        assert_eq!(
+            hex::encode(sha3_256(b"abc")),
+            "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532"
+        );
+        assert_ne!(sha3_256(b"abc"), keccak256(b"abc"));
+
+        assert_eq!(hash(HASH_ALGORITHM_KECCAK256, b"abc"), Some(keccak256(b"abc")));
+        assert_eq!(hash(HASH_ALGORITHM_SHA3_256, b"abc"), Some(sha3_256(b"abc")));
+        assert_eq!(hash(2, b"abc"), None);
+
+        assert_eq!(attestation_id(&[0; 15 * 32]), None, "too short to name an algorithm");
+        let mut encoded = [0; 16 * 32];
+        encoded[16 * 32 - 1] = 2;
+        assert_eq!(attestation_id(&encoded), None, "unknown algorithm");
+        encoded[16 * 32 - 1] = HASH_ALGORITHM_SHA3_256;
+        assert_eq!(attestation_id(&encoded), Some(sha3_256(&encoded)));
+    }
+
+    #[test]
+    fn test_abi_vector_empty_chain() {
+        let payload = AttestationPayload {
+            casper_address_hash: [0x11; 32],
+            target_chain: String::new(),
+            target_address: "0x1234567890abcdef1234567890abcdef12345678".to_string(),
+            stake_amount: U512::zero(),
+            tier: 0,
+            account_age_days: 0,
+            created_at: 0,
+            expires_at: 0,
+            nonce: 0,
+            trusted_verifier: [0; 20],
+            casper_era_id: 0,
+            reputation_score: 0,
+            attestation_type: "identity".to_string(),
+            template_id: 0,
+            nonce_commitment: [0; 32],
+            hash_algorithm_version: HASH_ALGORITHM_KECCAK256,
+            claims_hash: [0; 32],
+            region_hint: String::new(),
+            stake_source: 0,
+            stake_epoch: 0,
+        };
+        assert_vector(payload, EMPTY_CHAIN_ENCODED, EMPTY_CHAIN_ID);
+    }
+
+    #[test]
+    fn test_abi_vector_chain_fills_one_slot() {
+        let mut casper_address_hash = [0u8; 32];
+        for (i, byte) in casper_address_hash.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        let payload = AttestationPayload {
+            casper_address_hash,
+            target_chain: "abcdefghijklmnopqrstuvwxyz012345".to_string(),
+            target_address: "0x70997970c51812dc3a010c7d01b50e0d17dc79c8".to_string(),
+            stake_amount: U512::from(123_456_000_000_000u64),
+            tier: 2,
+            account_age_days: 30,
+            created_at: 1_700_000_000_000,
+            expires_at: 1_700_000_000_000 + 7 * 24 * 60 * 60 * 1000,
+            nonce: 7,
+            trusted_verifier: hex::decode("5fbdb2315678afecb367f032d93f642f64180aa3").unwrap().try_into().unwrap(),
+            casper_era_id: 15_482,
+            reputation_score: 140,
+            attestation_type: "defi-season-1".to_string(),
+            template_id: 3,
+            nonce_commitment: [0; 32],
+            hash_algorithm_version: HASH_ALGORITHM_KECCAK256,
+            claims_hash: [0; 32],
+            region_hint: "EU".to_string(),
+            stake_source: 1,
+            stake_epoch: 15_482,
+        };
+        assert_eq!(payload.target_chain.len(), 32);
+        assert_vector(payload, CHAIN_ONE_FULL_SLOT_ENCODED, CHAIN_ONE_FULL_SLOT_ID);
+    }
+
+    #[test]
+    fn test_abi_vector_max_values() {
+        let payload = AttestationPayload {
+            casper_address_hash: [0xff; 32],
+            target_chain: "base-sepolia".to_string(),
+            target_address: "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".to_string(),
+            stake_amount: U512::from_big_endian(&[0xff; 32]),
+            tier: 5,
+            account_age_days: u64::MAX,
+            created_at: u64::MAX,
+            expires_at: u64::MAX,
+            nonce: u64::MAX,
+            trusted_verifier: [0xff; 20],
+            casper_era_id: u64::MAX,
+            reputation_score: u64::MAX,
+            attestation_type: "t".repeat(64),
+            template_id: u64::MAX,
+            nonce_commitment: [0xff; 32],
+            hash_algorithm_version: HASH_ALGORITHM_SHA3_256,
+            claims_hash: [0xff; 32],
+            region_hint: "r".repeat(10),
+            stake_source: 3,
+            stake_epoch: u64::MAX,
+        };
+        assert_vector(payload, MAX_VALUES_ENCODED, MAX_VALUES_ID);
+    }
+
+    /// Valid payloads: a chain name from the characters chain ids use (long
+    /// enough to span several slots), a lowercase EVM address, any stake
+    /// that fits in a uint256, an attestation type of up to 64 characters, any
+    /// template ID, nonce commitment and claims hash, a known hash algorithm,
+    /// a region hint of up to 10 characters, possibly none, a known stake
+    /// source and any stake epoch
+    fn payload_strategy() -> impl Strategy<Value = AttestationPayload> {
+        let head = (
+            any::<[u8; 32]>(),
+            "[a-z0-9-]{0,100}",
+            any::<[u8; 20]>(),
+            any::<[u8; 32]>(),
+            0u8..=5,
+            any::<u64>(),
+            any::<u64>(),
+            any::<u64>(),
+            any::<u64>(),
+            any::<[u8; 20]>(),
+            any::<u64>(),
+            any::<u64>(),
+        );
+        let tail = (
+            "[a-z0-9-]{1,64}",
+            any::<u64>(),
+            any::<[u8; 32]>(),
+            HASH_ALGORITHM_KECCAK256..=HASH_ALGORITHM_SHA3_256,
+            any::<[u8; 32]>(),
+            "[A-Z-]{0,10}",
+            0u8..=3,
+            any::<u64>(),
+        );
+        (head, tail).prop_map(
+            |(
+                (hash, chain, address, stake, tier, age, created_at, expires_at, nonce, verifier, era, score),
+                (kind, template, commitment, hash_algorithm_version, claims_hash, region_hint, source, epoch),
+            )| {
+                AttestationPayload {
+                    casper_address_hash: hash,
+                    target_chain: chain,
+                    target_address: format!("0x{}", hex::encode(address)),
+                    stake_amount: U512::from_big_endian(&stake),
+                    tier,
+                    account_age_days: age,
+                    created_at,
+                    expires_at,
+                    nonce,
+                    trusted_verifier: verifier,
+                    casper_era_id: era,
+                    reputation_score: score,
+                    attestation_type: kind,
+                    template_id: template,
+                    nonce_commitment: commitment,
+                    hash_algorithm_version,
+                    claims_hash,
+                    region_hint,
+                    stake_source: source,
+                    stake_epoch: epoch,
+                }
+            },
+        )
+    }
+
+    /// Run `test` over `strategy` with a fixed seed so failures reproduce
+    fn run_seeded<S: Strategy>(strategy: S, test: impl Fn(S::Value) -> Result<(), TestCaseError>) {
+        let config = Config { cases: 256, failure_persistence: None, ..Config::default() };
+        let mut runner = TestRunner::new_with_rng(config, TestRng::deterministic_rng(RngAlgorithm::ChaCha));
+        if let Err(e) = runner.run(&strategy, test) {
+            panic!("{}", e);
+        }
+    }
+
+    fn padded_len(len: usize) -> usize {
+        (len + 31) / 32 * 32
+    }
+
+    fn word_as_usize(encoded: &[u8], slot: usize) -> usize {
+        let word = &encoded[slot * 32..(slot + 1) * 32];
+        assert!(word[..24].iter().all(|b| *b == 0), "offset word overflows u64");
+        u64::from_be_bytes(word[24..].try_into().unwrap()) as usize
+    }
+
+    #[test]
+    fn test_abi_encoding_length_and_offsets() {
+        run_seeded(payload_strategy(), |payload| {
+            let encoded = abi_encode_payload(&payload);
+            let chain_section = 32 + padded_len(payload.target_chain.len());
+            let address_section = 32 + padded_len(payload.target_address.len());
+            let type_section = 32 + padded_len(payload.attestation_type.len());
+            let region_section = 32 + padded_len(payload.region_hint.len());
+            prop_assert_eq!(encoded.len(), 20 * 32 + chain_section + address_section + type_section + region_section);
+
+            let strings = [
+                (1, payload.target_chain.len()),
+                (2, payload.target_address.len()),
+                (12, payload.attestation_type.len()),
+                (17, payload.region_hint.len()),
+            ];
+            for (slot, len) in strings {
+                let offset = word_as_usize(&encoded, slot);
+                prop_assert!(offset >= 20 * 32, "offset {} points into the head", offset);
+                prop_assert!(offset + 32 + len <= encoded.len(), "offset {} runs past the buffer", offset);
+                prop_assert_eq!(word_as_usize(&encoded, offset / 32), len);
+            }
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_abi_encoding_round_trips() {
+        run_seeded(payload_strategy(), |payload| {
+            prop_assert_eq!(abi_decode_payload(&abi_encode_payload(&payload)), Some(payload));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_abi_encoding_ids_unique_across_corpus() {
+        run_seeded(proptest::collection::vec(payload_strategy(), 2..64), |payloads| {
+            let mut seen = BTreeMap::new();
+            for payload in payloads {
+                let id = attestation_id(&abi_encode_payload(&payload)).unwrap();
+                if let Some(previous) = seen.insert(id, payload.clone()) {
+                    prop_assert_eq!(previous, payload, "distinct payloads share id {}", hex::encode(id));
+                }
+            }
+            Ok(())
+        });
+    }
+}
@@ -0,0 +1,268 @@
+/// Errors raised by the Veil attestation contract
+#[odra::odra_error]
+pub enum VeilError {
+    /// No unused attestation ID found after bumping the nonce
+    AttestationIdCollision = 1,
+    /// Caller is not the contract admin
+    NotAdmin = 2,
+    /// Admin contact exceeds the maximum length
+    ContactTooLong = 3,
+    /// Admin contact contains non-printable ASCII bytes
+    InvalidContact = 4,
+    /// Attestation creation is paused
+    ContractPaused = 5,
+    /// Caller is neither the admin nor the emergency admin
+    NotEmergencyAdmin = 6,
+    /// Signer private key is not a valid secp256k1 scalar
+    InvalidSignerKey = 7,
+    /// Tier thresholds are not strictly increasing
+    InvalidTierThresholds = 8,
+    /// Requested custom validity is below the configured minimum
+    ValidityTooShort = 9,
+    /// Caller's stake is below the minimum required to create an attestation
+    InsufficientStake = 10,
+    /// No revocation has been requested for the attestation
+    NoPendingRevocation = 11,
+    /// The revocation delay has not passed yet
+    RevocationNotYetEffective = 12,
+    /// More tags than `max_tags` allows
+    TooManyTags = 13,
+    /// Tag is empty, too long, duplicated or not printable ASCII
+    InvalidTag = 14,
+    /// Caller is neither the attestation owner nor the admin
+    NotOwnerOrAdmin = 15,
+    /// Caller already holds an unrevoked attestation for this chain and target address
+    DuplicateAttestation = 16,
+    /// Witness signature is malformed or does not recover to an address
+    InvalidWitnessSignature = 17,
+    /// Witness signature recovers to an address outside the trusted registry
+    UntrustedWitness = 18,
+    /// The attestation already has a witness
+    AlreadyWitnessed = 19,
+    /// Security parameters are locked until `get_upgrade_lock_until`
+    UpgradeLocked = 20,
+    /// Proof-of-work nonce is missing or its hash has too few leading zero bits
+    InsufficientProofOfWork = 21,
+    /// Proof-of-work difficulty is above `MAX_POW_DIFFICULTY`
+    PowDifficultyTooHigh = 22,
+    /// Target address is not a 0x-prefixed, 40-hex-digit EVM address
+    InvalidTargetAddress = 23,
+    /// No attestation is stored under the given ID
+    AttestationNotFound = 24,
+    /// Caller does not own the attestation
+    NotAttestationOwner = 25,
+    /// The attestation is already revoked
+    AlreadyRevoked = 26,
+    /// A revocation has already been requested for the attestation
+    RevocationAlreadyPending = 27,
+    /// Caller is not a registered relayer
+    NotRelayer = 28,
+    /// The attestation owner has not enabled auto-renewal
+    AutoRenewDisabled = 29,
+    /// The attestation is not within `renewal_grace_secs` of its expiry, or has expired
+    OutsideRenewalWindow = 30,
+    /// The attestation was created or marked non-renewable
+    AttestationNotRenewable = 31,
+    /// Caller already holds as many active attestations for the chain as its limit allows
+    ChainAttestationLimitReached = 32,
+    /// EVM attestation signature is malformed or does not recover to an address
+    InvalidEvmSignature = 33,
+    /// EVM attestation signature recovers to an address other than the trusted EVM signer
+    UntrustedEvmSigner = 34,
+    /// EVM attestation is not a well-formed ABI encoding
+    InvalidEvmAttestation = 35,
+    /// The EVM attestation has already been submitted
+    EvmAttestationAlreadyUsed = 36,
+    /// The EVM attestation's expiry has passed
+    EvmAttestationExpired = 37,
+    /// The target chain's deployment is disabled
+    ChainDisabled = 38,
+    /// Caller's tier is below the target chain's minimum
+    TierBelowChainMinimum = 39,
+    /// No CSPR attached to `lock`, or a zero `unlock` amount
+    InvalidLockAmount = 40,
+    /// Unlock amount exceeds the caller's locked CSPR
+    InsufficientLockedStake = 41,
+    /// The minimum lock period has not passed since the caller's latest lock
+    LockNotMatured = 42,
+    /// The chain is already supported
+    ChainAlreadySupported = 43,
+    /// The chain already has a pending proposal
+    ChainAlreadyProposed = 44,
+    /// The chain has no pending proposal
+    ChainNotProposed = 45,
+    /// The chain's proposal period has not passed yet
+    ChainProposalNotReady = 46,
+    /// Caller is not the badge collection's minter
+    NotBadgeMinter = 47,
+    /// A badge already exists for the token ID
+    BadgeAlreadyMinted = 48,
+    /// Attestation payload has a zero address hash, empty chain, malformed
+    /// target address or an expiry not after its creation
+    IncompletePayload = 49,
+    /// Caller has no active attestation of the required tier
+    AttestationRequired = 50,
+    /// Attached CSPR differs from the dispute bond
+    InvalidDisputeBond = 51,
+    /// The attestation already has an open dispute
+    DisputeAlreadyOpen = 52,
+    /// No dispute is stored under the given ID
+    DisputeNotFound = 53,
+    /// The dispute has already been resolved
+    DisputeNotOpen = 54,
+    /// Caller is neither the admin nor the arbiter
+    NotArbiter = 55,
+    /// More IDs than `max_batch_size` were passed to a batch call
+    BatchTooLarge = 56,
+    /// The referrer is the caller itself
+    SelfReferral = 57,
+    /// The referrer has been blocked by the admin
+    ReferrerBlocked = 58,
+    /// Attestation type is empty, too long or not printable ASCII
+    InvalidAttestationType = 59,
+    /// Attestation type has not been registered by the admin
+    UnregisteredAttestationType = 60,
+    /// Attestation type is already registered
+    AttestationTypeAlreadyRegistered = 61,
+    /// No template has the given ID
+    TemplateNotFound = 62,
+    /// The template has been deactivated by the admin
+    TemplateInactive = 63,
+    /// Caller's tier is below the template's `min_tier`
+    TierBelowTemplateMinimum = 64,
+    /// Template's `min_tier` is not a tier
+    InvalidTemplate = 65,
+    /// The chain allowlist is enabled and does not list the target chain
+    ChainNotAllowed = 66,
+    /// Hash algorithm is not one of `encoding::HASH_ALGORITHM_*`
+    UnsupportedHashAlgorithm = 67,
+    /// Claim provider signature is malformed or does not recover
+    InvalidClaimSignature = 68,
+    /// The claim is not signed by a registered claim provider
+    UnknownClaimProvider = 69,
+    /// The same signed claim is already attached to the attestation
+    ClaimAlreadyAttached = 70,
+    /// Claim data exceeds `MAX_CLAIM_DATA_LEN`
+    ClaimDataTooLong = 71,
+    /// Confirmation signature is malformed, or no confirmation signer is set
+    /// or it did not sign
+    InvalidConfirmationSignature = 72,
+    /// The attestation's EVM verification is already confirmed
+    AlreadyConfirmed = 73,
+    /// The attestation has expired
+    AttestationExpired = 74,
+    /// The attestation is already frozen
+    AlreadyFrozen = 75,
+    /// The attestation is not frozen
+    NotFrozen = 76,
+    /// Region hint is empty, too long or not printable ASCII
+    InvalidRegionHint = 77,
+    /// Region hint missing or not on the region allowlist
+    RegionNotAllowed = 78,
+    /// Signature is malformed or recovers no public key
+    UnrecoverableSignature = 79,
+    /// Contract storage is not at the version the migration starts from
+    AlreadyMigrated = 80,
+    /// Time unit is not one of `TIME_UNIT_*`
+    InvalidTimeUnit = 81,
+    /// Attached motes differ from the attestation fee
+    IncorrectAttestationFee = 82,
+    /// Target address is not EIP-55 checksummed while checksums are required
+    InvalidAddressChecksum = 83,
+    /// Submitted signature does not recover to the signer over the attestation's payload
+    SignatureNotFromSigner = 84,
+}
+
+impl VeilError {
+    /// The error behind a user error code reported by the node
+    pub fn from_code(code: u16) -> Option<Self> {
+        let error = match code {
+            1 => VeilError::AttestationIdCollision,
+            2 => VeilError::NotAdmin,
+            3 => VeilError::ContactTooLong,
+            4 => VeilError::InvalidContact,
+            5 => VeilError::ContractPaused,
+            6 => VeilError::NotEmergencyAdmin,
+            7 => VeilError::InvalidSignerKey,
+            8 => VeilError::InvalidTierThresholds,
+            9 => VeilError::ValidityTooShort,
+            10 => VeilError::InsufficientStake,
+            11 => VeilError::NoPendingRevocation,
+            12 => VeilError::RevocationNotYetEffective,
+            13 => VeilError::TooManyTags,
+            14 => VeilError::InvalidTag,
+            15 => VeilError::NotOwnerOrAdmin,
+            16 => VeilError::DuplicateAttestation,
+            17 => VeilError::InvalidWitnessSignature,
+            18 => VeilError::UntrustedWitness,
+            19 => VeilError::AlreadyWitnessed,
+            20 => VeilError::UpgradeLocked,
+            21 => VeilError::InsufficientProofOfWork,
+            22 => VeilError::PowDifficultyTooHigh,
+            23 => VeilError::InvalidTargetAddress,
+            24 => VeilError::AttestationNotFound,
+            25 => VeilError::NotAttestationOwner,
+            26 => VeilError::AlreadyRevoked,
+            27 => VeilError::RevocationAlreadyPending,
+            28 => VeilError::NotRelayer,
+            29 => VeilError::AutoRenewDisabled,
+            30 => VeilError::OutsideRenewalWindow,
+            31 => VeilError::AttestationNotRenewable,
+            32 => VeilError::ChainAttestationLimitReached,
+            33 => VeilError::InvalidEvmSignature,
+            34 => VeilError::UntrustedEvmSigner,
+            35 => VeilError::InvalidEvmAttestation,
+            36 => VeilError::EvmAttestationAlreadyUsed,
+            37 => VeilError::EvmAttestationExpired,
+            38 => VeilError::ChainDisabled,
+            39 => VeilError::TierBelowChainMinimum,
+            40 => VeilError::InvalidLockAmount,
+            41 => VeilError::InsufficientLockedStake,
+            42 => VeilError::LockNotMatured,
+            43 => VeilError::ChainAlreadySupported,
+            44 => VeilError::ChainAlreadyProposed,
+            45 => VeilError::ChainNotProposed,
+            46 => VeilError::ChainProposalNotReady,
+            47 => VeilError::NotBadgeMinter,
+            48 => VeilError::BadgeAlreadyMinted,
+            49 => VeilError::IncompletePayload,
+            50 => VeilError::AttestationRequired,
+            51 => VeilError::InvalidDisputeBond,
+            52 => VeilError::DisputeAlreadyOpen,
+            53 => VeilError::DisputeNotFound,
+            54 => VeilError::DisputeNotOpen,
+            55 => VeilError::NotArbiter,
+            56 => VeilError::BatchTooLarge,
+            57 => VeilError::SelfReferral,
+            58 => VeilError::ReferrerBlocked,
+            59 => VeilError::InvalidAttestationType,
+            60 => VeilError::UnregisteredAttestationType,
+            61 => VeilError::AttestationTypeAlreadyRegistered,
+            62 => VeilError::TemplateNotFound,
+            63 => VeilError::TemplateInactive,
+            64 => VeilError::TierBelowTemplateMinimum,
+            65 => VeilError::InvalidTemplate,
+            66 => VeilError::ChainNotAllowed,
+            67 => VeilError::UnsupportedHashAlgorithm,
+            68 => VeilError::InvalidClaimSignature,
+            69 => VeilError::UnknownClaimProvider,
+            70 => VeilError::ClaimAlreadyAttached,
+            71 => VeilError::ClaimDataTooLong,
+            72 => VeilError::InvalidConfirmationSignature,
+            73 => VeilError::AlreadyConfirmed,
+            74 => VeilError::AttestationExpired,
+            75 => VeilError::AlreadyFrozen,
+            76 => VeilError::NotFrozen,
+            77 => VeilError::InvalidRegionHint,
+            78 => VeilError::RegionNotAllowed,
+            79 => VeilError::UnrecoverableSignature,
+            80 => VeilError::AlreadyMigrated,
+            81 => VeilError::InvalidTimeUnit,
+            82 => VeilError::IncorrectAttestationFee,
+            83 => VeilError::InvalidAddressChecksum,
+            84 => VeilError::SignatureNotFromSigner,
+            _ => return None,
+        };
+        Some(error)
+    }
+}
@@ -0,0 +1,356 @@
+//! Thin EVM client for the livenet binaries
+//!
+//! Drives Foundry's `anvil`, `forge` and `cast` tools so the binaries can
+//! spawn a local chain, deploy the VeilVerifier and talk to it without
+//! pulling an EVM library into the contract crate.
+
+use std::fmt;
+use std::format;
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::prelude::v1::*;
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// How long to wait for a freshly spawned Anvil to answer RPC calls
+pub const ANVIL_STARTUP_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Errors from the Foundry tools
+#[derive(Debug, Clone, PartialEq)]
+pub enum EvmError {
+    /// The tool is not installed or not on PATH
+    ToolMissing(&'static str),
+    /// The tool ran but exited unsuccessfully
+    CommandFailed { tool: &'static str, stderr: String },
+    /// The tool succeeded but its output could not be interpreted
+    UnexpectedOutput(String),
+}
+
+impl fmt::Display for EvmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvmError::ToolMissing(tool) => write!(
+                f,
+                "`{}` not found on PATH. Install Foundry (https://getfoundry.sh)",
+                tool
+            ),
+            EvmError::CommandFailed { tool, stderr } => write!(f, "{} failed: {}", tool, stderr.trim()),
+            EvmError::UnexpectedOutput(output) => write!(f, "unexpected output: {}", output),
+        }
+    }
+}
+
+impl std::error::Error for EvmError {}
+
+/// Run a Foundry tool, returning its trimmed stdout
+fn run(tool: &'static str, command: &mut Command) -> Result<String, EvmError> {
+    let output = command.output().map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            EvmError::ToolMissing(tool)
+        } else {
+            EvmError::CommandFailed { tool, stderr: e.to_string() }
+        }
+    })?;
+
+    if !output.status.success() {
+        return Err(EvmError::CommandFailed {
+            tool,
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+        });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// A running (or attached) Anvil node, killed on drop if we spawned it
+pub struct Anvil {
+    child: Option<Child>,
+    pub rpc_url: String,
+}
+
+impl Anvil {
+    /// Spawn Anvil on an ephemeral port and wait until it answers RPC calls
+    pub fn spawn() -> Result<Self, EvmError> {
+        let port = TcpListener::bind("127.0.0.1:0")
+            .and_then(|listener| listener.local_addr())
+            .map(|addr| addr.port())
+            .map_err(|e| EvmError::CommandFailed { tool: "anvil", stderr: e.to_string() })?;
+
+        let child = Command::new("anvil")
+            .args(["--port", &port.to_string(), "--silent"])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| {
+                if e.kind() == std::io::ErrorKind::NotFound {
+                    EvmError::ToolMissing("anvil")
+                } else {
+                    EvmError::CommandFailed { tool: "anvil", stderr: e.to_string() }
+                }
+            })?;
+
+        let anvil = Anvil { child: Some(child), rpc_url: format!("http://127.0.0.1:{}", port) };
+        anvil.wait_until_ready()?;
+        Ok(anvil)
+    }
+
+    /// Attach to an Anvil node we do not own
+    pub fn attach(rpc_url: String) -> Result<Self, EvmError> {
+        let anvil = Anvil { child: None, rpc_url };
+        anvil.wait_until_ready()?;
+        Ok(anvil)
+    }
+
+    /// Client for this node
+    pub fn client(&self) -> EvmClient {
+        EvmClient::new(self.rpc_url.clone())
+    }
+
+    fn wait_until_ready(&self) -> Result<(), EvmError> {
+        let started = Instant::now();
+        loop {
+            match self.client().block_number() {
+                Ok(_) => return Ok(()),
+                Err(EvmError::ToolMissing(tool)) => return Err(EvmError::ToolMissing(tool)),
+                Err(e) if started.elapsed() >= ANVIL_STARTUP_TIMEOUT => {
+                    return Err(EvmError::CommandFailed {
+                        tool: "anvil",
+                        stderr: format!("{} did not respond within {:?}: {}", self.rpc_url, ANVIL_STARTUP_TIMEOUT, e),
+                    });
+                }
+                Err(_) => std::thread::sleep(Duration::from_millis(250)),
+            }
+        }
+    }
+}
+
+impl Drop for Anvil {
+    fn drop(&mut self) {
+        if let Some(child) = self.child.as_mut() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+/// JSON-RPC client for one EVM node, backed by `cast`
+#[derive(Debug, Clone)]
+pub struct EvmClient {
+    pub rpc_url: String,
+}
+
+impl EvmClient {
+    pub fn new(rpc_url: String) -> Self {
+        EvmClient { rpc_url }
+    }
+
+    /// Deploy the VeilVerifier from the Foundry project with `casper_signer` as its signer,
+    /// reading payload timestamps in `time_unit` (a `TIME_UNIT_*` of the Casper contract)
+    pub fn deploy_verifier(&self, deployer_key: &str, casper_signer: &str, time_unit: u8) -> Result<String, EvmError> {
+        let evm_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../evm");
+        let output = run(
+            "forge",
+            Command::new("forge").current_dir(evm_dir).args([
+                "create",
+                "src/VeilVerifier.sol:VeilVerifier",
+                "--broadcast",
+                "--rpc-url", &self.rpc_url,
+                "--private-key", deployer_key,
+                "--constructor-args", casper_signer, &time_unit.to_string(),
+            ]),
+        )?;
+        parse_deployed_address(&output)
+    }
+
+    /// Send a transaction calling `signature` on `to`
+    pub fn send(&self, to: &str, signature: &str, args: &[&str], private_key: &str) -> Result<String, EvmError> {
+        let mut command = Command::new("cast");
+        command.args(["send", to, signature]).args(args);
+        command.args(["--rpc-url", &self.rpc_url, "--private-key", private_key]);
+        run("cast", &mut command)
+    }
+
+    /// Read-only call of `signature` (with return types) on `to`
+    pub fn call(&self, to: &str, signature: &str, args: &[&str]) -> Result<String, EvmError> {
+        let mut command = Command::new("cast");
+        command.args(["call", to, signature]).args(args);
+        command.args(["--rpc-url", &self.rpc_url]);
+        run("cast", &mut command)
+    }
+
+    /// Raw JSON-RPC request
+    pub fn rpc(&self, method: &str, params: &[&str]) -> Result<String, EvmError> {
+        let mut command = Command::new("cast");
+        command.args(["rpc", method]).args(params);
+        command.args(["--rpc-url", &self.rpc_url]);
+        run("cast", &mut command)
+    }
+
+    /// Submit an attestation to `VeilVerifier.verifyAndStore`
+    pub fn verify_and_store(
+        &self,
+        verifier: &str,
+        encoded: &[u8],
+        signature: &[u8],
+        private_key: &str,
+    ) -> Result<String, EvmError> {
+        let encoded = format!("0x{}", hex::encode(encoded));
+        let signature = format!("0x{}", hex::encode(signature));
+        self.send(
+            verifier,
+            "verifyAndStore(bytes,bytes)",
+            &[encoded.as_str(), signature.as_str()],
+            private_key,
+        )
+    }
+
+    /// Submit an attestation to `VeilVerifier.verifyAndStoreFor` on behalf of `user`
+    pub fn verify_and_store_for(
+        &self,
+        verifier: &str,
+        user: &str,
+        encoded: &[u8],
+        signature: &[u8],
+        private_key: &str,
+    ) -> Result<String, EvmError> {
+        let encoded = format!("0x{}", hex::encode(encoded));
+        let signature = format!("0x{}", hex::encode(signature));
+        self.send(
+            verifier,
+            "verifyAndStoreFor(address,bytes,bytes)",
+            &[user, encoded.as_str(), signature.as_str()],
+            private_key,
+        )
+    }
+
+    /// `VeilVerifier.revokeAttestation(id)` (verifier owner only)
+    pub fn revoke_attestation(&self, verifier: &str, attestation_id: &[u8; 32], owner_key: &str) -> Result<String, EvmError> {
+        let attestation_id = format!("0x{}", hex::encode(attestation_id));
+        self.send(verifier, "revokeAttestation(bytes32)", &[attestation_id.as_str()], owner_key)
+    }
+
+    /// `VeilVerifier.isVerified(user)`
+    pub fn is_verified(&self, verifier: &str, user: &str) -> Result<bool, EvmError> {
+        parse_bool(&self.call(verifier, "isVerified(address)(bool)", &[user])?)
+    }
+
+    /// `VeilVerifier.getTier(user)`
+    pub fn get_tier(&self, verifier: &str, user: &str) -> Result<String, EvmError> {
+        self.call(verifier, "getTier(address)(uint8)", &[user])
+    }
+
+    /// `VeilVerifier.getStake(user)`
+    pub fn get_stake(&self, verifier: &str, user: &str) -> Result<String, EvmError> {
+        self.call(verifier, "getStake(address)(uint256)", &[user])
+    }
+
+    /// Latest block number
+    pub fn block_number(&self) -> Result<u64, EvmError> {
+        let output = run("cast", Command::new("cast").args(["block-number", "--rpc-url", &self.rpc_url]))?;
+        parse_u64(&output)
+    }
+
+    /// Timestamp (seconds) of the latest block
+    pub fn block_timestamp(&self) -> Result<u64, EvmError> {
+        let output = run(
+            "cast",
+            Command::new("cast").args(["block", "latest", "--field", "timestamp", "--rpc-url", &self.rpc_url]),
+        )?;
+        parse_u64(&output)
+    }
+
+    /// Move the chain clock forward by `secs` and mine a block (Anvil only)
+    pub fn increase_time(&self, secs: u64) -> Result<(), EvmError> {
+        self.rpc("evm_increaseTime", &[secs.to_string().as_str()])?;
+        self.rpc("evm_mine", &[])?;
+        Ok(())
+    }
+}
+
+/// 4-byte selector of a Solidity function or custom error, as `0x`-prefixed hex
+pub fn error_selector(signature: &str) -> Result<String, EvmError> {
+    run("cast", Command::new("cast").args(["sig", signature]))
+}
+
+/// Extract the contract address from `forge create` output
+pub fn parse_deployed_address(output: &str) -> Result<String, EvmError> {
+    output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("Deployed to:").map(|addr| addr.trim().to_string()))
+        .ok_or_else(|| EvmError::UnexpectedOutput(output.to_string()))
+}
+
+/// Extract the transaction hash from `cast send` output
+pub fn parse_tx_hash(output: &str) -> Result<String, EvmError> {
+    output
+        .lines()
+        .find_map(|line| {
+            let mut fields = line.split_whitespace();
+            match (fields.next(), fields.next()) {
+                (Some("transactionHash"), Some(hash)) => Some(hash.to_string()),
+                _ => None,
+            }
+        })
+        .ok_or_else(|| EvmError::UnexpectedOutput(output.to_string()))
+}
+
+/// Parse a `cast call ...(bool)` result
+pub fn parse_bool(output: &str) -> Result<bool, EvmError> {
+    match output.trim() {
+        "true" => Ok(true),
+        "false" => Ok(false),
+        other => Err(EvmError::UnexpectedOutput(other.to_string())),
+    }
+}
+
+/// Parse a decimal number, ignoring cast's scientific-notation suffix (e.g. `1700000000 [1.7e9]`)
+pub fn parse_u64(output: &str) -> Result<u64, EvmError> {
+    output
+        .split_whitespace()
+        .next()
+        .and_then(|number| number.parse().ok())
+        .ok_or_else(|| EvmError::UnexpectedOutput(output.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_deployed_address() {
+        let output = "Deployer: 0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266\n\
+                      Deployed to: 0x5FbDB2315678afecb367f032d93F642f64180aa3\n\
+                      Transaction hash: 0xabc";
+
+        assert_eq!(
+            parse_deployed_address(output),
+            Ok("0x5FbDB2315678afecb367f032d93F642f64180aa3".to_string())
+        );
+        assert!(parse_deployed_address("Compiler run successful").is_err());
+    }
+
+    #[test]
+    fn test_parse_tx_hash() {
+        let output = "blockHash            0x1111\n\
+                      blockNumber          3\n\
+                      status               1 (success)\n\
+                      transactionHash      0x2222\n\
+                      transactionIndex     0";
+
+        assert_eq!(parse_tx_hash(output), Ok("0x2222".to_string()));
+        assert!(parse_tx_hash("status 1 (success)").is_err());
+    }
+
+    #[test]
+    fn test_parse_bool() {
+        assert_eq!(parse_bool("true\n"), Ok(true));
+        assert_eq!(parse_bool("false"), Ok(false));
+        assert!(parse_bool("0x01").is_err());
+    }
+
+    #[test]
+    fn test_parse_u64() {
+        assert_eq!(parse_u64("1700000000"), Ok(1_700_000_000));
+        assert_eq!(parse_u64("1700000000 [1.7e9]"), Ok(1_700_000_000));
+        assert!(parse_u64("").is_err());
+    }
+}
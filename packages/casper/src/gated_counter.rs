@@ -0,0 +1,87 @@
+//! Example consumer of `interface::IVeilAttestation`: a counter only callers
+//! with an active Gold or higher attestation may increment.
+
+use odra::prelude::*;
+use crate::errors::VeilError;
+use crate::interface::IVeilAttestationContractRef;
+use crate::types::Tier;
+
+#[odra::module]
+pub struct GatedCounter {
+    /// The `VeilAttestation` contract gating `increment`
+    veil: Var<Address>,
+    count: Var<u64>,
+}
+
+#[odra::module]
+impl GatedCounter {
+    /// Initialize the counter, gated by the `VeilAttestation` at `veil`
+    pub fn init(&mut self, veil: Address) {
+        self.veil.set(veil);
+    }
+
+    /// Increment the counter (Gold or higher attestation holders only)
+    pub fn increment(&mut self) {
+        let veil = self.veil.get().expect("Veil contract not set");
+        let veil = IVeilAttestationContractRef::new(self.env(), veil);
+        if !veil.check_attestation(self.env().caller(), Tier::Gold, None) {
+            self.env().revert(VeilError::AttestationRequired);
+        }
+        self.count.set(self.get_count() + 1);
+    }
+
+    /// Get the counter
+    pub fn get_count(&self) -> u64 {
+        self.count.get_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use odra::host::Deployer;
+    use odra::prelude::Addressable;
+    use super::{GatedCounter, GatedCounterHostRef, GatedCounterInitArgs};
+    use crate::errors::VeilError;
+    use crate::test_support::Fixture;
+    use crate::types::{cspr_to_motes, Tier};
+
+    /// Fixture where account 1 stakes enough for Gold and account 2 for Silver
+    fn setup() -> (Fixture, GatedCounterHostRef) {
+        let fx = Fixture::new()
+            .with_mock_stake(1, cspr_to_motes(10_000, 0))
+            .with_mock_stake(2, cspr_to_motes(1_000, 0))
+            .build();
+        let counter = GatedCounter::deploy(&fx.env, GatedCounterInitArgs { veil: fx.contract.address() });
+        (fx, counter)
+    }
+
+    #[test]
+    fn test_gold_attestation_increments() {
+        let (mut fx, mut counter) = setup();
+        let (attestation_id, _) = fx.create_default_attestation(fx.user);
+        assert!(fx.contract.check_attestation(fx.user, Tier::Gold, None));
+        assert_eq!(fx.contract.get_active_attestation(fx.user).map(|a| a.id), Some(attestation_id));
+
+        counter.increment();
+        counter.increment();
+        assert_eq!(counter.get_count(), 2);
+
+        // Revoked attestations no longer pass the gate
+        fx.contract.revoke_attestation(attestation_id);
+        assert_eq!(fx.contract.get_active_attestation(fx.user), None);
+        assert_eq!(counter.try_increment(), Err(VeilError::AttestationRequired.into()));
+    }
+
+    #[test]
+    fn test_below_gold_or_unattested_rejected() {
+        let (mut fx, mut counter) = setup();
+        assert_eq!(counter.try_increment(), Err(VeilError::AttestationRequired.into()));
+
+        // Silver stake and attestation
+        fx.create_default_attestation(fx.other);
+        assert!(fx.contract.check_attestation(fx.other, Tier::Silver, None));
+        assert!(!fx.contract.check_attestation(fx.other, Tier::Gold, None));
+        assert_eq!(counter.try_increment(), Err(VeilError::AttestationRequired.into()));
+        assert_eq!(counter.get_count(), 0);
+    }
+}
@@ -0,0 +1,256 @@
+//! Inbound direction of the bridge: EVM users prove their Ethereum identity
+//! to Casper contracts
+//!
+//! A trusted EVM-side signer signs (EIP-191) the keccak256 of an ABI-encoded
+//! `EvmLinkPayload`; anyone may submit it, and the contract records the
+//! link between the EVM address and the Casper account it names.
+
+use odra::casper_types::account::AccountHash;
+use odra::casper_types::bytesrepr::Bytes;
+use odra::prelude::*;
+use crate::encoding::{self, abi_decode_evm_link_payload, eth_signed_message_hash, keccak256};
+use crate::errors::VeilError;
+use crate::types::{EvmIdentityLinked, EvmLink};
+
+#[odra::module]
+pub struct VeilInboundVerifier {
+    admin: Var<Address>,
+    /// Ethereum address whose signatures are accepted
+    trusted_signer: Var<[u8; 20]>,
+    /// Submitted attestation IDs (`keccak256` of the encoding), for replay protection
+    used_attestations: Mapping<[u8; 32], bool>,
+    /// Expiry (ms) of each EVM address's latest link
+    evm_verified_until: Mapping<[u8; 20], u64>,
+    /// Latest EVM link per Casper account
+    evm_links: Mapping<Address, EvmLink>,
+}
+
+#[odra::module]
+impl VeilInboundVerifier {
+    /// Initialize the contract with the EVM-side signer to trust
+    pub fn init(&mut self, admin: Address, trusted_signer: [u8; 20]) {
+        self.admin.set(admin);
+        self.trusted_signer.set(trusted_signer);
+    }
+
+    /// Verify and record an EVM-originated attestation (any caller). The
+    /// signature must be an EIP-191 signature by the trusted signer over the
+    /// keccak256 of `encoded`; each encoding is accepted once.
+    pub fn submit_evm_attestation(&mut self, encoded: Bytes, signature: Bytes) {
+        let attestation_id = keccak256(&encoded);
+        let signer = encoding::recover_signer(&eth_signed_message_hash(&attestation_id), &signature)
+            .unwrap_or_else(|| self.env().revert(VeilError::InvalidEvmSignature));
+        if signer != self.get_trusted_signer() {
+            self.env().revert(VeilError::UntrustedEvmSigner);
+        }
+
+        let payload = abi_decode_evm_link_payload(&encoded)
+            .unwrap_or_else(|| self.env().revert(VeilError::InvalidEvmAttestation));
+        if self.used_attestations.get(&attestation_id).unwrap_or_default() {
+            self.env().revert(VeilError::EvmAttestationAlreadyUsed);
+        }
+        if self.env().get_block_time() >= payload.expires_at {
+            self.env().revert(VeilError::EvmAttestationExpired);
+        }
+
+        let casper_account = Address::Account(AccountHash::new(payload.casper_account_hash));
+        self.used_attestations.set(&attestation_id, true);
+        self.evm_verified_until.set(&payload.evm_address, payload.expires_at);
+        self.evm_links.set(
+            &casper_account,
+            EvmLink { evm_address: payload.evm_address, expires_at: payload.expires_at, attestation_id },
+        );
+
+        self.env().emit_event(EvmIdentityLinked {
+            evm_address: payload.evm_address,
+            casper_account,
+            expires_at: payload.expires_at,
+        });
+    }
+
+    // ============ ADMIN FUNCTIONS ============
+
+    /// Replace the trusted EVM-side signer (admin only)
+    pub fn set_trusted_signer(&mut self, signer: [u8; 20]) {
+        if Some(self.env().caller()) != self.admin.get() {
+            self.env().revert(VeilError::NotAdmin);
+        }
+        self.trusted_signer.set(signer);
+    }
+
+    // ============ VIEW FUNCTIONS ============
+
+    /// Get the trusted EVM-side signer
+    pub fn get_trusted_signer(&self) -> [u8; 20] {
+        self.trusted_signer.get_or_default()
+    }
+
+    /// Whether `evm_address`'s latest link has not expired
+    pub fn is_evm_verified(&self, evm_address: [u8; 20]) -> bool {
+        self.evm_verified_until.get(&evm_address).is_some_and(|expires_at| self.env().get_block_time() < expires_at)
+    }
+
+    /// Get the latest EVM link recorded for `casper_account`, expired or not
+    pub fn get_evm_link(&self, casper_account: Address) -> Option<EvmLink> {
+        self.evm_links.get(&casper_account)
+    }
+
+    /// Whether the attestation with this ID has been submitted
+    pub fn is_attestation_used(&self, attestation_id: [u8; 32]) -> bool {
+        self.used_attestations.get(&attestation_id).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use odra::casper_types::account::AccountHash;
+    use odra::casper_types::bytesrepr::Bytes;
+    use odra::host::{Deployer, HostEnv};
+    use odra::prelude::Address;
+    use super::{VeilInboundVerifier, VeilInboundVerifierHostRef, VeilInboundVerifierInitArgs};
+    use crate::encoding::{abi_decode_evm_link_payload, abi_encode_evm_link_payload, keccak256, sign_message};
+    use crate::errors::VeilError;
+    use crate::test_support::{EXPECTED_SIGNER, ROTATED_PRIVATE_KEY, ROTATED_SIGNER, TEST_PRIVATE_KEY};
+    use crate::types::{EvmIdentityLinked, EvmLink, EvmLinkPayload};
+
+    const EVM_ADDRESS: [u8; 20] = [0x12; 20];
+    const CASPER_ACCOUNT_HASH: [u8; 32] = [0x34; 32];
+    const HOUR_MS: u64 = 60 * 60 * 1000;
+
+    fn setup() -> (HostEnv, VeilInboundVerifierHostRef) {
+        let env = odra_test::env();
+        let admin = env.get_account(0);
+        let contract = VeilInboundVerifier::deploy(
+            &env,
+            VeilInboundVerifierInitArgs { admin, trusted_signer: EXPECTED_SIGNER },
+        );
+        (env, contract)
+    }
+
+    /// Encoding of a payload expiring an hour from now, and its signature by `key`
+    fn signed_payload(env: &HostEnv, key: &[u8; 32], nonce: u64) -> (Bytes, Bytes) {
+        let encoded = abi_encode_evm_link_payload(&EvmLinkPayload {
+            evm_address: EVM_ADDRESS,
+            casper_account_hash: CASPER_ACCOUNT_HASH,
+            expires_at: env.block_time() + HOUR_MS,
+            nonce,
+        });
+        let signature = sign_message(key, &keccak256(&encoded));
+        (Bytes::from(encoded), Bytes::from(signature.to_vec()))
+    }
+
+    #[test]
+    fn test_evm_link_payload_round_trips() {
+        let payload = EvmLinkPayload {
+            evm_address: EVM_ADDRESS,
+            casper_account_hash: CASPER_ACCOUNT_HASH,
+            expires_at: u64::MAX,
+            nonce: 7,
+        };
+        let encoded = abi_encode_evm_link_payload(&payload);
+        assert_eq!(encoded.len(), 128);
+        assert_eq!(abi_decode_evm_link_payload(&encoded), Some(payload));
+
+        assert_eq!(abi_decode_evm_link_payload(&encoded[..96]), None);
+        let mut dirty_padding = encoded.clone();
+        dirty_padding[0] = 1;
+        assert_eq!(abi_decode_evm_link_payload(&dirty_padding), None);
+    }
+
+    #[test]
+    fn test_submit_records_link_until_expiry() {
+        let (env, mut contract) = setup();
+        let casper_account = Address::Account(AccountHash::new(CASPER_ACCOUNT_HASH));
+        assert!(!contract.is_evm_verified(EVM_ADDRESS));
+        assert_eq!(contract.get_evm_link(casper_account), None);
+
+        // Anyone may submit
+        env.set_caller(env.get_account(3));
+        let (encoded, signature) = signed_payload(&env, &TEST_PRIVATE_KEY, 0);
+        let expires_at = env.block_time() + HOUR_MS;
+        contract.submit_evm_attestation(encoded.clone(), signature);
+
+        assert!(contract.is_evm_verified(EVM_ADDRESS));
+        assert!(contract.is_attestation_used(keccak256(&encoded)));
+        assert_eq!(
+            contract.get_evm_link(casper_account),
+            Some(EvmLink { evm_address: EVM_ADDRESS, expires_at, attestation_id: keccak256(&encoded) })
+        );
+        assert!(env.emitted_event(
+            &contract,
+            EvmIdentityLinked { evm_address: EVM_ADDRESS, casper_account, expires_at }
+        ));
+
+        env.advance_block_time(HOUR_MS);
+        assert!(!contract.is_evm_verified(EVM_ADDRESS));
+        assert!(contract.get_evm_link(casper_account).is_some());
+    }
+
+    #[test]
+    fn test_submit_rejects_forged_replayed_and_expired() {
+        let (env, mut contract) = setup();
+
+        // Signed by a key other than the trusted signer
+        let (encoded, forged) = signed_payload(&env, &ROTATED_PRIVATE_KEY, 0);
+        assert_eq!(
+            contract.try_submit_evm_attestation(encoded.clone(), forged),
+            Err(VeilError::UntrustedEvmSigner.into())
+        );
+
+        // Valid signature over a different payload
+        let (_, other_signature) = signed_payload(&env, &TEST_PRIVATE_KEY, 1);
+        assert_eq!(
+            contract.try_submit_evm_attestation(encoded.clone(), other_signature),
+            Err(VeilError::UntrustedEvmSigner.into())
+        );
+
+        assert_eq!(
+            contract.try_submit_evm_attestation(encoded.clone(), Bytes::from(vec![0u8; 64])),
+            Err(VeilError::InvalidEvmSignature.into())
+        );
+
+        // Signed, but not a payload encoding
+        let garbage = Bytes::from(vec![0xff; 96]);
+        let signature = Bytes::from(sign_message(&TEST_PRIVATE_KEY, &keccak256(&garbage)).to_vec());
+        assert_eq!(
+            contract.try_submit_evm_attestation(garbage, signature),
+            Err(VeilError::InvalidEvmAttestation.into())
+        );
+        assert!(!contract.is_evm_verified(EVM_ADDRESS));
+
+        let (encoded, signature) = signed_payload(&env, &TEST_PRIVATE_KEY, 0);
+        contract.submit_evm_attestation(encoded.clone(), signature.clone());
+        assert_eq!(
+            contract.try_submit_evm_attestation(encoded, signature),
+            Err(VeilError::EvmAttestationAlreadyUsed.into())
+        );
+
+        let (encoded, signature) = signed_payload(&env, &TEST_PRIVATE_KEY, 2);
+        env.advance_block_time(HOUR_MS);
+        assert_eq!(
+            contract.try_submit_evm_attestation(encoded, signature),
+            Err(VeilError::EvmAttestationExpired.into())
+        );
+    }
+
+    #[test]
+    fn test_trusted_signer_rotation() {
+        let (env, mut contract) = setup();
+        assert_eq!(contract.get_trusted_signer(), EXPECTED_SIGNER);
+
+        env.set_caller(env.get_account(1));
+        assert_eq!(contract.try_set_trusted_signer(ROTATED_SIGNER), Err(VeilError::NotAdmin.into()));
+
+        env.set_caller(env.get_account(0));
+        contract.set_trusted_signer(ROTATED_SIGNER);
+        let (encoded, signature) = signed_payload(&env, &TEST_PRIVATE_KEY, 0);
+        assert_eq!(
+            contract.try_submit_evm_attestation(encoded, signature),
+            Err(VeilError::UntrustedEvmSigner.into())
+        );
+        let (encoded, signature) = signed_payload(&env, &ROTATED_PRIVATE_KEY, 0);
+        contract.submit_evm_attestation(encoded, signature);
+        assert!(contract.is_evm_verified(EVM_ADDRESS));
+    }
+}
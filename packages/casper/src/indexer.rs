@@ -0,0 +1,545 @@
+//! Indexer core: persists contract events to SQLite
+//!
+//! The indexer walks the contract's event list from a cursor stored in the
+//! database, decodes each event by its name (the `event_<Name>` prefix every
+//! Odra event is serialized with) and upserts it:
+//!
+//! ```text
+//! attestations   one row per AttestationCreatedV2, successors included; `revoked` set by AttestationRevoked
+//! revocations    one row per AttestationRevoked
+//! other_events   name of every event the indexer does not model (yet),
+//!                the deprecated AttestationCreated included
+//! sync_cursor    next event index to process, per contract
+//! ```
+//!
+//! Each event is applied together with the cursor update in one transaction
+//! and every write is an upsert, so restarts and replays are harmless. Odra
+//! exposes events by index rather than by block, so the starting point is an
+//! event index.
+
+use std::fmt;
+use std::format;
+use std::path::Path;
+use std::prelude::v1::*;
+
+use log::{debug, info};
+use odra::casper_types::bytesrepr::{Bytes, FromBytes};
+use odra::host::HostEnv;
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::types::{AttestationCreatedV2, AttestationRevoked};
+use crate::veil_attestation::VeilAttestationHostRef;
+
+/// Default database file, relative to `packages/casper`
+pub const DEFAULT_DB_PATH: &str = "veil-index.sqlite";
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS attestations (
+        id TEXT PRIMARY KEY,
+        casper_address TEXT NOT NULL,
+        target_chain TEXT NOT NULL,
+        target_address TEXT NOT NULL,
+        tier INTEGER NOT NULL,
+        created_at INTEGER,
+        expires_at INTEGER NOT NULL,
+        event_index INTEGER NOT NULL,
+        revoked INTEGER NOT NULL DEFAULT 0,
+        nonce INTEGER,
+        stake_amount TEXT,
+        supersedes TEXT
+    );
+    CREATE TABLE IF NOT EXISTS revocations (
+        id TEXT PRIMARY KEY,
+        casper_address TEXT NOT NULL,
+        event_index INTEGER NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS other_events (
+        event_index INTEGER PRIMARY KEY,
+        name TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS sync_cursor (
+        contract TEXT PRIMARY KEY,
+        next_event_index INTEGER NOT NULL
+    );
+";
+
+/// Columns added to `attestations` after its first release, with their types
+const ADDED_ATTESTATION_COLUMNS: [(&str, &str); 3] =
+    [("nonce", "INTEGER"), ("stake_amount", "TEXT"), ("supersedes", "TEXT")];
+
+/// Errors that stop a poll; the event being processed is retried next poll
+#[derive(Debug, PartialEq)]
+pub enum IndexerError {
+    /// The database could not be opened, read or written
+    Db(String),
+    /// A known event's payload did not decode
+    Decode { event_index: u32, message: String },
+    /// `--backfill` needs a paginated export view the contract does not have yet
+    BackfillUnavailable,
+}
+
+impl fmt::Display for IndexerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IndexerError::Db(message) => write!(f, "index database: {}", message),
+            IndexerError::Decode { event_index, message } => {
+                write!(f, "could not decode event {}: {}", event_index, message)
+            }
+            IndexerError::BackfillUnavailable => {
+                write!(f, "backfill needs a paginated export view, which this contract does not have yet")
+            }
+        }
+    }
+}
+
+impl std::error::Error for IndexerError {}
+
+impl From<rusqlite::Error> for IndexerError {
+    fn from(e: rusqlite::Error) -> Self {
+        IndexerError::Db(e.to_string())
+    }
+}
+
+/// A decoded contract event
+#[derive(Debug, PartialEq)]
+pub enum IndexedEvent {
+    Created(AttestationCreatedV2),
+    Revoked(AttestationRevoked),
+    /// Any other event, by name
+    Other(String),
+}
+
+/// Decode a serialized event by its name prefix
+pub fn decode_event(event_index: u32, bytes: &[u8]) -> Result<IndexedEvent, IndexerError> {
+    let decode_error = |message: String| IndexerError::Decode { event_index, message };
+    let (name, _) = String::from_bytes(bytes).map_err(|e| decode_error(format!("no event name: {:?}", e)))?;
+
+    match name.strip_prefix("event_").unwrap_or(&name) {
+        "AttestationCreatedV2" => AttestationCreatedV2::from_bytes(bytes)
+            .map(|(event, _)| IndexedEvent::Created(event))
+            .map_err(|e| decode_error(format!("AttestationCreatedV2: {:?}", e))),
+        "AttestationRevoked" => AttestationRevoked::from_bytes(bytes)
+            .map(|(event, _)| IndexedEvent::Revoked(event))
+            .map_err(|e| decode_error(format!("AttestationRevoked: {:?}", e))),
+        other => Ok(IndexedEvent::Other(other.to_string())),
+    }
+}
+
+fn hex0x(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+/// The SQLite database the indexer writes to
+pub struct IndexDb {
+    conn: Connection,
+}
+
+impl IndexDb {
+    /// Open (or create) the database and its tables
+    pub fn open(path: &Path) -> Result<Self, IndexerError> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self, IndexerError> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(conn: Connection) -> Result<Self, IndexerError> {
+        conn.execute_batch(SCHEMA)?;
+        // Databases created before `AttestationCreated` carried these lack the columns
+        for (column, column_type) in ADDED_ATTESTATION_COLUMNS {
+            let exists: bool = conn.query_row(
+                "SELECT EXISTS(SELECT 1 FROM pragma_table_info('attestations') WHERE name = ?1)",
+                [column],
+                |row| row.get(0),
+            )?;
+            if !exists {
+                conn.execute_batch(&format!("ALTER TABLE attestations ADD COLUMN {} {}", column, column_type))?;
+            }
+        }
+        Ok(IndexDb { conn })
+    }
+
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    /// Next event index to process for `contract`, if it was indexed before
+    pub fn cursor(&self, contract: &str) -> Result<Option<u32>, IndexerError> {
+        let next = self
+            .conn
+            .query_row("SELECT next_event_index FROM sync_cursor WHERE contract = ?1", [contract], |row| row.get(0))
+            .optional()?;
+        Ok(next)
+    }
+
+    /// Store `event` and move the cursor past it (never backwards), atomically
+    pub fn apply(&mut self, contract: &str, event_index: u32, event: &IndexedEvent) -> Result<(), IndexerError> {
+        let tx = self.conn.transaction()?;
+        match event {
+            IndexedEvent::Created(created) => {
+                tx.execute(
+                    "INSERT INTO attestations
+                        (id, casper_address, target_chain, target_address, tier, created_at, expires_at, event_index,
+                         revoked, nonce, stake_amount, supersedes)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, EXISTS(SELECT 1 FROM revocations WHERE id = ?1), ?9, ?10,
+                             ?11)
+                     ON CONFLICT(id) DO UPDATE SET
+                        casper_address = excluded.casper_address,
+                        target_chain = excluded.target_chain,
+                        target_address = excluded.target_address,
+                        tier = excluded.tier,
+                        created_at = excluded.created_at,
+                        expires_at = excluded.expires_at,
+                        event_index = excluded.event_index,
+                        nonce = excluded.nonce,
+                        stake_amount = excluded.stake_amount,
+                        supersedes = excluded.supersedes",
+                    params![
+                        hex0x(&created.id),
+                        created.casper_address.to_string(),
+                        created.target_chain,
+                        created.target_address,
+                        created.tier,
+                        created.created_at,
+                        created.expires_at,
+                        event_index,
+                        created.nonce,
+                        created.stake_motes.to_string(),
+                        created.supersedes.map(|id| hex0x(&id)),
+                    ],
+                )?;
+            }
+            IndexedEvent::Revoked(revoked) => {
+                let id = hex0x(&revoked.id);
+                tx.execute(
+                    "INSERT INTO revocations (id, casper_address, event_index) VALUES (?1, ?2, ?3)
+                     ON CONFLICT(id) DO UPDATE SET casper_address = excluded.casper_address, event_index = excluded.event_index",
+                    params![id, revoked.casper_address.to_string(), event_index],
+                )?;
+                tx.execute("UPDATE attestations SET revoked = 1 WHERE id = ?1", [&id])?;
+            }
+            IndexedEvent::Other(name) => {
+                tx.execute(
+                    "INSERT INTO other_events (event_index, name) VALUES (?1, ?2)
+                     ON CONFLICT(event_index) DO UPDATE SET name = excluded.name",
+                    params![event_index, name],
+                )?;
+            }
+        }
+        tx.execute(
+            "INSERT INTO sync_cursor (contract, next_event_index) VALUES (?1, ?2)
+             ON CONFLICT(contract) DO UPDATE SET
+                next_event_index = MAX(sync_cursor.next_event_index, excluded.next_event_index)",
+            params![contract, event_index + 1],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+/// Where contract events come from
+pub trait EventSource {
+    fn events_count(&self) -> u32;
+    /// Serialized event at `index`
+    fn event_bytes(&self, index: u32) -> Option<Bytes>;
+}
+
+/// Events of a deployed VeilAttestation
+pub struct ContractEvents<'a> {
+    pub env: &'a HostEnv,
+    pub contract: &'a VeilAttestationHostRef,
+}
+
+impl EventSource for ContractEvents<'_> {
+    fn events_count(&self) -> u32 {
+        self.env.events_count(self.contract)
+    }
+
+    fn event_bytes(&self, index: u32) -> Option<Bytes> {
+        self.env.get_event_bytes(self.contract, index).ok()
+    }
+}
+
+/// What one poll indexed
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct PollSummary {
+    pub created: u32,
+    pub revoked: u32,
+    pub other: u32,
+    pub next_event_index: u32,
+}
+
+/// Copies one contract's events into an [`IndexDb`]
+pub struct Indexer<S: EventSource> {
+    source: S,
+    db: IndexDb,
+    contract: String,
+    next_event_index: u32,
+}
+
+impl<S: EventSource> Indexer<S> {
+    /// Resume from the stored cursor, or start at `start_event` on a fresh database
+    pub fn new(source: S, db: IndexDb, contract: &str, start_event: u32) -> Result<Self, IndexerError> {
+        let next_event_index = db.cursor(contract)?.unwrap_or(start_event);
+        info!("indexer starting contract={} next_event_index={}", contract, next_event_index);
+        Ok(Indexer { source, db, contract: contract.to_string(), next_event_index })
+    }
+
+    pub fn db(&self) -> &IndexDb {
+        &self.db
+    }
+
+    /// Index every event emitted since the cursor. On error the cursor stays
+    /// on the failed event so it is retried by the next poll.
+    pub fn poll_once(&mut self) -> Result<PollSummary, IndexerError> {
+        let count = self.source.events_count();
+        let mut summary = PollSummary::default();
+
+        while self.next_event_index < count {
+            let index = self.next_event_index;
+            let Some(bytes) = self.source.event_bytes(index) else {
+                // Not readable yet; try again next poll
+                debug!("event index={} not available", index);
+                break;
+            };
+
+            let event = decode_event(index, &bytes)?;
+            match &event {
+                IndexedEvent::Created(_) => summary.created += 1,
+                IndexedEvent::Revoked(_) => summary.revoked += 1,
+                IndexedEvent::Other(name) => {
+                    debug!("recording other event index={} name={}", index, name);
+                    summary.other += 1;
+                }
+            }
+            self.db.apply(&self.contract, index, &event)?;
+            self.next_event_index = index + 1;
+        }
+
+        summary.next_event_index = self.next_event_index;
+        Ok(summary)
+    }
+
+    /// Rebuild the index from the contract's paginated export view
+    pub fn backfill(&mut self) -> Result<PollSummary, IndexerError> {
+        Err(IndexerError::BackfillUnavailable)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::vec;
+
+    use odra::casper_types::bytesrepr::ToBytes;
+    use odra::casper_types::U512;
+    use odra::prelude::Address;
+
+    use crate::types::RevocationPending;
+    use crate::veil_attestation::SCHEMA_VERSION;
+
+    const CONTRACT: &str = "hash-0000000000000000000000000000000000000000000000000000000000000001";
+
+    fn account(byte: u8) -> Address {
+        Address::Account(odra::casper_types::account::AccountHash::new([byte; 32]))
+    }
+
+    /// Synthetic event payloads, serialized the way the contract emits them
+    #[derive(Default)]
+    struct SyntheticEvents {
+        events: Vec<Bytes>,
+    }
+
+    impl SyntheticEvents {
+        fn push(&mut self, event: impl ToBytes) {
+            self.events.push(Bytes::from(event.to_bytes().unwrap()));
+        }
+    }
+
+    impl EventSource for &SyntheticEvents {
+        fn events_count(&self) -> u32 {
+            self.events.len() as u32
+        }
+
+        fn event_bytes(&self, index: u32) -> Option<Bytes> {
+            self.events.get(index as usize).cloned()
+        }
+    }
+
+    fn created(id: u8, chain: &str) -> AttestationCreatedV2 {
+        AttestationCreatedV2 {
+            id: [id; 32],
+            casper_address: account(1),
+            target_chain: chain.to_string(),
+            target_address: "0x70997970c51812dc3a010c7d01b50e0d17dc79c8".to_string(),
+            tier: 2,
+            expires_at: 604_801_000,
+            created_at: 1_000,
+            stake_motes: U512::from(1_000_000_000_000u64),
+            nonce: u64::from(id),
+            payload_hash: [id; 32],
+            schema_version: SCHEMA_VERSION,
+            referrer: None,
+            supersedes: None,
+        }
+    }
+
+    fn synthetic() -> SyntheticEvents {
+        let mut events = SyntheticEvents::default();
+        events.push(created(0xaa, "base-sepolia"));
+        events.push(RevocationPending { id: [0xaa; 32], effective_at: 5_000 });
+        events.push(created(0xbb, "anvil-local"));
+        events.push(AttestationRevoked { id: [0xaa; 32], casper_address: account(1) });
+        events
+    }
+
+    fn attestation_rows(db: &IndexDb) -> Vec<(String, String, Option<u64>, u64, bool)> {
+        let mut statement = db
+            .connection()
+            .prepare("SELECT id, target_chain, created_at, event_index, revoked FROM attestations ORDER BY event_index")
+            .unwrap();
+        statement
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?)))
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+    }
+
+    fn count(db: &IndexDb, table: &str) -> u32 {
+        db.connection()
+            .query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| row.get(0))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_decode_event_by_name() {
+        let event = created(0xaa, "base-sepolia");
+        let bytes = event.to_bytes().unwrap();
+        assert_eq!(decode_event(0, &bytes), Ok(IndexedEvent::Created(event)));
+
+        let pending = RevocationPending { id: [1; 32], effective_at: 5 }.to_bytes().unwrap();
+        assert_eq!(decode_event(1, &pending), Ok(IndexedEvent::Other("RevocationPending".to_string())));
+
+        // Right name, truncated body
+        assert!(matches!(decode_event(2, &bytes[..bytes.len() - 4]), Err(IndexerError::Decode { event_index: 2, .. })));
+        assert!(matches!(decode_event(3, &[]), Err(IndexerError::Decode { event_index: 3, .. })));
+    }
+
+    #[test]
+    fn test_poll_upserts_rows() {
+        let events = synthetic();
+        let mut indexer = Indexer::new(&events, IndexDb::open_in_memory().unwrap(), CONTRACT, 0).unwrap();
+
+        let summary = indexer.poll_once().unwrap();
+        assert_eq!(summary, PollSummary { created: 2, revoked: 1, other: 1, next_event_index: 4 });
+
+        assert_eq!(
+            attestation_rows(indexer.db()),
+            vec![
+                (hex0x(&[0xaa; 32]), "base-sepolia".to_string(), Some(1_000), 0, true),
+                (hex0x(&[0xbb; 32]), "anvil-local".to_string(), Some(1_000), 2, false),
+            ]
+        );
+        let (nonce, stake_amount): (u64, String) = indexer
+            .db()
+            .connection()
+            .query_row("SELECT nonce, stake_amount FROM attestations WHERE event_index = 2", [], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .unwrap();
+        assert_eq!((nonce, stake_amount.as_str()), (0xbb, "1000000000000"));
+        assert_eq!(count(indexer.db(), "revocations"), 1);
+        let other: String = indexer
+            .db()
+            .connection()
+            .query_row("SELECT name FROM other_events WHERE event_index = 1", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(other, "RevocationPending");
+        assert_eq!(indexer.db().cursor(CONTRACT).unwrap(), Some(4));
+    }
+
+    #[test]
+    fn test_successor_row_names_what_it_supersedes() {
+        let mut events = synthetic();
+        events.push(AttestationCreatedV2 { supersedes: Some([0xbb; 32]), ..created(0xcc, "anvil-local") });
+        let mut indexer = Indexer::new(&events, IndexDb::open_in_memory().unwrap(), CONTRACT, 0).unwrap();
+        assert_eq!(indexer.poll_once().unwrap().created, 3);
+
+        let supersedes = |id: u8| -> Option<String> {
+            indexer
+                .db()
+                .connection()
+                .query_row("SELECT supersedes FROM attestations WHERE id = ?1", [hex0x(&[id; 32])], |row| row.get(0))
+                .unwrap()
+        };
+        assert_eq!(supersedes(0xcc), Some(hex0x(&[0xbb; 32])));
+        assert_eq!(supersedes(0xbb), None);
+    }
+
+    #[test]
+    fn test_restart_resumes_from_cursor() {
+        let path = std::env::temp_dir().join(format!("veil-index-{}.sqlite", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut events = synthetic();
+
+        {
+            let mut indexer = Indexer::new(&events, IndexDb::open(&path).unwrap(), CONTRACT, 0).unwrap();
+            indexer.poll_once().unwrap();
+        }
+
+        // A new event arrives; the restarted indexer only processes that one
+        events.push(created(0xcc, "base-sepolia"));
+        let mut indexer = Indexer::new(&events, IndexDb::open(&path).unwrap(), CONTRACT, 0).unwrap();
+        let summary = indexer.poll_once().unwrap();
+        assert_eq!(summary, PollSummary { created: 1, revoked: 0, other: 0, next_event_index: 5 });
+        assert_eq!(count(indexer.db(), "attestations"), 3);
+
+        // Replaying already indexed events changes nothing
+        let mut db = IndexDb::open(&path).unwrap();
+        db.apply(CONTRACT, 0, &IndexedEvent::Created(created(0xaa, "base-sepolia"))).unwrap();
+        let rows = attestation_rows(&db);
+        assert_eq!(rows[0], (hex0x(&[0xaa; 32]), "base-sepolia".to_string(), Some(1_000), 0, true));
+        assert_eq!(count(&db, "attestations"), 3);
+        assert_eq!(db.cursor(CONTRACT).unwrap(), Some(5));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_open_adds_missing_columns() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE attestations (
+                id TEXT PRIMARY KEY,
+                casper_address TEXT NOT NULL,
+                target_chain TEXT NOT NULL,
+                target_address TEXT NOT NULL,
+                tier INTEGER NOT NULL,
+                created_at INTEGER,
+                expires_at INTEGER NOT NULL,
+                event_index INTEGER NOT NULL,
+                revoked INTEGER NOT NULL DEFAULT 0
+            )",
+        )
+        .unwrap();
+        let mut db = IndexDb::with_connection(conn).unwrap();
+        db.apply(CONTRACT, 0, &IndexedEvent::Created(created(0xaa, "base-sepolia"))).unwrap();
+        assert_eq!(count(&db, "attestations"), 1);
+
+        // Opening again finds the columns in place
+        assert!(IndexDb::with_connection(db.conn).is_ok());
+    }
+
+    #[test]
+    fn test_start_event_and_backfill() {
+        let events = synthetic();
+        let mut indexer = Indexer::new(&events, IndexDb::open_in_memory().unwrap(), CONTRACT, 2).unwrap();
+        let summary = indexer.poll_once().unwrap();
+        assert_eq!(summary, PollSummary { created: 1, revoked: 1, other: 0, next_event_index: 4 });
+        // The revocation of an attestation created before the start event is still recorded
+        assert_eq!(count(indexer.db(), "revocations"), 1);
+        assert_eq!(count(indexer.db(), "attestations"), 1);
+
+        assert_eq!(indexer.backfill(), Err(IndexerError::BackfillUnavailable));
+    }
+}
@@ -0,0 +1,22 @@
+//! What other Casper contracts can call on `VeilAttestation`
+//!
+//! Consumers gate their entry points through `IVeilAttestationContractRef`
+//! built on the address of a deployed `VeilAttestation`; this module depends
+//! only on the shared types, not on the implementation. See `gated_counter`
+//! for an example.
+
+use odra::prelude::*;
+use crate::types::{Attestation, Tier};
+
+/// The cross-contract surface of `VeilAttestation`
+#[odra::external_contract]
+pub trait IVeilAttestation {
+    /// Whether `user` holds an active attestation of at least `min_tier`
+    /// with no open dispute against it. With `require_evm_confirmation`, an
+    /// EVM verifier must also have been confirmed to consume it.
+    fn check_attestation(&self, user: Address, min_tier: Tier, require_evm_confirmation: Option<bool>) -> bool;
+    /// `user`'s current tier from their stake, attested or not
+    fn get_user_tier(&self, user: Address) -> Tier;
+    /// `user`'s highest-tier active attestation, the most recent on a tie
+    fn get_active_attestation(&self, user: Address) -> Option<Attestation>;
+}
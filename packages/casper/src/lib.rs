@@ -1,70 +1,563 @@
 #![no_std]
 
 extern crate alloc;
+#[cfg(not(target_arch = "wasm32"))]
+extern crate std;
 
+pub mod badge;
+pub mod claims;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod cli;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod config;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod deploys;
+pub mod disputes;
+pub mod encoding;
+pub mod errors;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod evm;
+pub mod gated_counter;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod indexer;
+pub mod inbound_verifier;
+pub mod interface;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod migrate;
+#[cfg(any(test, feature = "mocks"))]
+pub mod mock_auction;
+#[cfg(any(test, feature = "mocks"))]
+pub mod mock_reward_token;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod monitor;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod output;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod relayer;
+pub mod reward_token;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod schema;
+pub mod stake_source;
+pub mod staking;
 pub mod types;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod vectors;
 pub mod veil_attestation;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod verify;
+#[cfg(test)]
+mod test_support;
 
+pub use encoding::compute_function_selector;
+pub use errors::VeilError;
 pub use types::*;
 pub use veil_attestation::VeilAttestation;
 
-#[cfg(test)]
+// Most of the suite signs through the contract, so it needs on-chain signing;
+// `external_signing_tests` covers `no-onchain-signing` builds
+#[cfg(all(test, not(feature = "no-onchain-signing")))]
 mod tests {
-    use alloc::string::ToString;
+    use alloc::collections::{BTreeMap, BTreeSet};
+    use alloc::string::{String, ToString};
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use std::time::Instant;
     use odra::host::{Deployer, HostEnv};
-    use odra::casper_types::bytesrepr::Bytes;
-    use crate::veil_attestation::{VeilAttestation, VeilAttestationHostRef, VeilAttestationInitArgs};
-
-    // Test private key (matches EVM tests)
-    const TEST_PRIVATE_KEY: [u8; 32] = [
-        0xac, 0x09, 0x74, 0xbe, 0xc3, 0x9a, 0x17, 0xe3,
-        0x6b, 0xa4, 0xa6, 0xb4, 0xd2, 0x38, 0xff, 0x94,
-        0x4b, 0xac, 0xb4, 0x78, 0xcb, 0xed, 0x5e, 0xfc,
-        0xae, 0x78, 0x4d, 0x7b, 0xf4, 0xf2, 0xff, 0x80,
-    ];
-
-    // Expected signer address (Ethereum format)
-    const EXPECTED_SIGNER: [u8; 20] = [
-        0xf3, 0x9F, 0xd6, 0xe5, 0x1a, 0xad, 0x88, 0xF6,
-        0xF4, 0xce, 0x6a, 0xB8, 0x82, 0x72, 0x79, 0xcf,
-        0xff, 0xFb, 0x92, 0x26,
-    ];
-
-    fn setup() -> (HostEnv, VeilAttestationHostRef) {
-        let env = odra_test::env();
-        let admin = env.get_account(0);
+    use odra::prelude::{Address, Addressable};
+    use odra::casper_types::bytesrepr::{Bytes, ToBytes};
+    use odra::casper_types::{U256, U512};
+    use odra::OdraResult;
+    use crate::claims::MAX_CLAIM_DATA_LEN;
+    use crate::cli::CALL_GAS;
+    use crate::encoding::{
+        self, abi_decode_payload, abi_encode_payload, find_proof_of_work, keccak256, leading_zero_bits,
+        proof_of_work_hash,
+    };
+    use crate::errors::VeilError;
+    use crate::mock_reward_token::{MockRewardToken, MockRewardTokenInitArgs};
+    use crate::types::{
+        compress_attestation, cspr_to_motes, decompress_attestation, motes_to_cspr_string, AdminContactUpdated,
+        AdminTransferred, Attestation, AttestationConfirmed, AttestationCreated, AttestationCreatedV2,
+        AttestationExtended, AttestationFrozen, AttestationMarkedStale, AttestationPayload, AttestationPreview,
+        AttestationRenewabilityChanged, AttestationRenewed, AttestationRevoked, AttestationSuperseded,
+        AttestationTypeRegistered, AttestationUnfrozen, AttestedAddresses, AutoRenewExecuted, ChainActivated,
+        ChainAllowlistUpdated, ChainConfigChanged, ChainDeployment, ChainDeploymentRemoved, ChainDeploymentUpdated,
+        ChainProposalCancelled, ChainProposed, ClaimAttached, ClaimProviderUpdated, ConfigChanged, CustomTierSet,
+        Dispute, DisputeOpened, DisputeResolved, DisputeStatus, EmergencyActionTaken, HashAlgorithmChanged, InitConfig,
+        RegionAllowlistUpdated, ReputationRecord, ReputationWeights, RevocationPending, RewardMinted, RewardMultipliers,
+        RewardSkipped, SignaturesBatchRefreshed, SignerRotated, StakePolicy, Template, TemplateUpdated, Tier,
+        TierThresholds, TierThresholdsChanged, TimeUnitChanged, ValidityPeriodChanged, WitnessAdded,
+        STAKE_SOURCE_CACHED, STAKE_SOURCE_CUSTOM, STAKE_SOURCE_LIVE, SUPERSEDED_BY_EXTENSION, SUPERSEDED_BY_REFRESH,
+        SUPERSEDED_BY_RENEWAL,
+    };
+    use crate::veil_attestation::{
+        check_min_stake, check_tags, find_unused_attestation_id, recommends_rotation, verify_payload_completeness,
+        ConfigValue, VeilAttestation, VeilAttestationHostRef, VeilAttestationInitArgs, ADMIN_AUDIT_LOG_LEN,
+        CHAIN_PROPOSAL_DELAY_SECS, CONTRACT_VERSION, CREATE_BASE_GAS, DEFAULT_ATTESTATION_TYPE,
+        DEFAULT_CHAIN_ATTESTATION_LIMIT, DEFAULT_DISPUTE_BOND_MOTES, DEFAULT_MAX_TAGS, DEFAULT_MIN_VALIDITY_SECS,
+        DEFAULT_RENEWAL_GRACE_SECS, EIP1271_MAGIC_VALUE, MAX_ADMIN_CONTACT_LEN, MAX_ATTESTATION_ID_RETRIES,
+        MAX_ATTESTATION_TYPE_LEN, MAX_POW_DIFFICULTY, MAX_TAG_LEN, SCHEMA_VERSION, SIGNER_ROTATION_AGE_SECS,
+        SIGNER_ROTATION_SIGNATURES, STAKE_QUERY_GAS, STORAGE_VERSION, TIME_UNIT_MILLIS, TIME_UNIT_SECONDS,
+    };
+    use crate::test_support::{
+        known_keypairs, recover_eth_address, Fixture, DEFAULT_CHAIN, DEFAULT_TARGET, EXPECTED_SIGNER,
+        ROTATED_PRIVATE_KEY, ROTATED_SIGNER, TEST_PRIVATE_KEY,
+    };
 
-        let init_args = VeilAttestationInitArgs {
-            admin,
-            signer_private_key: TEST_PRIVATE_KEY,
+    #[test]
+    fn test_init_derives_correct_signer_address() {
+        let fx = Fixture::new().build();
+        assert_eq!(fx.contract.get_signer_address(), EXPECTED_SIGNER, "Signer address derivation failed");
+    }
+
+    #[test]
+    fn test_signer_address_matches_known_keypairs() {
+        let keypairs = known_keypairs();
+        assert_eq!(keypairs[0], (TEST_PRIVATE_KEY, EXPECTED_SIGNER));
+        assert_eq!(keypairs[1], (ROTATED_PRIVATE_KEY, ROTATED_SIGNER));
+
+        for (key, address) in keypairs {
+            // Derivation outside the contract
+            let pubkey = encoding::derive_public_key(&key).unwrap();
+            assert_eq!(encoding::public_key_to_address(&pubkey), address);
+
+            // Address from the public key cached at deployment
+            let mut fx = Fixture::new().with_signer_key(key).build();
+            assert_eq!(fx.contract.get_signer_address(), address);
+            assert_eq!(fx.contract.get_deployment_info().signer_address, alloc::format!("0x{}", hex::encode(address)));
+
+            // Signatures recover to the full address
+            let (attestation_id, signature) = fx.create_default_attestation(fx.user);
+            assert_eq!(recover_eth_address(&attestation_id, &signature), address);
+        }
+    }
+
+    #[test]
+    fn test_rotated_signer_address_matches_known_keypairs() {
+        let mut fx = Fixture::new().build();
+        for (key, address) in known_keypairs().into_iter().rev() {
+            fx.contract.rotate_signer(key);
+            assert_eq!(fx.contract.get_signer_address(), address);
+        }
+    }
+
+    #[test]
+    fn test_debug_recover_signer_reports_signer_and_rotation() {
+        let mut fx = Fixture::new().build();
+        let (attestation_id, _) = fx.create_default_attestation(fx.user);
+
+        assert_eq!(fx.contract.try_debug_recover_signer(attestation_id), Err(VeilError::NotAdmin.into()));
+
+        fx.env.set_caller(fx.admin);
+        let (recovered, debug_info) = fx.contract.debug_recover_signer(attestation_id).unwrap();
+        assert_eq!(recovered, EXPECTED_SIGNER);
+        assert!(debug_info.contains("matches"), "{}", debug_info);
+        assert_eq!(fx.contract.debug_recover_signer([0xee; 32]), None);
+
+        // The cached signature still recovers to the old signer after rotation
+        fx.contract.rotate_signer(ROTATED_PRIVATE_KEY);
+        let (recovered, debug_info) = fx.contract.debug_recover_signer(attestation_id).unwrap();
+        assert_eq!(recovered, EXPECTED_SIGNER);
+        assert!(debug_info.contains(&hex::encode(ROTATED_SIGNER)), "{}", debug_info);
+    }
+
+    #[test]
+    fn test_recover_signer_view() {
+        let fx = Fixture::new().build();
+        let message_hash = [0x42; 32];
+
+        let signature = Bytes::from(encoding::sign_message(&TEST_PRIVATE_KEY, &message_hash).to_vec());
+        assert_eq!(fx.contract.recover_signer(message_hash, signature.clone(), true), EXPECTED_SIGNER);
+        assert_ne!(fx.contract.recover_signer(message_hash, signature.clone(), false), EXPECTED_SIGNER);
+        let unprefixed = Bytes::from(encoding::sign_prehash(&TEST_PRIVATE_KEY, &message_hash).to_vec());
+        assert_eq!(fx.contract.recover_signer(message_hash, unprefixed, false), EXPECTED_SIGNER);
+
+        // A corrupted s still recovers, to some other address; a bad v or length does not
+        let mut corrupted = signature.to_vec();
+        corrupted[40] ^= 0x01;
+        assert_ne!(fx.contract.recover_signer(message_hash, Bytes::from(corrupted), true), EXPECTED_SIGNER);
+        let mut bad_v = signature.to_vec();
+        bad_v[64] = 29;
+        assert_eq!(
+            fx.contract.try_recover_signer(message_hash, Bytes::from(bad_v), true),
+            Err(VeilError::UnrecoverableSignature.into())
+        );
+        assert_eq!(
+            fx.contract.try_recover_signer(message_hash, Bytes::from(signature[..64].to_vec()), true),
+            Err(VeilError::UnrecoverableSignature.into())
+        );
+    }
+
+    #[test]
+    fn test_verify_own_signature_tracks_rotation() {
+        let mut fx = Fixture::new().build();
+        let (attestation_id, _) = fx.create_default_attestation(fx.user);
+        assert!(fx.contract.verify_own_signature(attestation_id));
+        assert!(!fx.contract.verify_own_signature([0xee; 32]));
+
+        fx.env.set_caller(fx.admin);
+        fx.contract.rotate_signer(ROTATED_PRIVATE_KEY);
+        assert!(!fx.contract.verify_own_signature(attestation_id));
+        fx.contract.batch_refresh_signatures(vec![attestation_id]);
+        assert!(fx.contract.verify_own_signature(attestation_id));
+    }
+
+    #[test]
+    fn test_signer_health_tracks_usage_and_age() {
+        let mut fx = Fixture::new().build();
+        let fresh = fx.contract.check_signer_health();
+        assert_eq!((fresh.key_version, fresh.signatures_produced, fresh.last_signature_at), (1, 0, 0));
+        assert_eq!(fresh.signer_address, fx.contract.get_signer_address());
+        assert!(!fresh.recommend_rotation);
+
+        fx.create_default_attestation(fx.user);
+        let health = fx.contract.check_signer_health();
+        assert_eq!((health.signatures_produced, health.last_signature_at), (1, fx.env.block_time()));
+        assert!(!health.recommend_rotation);
+
+        fx.env.advance_block_time((SIGNER_ROTATION_AGE_SECS + 1) * 1000);
+        let aged = fx.contract.check_signer_health();
+        assert_eq!(aged.key_age_secs, fresh.key_age_secs + SIGNER_ROTATION_AGE_SECS + 1);
+        assert!(aged.recommend_rotation);
+
+        // Rotation starts the new key's count and age from zero
+        fx.env.set_caller(fx.admin);
+        fx.contract.rotate_signer(ROTATED_PRIVATE_KEY);
+        let rotated = fx.contract.check_signer_health();
+        assert_eq!((rotated.key_version, rotated.signatures_produced, rotated.key_age_secs), (2, 0, 0));
+        assert_eq!(rotated.signer_address, ROTATED_SIGNER);
+        assert!(!rotated.recommend_rotation);
+
+        // Either threshold on its own is enough
+        assert!(!recommends_rotation(SIGNER_ROTATION_SIGNATURES, SIGNER_ROTATION_AGE_SECS));
+        assert!(recommends_rotation(SIGNER_ROTATION_SIGNATURES + 1, 0));
+        assert!(recommends_rotation(0, SIGNER_ROTATION_AGE_SECS + 1));
+    }
+
+    #[test]
+    fn test_signature_timestamp_tracks_latest_signing() {
+        let mut fx = Fixture::new().build();
+        let (attestation_id, _) = fx.create_default_attestation(fx.user);
+        let created_at = fx.env.block_time();
+        assert_eq!(fx.contract.get_signature_produced_at(attestation_id), Some(created_at));
+        assert_eq!(fx.contract.get_signature_age_secs(attestation_id), Some(0));
+        assert!(fx.contract.is_signature_fresh(attestation_id, 0));
+
+        fx.env.advance_block_time(3_600_500);
+        assert_eq!(fx.contract.get_signature_age_secs(attestation_id), Some(3_600));
+        assert!(fx.contract.is_signature_fresh(attestation_id, 3_600));
+        assert!(!fx.contract.is_signature_fresh(attestation_id, 3_599));
+        assert_eq!(fx.contract.get_attestation_list(fx.user)[0].signature_produced_at, created_at);
+
+        // Renewal signs a new attestation now; the old signature keeps its time
+        let (renewed_id, _) = fx.contract.refresh_attestation(attestation_id);
+        let renewed_at = fx.env.block_time();
+        assert_eq!(fx.contract.get_signature_produced_at(renewed_id), Some(renewed_at));
+        assert!(fx.contract.is_signature_fresh(renewed_id, 0));
+        assert_eq!(fx.contract.get_signature_produced_at(attestation_id), Some(created_at));
+
+        // Re-signing moves the timestamp
+        fx.env.advance_block_time(60_000);
+        fx.env.set_caller(fx.admin);
+        fx.contract.batch_refresh_signatures(vec![attestation_id]);
+        assert_eq!(fx.contract.get_signature_produced_at(attestation_id), Some(fx.env.block_time()));
+        let list = fx.contract.get_attestation_list(fx.user);
+        assert_eq!(list[0].signature_produced_at, fx.env.block_time());
+        assert_eq!(list[1].signature_produced_at, renewed_at);
+
+        assert_eq!(fx.contract.get_signature_age_secs([0xee; 32]), None);
+        assert!(!fx.contract.is_signature_fresh([0xee; 32], u64::MAX));
+    }
+
+    #[test]
+    fn test_reuse_window_returns_recent_attestation() {
+        let mut fx = Fixture::new().build();
+        let (attestation_id, signature) = fx.create_default_attestation(fx.user);
+
+        fx.env.set_caller(fx.admin);
+        fx.contract.set_reuse_window_secs(600);
+        assert_eq!(fx.contract.get_reuse_window_secs(), 600);
+
+        // Within the window the same attestation comes back, address case aside, and nothing is written
+        fx.env.advance_block_time(599 * 1000);
+        fx.env.set_caller(fx.user);
+        let events_before = fx.events_count();
+        let reused = fx.contract.create_attestation(
+            DEFAULT_CHAIN.to_string(),
+            DEFAULT_TARGET.to_uppercase().replacen("0X", "0x", 1),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        assert_eq!(reused, (attestation_id, signature));
+        assert_eq!(fx.events_count(), events_before);
+        assert_eq!(fx.contract.get_user_attestations(fx.user).len(), 1);
+
+        // Outside it the duplicate rules apply again
+        fx.env.advance_block_time(1000);
+        assert_eq!(
+            fx.contract.try_create_attestation(
+                DEFAULT_CHAIN.to_string(),
+                DEFAULT_TARGET.to_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            ),
+            Err(VeilError::DuplicateAttestation.into())
+        );
+        fx.env.set_caller(fx.admin);
+        fx.contract.set_allow_duplicate_chain_address(true);
+        let (fresh_id, _) = fx.create_default_attestation(fx.user);
+        assert_ne!(fresh_id, attestation_id);
+
+        // A revoked attestation is never reused
+        fx.env.set_caller(fx.user);
+        fx.contract.revoke_attestation(fresh_id);
+        let (newest_id, _) = fx.create_default_attestation(fx.user);
+        assert_ne!(newest_id, fresh_id);
+        assert_eq!(fx.contract.get_user_attestations(fx.user).len(), 3);
+    }
+
+    #[test]
+    fn test_reuse_window_is_admin_only() {
+        let mut fx = Fixture::new().build();
+        fx.env.set_caller(fx.user);
+        assert_eq!(fx.contract.try_set_reuse_window_secs(600), Err(VeilError::NotAdmin.into()));
+    }
+
+    #[test]
+    fn test_stake_info_distinguishes_live_and_cached_stake() {
+        let mut fx = Fixture::new().with_mock_stake(1, cspr_to_motes(1_000, 0)).build();
+        let other_target = "0x00000000000000000000000000000000000000aa";
+        let stake_source = |fx: &Fixture, id: [u8; 32]| {
+            let (encoded, _) = fx.contract.get_attestation_for_evm(id).unwrap();
+            abi_decode_payload(&encoded).unwrap().stake_source
         };
 
-        let contract = VeilAttestation::deploy(&env, init_args);
-        (env, contract)
+        // Without a cache every attestation reads live stake
+        let (live_id, _) = fx.create_default_attestation(fx.user);
+        assert_eq!(
+            fx.contract.get_attestation_stake_info(live_id),
+            Some((cspr_to_motes(1_000, 0), STAKE_SOURCE_LIVE, 0))
+        );
+        assert_eq!(stake_source(&fx, live_id), STAKE_SOURCE_LIVE);
+
+        fx.env.set_caller(fx.admin);
+        fx.contract.set_stake_cache_secs(600);
+        assert_eq!(fx.contract.get_stake_cache_secs(), 600);
+        fx.env.set_caller(fx.user);
+        let (refreshed_id, _) = fx.contract.refresh_attestation(live_id);
+        assert_eq!(fx.contract.get_attestation_stake_info(refreshed_id).unwrap().1, STAKE_SOURCE_LIVE);
+
+        // Within the window the live reading above is reused, stake changes aside
+        fx.auction.as_mut().unwrap().set_delegated_stake(fx.user, cspr_to_motes(5_000, 0));
+        fx.env.advance_block_time(599 * 1000);
+        let (cached_id, _) = fx.create_attestation(fx.user, DEFAULT_CHAIN, other_target);
+        assert_eq!(
+            fx.contract.get_attestation_stake_info(cached_id),
+            Some((cspr_to_motes(1_000, 0), STAKE_SOURCE_CACHED, 0))
+        );
+        assert_eq!(stake_source(&fx, cached_id), STAKE_SOURCE_CACHED);
+
+        // Past it the stake sources are queried again
+        fx.env.advance_block_time(1000);
+        fx.env.set_caller(fx.user);
+        let (expired_id, _) = fx.contract.refresh_attestation(cached_id);
+        assert_eq!(
+            fx.contract.get_attestation_stake_info(expired_id),
+            Some((cspr_to_motes(5_000, 0), STAKE_SOURCE_LIVE, 0))
+        );
+        assert_eq!(fx.contract.get_attestation_stake_info([0xee; 32]), None);
+
+        assert_eq!(fx.contract.try_set_stake_cache_secs(0), Err(VeilError::NotAdmin.into()));
     }
 
     #[test]
-    fn test_init_derives_correct_signer_address() {
-        let (_, contract) = setup();
-        let signer_addr = contract.get_signer_address();
+    fn test_stake_info_marks_custom_tier_stake() {
+        let mut fx = Fixture::new().with_mock_stake(1, cspr_to_motes(1_000, 0)).build();
+        fx.contract.set_custom_tier(fx.user, Some(Tier::Gold));
+
+        // The stake is still read and signed, but labelled as not deciding the tier
+        let (custom_id, _) = fx.create_default_attestation(fx.user);
+        assert_eq!(
+            fx.contract.get_attestation_stake_info(custom_id),
+            Some((cspr_to_motes(1_000, 0), STAKE_SOURCE_CUSTOM, 0))
+        );
+        let (encoded, _) = fx.contract.get_attestation_for_evm(custom_id).unwrap();
+        assert_eq!(abi_decode_payload(&encoded).unwrap().stake_source, STAKE_SOURCE_CUSTOM);
 
-        // Verify first few bytes match expected Ethereum address
-        assert_eq!(&signer_addr[..4], &EXPECTED_SIGNER[..4],
-            "Signer address derivation failed");
+        fx.env.set_caller(fx.admin);
+        fx.contract.set_custom_tier(fx.user, None);
+        fx.env.set_caller(fx.user);
+        let (live_id, _) = fx.contract.refresh_attestation(custom_id);
+        assert_eq!(fx.contract.get_attestation_stake_info(live_id).unwrap().1, STAKE_SOURCE_LIVE);
     }
 
     #[test]
-    fn test_create_attestation_returns_valid_signature() {
-        let (env, mut contract) = setup();
+    fn test_preview_matches_subsequent_creation() {
+        let mut fx = Fixture::new().with_mock_stake(1, cspr_to_motes(1_000, 0)).build();
+        let user = fx.user;
+        let create = |fx: &mut Fixture, chain: &str| {
+            fx.env.set_caller(user);
+            fx.contract.create_attestation(
+                chain.to_string(),
+                DEFAULT_TARGET.to_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+        };
 
-        let target_chain = "base-sepolia".to_string();
-        let target_address = "0x1234567890abcdef1234567890abcdef12345678".to_string();
+        // The second chain is a first for reputation, which the preview has to count
+        for chain in [DEFAULT_CHAIN, "anvil-local"] {
+            let preview = fx.contract.preview_attestation(user, chain.to_string(), DEFAULT_TARGET.to_string(), None);
+            assert_eq!(
+                fx.contract.preview_attestation(user, chain.to_string(), DEFAULT_TARGET.to_string(), None),
+                preview,
+                "previewing writes nothing"
+            );
 
-        env.set_caller(env.get_account(1));
-        let (attestation_id, signature) = contract.create_attestation(
-            target_chain.clone(),
-            target_address.clone(),
+            let (attestation_id, _) = create(&mut fx, chain);
+            let attestation = fx.contract.get_attestation(attestation_id).unwrap();
+            let (encoded, _) = fx.contract.get_attestation_for_evm(attestation_id).unwrap();
+            assert_eq!(preview.attestation_id, attestation_id);
+            assert_eq!(preview.encoded, encoded);
+            assert_eq!(preview.tier, attestation.tier);
+            assert_eq!(preview.stake_amount, attestation.stake_amount);
+            assert_eq!(preview.expires_at, attestation.expires_at);
+            assert!(!preview.reused);
+            assert_eq!((preview.fee, preview.pow_difficulty), (U512::zero(), 0));
+        }
+        assert_eq!(fx.contract.get_user_attestations(user).len(), 2);
+
+        assert_eq!(
+            fx.contract.try_preview_attestation(user, DEFAULT_CHAIN.to_string(), DEFAULT_TARGET.to_string(), None),
+            Err(VeilError::DuplicateAttestation.into())
+        );
+        assert_eq!(
+            fx.contract.try_preview_attestation(user, DEFAULT_CHAIN.to_string(), "0x1234".to_string(), None),
+            Err(VeilError::InvalidTargetAddress.into())
+        );
+    }
+
+    #[test]
+    fn test_preview_matches_creation_with_reuse_and_region_allowlist() {
+        let mut fx = Fixture::new().with_mock_stake(1, cspr_to_motes(1_000, 0)).build();
+        let user = fx.user;
+        fx.contract.add_allowed_region("EU".to_string());
+        fx.contract.set_reuse_window_secs(600);
+        fx.contract.set_attestation_fee(U512::from(1_000u64));
+        fx.contract.set_pow_difficulty(4);
+        let pow_nonce = find_proof_of_work(&user.to_bytes().unwrap(), DEFAULT_CHAIN, 4);
+        let preview = |fx: &Fixture, region: Option<&str>| {
+            fx.contract.try_preview_attestation(
+                user,
+                DEFAULT_CHAIN.to_string(),
+                DEFAULT_TARGET.to_string(),
+                region.map(String::from),
+            )
+        };
+        let create = |fx: &mut Fixture| {
+            fx.env.set_caller(user);
+            fx.contract.with_tokens(U512::from(1_000u64)).create_attestation(
+                DEFAULT_CHAIN.to_string(),
+                DEFAULT_TARGET.to_string(),
+                None,
+                Some(pow_nonce),
+                None,
+                None,
+                None,
+                Some("EU".to_string()),
+            )
+        };
+
+        // The region allowlist applies to the hint as on creation
+        assert_eq!(preview(&fx, None).err(), Some(VeilError::RegionNotAllowed.into()));
+        assert_eq!(preview(&fx, Some("US")).err(), Some(VeilError::RegionNotAllowed.into()));
+        let fresh = preview(&fx, Some("EU")).unwrap();
+        assert!(!fresh.reused);
+        assert_eq!((fresh.fee, fresh.pow_difficulty), (U512::from(1_000u64), 4));
+
+        let (attestation_id, _) = create(&mut fx);
+        let (encoded, _) = fx.contract.get_attestation_for_evm(attestation_id).unwrap();
+        assert_eq!((fresh.attestation_id, &fresh.encoded), (attestation_id, &encoded));
+
+        // Within the reuse window both hand back the same attestation
+        fx.env.advance_block_time(60 * 1000);
+        assert_eq!(preview(&fx, Some("EU")).unwrap(), AttestationPreview { reused: true, ..fresh });
+        assert_eq!(create(&mut fx).0, attestation_id);
+        assert_eq!(fx.contract.get_user_attestations(user).len(), 1);
+    }
+
+    #[test]
+    fn test_attestation_export_reproduces_recovery() {
+        let mut fx = Fixture::new().build();
+        let (attestation_id, signature) = fx.create_default_attestation(fx.user);
+
+        let export = fx.contract.get_attestation_export(attestation_id).unwrap();
+        let (encoded, evm_signature) = fx.contract.get_attestation_for_evm(attestation_id).unwrap();
+        assert_eq!(export.encoded, encoded);
+        assert_eq!(export.signature, evm_signature);
+        assert_eq!(export.signature, signature);
+        assert_eq!(export.attestation_id, attestation_id);
+        assert_eq!(export.signer_address, EXPECTED_SIGNER);
+        assert_eq!(export.schema_version, SCHEMA_VERSION);
+
+        // The digest is the EIP-191 prefixed hash of the encoding, recomputed here by hand
+        let mut prefixed = b"\x19Ethereum Signed Message:\n32".to_vec();
+        prefixed.extend_from_slice(&keccak256(&export.encoded));
+        assert_eq!(export.signed_digest, keccak256(&prefixed));
+        assert_eq!(encoding::recover_signer(&export.signed_digest, &export.signature), Some(EXPECTED_SIGNER));
+
+        assert_eq!(fx.contract.get_attestation_export([0xee; 32]), None);
+        let attestation = fx.contract.get_attestation(attestation_id).unwrap();
+        fx.env.advance_block_time(attestation.expires_at - attestation.created_at);
+        assert_eq!(fx.contract.get_attestation_export(attestation_id), None);
+    }
+
+    #[test]
+    fn test_batch_refresh_signatures_after_rotation() {
+        let mut fx = Fixture::new().build();
+        let created: Vec<([u8; 32], Bytes)> = (0..5).map(|_| fx.create_default_attestation(fx.user)).collect();
+        let ids: Vec<[u8; 32]> = created.iter().map(|(id, _)| *id).collect();
+
+        fx.env.set_caller(fx.admin);
+        fx.contract.rotate_signer(ROTATED_PRIVATE_KEY);
+        let events_before = fx.events_count();
+        let signatures = fx.contract.batch_refresh_signatures(ids.clone());
+        fx.assert_single_event(events_before, SignaturesBatchRefreshed { count: 5, key_version: 2 });
+
+        assert_eq!(signatures.len(), 5);
+        for ((id, old_signature), signature) in created.iter().zip(&signatures) {
+            assert_eq!(recover_eth_address(id, signature), ROTATED_SIGNER);
+            assert_ne!(recover_eth_address(id, old_signature), ROTATED_SIGNER);
+            assert_eq!(fx.contract.get_attestation_raw_signature(*id).as_ref(), Some(signature));
+            fx.assert_signature_valid(id, signature);
+        }
+
+        fx.contract.set_max_batch_size(4);
+        assert_eq!(fx.contract.get_max_batch_size(), 4);
+        assert_eq!(fx.contract.try_batch_refresh_signatures(ids), Err(VeilError::BatchTooLarge.into()));
+        assert_eq!(
+            fx.contract.try_batch_refresh_signatures(vec![[0xee; 32]]),
+            Err(VeilError::AttestationNotFound.into())
         );
+    }
+
+    #[test]
+    fn test_create_attestation_returns_valid_signature() {
+        let mut fx = Fixture::new().build();
+
+        let (attestation_id, signature) = fx.create_default_attestation(fx.user);
 
         // Verify attestation ID is not zero
         assert_ne!(attestation_id, [0u8; 32], "Attestation ID should not be zero");
@@ -79,133 +572,4575 @@ mod tests {
 
     #[test]
     fn test_create_attestation_stores_data() {
-        let (env, mut contract) = setup();
-        let caller = env.get_account(1);
+        let mut fx = Fixture::new().build();
+        let target_address = "0xabcdef1234567890abcdef1234567890abcdef12";
 
-        let target_chain = "base-sepolia".to_string();
-        let target_address = "0xabcdef1234567890abcdef1234567890abcdef12".to_string();
-
-        env.set_caller(caller);
-        let (attestation_id, _) = contract.create_attestation(
-            target_chain.clone(),
-            target_address.clone(),
-        );
+        let (attestation_id, _) = fx.create_attestation(fx.user, DEFAULT_CHAIN, target_address);
 
         // Retrieve attestation
-        let attestation = contract.get_attestation(attestation_id)
+        let attestation = fx.contract.get_attestation(attestation_id)
             .expect("Attestation should exist");
 
-        assert_eq!(attestation.target_chain, target_chain);
+        assert_eq!(attestation.target_chain, DEFAULT_CHAIN);
         assert_eq!(attestation.target_address, target_address);
-        assert_eq!(attestation.casper_address, caller);
+        assert_eq!(attestation.casper_address, fx.user);
         assert!(!attestation.revoked);
     }
 
     #[test]
     fn test_get_user_attestations() {
-        let (env, mut contract) = setup();
-        let caller = env.get_account(1);
-
-        env.set_caller(caller);
+        let mut fx = Fixture::new().build();
 
         // Create multiple attestations
-        contract.create_attestation(
-            "base-sepolia".to_string(),
-            "0x1111111111111111111111111111111111111111".to_string(),
-        );
-        contract.create_attestation(
-            "base-sepolia".to_string(),
-            "0x2222222222222222222222222222222222222222".to_string(),
-        );
+        fx.create_attestation(fx.user, DEFAULT_CHAIN, "0x1111111111111111111111111111111111111111");
+        fx.create_attestation(fx.user, DEFAULT_CHAIN, "0x2222222222222222222222222222222222222222");
 
-        let attestations = contract.get_user_attestations(caller);
+        let attestations = fx.contract.get_user_attestations(fx.user);
         assert_eq!(attestations.len(), 2);
     }
 
     #[test]
-    fn test_revoke_attestation() {
-        let (env, mut contract) = setup();
-        let caller = env.get_account(1);
+    fn test_attestation_list_matches_full_records() {
+        let mut fx = Fixture::new().with_mock_stake(1, cspr_to_motes(1_000, 0)).build();
+        let (revoked_id, _) =
+            fx.create_attestation(fx.user, DEFAULT_CHAIN, "0x1111111111111111111111111111111111111111");
+        fx.create_attestation(fx.user, "anvil-local", "0x2222222222222222222222222222222222222222");
+        fx.env.set_caller(fx.admin);
+        fx.contract.set_compressed_storage(true);
+        fx.create_attestation(fx.user, DEFAULT_CHAIN, "0x3333333333333333333333333333333333333333");
+        fx.env.set_caller(fx.user);
+        fx.contract.revoke_attestation(revoked_id);
 
-        env.set_caller(caller);
-        let (attestation_id, _) = contract.create_attestation(
-            "base-sepolia".to_string(),
-            "0x1234567890abcdef1234567890abcdef12345678".to_string(),
-        );
+        let list = fx.contract.get_attestation_list(fx.user);
+        let full = fx.contract.get_user_attestations(fx.user);
+        assert_eq!(list.len(), 3);
+        for (item, attestation) in list.iter().zip(&full) {
+            assert_eq!(item.id, attestation.id);
+            assert_eq!(item.target_chain, attestation.target_chain);
+            assert_eq!(item.tier, attestation.tier);
+            assert_eq!(item.expires_at, attestation.expires_at);
+            assert_eq!(item.revoked, attestation.revoked);
+        }
+        assert!(list[0].revoked);
+        assert_eq!(list[1].target_chain, "anvil-local");
+        assert_eq!(list[2].tier, Tier::Silver);
+        assert!(fx.contract.get_attestation_list(fx.other).is_empty());
+    }
+
+    #[test]
+    fn test_user_profile_aggregates_active_attestations() {
+        let mut fx = Fixture::new().with_mock_stake(1, cspr_to_motes(1_000, 0)).build();
+        let empty = fx.contract.get_user_profile(fx.other);
+        assert_eq!((empty.active_attestation_count, empty.oldest_attestation_days), (0, None));
+        assert!(empty.verified_chains.is_empty());
+
+        fx.create_attestation(fx.user, DEFAULT_CHAIN, "0x1111111111111111111111111111111111111111");
+        fx.env.advance_block_time(2 * 24 * 60 * 60 * 1000);
+        fx.create_attestation(fx.user, "anvil-local", "0x2222222222222222222222222222222222222222");
+        let (revoked_id, _) =
+            fx.create_attestation(fx.user, DEFAULT_CHAIN, "0x3333333333333333333333333333333333333333");
+        fx.env.set_caller(fx.user);
+        fx.contract.revoke_attestation(revoked_id);
+
+        let profile = fx.contract.get_user_profile(fx.user);
+        assert_eq!(profile.address, fx.user);
+        assert_eq!(profile.stake_motes, cspr_to_motes(1_000, 0));
+        assert_eq!(profile.tier, Tier::Silver);
+        assert_eq!(profile.trust_score as u64, fx.contract.get_reputation(fx.user));
+        assert_eq!(profile.active_attestation_count, 2);
+        assert_eq!(profile.verified_chains, vec![DEFAULT_CHAIN.to_string(), "anvil-local".to_string()]);
+        assert_eq!(profile.oldest_attestation_days, Some(2));
+        assert!(!profile.has_custom_tier);
+
+        fx.env.set_caller(fx.admin);
+        fx.contract.set_custom_tier(fx.user, Some(Tier::Gold));
+        let profile = fx.contract.get_user_profile(fx.user);
+        assert_eq!(profile.tier, Tier::Gold);
+        assert!(profile.has_custom_tier);
+    }
+
+    #[test]
+    fn test_user_chains_lists_each_chain_once() {
+        let mut fx = Fixture::new().build();
+        assert!(fx.contract.get_user_chains(fx.user).is_empty());
+
+        fx.create_attestation(fx.user, DEFAULT_CHAIN, "0x1111111111111111111111111111111111111111");
+        fx.create_attestation(fx.user, "anvil-local", "0x2222222222222222222222222222222222222222");
+        let (revoked_id, _) =
+            fx.create_attestation(fx.user, DEFAULT_CHAIN, "0x3333333333333333333333333333333333333333");
+        fx.contract.revoke_attestation(revoked_id);
+
+        assert_eq!(fx.contract.get_user_chains(fx.user), vec![DEFAULT_CHAIN.to_string(), "anvil-local".to_string()]);
+        assert_eq!(fx.contract.get_user_chain_count(fx.user), 2);
+        assert_eq!(fx.contract.get_user_chain_count(fx.other), 0);
+    }
+
+    /// Baseline for the Vec-based user index: 500 attestations for one
+    /// account and 50 for another, then listing and revoking at the tail.
+    /// Prints one JSON object per line (`op`, `account`, `index`,
+    /// `gas_motes`, `micros`); gas is only metered on the casper backend
+    /// (`ODRA_BACKEND=casper`) and reads 0 on OdraVM. Run with
+    /// `cargo test test_attestation_volume -- --ignored --nocapture`.
+    #[test]
+    #[ignore = "slow; run explicitly to record the index growth baseline"]
+    fn test_attestation_volume_and_index_growth() {
+        let mut fx = Fixture::new().build();
+        let accounts = [("heavy", fx.user, 500), ("light", fx.other, 50)];
+        let report = |op: &str, account: &str, index: usize, gas_motes: u64, started: Instant| {
+            let micros = started.elapsed().as_micros() as u64;
+            let record = serde_json::json!({
+                "op": op, "account": account, "index": index, "gas_motes": gas_motes, "micros": micros,
+            });
+            std::println!("{}", record);
+        };
+
+        let mut ids: Vec<Vec<[u8; 32]>> = Vec::new();
+        for (label, account, count) in accounts {
+            fx.env.set_caller(account);
+            let mut account_ids = Vec::with_capacity(count);
+            for index in 0..count {
+                let target = alloc::format!("0x{:040x}", index + 1);
+                let started = Instant::now();
+                let (id, _) = fx.contract.create_attestation(
+                    DEFAULT_CHAIN.to_string(),
+                    target,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                );
+                report("create", label, index, fx.env.last_call_gas_cost(), started);
+                account_ids.push(id);
+            }
+            ids.push(account_ids);
+        }
+
+        for ((label, account, count), account_ids) in accounts.into_iter().zip(&ids) {
+            let started = Instant::now();
+            let listed = fx.contract.get_user_attestations(account);
+            report("list", label, count, 0, started);
+            assert_eq!(listed.len(), count);
+            assert!(listed.iter().all(|attestation| attestation.casper_address == account));
+            assert_eq!(&listed.iter().map(|attestation| attestation.id).collect::<Vec<_>>(), account_ids);
+            assert_eq!(listed[count - 1].nonce, count as u64 - 1);
+
+            fx.env.set_caller(account);
+            let tail = account_ids[count - 1];
+            let started = Instant::now();
+            fx.contract.revoke_attestation(tail);
+            report("revoke_tail", label, count - 1, fx.env.last_call_gas_cost(), started);
+            assert!(fx.contract.get_attestation(tail).unwrap().revoked);
+            assert!(!fx.contract.get_attestation(account_ids[0]).unwrap().revoked);
+            assert!(!fx.contract.get_attestation(account_ids[count - 2]).unwrap().revoked);
+        }
+
+        let unique: BTreeSet<[u8; 32]> = ids.iter().flatten().copied().collect();
+        assert_eq!(unique.len(), 550);
+    }
+
+    #[test]
+    fn test_revoke_attestation() {
+        let mut fx = Fixture::new().build();
+        let (attestation_id, _) = fx.create_default_attestation(fx.user);
 
         // Revoke
-        contract.revoke_attestation(attestation_id);
+        let events_before = fx.events_count();
+        fx.contract.revoke_attestation(attestation_id);
+        fx.assert_single_event(events_before, AttestationRevoked { id: attestation_id, casper_address: fx.user });
 
         // Verify revoked
-        let attestation = contract.get_attestation(attestation_id)
+        let attestation = fx.contract.get_attestation(attestation_id)
             .expect("Attestation should exist");
         assert!(attestation.revoked);
     }
 
     #[test]
-    #[should_panic(expected = "VmError")]
-    fn test_revoke_others_attestation_fails() {
-        let (env, mut contract) = setup();
+    fn test_duplicate_chain_address_allowed_by_default() {
+        let mut fx = Fixture::new().build();
+        assert!(fx.contract.get_allow_duplicate_chain_address());
 
-        // User 1 creates attestation
-        env.set_caller(env.get_account(1));
-        let (attestation_id, _) = contract.create_attestation(
-            "base-sepolia".to_string(),
-            "0x1234567890abcdef1234567890abcdef12345678".to_string(),
+        for _ in 0..2 {
+            fx.create_default_attestation(fx.user);
+        }
+        assert_eq!(fx.contract.get_user_attestations(fx.user).len(), 2);
+    }
+
+    #[test]
+    fn test_duplicate_chain_address_rejected_until_revoked() {
+        let mut fx = Fixture::new().build();
+        fx.contract.set_allow_duplicate_chain_address(false);
+        assert!(!fx.contract.get_allow_duplicate_chain_address());
+
+        let (first_id, _) = fx.create_default_attestation(fx.user);
+
+        // Same pair, even with a differently cased address
+        let events_before = fx.events_count();
+        assert_eq!(
+            fx.contract.try_create_attestation(
+                DEFAULT_CHAIN.to_string(),
+                "0x1234567890ABCDEF1234567890abcdef12345678".to_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            ),
+            Err(VeilError::DuplicateAttestation.into())
         );
+        fx.assert_no_events_since(events_before);
+
+        // Another chain, another address or another user is fine
+        fx.create_attestation(fx.user, "anvil-local", DEFAULT_TARGET);
+        fx.create_attestation(fx.user, DEFAULT_CHAIN, "0x70997970c51812dc3a010c7d01b50e0d17dc79c8");
+        fx.create_default_attestation(fx.other);
+
+        fx.env.set_caller(fx.user);
+        fx.contract.revoke_attestation(first_id);
+        let (second_id, _) = fx.create_default_attestation(fx.user);
+        assert_ne!(second_id, first_id);
 
-        // User 2 tries to revoke - should fail
-        env.set_caller(env.get_account(2));
-        contract.revoke_attestation(attestation_id);
+        assert_eq!(
+            fx.contract.try_set_allow_duplicate_chain_address(true),
+            Err(VeilError::NotAdmin.into())
+        );
     }
 
     #[test]
-    #[should_panic(expected = "VmError")]
-    fn test_invalid_evm_address_rejected() {
-        let (env, mut contract) = setup();
+    fn test_chain_attestation_limit_counts_active_attestations() {
+        let mut fx = Fixture::new().build();
+        assert_eq!(fx.contract.get_chain_attestation_limit(DEFAULT_CHAIN.to_string()), DEFAULT_CHAIN_ATTESTATION_LIMIT);
+        fx.contract.set_chain_attestation_limit(DEFAULT_CHAIN.to_string(), 1);
+        assert_eq!(fx.contract.get_chain_attestation_limit(DEFAULT_CHAIN.to_string()), 1);
 
-        env.set_caller(env.get_account(1));
-        contract.create_attestation(
-            "base-sepolia".to_string(),
-            "not-an-address".to_string(), // Invalid - should panic
+        let (first_id, _) = fx.create_default_attestation(fx.user);
+        let events_before = fx.events_count();
+        assert_eq!(
+            fx.contract.try_create_attestation(
+                DEFAULT_CHAIN.to_string(),
+                "0x70997970c51812dc3a010c7d01b50e0d17dc79c8".to_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            ),
+            Err(VeilError::ChainAttestationLimitReached.into())
+        );
+        fx.assert_no_events_since(events_before);
+
+        // The limit is per user and per chain
+        fx.create_attestation(fx.user, "anvil-local", DEFAULT_TARGET);
+        fx.create_attestation(fx.user, "anvil-local", DEFAULT_TARGET);
+        fx.create_default_attestation(fx.other);
+
+        // Revoking frees the slot
+        fx.env.set_caller(fx.user);
+        fx.contract.revoke_attestation(first_id);
+        fx.create_default_attestation(fx.user);
+
+        assert_eq!(
+            fx.contract.try_set_chain_attestation_limit(DEFAULT_CHAIN.to_string(), 5),
+            Err(VeilError::NotAdmin.into())
         );
     }
 
     #[test]
-    fn test_nonce_increments() {
-        let (env, mut contract) = setup();
-        let caller = env.get_account(1);
+    fn test_chain_addition_waits_for_proposal_period() {
+        let mut fx = Fixture::new().build();
+        let proposed_at = fx.env.block_time();
+        let activates_at = proposed_at + CHAIN_PROPOSAL_DELAY_SECS * 1000;
 
-        env.set_caller(caller);
+        let events_before = fx.events_count();
+        fx.contract.propose_add_chain("ethereum".to_string());
+        fx.assert_single_event(events_before, ChainProposed { chain: "ethereum".to_string(), activates_at });
+        assert_eq!(fx.contract.get_pending_chain_additions(), vec![("ethereum".to_string(), activates_at)]);
+        assert_eq!(
+            fx.contract.try_propose_add_chain("ethereum".to_string()),
+            Err(VeilError::ChainAlreadyProposed.into())
+        );
 
-        // First attestation
-        let (id1, _) = contract.create_attestation(
-            "base-sepolia".to_string(),
-            "0x1111111111111111111111111111111111111111".to_string(),
+        fx.env.advance_block_time(CHAIN_PROPOSAL_DELAY_SECS * 1000 - 1);
+        assert_eq!(
+            fx.contract.try_confirm_add_chain("ethereum".to_string()),
+            Err(VeilError::ChainProposalNotReady.into())
         );
+        assert_eq!(fx.contract.get_pending_chain_additions().len(), 1);
 
-        // Second attestation
-        let (id2, _) = contract.create_attestation(
-            "base-sepolia".to_string(),
-            "0x2222222222222222222222222222222222222222".to_string(),
+        fx.env.advance_block_time(1);
+        let events_before = fx.events_count();
+        fx.contract.confirm_add_chain("ethereum".to_string());
+        fx.assert_single_event(events_before, ChainActivated { chain: "ethereum".to_string() });
+        assert!(fx.contract.get_pending_chain_additions().is_empty());
+        assert_eq!(fx.contract.get_deployment_info().supported_chains, vec!["ethereum".to_string()]);
+
+        assert_eq!(
+            fx.contract.try_propose_add_chain("ethereum".to_string()),
+            Err(VeilError::ChainAlreadySupported.into())
+        );
+        assert_eq!(
+            fx.contract.try_confirm_add_chain("ethereum".to_string()),
+            Err(VeilError::ChainNotProposed.into())
         );
+    }
 
-        let att1 = contract.get_attestation(id1).unwrap();
-        let att2 = contract.get_attestation(id2).unwrap();
+    #[test]
+    fn test_chain_proposal_cancellation() {
+        let mut fx = Fixture::new().build();
+        fx.contract.propose_add_chain("ethereum".to_string());
+        fx.contract.propose_add_chain("arbitrum".to_string());
 
-        assert_eq!(att1.nonce, 0);
-        assert_eq!(att2.nonce, 1);
+        let events_before = fx.events_count();
+        fx.contract.cancel_chain_proposal("ethereum".to_string());
+        fx.assert_single_event(events_before, ChainProposalCancelled { chain: "ethereum".to_string() });
+        let pending: Vec<String> =
+            fx.contract.get_pending_chain_additions().into_iter().map(|(chain, _)| chain).collect();
+        assert_eq!(pending, vec!["arbitrum".to_string()]);
+
+        fx.env.advance_block_time(CHAIN_PROPOSAL_DELAY_SECS * 1000);
+        assert_eq!(
+            fx.contract.try_confirm_add_chain("ethereum".to_string()),
+            Err(VeilError::ChainNotProposed.into())
+        );
+        assert_eq!(
+            fx.contract.try_cancel_chain_proposal("ethereum".to_string()),
+            Err(VeilError::ChainNotProposed.into())
+        );
+
+        // A cancelled chain can be proposed again, restarting the period
+        fx.contract.propose_add_chain("ethereum".to_string());
+        assert_eq!(
+            fx.contract.try_confirm_add_chain("ethereum".to_string()),
+            Err(VeilError::ChainProposalNotReady.into())
+        );
+
+        fx.env.set_caller(fx.user);
+        assert_eq!(
+            fx.contract.try_cancel_chain_proposal("arbitrum".to_string()),
+            Err(VeilError::NotAdmin.into())
+        );
+    }
+
+    fn chain_deployment(enabled: bool, min_tier: Tier) -> ChainDeployment {
+        ChainDeployment {
+            verifier_address: [0x11; 20],
+            explorer_tx_prefix: "https://sepolia.basescan.org/tx/".to_string(),
+            enabled,
+            min_tier: min_tier as u8,
+            schema_version: SCHEMA_VERSION,
+        }
     }
 
     #[test]
-    fn test_tier_calculation() {
-        let (env, contract) = setup();
-        let user = env.get_account(1);
+    fn test_chain_deployment_registry_crud() {
+        let mut fx = Fixture::new().build();
+        assert_eq!(fx.contract.get_chain_deployment(DEFAULT_CHAIN.to_string()), None);
+        assert!(fx.contract.list_chain_deployments().is_empty());
 
-        // With zero stake (query_user_stake returns 0 for MVP)
-        let tier = contract.get_user_tier(user);
+        let events_before = fx.events_count();
+        fx.contract.set_chain_deployment(DEFAULT_CHAIN.to_string(), chain_deployment(true, Tier::None));
+        fx.assert_single_event(
+            events_before,
+            ChainDeploymentUpdated {
+                chain: DEFAULT_CHAIN.to_string(),
+                verifier_address: [0x11; 20],
+                enabled: true,
+                min_tier: 0,
+            },
+        );
+        fx.contract.set_chain_deployment("anvil-local".to_string(), chain_deployment(true, Tier::Bronze));
 
-        // Should be None tier
-        assert_eq!(tier as u8, 0);
+        // Updating keeps the chain's place in the list
+        let updated = ChainDeployment { verifier_address: [0x22; 20], ..chain_deployment(false, Tier::Silver) };
+        fx.contract.set_chain_deployment(DEFAULT_CHAIN.to_string(), updated.clone());
+        assert_eq!(fx.contract.get_chain_deployment(DEFAULT_CHAIN.to_string()), Some(updated.clone()));
+        assert_eq!(
+            fx.contract.list_chain_deployments(),
+            vec![
+                (DEFAULT_CHAIN.to_string(), updated),
+                ("anvil-local".to_string(), chain_deployment(true, Tier::Bronze)),
+            ]
+        );
+
+        let events_before = fx.events_count();
+        fx.contract.remove_chain_deployment(DEFAULT_CHAIN.to_string());
+        fx.assert_single_event(events_before, ChainDeploymentRemoved { chain: DEFAULT_CHAIN.to_string() });
+        assert_eq!(fx.contract.get_chain_deployment(DEFAULT_CHAIN.to_string()), None);
+        assert_eq!(fx.contract.list_chain_deployments().len(), 1);
+
+        // Removing an unknown chain is a no-op
+        let events_before = fx.events_count();
+        fx.contract.remove_chain_deployment(DEFAULT_CHAIN.to_string());
+        fx.assert_no_events_since(events_before);
+
+        fx.env.set_caller(fx.user);
+        assert_eq!(
+            fx.contract.try_remove_chain_deployment("anvil-local".to_string()),
+            Err(VeilError::NotAdmin.into())
+        );
+    }
+
+    #[test]
+    fn test_chain_deployment_enabled_gate() {
+        let mut fx = Fixture::new().build();
+        fx.contract.set_chain_deployment(DEFAULT_CHAIN.to_string(), chain_deployment(false, Tier::None));
+
+        fx.env.set_caller(fx.user);
+        let events_before = fx.events_count();
+        assert_eq!(
+            fx.contract.try_create_attestation(
+                DEFAULT_CHAIN.to_string(),
+                DEFAULT_TARGET.to_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            ),
+            Err(VeilError::ChainDisabled.into())
+        );
+        fx.assert_no_events_since(events_before);
+        // Chains without metadata are not gated
+        fx.create_attestation(fx.user, "anvil-local", DEFAULT_TARGET);
+
+        fx.env.set_caller(fx.admin);
+        fx.contract.set_chain_deployment(DEFAULT_CHAIN.to_string(), chain_deployment(true, Tier::None));
+        fx.create_default_attestation(fx.user);
+    }
+
+    #[test]
+    fn test_chain_allowlist_gates_creation_when_enabled() {
+        let mut fx = Fixture::new().build();
+        let create_on = |fx: &mut Fixture, chain: &str| {
+            fx.env.set_caller(fx.user);
+            fx.contract
+                .try_create_attestation(
+                    chain.to_string(),
+                    DEFAULT_TARGET.to_string(),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .map(|_| ())
+        };
+
+        // Off by default: any chain goes
+        assert!(!fx.contract.get_allowlist_enabled());
+        assert!(fx.contract.is_chain_allowed("unlisted-chain".to_string()));
+        assert_eq!(create_on(&mut fx, "unlisted-chain"), Ok(()));
+
+        fx.env.set_caller(fx.admin);
+        fx.contract.allow_chain(DEFAULT_CHAIN.to_string());
+        assert!(fx.env.emitted_event(
+            &fx.contract,
+            ChainAllowlistUpdated { chain: DEFAULT_CHAIN.to_string(), allowed: true }
+        ));
+        fx.contract.allow_chain("ethereum-sepolia".to_string());
+        fx.contract.allow_chain(DEFAULT_CHAIN.to_string());
+        assert_eq!(fx.contract.get_allowed_chains(), vec![DEFAULT_CHAIN.to_string(), "ethereum-sepolia".to_string()]);
+        fx.contract.set_allowlist_enabled(true);
+
+        assert_eq!(create_on(&mut fx, DEFAULT_CHAIN), Ok(()));
+        assert_eq!(create_on(&mut fx, "unlisted-chain"), Err(VeilError::ChainNotAllowed.into()));
+        assert!(!fx.contract.is_chain_allowed("unlisted-chain".to_string()));
+
+        // Disallowed chains are rejected from then on
+        fx.env.set_caller(fx.admin);
+        fx.contract.disallow_chain(DEFAULT_CHAIN.to_string());
+        assert_eq!(fx.contract.get_allowed_chains(), vec!["ethereum-sepolia".to_string()]);
+        assert_eq!(create_on(&mut fx, DEFAULT_CHAIN), Err(VeilError::ChainNotAllowed.into()));
+
+        // Turning the mode off restores the permissive default
+        fx.env.set_caller(fx.admin);
+        fx.contract.set_allowlist_enabled(false);
+        assert_eq!(create_on(&mut fx, "unlisted-chain"), Ok(()));
+    }
+
+    #[test]
+    fn test_region_allowlist_filters_region_hints() {
+        let mut fx = Fixture::new().build();
+        let create_in = |fx: &mut Fixture, target: &str, region: Option<&str>| {
+            fx.env.set_caller(fx.user);
+            fx.contract
+                .try_create_attestation(
+                    DEFAULT_CHAIN.to_string(),
+                    target.to_string(),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    region.map(String::from),
+                )
+                .map(|(id, _)| id)
+        };
+
+        // Empty allowlist: any well-formed hint, or none, goes
+        assert!(fx.contract.get_allowed_regions().is_empty());
+        let unrestricted = create_in(&mut fx, "0x0000000000000000000000000000000000000001", Some("LATAM")).unwrap();
+        assert_eq!(fx.contract.get_attestation(unrestricted).unwrap().region_hint, Some("LATAM".to_string()));
+        assert_eq!(
+            create_in(&mut fx, "0x0000000000000000000000000000000000000002", Some("EUROPE-WEST")),
+            Err(VeilError::InvalidRegionHint.into())
+        );
+
+        fx.env.set_caller(fx.admin);
+        fx.contract.add_allowed_region("EU".to_string());
+        assert!(fx.env.emitted_event(&fx.contract, RegionAllowlistUpdated { region: "EU".to_string(), allowed: true }));
+        fx.contract.add_allowed_region("US".to_string());
+        fx.contract.add_allowed_region("EU".to_string());
+        assert_eq!(fx.contract.get_allowed_regions(), vec!["EU".to_string(), "US".to_string()]);
+        assert_eq!(fx.contract.try_add_allowed_region(String::new()), Err(VeilError::InvalidRegionHint.into()));
+
+        // Listed regions pass and are signed into the payload; others and no hint are rejected
+        let eu = create_in(&mut fx, "0x0000000000000000000000000000000000000003", Some("EU")).unwrap();
+        let (encoded, _) = fx.contract.get_attestation_for_evm(eu).unwrap();
+        assert_eq!(abi_decode_payload(&encoded).unwrap().region_hint, "EU");
+        assert_eq!(
+            create_in(&mut fx, "0x0000000000000000000000000000000000000004", Some("APAC")),
+            Err(VeilError::RegionNotAllowed.into())
+        );
+        assert_eq!(
+            create_in(&mut fx, "0x0000000000000000000000000000000000000004", None),
+            Err(VeilError::RegionNotAllowed.into())
+        );
+        assert!(!fx.contract.is_region_allowed(Some("APAC".to_string())));
+
+        // Removing every region lifts the restriction; existing hints stay
+        fx.env.set_caller(fx.admin);
+        fx.contract.remove_allowed_region("EU".to_string());
+        assert_eq!(
+            create_in(&mut fx, "0x0000000000000000000000000000000000000004", Some("EU")),
+            Err(VeilError::RegionNotAllowed.into())
+        );
+        fx.env.set_caller(fx.admin);
+        fx.contract.remove_allowed_region("US".to_string());
+        assert!(fx.contract.get_allowed_regions().is_empty());
+        assert!(create_in(&mut fx, "0x0000000000000000000000000000000000000004", None).is_ok());
+        assert_eq!(fx.contract.get_attestation(eu).unwrap().region_hint, Some("EU".to_string()));
+    }
+
+    #[test]
+    fn test_region_allowlist_is_admin_only() {
+        let mut fx = Fixture::new().build();
+        fx.env.set_caller(fx.user);
+        assert_eq!(fx.contract.try_add_allowed_region("EU".to_string()), Err(VeilError::NotAdmin.into()));
+        assert_eq!(fx.contract.try_remove_allowed_region("EU".to_string()), Err(VeilError::NotAdmin.into()));
+    }
+
+    #[test]
+    fn test_migration_only_runs_from_v1() {
+        let mut fx = Fixture::new().build();
+        assert_eq!(fx.contract.get_contract_version(), STORAGE_VERSION);
+        assert_eq!(fx.contract.try_migrate_v1_to_v2(), Err(VeilError::AlreadyMigrated.into()));
+        fx.env.set_caller(fx.user);
+        assert_eq!(fx.contract.try_migrate_v1_to_v2(), Err(VeilError::NotAdmin.into()));
+    }
+
+    #[test]
+    fn test_chain_deployment_min_tier_gate() {
+        let mut fx = Fixture::new()
+            .with_mock_stake(1, cspr_to_motes(1_000, 0))
+            .with_mock_stake(2, cspr_to_motes(100, 0))
+            .build();
+        fx.contract.set_chain_deployment(DEFAULT_CHAIN.to_string(), chain_deployment(true, Tier::Silver));
+
+        // Silver meets the minimum, Bronze does not
+        fx.create_default_attestation(fx.user);
+        fx.env.set_caller(fx.other);
+        assert_eq!(
+            fx.contract.try_create_attestation(
+                DEFAULT_CHAIN.to_string(),
+                DEFAULT_TARGET.to_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            ),
+            Err(VeilError::TierBelowChainMinimum.into())
+        );
+        fx.create_attestation(fx.other, "anvil-local", DEFAULT_TARGET);
+    }
+
+    #[test]
+    fn test_revoke_without_delay_is_immediate() {
+        let mut fx = Fixture::new().build();
+        assert_eq!(fx.contract.get_revocation_delay_secs(), 0);
+
+        let (attestation_id, _) = fx.create_default_attestation(fx.user);
+        let events_before = fx.events_count();
+        fx.contract.revoke_attestation(attestation_id);
+
+        assert!(fx.contract.get_attestation(attestation_id).unwrap().revoked);
+        assert_eq!(fx.contract.get_pending_revocation(attestation_id), None);
+        fx.assert_single_event(events_before, AttestationRevoked { id: attestation_id, casper_address: fx.user });
+    }
+
+    #[test]
+    fn test_revoke_with_delay_requires_waiting() {
+        let mut fx = Fixture::new().build();
+        fx.contract.set_revocation_delay_secs(600);
+
+        let (attestation_id, _) = fx.create_default_attestation(fx.user);
+        assert_eq!(
+            fx.contract.try_finalize_revocation(attestation_id),
+            Err(VeilError::NoPendingRevocation.into())
+        );
+
+        let events_before = fx.events_count();
+        fx.contract.revoke_attestation(attestation_id);
+        let effective_at = fx.contract.get_attestation(attestation_id).unwrap().created_at + 600 * 1000;
+        assert_eq!(fx.contract.get_pending_revocation(attestation_id), Some(effective_at));
+        fx.assert_single_event(events_before, RevocationPending { id: attestation_id, effective_at });
+
+        // Still usable while the delay runs
+        assert!(!fx.contract.get_attestation(attestation_id).unwrap().revoked);
+        assert_eq!(
+            fx.contract.is_attestation_usable_for_chain(attestation_id, DEFAULT_CHAIN.to_string()),
+            (true, "OK".to_string())
+        );
+        fx.env.advance_block_time(599 * 1000);
+        let events_before = fx.events_count();
+        assert_eq!(
+            fx.contract.try_finalize_revocation(attestation_id),
+            Err(VeilError::RevocationNotYetEffective.into())
+        );
+        fx.assert_no_events_since(events_before);
+
+        // Rejected once the delay has passed, even before finalizing
+        fx.env.advance_block_time(1000);
+        assert_eq!(
+            fx.contract.is_attestation_usable_for_chain(attestation_id, DEFAULT_CHAIN.to_string()),
+            (false, "Attestation revoked".to_string())
+        );
+
+        // Anyone can finalize; the event still names the owner
+        fx.env.set_caller(fx.other);
+        fx.contract.finalize_revocation(attestation_id);
+        assert!(fx.contract.get_attestation(attestation_id).unwrap().revoked);
+        fx.assert_single_event(events_before, AttestationRevoked { id: attestation_id, casper_address: fx.user });
+    }
+
+    /// Default attestation validity
+    const WEEK_SECS: u64 = 7 * 24 * 60 * 60;
+
+    /// Fixture where account 3 is a relayer and the user has opted in to
+    /// auto-renewal of their default attestation
+    fn auto_renew_fixture() -> (Fixture, Address, [u8; 32]) {
+        let mut fx = Fixture::new().build();
+        let relayer = fx.env.get_account(3);
+        fx.contract.add_relayer(relayer);
+        let (attestation_id, _) = fx.create_default_attestation(fx.user);
+        fx.contract.set_auto_renew(true);
+        (fx, relayer, attestation_id)
+    }
+
+    #[test]
+    fn test_auto_renew_requires_relayer_and_opt_in() {
+        let (mut fx, relayer, attestation_id) = auto_renew_fixture();
+        fx.env.advance_block_time((WEEK_SECS - DEFAULT_RENEWAL_GRACE_SECS) * 1000);
+
+        fx.env.set_caller(fx.other);
+        assert_eq!(
+            fx.contract.try_execute_auto_renew(fx.user, attestation_id),
+            Err(VeilError::NotRelayer.into())
+        );
+        assert_eq!(fx.contract.try_add_relayer(fx.other), Err(VeilError::NotAdmin.into()));
+
+        fx.env.set_caller(fx.user);
+        fx.contract.set_auto_renew(false);
+        assert!(!fx.contract.get_auto_renew(fx.user));
+        fx.env.set_caller(relayer);
+        assert_eq!(
+            fx.contract.try_execute_auto_renew(fx.user, attestation_id),
+            Err(VeilError::AutoRenewDisabled.into())
+        );
+
+        fx.env.set_caller(fx.admin);
+        fx.contract.remove_relayer(relayer);
+        assert!(!fx.contract.is_relayer(relayer));
+    }
+
+    #[test]
+    fn test_attested_addresses_deduplicated_by_chain_and_address() {
+        let mut fx = Fixture::new().with_validity(60 * 60).build();
+        assert_eq!(fx.contract.get_all_attested_addresses(fx.user), AttestedAddresses::default());
+
+        // Revoked and re-attested: one entry, reported by the active attestation
+        let (revoked_id, _) = fx.create_default_attestation(fx.user);
+        fx.contract.revoke_attestation(revoked_id);
+        fx.create_default_attestation(fx.user);
+        fx.create_attestation(fx.user, "anvil-local", DEFAULT_TARGET);
+
+        let book = fx.contract.get_all_attested_addresses(fx.user);
+        assert_eq!(
+            book,
+            AttestedAddresses {
+                addresses: vec![DEFAULT_TARGET.to_string(), DEFAULT_TARGET.to_string()],
+                chains: vec![DEFAULT_CHAIN.to_string(), "anvil-local".to_string()],
+                is_active: vec![true, true],
+            }
+        );
+
+        fx.env.advance_block_time(60 * 60 * 1000);
+        let book = fx.contract.get_all_attested_addresses(fx.user);
+        assert_eq!((book.addresses.len(), book.chains.len()), (2, 2));
+        assert_eq!(book.is_active, vec![false, false]);
+    }
+
+    /// Create the default attestation from `account`, referred by `referrer`
+    fn create_referred(fx: &mut Fixture, account: Address, referrer: Address) -> OdraResult<[u8; 32]> {
+        fx.env.set_caller(account);
+        let (chain, target) = (DEFAULT_CHAIN.to_string(), DEFAULT_TARGET.to_string());
+        fx.contract
+            .try_create_attestation(chain, target, None, None, None, Some(referrer), None, None)
+            .map(|(id, _)| id)
+    }
+
+    #[test]
+    fn test_referrals_are_attributed_and_counted() {
+        let mut fx = Fixture::new().build();
+        let referrer = fx.env.get_account(3);
+        let first = create_referred(&mut fx, fx.user, referrer).unwrap();
+        let second = create_referred(&mut fx, fx.other, referrer).unwrap();
+        let third = create_referred(&mut fx, fx.user, referrer).unwrap();
+        fx.create_default_attestation(fx.user);
+
+        let event: AttestationCreatedV2 = fx.env.get_event(&fx.contract, -3).unwrap();
+        assert_eq!((event.id, event.referrer), (third, Some(referrer)));
+        let event_v1: AttestationCreated = fx.env.get_event(&fx.contract, -4).unwrap();
+        assert_eq!((event_v1.id, event_v1.referrer), (third, Some(referrer)));
+        assert_eq!(fx.contract.get_attestation(first).unwrap().referrer, Some(referrer));
+        assert_eq!(fx.contract.get_referral_count(referrer), 3);
+        assert_eq!(fx.contract.get_referral_count(fx.user), 0);
+        assert_eq!(fx.contract.get_referred_attestations(referrer, 0, 10), vec![first, second, third]);
+        assert_eq!(fx.contract.get_referred_attestations(referrer, 1, 1), vec![second]);
+        assert!(fx.contract.get_referred_attestations(referrer, 3, 10).is_empty());
+
+        // Blocking stops new referrals and keeps the old ones
+        fx.env.set_caller(fx.admin);
+        fx.contract.set_referrer_blocked(referrer, true);
+        assert!(fx.contract.is_referrer_blocked(referrer));
+        let events_before = fx.events_count();
+        assert_eq!(create_referred(&mut fx, fx.other, referrer), Err(VeilError::ReferrerBlocked.into()));
+        fx.assert_no_events_since(events_before);
+        assert_eq!(fx.contract.get_referral_count(referrer), 3);
+    }
+
+    #[test]
+    fn test_reputation_accumulates_from_history() {
+        let (mut fx, relayer, attestation_id) = auto_renew_fixture();
+        let weights = ReputationWeights::default();
+        assert_eq!(fx.contract.get_reputation_weights(), weights);
+        assert_eq!(fx.contract.get_reputation(fx.other), 0);
+        assert_eq!(fx.contract.get_reputation(fx.user), weights.per_distinct_chain);
+        assert_eq!(fx.contract.get_attestation(attestation_id).unwrap().reputation_score, 5);
+
+        // A second chain counts once, however often it is attested to
+        let (second_id, _) = fx.create_attestation(fx.user, "anvil-local", DEFAULT_TARGET);
+        fx.contract.revoke_attestation(second_id);
+        fx.create_attestation(fx.user, "anvil-local", DEFAULT_TARGET);
+        assert_eq!(fx.contract.get_attestation(second_id).unwrap().reputation_score, 10);
+        assert_eq!(fx.contract.get_reputation(fx.user), 10);
+
+        // Renewal completes a period; the new attestation carries the score
+        fx.env.advance_block_time((WEEK_SECS - DEFAULT_RENEWAL_GRACE_SECS) * 1000);
+        fx.env.set_caller(relayer);
+        let (renewed_id, _) = fx.contract.execute_auto_renew(fx.user, attestation_id);
+        assert_eq!(
+            fx.contract.get_reputation_record(fx.user),
+            ReputationRecord { completed_periods: 1, distinct_chains: 2, admin_revocations: 0 }
+        );
+        assert_eq!(fx.contract.get_reputation(fx.user), 20);
+        let (encoded, _) = fx.contract.get_attestation_for_evm(renewed_id).unwrap();
+        assert_eq!(abi_decode_payload(&encoded).unwrap().reputation_score, 20);
+
+        // An upheld dispute is an admin revocation; the score floors at zero
+        fx.env.set_caller(fx.other);
+        let bond = fx.contract.get_dispute_bond();
+        let dispute_id = fx.contract.with_tokens(bond).open_dispute(renewed_id, "stale stake".to_string());
+        fx.env.set_caller(fx.admin);
+        fx.contract.resolve_dispute(dispute_id, true);
+        assert_eq!(fx.contract.get_reputation_record(fx.user).admin_revocations, 1);
+        assert_eq!(fx.contract.get_reputation(fx.user), 0);
+
+        // Scores follow the weights
+        let lenient = ReputationWeights { per_admin_revocation: 5, ..weights };
+        fx.contract.set_reputation_weights(lenient);
+        assert_eq!(fx.contract.get_reputation_weights(), lenient);
+        assert_eq!(fx.contract.get_reputation(fx.user), 15);
+    }
+
+    #[test]
+    fn test_identity_fingerprint_survives_renewal() {
+        let (mut fx, relayer, attestation_id) = auto_renew_fixture();
+        let fingerprint = fx.contract.get_identity_fingerprint(fx.user);
+        let first = fx.contract.get_attestation(attestation_id).unwrap();
+        let mut preimage = Vec::new();
+        preimage.extend_from_slice(&first.id);
+        preimage.extend_from_slice(&first.nonce.to_be_bytes());
+        assert_eq!(
+            fingerprint,
+            keccak256(&[keccak256(fx.user.to_string().as_bytes()).as_slice(), &preimage].concat())
+        );
+        assert_eq!(fx.contract.get_identity_fingerprint(fx.other), [0u8; 32]);
+
+        fx.env.advance_block_time((WEEK_SECS - DEFAULT_RENEWAL_GRACE_SECS) * 1000);
+        fx.env.set_caller(relayer);
+        let (renewed_id, _) = fx.contract.execute_auto_renew(fx.user, attestation_id);
+        assert_ne!(renewed_id, attestation_id);
+        assert_eq!(fx.contract.get_identity_fingerprint(fx.user), fingerprint);
+
+        fx.env.set_caller(fx.user);
+        fx.contract.revoke_attestation(renewed_id);
+        assert_eq!(fx.contract.get_identity_fingerprint(fx.user), fingerprint);
+    }
+
+    #[test]
+    fn test_relayer_renews_attestation_near_expiry() {
+        let (mut fx, relayer, attestation_id) = auto_renew_fixture();
+        let original = fx.contract.get_attestation(attestation_id).unwrap();
+        fx.env.advance_block_time((WEEK_SECS - DEFAULT_RENEWAL_GRACE_SECS) * 1000);
+
+        fx.env.set_caller(relayer);
+        let (renewed_id, signature) = fx.contract.execute_auto_renew(fx.user, attestation_id);
+        fx.assert_signature_valid(&renewed_id, &signature);
+        assert_eq!(
+            fx.event_at::<AutoRenewExecuted>(-1),
+            AutoRenewExecuted { user: fx.user, attestation_id, renewed_id, renewed_by: relayer }
+        );
+
+        assert!(fx.contract.get_attestation(attestation_id).unwrap().revoked);
+        let renewed = fx.contract.get_attestation(renewed_id).unwrap();
+        assert!(!renewed.revoked);
+        assert_eq!(renewed.casper_address, fx.user);
+        assert_eq!(renewed.target_chain, original.target_chain);
+        assert_eq!(renewed.target_address, original.target_address);
+        assert_eq!(renewed.nonce, original.nonce + 1);
+        assert_eq!(renewed.expires_at, original.expires_at - DEFAULT_RENEWAL_GRACE_SECS * 1000 + WEEK_SECS * 1000);
+    }
+
+    #[test]
+    fn test_refresh_and_renewal_in_last_hour() {
+        let (mut fx, relayer, attestation_id) = auto_renew_fixture();
+        let expires_at = fx.contract.get_attestation(attestation_id).unwrap().expires_at;
+        fx.env.advance_block_time((WEEK_SECS - DEFAULT_MIN_VALIDITY_SECS / 2) * 1000);
+
+        // Less than the minimum validity is left; refreshing keeps it
+        let (refreshed_id, _) = fx.contract.refresh_attestation(attestation_id);
+        let refreshed = fx.contract.get_attestation(refreshed_id).unwrap();
+        assert_eq!(refreshed.expires_at, expires_at);
+        assert_eq!(refreshed.attestation_validity_secs, DEFAULT_MIN_VALIDITY_SECS / 2);
+
+        // Renewing the refreshed attestation carries its validity over too
+        fx.env.set_caller(relayer);
+        let (renewed_id, _) = fx.contract.execute_auto_renew(fx.user, refreshed_id);
+        let renewed = fx.contract.get_attestation(renewed_id).unwrap();
+        assert_eq!(renewed.expires_at, fx.env.block_time() + DEFAULT_MIN_VALIDITY_SECS / 2 * 1000);
+    }
+
+    #[test]
+    fn test_supersession_events_match_successor_chain() {
+        let (mut fx, relayer, created_id) = auto_renew_fixture();
+        fx.env.advance_block_time((WEEK_SECS - DEFAULT_RENEWAL_GRACE_SECS) * 1000);
+        fx.env.set_caller(relayer);
+        let (renewed_id, _) = fx.contract.execute_auto_renew(fx.user, created_id);
+        assert_eq!(
+            fx.event_at::<AttestationSuperseded>(-3),
+            AttestationSuperseded { old_id: created_id, new_id: renewed_id, reason: SUPERSEDED_BY_RENEWAL }
+        );
+        assert_eq!(
+            fx.event_at::<AttestationRenewed>(-2),
+            AttestationRenewed { old_id: created_id, new_id: renewed_id, casper_address: fx.user }
+        );
+
+        fx.env.set_caller(fx.other);
+        assert_eq!(fx.contract.try_refresh_attestation(renewed_id), Err(VeilError::NotAttestationOwner.into()));
+        fx.env.set_caller(fx.user);
+        let (refreshed_id, signature) = fx.contract.refresh_attestation(renewed_id);
+        fx.assert_signature_valid(&refreshed_id, &signature);
+        assert_eq!(
+            fx.event_at::<AttestationSuperseded>(-1),
+            AttestationSuperseded { old_id: renewed_id, new_id: refreshed_id, reason: SUPERSEDED_BY_REFRESH }
+        );
+        let refreshed = fx.contract.get_attestation(refreshed_id).unwrap();
+        assert_eq!(refreshed.expires_at, fx.contract.get_attestation(renewed_id).unwrap().expires_at);
+
+        fx.env.advance_block_time(24 * 60 * 60 * 1000);
+        let (extended_id, _) = fx.contract.extend_attestation(refreshed_id, 60 * 60);
+        let new_expires_at = refreshed.expires_at + 60 * 60 * 1000;
+        assert_eq!(fx.contract.get_attestation(extended_id).unwrap().expires_at, new_expires_at);
+        assert_eq!(
+            fx.event_at::<AttestationSuperseded>(-2),
+            AttestationSuperseded { old_id: refreshed_id, new_id: extended_id, reason: SUPERSEDED_BY_EXTENSION }
+        );
+        assert_eq!(
+            fx.event_at::<AttestationExtended>(-1),
+            AttestationExtended { id: refreshed_id, new_id: extended_id, new_expires_at }
+        );
+        assert_eq!(fx.contract.try_extend_attestation(refreshed_id, 60), Err(VeilError::AlreadyRevoked.into()));
+
+        // Walking the view reproduces the chain the events describe
+        let mut chain = vec![created_id];
+        while let Some(next) = fx.contract.get_successor(*chain.last().unwrap()) {
+            chain.push(next);
+        }
+        assert_eq!(chain, vec![created_id, renewed_id, refreshed_id, extended_id]);
+        for id in &chain[..3] {
+            assert!(fx.contract.get_attestation(*id).unwrap().revoked);
+        }
+    }
+
+    #[test]
+    fn test_auto_renew_rejected_outside_grace_window() {
+        let (mut fx, relayer, attestation_id) = auto_renew_fixture();
+        fx.env.set_caller(relayer);
+
+        fx.env.advance_block_time((WEEK_SECS - DEFAULT_RENEWAL_GRACE_SECS) * 1000 - 1);
+        let events_before = fx.events_count();
+        assert_eq!(
+            fx.contract.try_execute_auto_renew(fx.user, attestation_id),
+            Err(VeilError::OutsideRenewalWindow.into())
+        );
+
+        // Too late once expired
+        fx.env.advance_block_time(DEFAULT_RENEWAL_GRACE_SECS * 1000 + 1);
+        assert_eq!(
+            fx.contract.try_execute_auto_renew(fx.user, attestation_id),
+            Err(VeilError::OutsideRenewalWindow.into())
+        );
+        fx.assert_no_events_since(events_before);
+        assert!(!fx.contract.get_attestation(attestation_id).unwrap().revoked);
+    }
+
+    #[test]
+    fn test_non_renewable_attestation_rejects_renewal() {
+        let mut fx = Fixture::new().build();
+        let relayer = fx.env.get_account(3);
+        fx.contract.add_relayer(relayer);
+
+        fx.env.set_caller(fx.user);
+        let (attestation_id, _) = fx.contract.create_attestation(
+            DEFAULT_CHAIN.to_string(),
+            DEFAULT_TARGET.to_string(),
+            None,
+            None,
+            Some(false),
+            None,
+            None,
+            None,
+        );
+        fx.contract.set_auto_renew(true);
+        assert_eq!(fx.contract.get_attestation_renewable(attestation_id), Some(false));
+        assert!(!fx.contract.get_attestation(attestation_id).unwrap().is_renewable);
+
+        fx.env.advance_block_time((WEEK_SECS - DEFAULT_RENEWAL_GRACE_SECS) * 1000);
+        fx.env.set_caller(relayer);
+        assert_eq!(
+            fx.contract.try_execute_auto_renew(fx.user, attestation_id),
+            Err(VeilError::AttestationNotRenewable.into())
+        );
+    }
+
+    #[test]
+    fn test_make_attestation_non_renewable_is_one_way() {
+        let (mut fx, relayer, attestation_id) = auto_renew_fixture();
+        assert_eq!(fx.contract.get_attestation_renewable(attestation_id), Some(true));
+        assert_eq!(fx.contract.get_attestation_renewable([0xab; 32]), None);
+
+        let events_before = fx.events_count();
+        fx.contract.make_attestation_non_renewable(attestation_id);
+        fx.assert_single_event(
+            events_before,
+            AttestationRenewabilityChanged { id: attestation_id, is_renewable: false },
+        );
+        assert_eq!(fx.contract.get_attestation_renewable(attestation_id), Some(false));
+
+        // Already non-renewable: nothing to change
+        fx.contract.make_attestation_non_renewable(attestation_id);
+        fx.assert_no_events_since(events_before + 1);
+
+        fx.env.advance_block_time((WEEK_SECS - DEFAULT_RENEWAL_GRACE_SECS) * 1000);
+        fx.env.set_caller(relayer);
+        assert_eq!(
+            fx.contract.try_execute_auto_renew(fx.user, attestation_id),
+            Err(VeilError::AttestationNotRenewable.into())
+        );
+    }
+
+    #[test]
+    fn test_nonce_increments() {
+        let mut fx = Fixture::new().build();
+
+        // First attestation
+        let (id1, _) = fx.create_attestation(fx.user, DEFAULT_CHAIN, "0x1111111111111111111111111111111111111111");
+
+        // Second attestation
+        let (id2, _) = fx.create_attestation(fx.user, DEFAULT_CHAIN, "0x2222222222222222222222222222222222222222");
+
+        let att1 = fx.contract.get_attestation(id1).unwrap();
+        let att2 = fx.contract.get_attestation(id2).unwrap();
+
+        assert_eq!(att1.nonce, 0);
+        assert_eq!(att2.nonce, 1);
+    }
+
+    #[test]
+    fn test_interleaved_users_keep_separate_nonces_and_indexes() {
+        let mut fx = Fixture::new().build();
+        let users = [fx.user, fx.other, fx.env.get_account(3)];
+        let targets = [
+            "0x1111111111111111111111111111111111111111",
+            "0x2222222222222222222222222222222222222222",
+            "0x3333333333333333333333333333333333333333",
+        ];
+
+        // Each round every user attests the same target, so only the owner
+        // tells the records apart
+        let mut ids: Vec<Vec<[u8; 32]>> = vec![Vec::new(); users.len()];
+        for target in targets {
+            for (user, user_ids) in users.iter().zip(ids.iter_mut()) {
+                let (id, _) = fx.create_attestation(*user, DEFAULT_CHAIN, target);
+                user_ids.push(id);
+            }
+        }
+
+        for (user, user_ids) in users.iter().zip(&ids) {
+            for (nonce, id) in user_ids.iter().enumerate() {
+                let attestation = fx.contract.get_attestation(*id).unwrap();
+                assert_eq!(attestation.casper_address, *user);
+                assert_eq!(attestation.nonce, nonce as u64);
+                assert_eq!(fx.contract.get_attestation_by_nonce(*user, nonce as u64), Some(*id));
+            }
+            assert_eq!(fx.contract.get_attestation_by_nonce(*user, user_ids.len() as u64), None);
+
+            let listed: Vec<[u8; 32]> = fx.contract.get_user_attestations(*user).iter().map(|a| a.id).collect();
+            assert_eq!(&listed, user_ids);
+        }
+
+        let unique: BTreeSet<[u8; 32]> = ids.iter().flatten().copied().collect();
+        assert_eq!(unique.len(), users.len() * targets.len());
+    }
+
+    #[test]
+    fn test_revoking_one_users_attestation_leaves_others_for_same_target() {
+        let mut fx = Fixture::new().build();
+        fx.contract.set_allow_duplicate_chain_address(false);
+
+        let (user_id, _) = fx.create_default_attestation(fx.user);
+        let (other_id, _) = fx.create_default_attestation(fx.other);
+
+        fx.env.set_caller(fx.user);
+        fx.contract.revoke_attestation(user_id);
+        assert!(fx.contract.get_attestation(user_id).unwrap().revoked);
+        assert!(!fx.contract.get_attestation(other_id).unwrap().revoked);
+        assert!(fx.contract.is_attestation_valid(other_id, None));
+
+        // The revoked pair is free again for its owner only
+        fx.create_default_attestation(fx.user);
+        fx.env.set_caller(fx.other);
+        assert_eq!(
+            fx.contract.try_create_attestation(
+                DEFAULT_CHAIN.to_string(),
+                DEFAULT_TARGET.to_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            ),
+            Err(VeilError::DuplicateAttestation.into())
+        );
+        assert_eq!(fx.contract.get_user_attestations(fx.other).len(), 1);
+    }
+
+    #[test]
+    fn test_attestation_created_v2_event_carries_payload_hash() {
+        let mut fx = Fixture::new().build();
+        assert!(fx.contract.get_emit_v1_events());
+
+        // Both events while v1 is on; the fixture checks their fields
+        let events_before = fx.events_count();
+        let (attestation_id, _) = fx.create_default_attestation(fx.user);
+        assert_eq!(fx.events_count(), events_before + 2);
+        let event: AttestationCreatedV2 = fx.env.get_event(&fx.contract, -1).unwrap();
+        assert_eq!(event.payload_hash, attestation_id);
+        assert_eq!(event.schema_version, SCHEMA_VERSION);
+
+        assert_eq!(fx.contract.try_set_emit_v1_events(false), Err(VeilError::NotAdmin.into()));
+        fx.env.set_caller(fx.admin);
+        fx.contract.set_emit_v1_events(false);
+        assert!(!fx.contract.get_emit_v1_events());
+
+        let events_before = fx.events_count();
+        let (attestation_id, _) = fx.create_default_attestation(fx.user);
+        assert_eq!(fx.events_count(), events_before + 1);
+        let event: AttestationCreatedV2 = fx.env.get_event(&fx.contract, -1).unwrap();
+        assert_eq!(event.payload_hash, attestation_id);
+        assert_eq!(event.nonce, 1);
+    }
+
+    #[test]
+    fn test_attestation_created_event_carries_every_field() {
+        let mut fx = Fixture::new().with_mock_stake(1, cspr_to_motes(1_000, 0)).build();
+        fx.create_default_attestation(fx.user);
+        fx.env.advance_block_time(60 * 1000);
+        let target = "0x2222222222222222222222222222222222222222";
+        let (attestation_id, _) = fx.create_attestation(fx.user, DEFAULT_CHAIN, target);
+        let attestation = fx.contract.get_attestation(attestation_id).unwrap();
+
+        let event: AttestationCreated = fx.env.get_event(&fx.contract, -2).unwrap();
+        assert_eq!(
+            event,
+            AttestationCreated {
+                id: attestation_id,
+                casper_address: fx.user,
+                target_chain: DEFAULT_CHAIN.to_string(),
+                target_address: target.to_string(),
+                tier: Tier::Silver as u8,
+                expires_at: attestation.expires_at,
+                nonce: 1,
+                stake_amount: cspr_to_motes(1_000, 0),
+                created_at: fx.env.block_time(),
+                referrer: None,
+                supersedes: None,
+            }
+        );
+        assert_eq!(event.expires_at, event.created_at + attestation.attestation_validity_secs * 1000);
+    }
+
+    #[test]
+    fn test_attestation_events_replay_in_creation_order() {
+        let mut fx = Fixture::new().build();
+        let ids = vec![
+            fx.create_default_attestation(fx.user).0,
+            fx.create_default_attestation(fx.other).0,
+            fx.create_attestation(fx.user, "anvil-local", DEFAULT_TARGET).0,
+            fx.create_default_attestation(fx.other).0,
+        ];
+        assert_eq!(fx.contract.get_total_attestation_count(), 4);
+
+        let replay = fx.contract.get_attestation_events_replay(0, 10);
+        assert_eq!(replay.iter().map(|event| event.id).collect::<Vec<_>>(), ids);
+        assert_eq!(replay.last().unwrap(), &fx.event_at::<AttestationCreated>(-2));
+        for (index, id) in ids.iter().enumerate() {
+            assert_eq!(fx.contract.get_attestation_by_global_index(index as u64).unwrap().id, *id);
+        }
+        assert_eq!(fx.contract.get_attestation_by_global_index(4), None);
+
+        // Paging respects the limit and stops at the end
+        let page = fx.contract.get_attestation_events_replay(1, 2);
+        assert_eq!(page.iter().map(|event| event.id).collect::<Vec<_>>(), ids[1..3]);
+        assert_eq!(fx.contract.get_attestation_events_replay(3, 10).len(), 1);
+        assert!(fx.contract.get_attestation_events_replay(4, 10).is_empty());
+        assert!(fx.contract.get_attestation_events_replay(0, 0).is_empty());
+    }
+
+    #[test]
+    fn test_attestation_findable_by_nonce() {
+        let mut fx = Fixture::new().build();
+
+        let ids: Vec<[u8; 32]> = (0..3).map(|_| fx.create_default_attestation(fx.user).0).collect();
+        let (other_id, _) = fx.create_default_attestation(fx.other);
+
+        for (nonce, id) in ids.iter().enumerate() {
+            assert_eq!(fx.contract.get_attestation(*id).unwrap().nonce, nonce as u64);
+            assert_eq!(fx.contract.get_attestation_by_nonce(fx.user, nonce as u64), Some(*id));
+        }
+        assert_eq!(fx.contract.get_attestation_by_nonce(fx.other, 0), Some(other_id));
+
+        // Unused nonces, and nonces only another user has used, find nothing
+        assert_eq!(fx.contract.get_attestation_by_nonce(fx.user, 3), None);
+        assert_eq!(fx.contract.get_attestation_by_nonce(fx.other, 1), None);
+        assert_eq!(fx.contract.get_attestation_by_nonce(fx.env.get_account(3), 0), None);
+    }
+
+    #[test]
+    fn test_tier_calculation() {
+        let fx = Fixture::new().build();
+
+        // With zero stake (query_user_stake returns 0 for MVP)
+        let tier = fx.contract.get_user_tier(fx.user);
+
+        // Should be None tier
+        assert_eq!(tier as u8, 0);
+    }
+
+    #[test]
+    fn test_custom_tier_overrides_stake() {
+        let mut fx = Fixture::new().with_mock_stake(2, cspr_to_motes(1_000, 0)).build();
+        assert!(!fx.contract.has_custom_tier(fx.user));
+
+        fx.contract.set_custom_tier(fx.user, Some(Tier::Gold));
+        assert!(fx.env.emitted_event(
+            &fx.contract,
+            CustomTierSet { user: fx.user, tier: Some(Tier::Gold), set_by: fx.admin }
+        ));
+        assert!(fx.contract.has_custom_tier(fx.user));
+        assert_eq!(fx.contract.get_user_tier(fx.user), Tier::Gold);
+        let (attestation_id, _) = fx.create_default_attestation(fx.user);
+        assert_eq!(fx.contract.get_attestation(attestation_id).unwrap().tier, Tier::Gold);
+
+        // A custom tier may also be below the stake-based one
+        fx.env.set_caller(fx.admin);
+        fx.contract.set_custom_tier(fx.other, Some(Tier::Bronze));
+        assert_eq!(fx.contract.get_user_tier(fx.other), Tier::Bronze);
+
+        fx.contract.set_custom_tier(fx.user, None);
+        fx.contract.set_custom_tier(fx.other, None);
+        assert!(!fx.contract.has_custom_tier(fx.user));
+        assert_eq!(fx.contract.get_user_tier(fx.user), Tier::None);
+        assert_eq!(fx.contract.get_user_tier(fx.other), Tier::Silver);
+    }
+
+    #[test]
+    fn test_tier_change_log_records_only_changes() {
+        let mut fx = Fixture::new().build();
+        let user = fx.user;
+
+        let (first_id, _) = fx.create_attestation(user, DEFAULT_CHAIN, "0x1111111111111111111111111111111111111111");
+        let first_created_at = fx.env.block_time();
+        for address in ["0x2222222222222222222222222222222222222222", "0x3333333333333333333333333333333333333333"] {
+            fx.env.advance_block_time(1_000);
+            fx.create_attestation(user, DEFAULT_CHAIN, address);
+        }
+        assert_eq!(fx.contract.get_tier_change_count(user), 1);
+        assert_eq!(fx.contract.get_tier_change_log(user), vec![(Tier::None, first_created_at, first_id)]);
+
+        // Without a stake source stake is zero, so lower the Bronze threshold to move
+        // the user up a tier as a stake increase would
+        fx.env.set_caller(fx.admin);
+        fx.contract.update_tier_thresholds(TierThresholds { bronze: 0, silver: 500, gold: 5_000, platinum: 50_000 });
+        fx.env.advance_block_time(1_000);
+        let (bronze_id, _) = fx.create_attestation(user, DEFAULT_CHAIN, "0x4444444444444444444444444444444444444444");
+
+        assert_eq!(fx.contract.get_tier_change_count(user), 2);
+        assert_eq!(fx.contract.get_tier_change_log(user)[1], (Tier::Bronze, fx.env.block_time(), bronze_id));
+        assert_eq!(fx.contract.get_tier_change_count(fx.other), 0);
+    }
+
+    fn sample_payload() -> AttestationPayload {
+        AttestationPayload {
+            casper_address_hash: [0x11; 32],
+            target_chain: DEFAULT_CHAIN.to_string(),
+            target_address: DEFAULT_TARGET.to_string(),
+            stake_amount: U512::zero(),
+            tier: 0,
+            account_age_days: 0,
+            created_at: 1_000,
+            expires_at: 604_801_000,
+            nonce: 0,
+            trusted_verifier: [0; 20],
+            casper_era_id: 0,
+            reputation_score: 0,
+            attestation_type: DEFAULT_ATTESTATION_TYPE.to_string(),
+            template_id: 0,
+            nonce_commitment: [0; 32],
+            hash_algorithm_version: encoding::HASH_ALGORITHM_KECCAK256,
+            claims_hash: [0; 32],
+            region_hint: String::new(),
+            stake_source: STAKE_SOURCE_LIVE,
+            stake_epoch: 0,
+        }
+    }
+
+    #[test]
+    fn test_attestation_id_no_collision_uses_original_nonce() {
+        let mut payload = sample_payload();
+
+        let (_, _, retries) = find_unused_attestation_id(&mut payload, None, |_| false)
+            .expect("Should find an ID");
+
+        assert_eq!(retries, 0);
+        assert_eq!(payload.nonce, 0);
+    }
+
+    #[test]
+    fn test_attestation_id_collision_retries_with_bumped_nonce() {
+        // Work out the ID the payload would normally get
+        let (expected_id, _, _) = find_unused_attestation_id(&mut sample_payload(), None, |_| false)
+            .expect("Should find an ID");
+
+        // Inject a pre-existing attestation at that ID
+        let mut payload = sample_payload();
+        let (attestation_id, _, retries) =
+            find_unused_attestation_id(&mut payload, None, |id| *id == expected_id)
+                .expect("Should find an ID after retrying");
+
+        assert_ne!(attestation_id, expected_id, "Retry should produce a different ID");
+        assert_eq!(retries, 1);
+        assert_eq!(payload.nonce, 1);
+    }
+
+    #[test]
+    fn test_attestation_id_collision_gives_up_after_max_retries() {
+        let mut payload = sample_payload();
+
+        let result = find_unused_attestation_id(&mut payload, None, |_| true);
+
+        assert!(result.is_none());
+        assert_eq!(payload.nonce, MAX_ATTESTATION_ID_RETRIES as u64 + 1);
+    }
+
+    #[test]
+    fn test_attestation_ids_never_change_owner() {
+        let mut fx = Fixture::new()
+            .with_mock_stake(1, cspr_to_motes(1_000, 0))
+            .with_mock_stake(2, cspr_to_motes(100, 0))
+            .with_mock_stake(3, cspr_to_motes(10_000, 0))
+            .build();
+        let users = [fx.env.get_account(1), fx.env.get_account(2), fx.env.get_account(3)];
+        let mut owners: BTreeMap<[u8; 32], Address> = BTreeMap::new();
+        let mut assert_owners_unchanged = |fx: &Fixture, id: [u8; 32], owner: Address| {
+            assert!(owners.insert(id, owner).is_none(), "attestation ID issued twice");
+            for (id, owner) in owners.iter() {
+                assert_eq!(fx.contract.get_attestation(*id).map(|a| a.casper_address), Some(*owner));
+            }
+        };
+
+        for round in 0..4u64 {
+            for (index, user) in users.iter().enumerate() {
+                let target = alloc::format!("0x{:040x}", round * 16 + index as u64 + 1);
+                let (id, _) = fx.create_attestation(*user, DEFAULT_CHAIN, &target);
+                assert_owners_unchanged(&fx, id, *user);
+                if round % 2 == 1 {
+                    let (refreshed_id, _) = fx.contract.refresh_attestation(id);
+                    assert_owners_unchanged(&fx, refreshed_id, *user);
+                }
+            }
+        }
+        // Refreshed successors are not new attestations
+        assert_eq!(fx.contract.get_total_attestation_count(), 12);
+    }
+
+    #[test]
+    fn test_admin_contact_stored_and_hashed_in_event() {
+        let mut fx = Fixture::new().build();
+        let contact = "mailto:compliance@veil.example".to_string();
+
+        let events_before = fx.events_count();
+        fx.contract.set_admin_contact(contact.clone());
+
+        assert_eq!(fx.contract.get_admin_contact(), contact);
+        fx.assert_single_event(events_before, AdminContactUpdated { new_contact_hash: keccak256(contact.as_bytes()) });
+    }
+
+    #[test]
+    fn test_admin_contact_length_limit() {
+        let mut fx = Fixture::new().build();
+
+        let max_contact: String = "a".repeat(MAX_ADMIN_CONTACT_LEN);
+        fx.contract.set_admin_contact(max_contact.clone());
+        assert_eq!(fx.contract.get_admin_contact(), max_contact);
+
+        let too_long: String = "a".repeat(MAX_ADMIN_CONTACT_LEN + 1);
+        let events_before = fx.events_count();
+        assert_eq!(
+            fx.contract.try_set_admin_contact(too_long),
+            Err(VeilError::ContactTooLong.into())
+        );
+        fx.assert_no_events_since(events_before);
+    }
+
+    #[test]
+    fn test_admin_contact_rejects_non_printable() {
+        let mut fx = Fixture::new().build();
+        let events_before = fx.events_count();
+
+        assert_eq!(
+            fx.contract.try_set_admin_contact("ops\n@veil".to_string()),
+            Err(VeilError::InvalidContact.into())
+        );
+        fx.assert_no_events_since(events_before);
+    }
+
+    #[test]
+    fn test_admin_contact_admin_only() {
+        let mut fx = Fixture::new().build();
+
+        let events_before = fx.events_count();
+        fx.env.set_caller(fx.user);
+        assert_eq!(
+            fx.contract.try_set_admin_contact("did:example:123".to_string()),
+            Err(VeilError::NotAdmin.into())
+        );
+        fx.assert_no_events_since(events_before);
+    }
+
+    #[test]
+    fn test_attestation_usable_for_chain() {
+        let mut fx = Fixture::new().build();
+
+        let (attestation_id, _) = fx.create_default_attestation(fx.user);
+
+        assert_eq!(
+            fx.contract.is_attestation_usable_for_chain(attestation_id, DEFAULT_CHAIN.to_string()),
+            (true, "OK".to_string())
+        );
+    }
+
+    #[test]
+    fn test_cross_validate_attestation_reports_each_mismatch() {
+        let mut fx = Fixture::new().with_mock_stake(1, cspr_to_motes(1_000, 0)).build();
+        let (attestation_id, _) = fx.create_default_attestation(fx.user);
+        let stake = cspr_to_motes(1_000, 0);
+        let silver = Tier::Silver as u8;
+
+        let check = |fx: &Fixture, verified: bool, tier: u8, stake: U512| {
+            fx.contract.cross_validate_attestation(attestation_id, verified, tier, stake)
+        };
+        let fail = |reason: &str| (false, reason.to_string());
+
+        assert_eq!(check(&fx, true, silver, stake), (true, "OK".to_string()));
+        assert_eq!(check(&fx, false, silver, stake), fail("Not verified on EVM but not revoked on Casper"));
+        assert_eq!(check(&fx, true, Tier::Gold as u8, stake), fail("Tier mismatch: Casper 2, EVM 3"));
+        assert_eq!(
+            check(&fx, true, silver, stake + 1),
+            fail("Stake mismatch: Casper 1000000000000 motes, EVM 1000000000001 motes")
+        );
+        assert_eq!(
+            fx.contract.cross_validate_attestation([7u8; 32], true, silver, stake),
+            fail("Attestation not found")
+        );
+
+        fx.contract.revoke_attestation(attestation_id);
+        assert_eq!(check(&fx, true, silver, stake), fail("Verified on EVM but revoked on Casper"));
+        assert_eq!(check(&fx, false, silver, stake), (true, "OK".to_string()));
+    }
+
+    #[test]
+    fn test_attestation_usable_for_chain_failure_reasons() {
+        let mut fx = Fixture::new().build();
+
+        // Unknown ID
+        assert_eq!(
+            fx.contract.is_attestation_usable_for_chain([7u8; 32], DEFAULT_CHAIN.to_string()),
+            (false, "Attestation not found".to_string())
+        );
+
+        // Wrong chain
+        let (attestation_id, _) = fx.create_default_attestation(fx.user);
+        assert_eq!(
+            fx.contract.is_attestation_usable_for_chain(attestation_id, "ethereum".to_string()),
+            (false, "Attestation issued for a different chain".to_string())
+        );
+
+        // Expired
+        let attestation = fx.contract.get_attestation(attestation_id).unwrap();
+        fx.env.advance_block_time(attestation.expires_at - attestation.created_at);
+        assert_eq!(
+            fx.contract.is_attestation_usable_for_chain(attestation_id, DEFAULT_CHAIN.to_string()),
+            (false, "Attestation expired".to_string())
+        );
+
+        // Revoked is reported before expiry
+        fx.contract.revoke_attestation(attestation_id);
+        assert_eq!(
+            fx.contract.is_attestation_usable_for_chain(attestation_id, DEFAULT_CHAIN.to_string()),
+            (false, "Attestation revoked".to_string())
+        );
+    }
+
+    #[test]
+    fn test_attestation_unusable_for_unsupported_chain() {
+        let mut fx = Fixture::new().build();
+        let (attestation_id, _) = fx.create_default_attestation(fx.user);
+
+        fx.env.set_caller(fx.admin);
+        fx.contract.set_supported_chains(vec!["ethereum".to_string()]);
+        assert_eq!(
+            fx.contract.is_attestation_usable_for_chain(attestation_id, DEFAULT_CHAIN.to_string()),
+            (false, "Chain not supported".to_string())
+        );
+        fx.contract.set_supported_chains(vec!["ethereum".to_string(), DEFAULT_CHAIN.to_string()]);
+        assert_eq!(
+            fx.contract.is_attestation_usable_for_chain(attestation_id, DEFAULT_CHAIN.to_string()),
+            (true, "OK".to_string())
+        );
+    }
+
+    #[test]
+    fn test_attestation_unusable_for_verifier_on_other_schema() {
+        let mut fx = Fixture::new().build();
+        let (attestation_id, _) = fx.create_default_attestation(fx.user);
+        let usable = |fx: &Fixture| {
+            fx.contract.is_attestation_usable_for_chain(attestation_id, DEFAULT_CHAIN.to_string())
+        };
+
+        fx.env.set_caller(fx.admin);
+        let outdated = ChainDeployment { schema_version: SCHEMA_VERSION - 1, ..chain_deployment(true, Tier::None) };
+        fx.contract.set_chain_deployment(DEFAULT_CHAIN.to_string(), outdated);
+        assert_eq!(usable(&fx), (false, "Verifier expects a different schema version".to_string()));
+
+        // An unknown version is not held against the attestation
+        let unknown = ChainDeployment { schema_version: 0, ..chain_deployment(true, Tier::None) };
+        fx.contract.set_chain_deployment(DEFAULT_CHAIN.to_string(), unknown);
+        assert_eq!(usable(&fx), (true, "OK".to_string()));
+        fx.contract.set_chain_deployment(DEFAULT_CHAIN.to_string(), chain_deployment(true, Tier::None));
+        assert_eq!(usable(&fx), (true, "OK".to_string()));
+    }
+
+    #[test]
+    fn test_emergency_admin_can_pause() {
+        let mut fx = Fixture::new().build();
+        let emergency = fx.env.get_account(3);
+
+        fx.contract.set_emergency_admin(Some(emergency));
+        assert_eq!(fx.contract.get_emergency_admin(), Some(emergency));
+
+        fx.env.set_caller(emergency);
+        let events_before = fx.events_count();
+        fx.contract.emergency_pause();
+        assert!(fx.contract.is_paused());
+        fx.assert_single_event(events_before, EmergencyActionTaken { action: "pause".to_string(), by: emergency });
+
+        fx.env.set_caller(fx.user);
+        assert_eq!(
+            fx.contract.try_create_attestation(
+                DEFAULT_CHAIN.to_string(),
+                DEFAULT_TARGET.to_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            ),
+            Err(VeilError::ContractPaused.into())
+        );
+        fx.assert_no_events_since(events_before + 1);
+
+        // Only the main admin can resume
+        fx.env.set_caller(emergency);
+        assert_eq!(fx.contract.try_unpause(), Err(VeilError::NotAdmin.into()));
+        fx.env.set_caller(fx.admin);
+        fx.contract.unpause();
+        assert!(!fx.contract.is_paused());
+    }
+
+    #[test]
+    fn test_emergency_admin_cannot_update_tier_thresholds() {
+        let mut fx = Fixture::new().build();
+        let emergency = fx.env.get_account(3);
+        fx.contract.set_emergency_admin(Some(emergency));
+
+        fx.env.set_caller(emergency);
+        assert_eq!(
+            fx.contract.try_update_tier_thresholds(TierThresholds::default()),
+            Err(VeilError::NotAdmin.into())
+        );
+        assert_eq!(
+            fx.contract.try_set_emergency_admin(None),
+            Err(VeilError::NotAdmin.into())
+        );
+    }
+
+    #[test]
+    fn test_admin_setters_emit_change_events() {
+        let mut fx = Fixture::new().build();
+
+        let events_before = fx.events_count();
+        fx.contract.set_attestation_validity_secs(24 * 60 * 60);
+        fx.assert_single_event(
+            events_before,
+            ValidityPeriodChanged { old_secs: 7 * 24 * 60 * 60, new_secs: 24 * 60 * 60, by: fx.admin },
+        );
+        assert_eq!(fx.contract.get_attestation_validity_secs(), 24 * 60 * 60);
+        assert_eq!(fx.contract.try_set_attestation_validity_secs(60), Err(VeilError::ValidityTooShort.into()));
+
+        let new_thresholds = TierThresholds { bronze: 0, silver: 500, gold: 5_000, platinum: 50_000 };
+        let events_before = fx.events_count();
+        fx.contract.update_tier_thresholds(new_thresholds);
+        fx.assert_single_event(
+            events_before,
+            TierThresholdsChanged { old_thresholds: TierThresholds::default(), new_thresholds, by: fx.admin },
+        );
+
+        let events_before = fx.events_count();
+        fx.contract.rotate_signer(ROTATED_PRIVATE_KEY);
+        fx.assert_single_event(
+            events_before,
+            SignerRotated { old_signer: EXPECTED_SIGNER, new_signer: ROTATED_SIGNER, key_version: 2, by: fx.admin },
+        );
+
+        let events_before = fx.events_count();
+        fx.contract.set_chain_attestation_limit(DEFAULT_CHAIN.to_string(), 2);
+        fx.assert_single_event(
+            events_before,
+            ChainConfigChanged {
+                chain: DEFAULT_CHAIN.to_string(),
+                old_attestation_limit: DEFAULT_CHAIN_ATTESTATION_LIMIT,
+                new_attestation_limit: 2,
+                by: fx.admin,
+            },
+        );
+
+        let events_before = fx.events_count();
+        fx.contract.transfer_admin(fx.other);
+        fx.assert_single_event(events_before, AdminTransferred { old_admin: fx.admin, new_admin: fx.other });
+        assert_eq!(fx.contract.get_admin(), fx.other);
+        assert_eq!(fx.contract.try_set_network_name("x".to_string()), Err(VeilError::NotAdmin.into()));
+    }
+
+    /// An admin setter reported through `ConfigChanged`
+    struct ConfigCase {
+        arrange: fn(&mut Fixture),
+        /// Key, old and new value, read before `act`
+        expected: fn(&Fixture) -> (String, String, String),
+        act: fn(&mut Fixture),
+    }
+
+    fn change(key: &str, old_value: impl ConfigValue, new_value: impl ConfigValue) -> (String, String, String) {
+        (key.to_string(), old_value.render(), new_value.render())
+    }
+
+    const CONFIG_VERIFIER: [u8; 20] = [0x22; 20];
+
+    fn trusted_verifier_key() -> String {
+        format!("trusted_verifier:{}:{}", DEFAULT_CHAIN, CONFIG_VERIFIER.render())
+    }
+
+    #[test]
+    fn test_every_admin_setter_emits_config_changed() {
+        let cases: Vec<ConfigCase> = vec![
+            ConfigCase {
+                arrange: |_| {},
+                expected: |fx| {
+                    let key = format!("referrer_blocked:{}", fx.other.render());
+                    change(&key, false, true)
+                },
+                act: |fx| fx.contract.set_referrer_blocked(fx.other, true),
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |fx| {
+                    let old = fx.contract.get_reputation_weights();
+                    change("reputation_weights", old, ReputationWeights { per_admin_revocation: 5, ..old })
+                },
+                act: |fx| {
+                    let old = fx.contract.get_reputation_weights();
+                    fx.contract.set_reputation_weights(ReputationWeights { per_admin_revocation: 5, ..old })
+                },
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |fx| change("dispute_bond", fx.contract.get_dispute_bond(), U512::from(5u64)),
+                act: |fx| fx.contract.set_dispute_bond(U512::from(5u64)),
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |fx| change("attestation_fee", fx.contract.get_attestation_fee(), U512::from(7u64)),
+                act: |fx| fx.contract.set_attestation_fee(U512::from(7u64)),
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |fx| change("arbiter", fx.contract.get_arbiter(), Some(fx.other)),
+                act: |fx| fx.contract.set_arbiter(Some(fx.other)),
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |fx| change("treasury", fx.contract.get_treasury(), Some(fx.other)),
+                act: |fx| fx.contract.set_treasury(Some(fx.other)),
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |fx| change("stake_source", fx.contract.get_stake_source(), Some(fx.other)),
+                act: |fx| fx.contract.set_stake_source(Some(fx.other)),
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |fx| change("locked_stake_source", fx.contract.get_locked_stake_source(), Some(fx.other)),
+                act: |fx| fx.contract.set_locked_stake_source(Some(fx.other)),
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |fx| change("badge_collection", fx.contract.get_badge_collection(), Some(fx.other)),
+                act: |fx| fx.contract.set_badge_collection(Some(fx.other)),
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |fx| change("mint_badges", fx.contract.get_mint_badges(), true),
+                act: |fx| fx.contract.set_mint_badges(true),
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |fx| change("reward_token", fx.contract.get_reward_token(), Some(fx.other)),
+                act: |fx| fx.contract.set_reward_token(Some(fx.other)),
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |fx| change("rewards_enabled", fx.contract.get_rewards_enabled(), true),
+                act: |fx| fx.contract.set_rewards_enabled(true),
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |fx| change("base_reward", fx.contract.get_base_reward(), U256::from(9u64)),
+                act: |fx| fx.contract.set_base_reward(U256::from(9u64)),
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |fx| {
+                    let new = RewardMultipliers { gold: 10, ..RewardMultipliers::default() };
+                    change("reward_multipliers", fx.contract.get_reward_multipliers(), new)
+                },
+                act: |fx| {
+                    fx.contract.set_reward_multipliers(RewardMultipliers { gold: 10, ..RewardMultipliers::default() })
+                },
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |fx| change("reward_emission_cap", fx.contract.get_reward_emission_cap(), U256::from(100u64)),
+                act: |fx| fx.contract.set_reward_emission_cap(U256::from(100u64)),
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |fx| change("stake_policy", fx.contract.get_stake_policy(), StakePolicy::LockedOnly),
+                act: |fx| fx.contract.set_stake_policy(StakePolicy::LockedOnly),
+            },
+            ConfigCase {
+                arrange: |fx| fx.contract.emergency_pause(),
+                expected: |_| change("paused", true, false),
+                act: |fx| fx.contract.unpause(),
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |fx| {
+                    change("confirmation_signer", fx.contract.get_confirmation_signer(), Some(CONFIG_VERIFIER))
+                },
+                act: |fx| fx.contract.set_confirmation_signer(Some(CONFIG_VERIFIER)),
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |fx| change("max_batch_size", fx.contract.get_max_batch_size(), 3u32),
+                act: |fx| fx.contract.set_max_batch_size(3),
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |fx| {
+                    let new_until = fx.env.block_time() + 60 * 1000;
+                    change("upgrade_lock_until", fx.contract.get_upgrade_lock_until(), new_until)
+                },
+                act: |fx| fx.contract.extend_upgrade_lock(60),
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |fx| change("pow_difficulty", fx.contract.get_pow_difficulty(), 4u8),
+                act: |fx| fx.contract.set_pow_difficulty(4),
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |_| change(&trusted_verifier_key(), false, true),
+                act: |fx| fx.contract.add_trusted_verifier(DEFAULT_CHAIN.to_string(), CONFIG_VERIFIER),
+            },
+            ConfigCase {
+                arrange: |fx| fx.contract.add_trusted_verifier(DEFAULT_CHAIN.to_string(), CONFIG_VERIFIER),
+                expected: |_| change(&trusted_verifier_key(), true, false),
+                act: |fx| fx.contract.remove_trusted_verifier(DEFAULT_CHAIN.to_string(), CONFIG_VERIFIER),
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |fx| change("allowlist_enabled", fx.contract.get_allowlist_enabled(), true),
+                act: |fx| fx.contract.set_allowlist_enabled(true),
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |fx| change("require_checksum", fx.contract.get_require_checksum(), true),
+                act: |fx| fx.contract.set_require_checksum(true),
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |fx| change("compressed_storage", fx.contract.is_compressed_storage(), true),
+                act: |fx| fx.contract.set_compressed_storage(true),
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |fx| change("revocation_delay_secs", fx.contract.get_revocation_delay_secs(), 600u64),
+                act: |fx| fx.contract.set_revocation_delay_secs(600),
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |fx| change("reuse_window_secs", fx.contract.get_reuse_window_secs(), 600u64),
+                act: |fx| fx.contract.set_reuse_window_secs(600),
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |fx| change("stake_cache_secs", fx.contract.get_stake_cache_secs(), 600u64),
+                act: |fx| fx.contract.set_stake_cache_secs(600),
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |fx| change("max_attestation_age_secs", fx.contract.get_max_attestation_age_secs(), 600u64),
+                act: |fx| fx.contract.set_max_attestation_age_secs(600),
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |fx| change(&format!("relayer:{}", fx.other.render()), false, true),
+                act: |fx| fx.contract.add_relayer(fx.other),
+            },
+            ConfigCase {
+                arrange: |fx| fx.contract.add_relayer(fx.other),
+                expected: |fx| change(&format!("relayer:{}", fx.other.render()), true, false),
+                act: |fx| fx.contract.remove_relayer(fx.other),
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |fx| change("renewal_grace_secs", fx.contract.get_renewal_grace_secs(), 600u64),
+                act: |fx| fx.contract.set_renewal_grace_secs(600),
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |fx| {
+                    change("network_name", fx.contract.get_deployment_info().network_name, "casper-test".to_string())
+                },
+                act: |fx| fx.contract.set_network_name("casper-test".to_string()),
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |fx| {
+                    let new = vec![DEFAULT_CHAIN.to_string(), "base".to_string()];
+                    change("supported_chains", fx.contract.get_deployment_info().supported_chains, new)
+                },
+                act: |fx| fx.contract.set_supported_chains(vec![DEFAULT_CHAIN.to_string(), "base".to_string()]),
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |_| change("allow_duplicate_chain_address", true, false),
+                act: |fx| fx.contract.set_allow_duplicate_chain_address(false),
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |_| change("emit_v1_events", true, false),
+                act: |fx| fx.contract.set_emit_v1_events(false),
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |_| change(&format!("trusted_witness:{}", WITNESS.render()), false, true),
+                act: |fx| fx.contract.set_trusted_witness(WITNESS, true),
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |fx| change("max_tags", fx.contract.get_max_tags(), 2u8),
+                act: |fx| fx.contract.set_max_tags(2),
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |fx| change("min_validity_secs", fx.contract.get_min_validity_secs(), 2 * 60 * 60u64),
+                act: |fx| fx.contract.set_min_validity_secs(2 * 60 * 60),
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |fx| change("require_min_stake", fx.contract.is_min_stake_required(), true),
+                act: |fx| fx.contract.set_require_min_stake(true),
+            },
+            ConfigCase {
+                arrange: |_| {},
+                expected: |fx| {
+                    let old = fx.contract.get_min_attestation_stake_motes();
+                    change("min_attestation_stake_motes", old, U512::from(1_000u64))
+                },
+                act: |fx| fx.contract.set_min_attestation_stake_motes(U512::from(1_000u64)),
+            },
+        ];
+
+        for case in cases {
+            let mut fx = Fixture::new().build();
+            (case.arrange)(&mut fx);
+            let (key, old_value, new_value) = (case.expected)(&fx);
+            let events_before = fx.events_count();
+            (case.act)(&mut fx);
+            fx.assert_single_event(events_before, ConfigChanged { key, old_value, new_value, by: fx.admin });
+        }
+    }
+
+    #[test]
+    fn test_emergency_admin_can_rotate_signer() {
+        let mut fx = Fixture::new().build();
+        let emergency = fx.env.get_account(3);
+        fx.contract.set_emergency_admin(Some(emergency));
+
+        fx.env.set_caller(emergency);
+        fx.contract.emergency_rotate_signer(ROTATED_PRIVATE_KEY);
+
+        assert_eq!(fx.contract.get_signer_address(), ROTATED_SIGNER);
+        assert_eq!(
+            fx.event_at::<SignerRotated>(-2),
+            SignerRotated { old_signer: EXPECTED_SIGNER, new_signer: ROTATED_SIGNER, key_version: 2, by: emergency }
+        );
+    }
+
+    #[test]
+    fn test_emergency_functions_reject_other_callers() {
+        let mut fx = Fixture::new().build();
+        fx.contract.set_emergency_admin(Some(fx.env.get_account(3)));
+
+        fx.env.set_caller(fx.user);
+        assert_eq!(fx.contract.try_emergency_pause(), Err(VeilError::NotEmergencyAdmin.into()));
+        assert_eq!(
+            fx.contract.try_emergency_rotate_signer(ROTATED_PRIVATE_KEY),
+            Err(VeilError::NotEmergencyAdmin.into())
+        );
+
+        // Clearing the emergency admin removes its rights
+        fx.env.set_caller(fx.admin);
+        fx.contract.set_emergency_admin(None);
+        fx.env.set_caller(fx.env.get_account(3));
+        assert_eq!(fx.contract.try_emergency_pause(), Err(VeilError::NotEmergencyAdmin.into()));
+    }
+
+    #[test]
+    fn test_admin_audit_log_records_actions_in_order() {
+        let mut fx = Fixture::new().build();
+        let emergency = fx.env.get_account(3);
+        fx.contract.set_emergency_admin(Some(emergency));
+        let thresholds = TierThresholds { bronze: 50, silver: 500, gold: 5_000, platinum: 50_000 };
+
+        fx.contract.rotate_signer(ROTATED_PRIVATE_KEY);
+        fx.contract.update_tier_thresholds(thresholds);
+        fx.env.set_caller(emergency);
+        fx.contract.emergency_pause();
+        fx.env.set_caller(fx.admin);
+        fx.contract.unpause();
+        fx.contract.set_attestation_fee(U512::from(1_000u64));
+
+        let now = fx.env.block_time();
+        let expected: Vec<(String, Address, u64)> = [
+            ("set_emergency_admin", fx.admin),
+            ("rotate_signer", fx.admin),
+            ("update_tier_thresholds", fx.admin),
+            ("emergency_pause", emergency),
+            ("unpause", fx.admin),
+            ("set_attestation_fee", fx.admin),
+        ]
+        .into_iter()
+        .map(|(action, actor)| (action.to_string(), actor, now))
+        .collect();
+        assert_eq!(fx.contract.get_admin_audit_log(), expected);
+        assert_eq!(fx.contract.get_last_admin_action(), expected.last().cloned());
+
+        // Reverted calls are not recorded and only the admin reads the full
+        // log; anyone can see the last action
+        fx.env.set_caller(fx.user);
+        assert_eq!(fx.contract.try_unpause(), Err(VeilError::NotAdmin.into()));
+        assert_eq!(fx.contract.try_get_admin_audit_log(), Err(VeilError::NotAdmin.into()));
+        assert_eq!(fx.contract.get_last_admin_action(), expected.last().cloned());
+
+        fx.env.set_caller(fx.admin);
+        assert_eq!(fx.contract.get_admin_audit_log(), expected);
+
+        // A full log drops its oldest entries
+        for _ in 0..ADMIN_AUDIT_LOG_LEN {
+            fx.contract.set_network_name("casper-test".to_string());
+        }
+        let log = fx.contract.get_admin_audit_log();
+        assert_eq!(log.len(), ADMIN_AUDIT_LOG_LEN);
+        assert!(log.iter().all(|(action, _, _)| action == "set_network_name"));
+    }
+
+    #[test]
+    fn test_upgrade_lock_blocks_security_changes_until_expiry() {
+        let mut fx = Fixture::new().with_initial_lock(30 * 24 * 60 * 60).build();
+        let thresholds = TierThresholds { bronze: 50, silver: 500, gold: 5_000, platinum: 50_000 };
+
+        assert!(fx.contract.is_upgrade_locked());
+        assert_eq!(fx.contract.get_upgrade_lock_until(), fx.env.block_time() + 30 * 24 * 60 * 60 * 1000);
+        assert_eq!(fx.contract.try_rotate_signer(ROTATED_PRIVATE_KEY), Err(VeilError::UpgradeLocked.into()));
+        assert_eq!(fx.contract.try_update_tier_thresholds(thresholds), Err(VeilError::UpgradeLocked.into()));
+        assert_eq!(
+            fx.contract.try_set_hash_algorithm(encoding::HASH_ALGORITHM_SHA3_256),
+            Err(VeilError::UpgradeLocked.into())
+        );
+
+        fx.env.advance_block_time(30 * 24 * 60 * 60 * 1000);
+        assert!(!fx.contract.is_upgrade_locked());
+        fx.contract.rotate_signer(ROTATED_PRIVATE_KEY);
+        fx.contract.update_tier_thresholds(thresholds);
+        assert_eq!(fx.contract.get_signer_address(), ROTATED_SIGNER);
+        assert_eq!(fx.contract.get_tier_thresholds(), thresholds);
+    }
+
+    #[test]
+    fn test_emergency_rotation_ignores_upgrade_lock() {
+        let mut fx = Fixture::new().with_initial_lock(30 * 24 * 60 * 60).build();
+        let emergency = fx.env.get_account(3);
+        fx.contract.set_emergency_admin(Some(emergency));
+        assert!(fx.contract.is_upgrade_locked());
+
+        fx.env.set_caller(emergency);
+        fx.contract.emergency_rotate_signer(ROTATED_PRIVATE_KEY);
+        assert_eq!(fx.contract.get_signer_address(), ROTATED_SIGNER);
+        assert!(fx.contract.is_upgrade_locked());
+
+        // The routine rotation stays locked
+        fx.env.set_caller(fx.admin);
+        assert_eq!(fx.contract.try_rotate_signer(TEST_PRIVATE_KEY), Err(VeilError::UpgradeLocked.into()));
+    }
+
+    #[test]
+    fn test_upgrade_lock_can_only_be_extended() {
+        let mut fx = Fixture::new().build();
+        assert!(!fx.contract.is_upgrade_locked());
+
+        fx.contract.extend_upgrade_lock(60);
+        assert!(fx.contract.is_upgrade_locked());
+        assert_eq!(fx.contract.try_rotate_signer(ROTATED_PRIVATE_KEY), Err(VeilError::UpgradeLocked.into()));
+
+        // Extending again adds to the current end rather than to now
+        let until = fx.contract.get_upgrade_lock_until();
+        fx.contract.extend_upgrade_lock(0);
+        assert_eq!(fx.contract.get_upgrade_lock_until(), until);
+        fx.contract.extend_upgrade_lock(60);
+        assert_eq!(fx.contract.get_upgrade_lock_until(), until + 60 * 1000);
+
+        fx.env.set_caller(fx.user);
+        assert_eq!(fx.contract.try_extend_upgrade_lock(60), Err(VeilError::NotAdmin.into()));
+
+        fx.env.advance_block_time(120 * 1000);
+        fx.env.set_caller(fx.admin);
+        fx.contract.rotate_signer(ROTATED_PRIVATE_KEY);
+    }
+
+    #[test]
+    fn test_update_tier_thresholds_requires_increasing_values() {
+        let mut fx = Fixture::new().build();
+
+        let thresholds = TierThresholds { bronze: 50, silver: 500, gold: 5_000, platinum: 50_000 };
+        fx.contract.update_tier_thresholds(thresholds);
+        assert_eq!(fx.contract.get_tier_thresholds(), thresholds);
+
+        let unordered = TierThresholds { bronze: 500, silver: 500, gold: 5_000, platinum: 50_000 };
+        assert_eq!(
+            fx.contract.try_update_tier_thresholds(unordered),
+            Err(VeilError::InvalidTierThresholds.into())
+        );
+    }
+
+    #[test]
+    fn test_motes_to_cspr_string() {
+        assert_eq!(motes_to_cspr_string(U512::zero()), "0.000000");
+        assert_eq!(motes_to_cspr_string(U512::one()), "0.000000001");
+        assert_eq!(motes_to_cspr_string(U512::from(100_000_000_000_000u64)), "100000.000000");
+        assert_eq!(motes_to_cspr_string(U512::from(1_500_000_000u64)), "1.500000");
+        assert_eq!(motes_to_cspr_string(U512::from(1_234_567_891u64)), "1.234567891");
+        assert_eq!(motes_to_cspr_string(U512::from(1_234_567_890u64)), "1.23456789");
+    }
+
+    #[test]
+    fn test_cspr_to_motes() {
+        assert_eq!(cspr_to_motes(0, 0), U512::zero());
+        assert_eq!(cspr_to_motes(0, 1), U512::one());
+        assert_eq!(cspr_to_motes(100, 0), U512::from(100_000_000_000u64));
+        assert_eq!(cspr_to_motes(1, 500_000_000), U512::from(1_500_000_000u64));
+    }
+
+    #[test]
+    fn test_cspr_conversion_max_u64_does_not_overflow() {
+        let motes = cspr_to_motes(u64::MAX, 999_999_999);
+
+        assert_eq!(
+            motes_to_cspr_string(motes),
+            alloc::format!("{}.999999999", u64::MAX)
+        );
+        assert_eq!(motes / U512::from(1_000_000_000u64), U512::from(u64::MAX));
+    }
+
+    #[test]
+    fn test_expires_at_is_created_at_plus_validity_ms() {
+        let mut fx = Fixture::new().build();
+        fx.env.advance_block_time(12_345);
+
+        let (default_id, _) = fx.create_default_attestation(fx.user);
+        let (custom_id, signature) = fx.contract.create_attestation(
+            DEFAULT_CHAIN.to_string(),
+            DEFAULT_TARGET.to_string(),
+            Some(2 * 60 * 60),
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        fx.assert_signature_valid(&custom_id, &signature);
+
+        for (id, validity_secs) in [(default_id, 7 * 24 * 60 * 60), (custom_id, 2 * 60 * 60)] {
+            let attestation = fx.contract.get_attestation(id).unwrap();
+            assert_eq!(attestation.created_at, fx.env.block_time());
+            assert_eq!(attestation.attestation_validity_secs, validity_secs);
+            assert_eq!(attestation.expires_at, attestation.created_at + validity_secs * 1000);
+        }
+    }
+
+    #[test]
+    fn test_expired_attestation_is_invalid_and_not_exported() {
+        let mut fx = Fixture::new().build();
+
+        let (attestation_id, _) = fx.create_default_attestation(fx.user);
+        let attestation = fx.contract.get_attestation(attestation_id).unwrap();
+
+        // Still valid on the last millisecond before expiry
+        fx.env.advance_block_time(attestation.expires_at - attestation.created_at - 1);
+        assert!(fx.contract.is_attestation_valid(attestation_id, None));
+        assert!(fx.contract.get_attestation_for_evm(attestation_id).is_some());
+
+        fx.env.advance_block_time(1);
+        assert_eq!(fx.env.block_time(), attestation.expires_at);
+        assert!(!fx.contract.is_attestation_valid(attestation_id, None));
+        assert_eq!(
+            fx.contract.is_attestation_usable_for_chain(attestation_id, DEFAULT_CHAIN.to_string()),
+            (false, "Attestation expired".to_string())
+        );
+        assert_eq!(fx.contract.get_attestation_for_evm(attestation_id), None);
+        // The record itself is kept
+        assert_eq!(fx.contract.get_attestation(attestation_id), Some(attestation.clone()));
+
+        // A fresh attestation after expiry is usable again
+        let (fresh_id, _) = fx.create_default_attestation(fx.user);
+        let fresh = fx.contract.get_attestation(fresh_id).unwrap();
+        assert_ne!(fresh_id, attestation_id);
+        assert!(fresh.expires_at > attestation.expires_at);
+        assert!(fx.contract.is_attestation_valid(fresh_id, None));
+    }
+
+    #[test]
+    fn test_custom_validity_shortens_default() {
+        let mut fx = Fixture::new().with_validity(60 * 60).build();
+
+        let (attestation_id, _) = fx.create_default_attestation(fx.user);
+
+        let attestation = fx.contract.get_attestation(attestation_id).unwrap();
+        assert_eq!(attestation.attestation_validity_secs, 60 * 60);
+        assert_eq!(attestation.expires_at, attestation.created_at + 60 * 60 * 1000);
+    }
+
+    #[test]
+    fn test_custom_validity_capped_at_global() {
+        let mut fx = Fixture::new().with_validity(30 * 24 * 60 * 60).build();
+
+        let (attestation_id, _) = fx.create_default_attestation(fx.user);
+
+        let attestation = fx.contract.get_attestation(attestation_id).unwrap();
+        assert_eq!(attestation.attestation_validity_secs, 7 * 24 * 60 * 60);
+        assert_eq!(attestation.expires_at, attestation.created_at + 7 * 24 * 60 * 60 * 1000);
+    }
+
+    #[test]
+    fn test_custom_validity_below_minimum_rejected() {
+        let mut fx = Fixture::new().build();
+        assert_eq!(fx.contract.get_min_validity_secs(), DEFAULT_MIN_VALIDITY_SECS);
+        let events_before = fx.events_count();
+
+        fx.env.set_caller(fx.user);
+        assert_eq!(
+            fx.contract.try_create_attestation(
+                DEFAULT_CHAIN.to_string(),
+                DEFAULT_TARGET.to_string(),
+                Some(DEFAULT_MIN_VALIDITY_SECS - 1),
+                None,
+                None,
+                None,
+                None,
+                None,
+            ),
+            Err(VeilError::ValidityTooShort.into())
+        );
+        fx.assert_no_events_since(events_before);
+    }
+
+    #[test]
+    fn test_raw_signature_matches_recomputed() {
+        let mut fx = Fixture::new().build();
+
+        let (attestation_id, signature) = fx.create_default_attestation(fx.user);
+
+        let expected = Bytes::from(encoding::sign_message(&TEST_PRIVATE_KEY, &attestation_id).to_vec());
+        assert_eq!(fx.contract.get_attestation_raw_signature(attestation_id), Some(expected.clone()));
+        assert_eq!(signature, expected);
+        assert_eq!(fx.contract.get_attestation_for_evm(attestation_id).unwrap().1, expected);
+        assert_eq!(fx.contract.get_attestation_raw_signature([0u8; 32]), None);
+    }
+
+    #[test]
+    fn test_raw_signature_stale_after_rotate_signer() {
+        let mut fx = Fixture::new().build();
+
+        let (attestation_id, signature) = fx.create_default_attestation(fx.user);
+
+        fx.env.set_caller(fx.admin);
+        fx.contract.rotate_signer(ROTATED_PRIVATE_KEY);
+
+        let current = Bytes::from(encoding::sign_message(&ROTATED_PRIVATE_KEY, &attestation_id).to_vec());
+        assert_eq!(fx.contract.get_attestation_raw_signature(attestation_id), Some(signature));
+        assert_ne!(fx.contract.get_attestation_raw_signature(attestation_id), Some(current.clone()));
+        // The export no longer uses the cached signature
+        assert_eq!(fx.contract.get_attestation_for_evm(attestation_id).unwrap().1, current);
+    }
+
+    #[test]
+    fn test_attestation_unusable_until_refreshed_after_rotate_signer() {
+        let mut fx = Fixture::new().build();
+        let (attestation_id, _) = fx.create_default_attestation(fx.user);
+        let usable =
+            |fx: &Fixture| fx.contract.is_attestation_usable_for_chain(attestation_id, DEFAULT_CHAIN.to_string());
+        assert_eq!(usable(&fx), (true, "OK".to_string()));
+
+        fx.env.set_caller(fx.admin);
+        fx.contract.rotate_signer(ROTATED_PRIVATE_KEY);
+        assert_eq!(usable(&fx), (false, "Not signed under the current signing key".to_string()));
+
+        fx.contract.batch_refresh_signatures(vec![attestation_id]);
+        assert_eq!(usable(&fx), (true, "OK".to_string()));
+    }
+
+    #[test]
+    fn test_deployment_info() {
+        let env = odra_test::env();
+        env.advance_block_time(5_000);
+        let deployed_at = env.block_time();
+        let mut contract = VeilAttestation::deploy(
+            &env,
+            VeilAttestationInitArgs {
+                admin: env.get_account(0),
+                signer_private_key: TEST_PRIVATE_KEY,
+                initial_lock_secs: None,
+                config: None,
+            },
+        );
+
+        contract.set_network_name("casper-testnet".to_string());
+        contract.set_supported_chains(vec!["base-sepolia".to_string(), "anvil-local".to_string()]);
+
+        let info = contract.get_deployment_info();
+        assert_eq!(info.contract_address, contract.address().to_string());
+        assert_eq!(info.signer_address, alloc::format!("0x{}", hex::encode(contract.get_signer_address())));
+        assert_eq!(info.network_name, "casper-testnet");
+        assert_eq!(info.deployed_at, deployed_at);
+        assert!(info.deployed_at > 0);
+        assert_eq!(info.contract_version, CONTRACT_VERSION);
+        assert!(!info.contract_version.is_empty());
+        assert_eq!(info.schema_version, SCHEMA_VERSION);
+        assert_eq!(info.supported_chains, vec!["base-sepolia".to_string(), "anvil-local".to_string()]);
+
+        env.set_caller(env.get_account(1));
+        assert_eq!(contract.try_set_network_name("mainnet".to_string()), Err(VeilError::NotAdmin.into()));
+        assert_eq!(contract.try_set_supported_chains(Vec::new()), Err(VeilError::NotAdmin.into()));
+    }
+
+    #[test]
+    fn test_init_config_applies_at_deployment() {
+        let env = odra_test::env();
+        let (admin, user) = (env.get_account(0), env.get_account(1));
+        let thresholds = TierThresholds { bronze: 10, silver: 20, gold: 30, platinum: 40 };
+        let config = InitConfig {
+            validity_secs: Some(DEFAULT_MIN_VALIDITY_SECS + 60),
+            tier_thresholds: Some(thresholds),
+            attestation_fee: Some(U512::from(5_000u64)),
+            require_checksum: Some(true),
+            allowlist_enabled: Some(true),
+            time_unit: Some(TIME_UNIT_MILLIS),
+        };
+        let init_args = |config: InitConfig| VeilAttestationInitArgs {
+            admin,
+            signer_private_key: TEST_PRIVATE_KEY,
+            initial_lock_secs: None,
+            config: Some(config),
+        };
+        let mut contract = VeilAttestation::deploy(&env, init_args(config));
+        assert_eq!(contract.get_attestation_validity_secs(), DEFAULT_MIN_VALIDITY_SECS + 60);
+        assert_eq!(contract.get_tier_thresholds(), thresholds);
+        assert_eq!(contract.get_attestation_fee(), U512::from(5_000u64));
+        assert!(contract.get_require_checksum());
+        assert!(contract.get_allowlist_enabled());
+        assert_eq!(contract.get_time_unit(), TIME_UNIT_MILLIS);
+
+        // Creation needs an allowlisted chain, a checksummed target and exactly the fee
+        let checksummed = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+        let create = |contract: &VeilAttestationHostRef, target: &str, fee: u64| {
+            contract.with_tokens(U512::from(fee)).try_create_attestation(
+                DEFAULT_CHAIN.to_string(),
+                target.to_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+        };
+        env.set_caller(user);
+        assert_eq!(create(&contract, checksummed, 5_000).err(), Some(VeilError::ChainNotAllowed.into()));
+        env.set_caller(admin);
+        contract.allow_chain(DEFAULT_CHAIN.to_string());
+        env.set_caller(user);
+        assert_eq!(
+            create(&contract, &checksummed.to_ascii_lowercase(), 5_000).err(),
+            Some(VeilError::InvalidAddressChecksum.into())
+        );
+        assert_eq!(create(&contract, checksummed, 4_999).err(), Some(VeilError::IncorrectAttestationFee.into()));
+        let treasury_before = env.balance_of(&admin);
+        let (attestation_id, _) = create(&contract, checksummed, 5_000).unwrap();
+        assert_eq!(env.balance_of(&admin), treasury_before + U512::from(5_000u64));
+        assert_eq!(env.balance_of(&contract.address()), U512::zero());
+        assert_eq!(contract.get_attestation(attestation_id).unwrap().target_address, checksummed);
+
+        // Invalid settings fail the deployment rather than being clamped
+        let too_short = InitConfig { validity_secs: Some(DEFAULT_MIN_VALIDITY_SECS - 1), ..Default::default() };
+        assert_eq!(
+            VeilAttestation::try_deploy(&env, init_args(too_short)).err(),
+            Some(VeilError::ValidityTooShort.into())
+        );
+        let unordered = InitConfig {
+            tier_thresholds: Some(TierThresholds { silver: 10, ..thresholds }),
+            ..Default::default()
+        };
+        assert_eq!(
+            VeilAttestation::try_deploy(&env, init_args(unordered)).err(),
+            Some(VeilError::InvalidTierThresholds.into())
+        );
+        let bad_unit = InitConfig { time_unit: Some(2), ..Default::default() };
+        assert_eq!(
+            VeilAttestation::try_deploy(&env, init_args(bad_unit)).err(),
+            Some(VeilError::InvalidTimeUnit.into())
+        );
+
+        // An empty config keeps every default
+        let defaults = VeilAttestation::deploy(&env, init_args(InitConfig::default()));
+        assert_eq!(defaults.get_attestation_validity_secs(), 7 * 24 * 60 * 60);
+        assert_eq!(defaults.get_tier_thresholds(), TierThresholds::default());
+        assert_eq!(defaults.get_attestation_fee(), U512::zero());
+        assert!(!defaults.get_require_checksum() && !defaults.get_allowlist_enabled());
+        assert_eq!(defaults.get_time_unit(), TIME_UNIT_SECONDS);
+    }
+
+    // Witnesses co-sign with Anvil account[1], the same key as the rotation tests
+    const WITNESS_PRIVATE_KEY: [u8; 32] = ROTATED_PRIVATE_KEY;
+    const WITNESS: [u8; 20] = ROTATED_SIGNER;
+
+    fn witness_signature(key: &[u8; 32], attestation_id: &[u8; 32]) -> Bytes {
+        Bytes::from(encoding::sign_message(key, attestation_id).to_vec())
+    }
+
+    #[test]
+    fn test_add_witness_accepts_trusted_signature() {
+        let mut fx = Fixture::new().build();
+        fx.contract.set_trusted_witness(WITNESS, true);
+        assert!(fx.contract.is_trusted_witness(WITNESS));
+
+        let (witnessed_id, _) = fx.create_default_attestation(fx.user);
+        let (plain_id, _) = fx.create_default_attestation(fx.user);
+
+        // Anyone may submit the witness signature
+        fx.env.set_caller(fx.other);
+        let signature = witness_signature(&WITNESS_PRIVATE_KEY, &witnessed_id);
+        let events_before = fx.events_count();
+        fx.contract.add_witness(witnessed_id, signature.clone());
+        fx.assert_single_event(events_before, WitnessAdded { attestation_id: witnessed_id, witness: WITNESS });
+
+        let witnessed = fx.contract.get_attestation(witnessed_id).unwrap();
+        assert_eq!(witnessed.witness_address, Some(WITNESS));
+        assert_eq!(witnessed.witness_signature, Some(signature.clone()));
+        assert_eq!(fx.contract.get_attestation(plain_id).unwrap().witness_address, None);
+
+        // Witnessed and unwitnessed attestations are told apart only when asked
+        assert!(fx.contract.is_attestation_valid(witnessed_id, Some(true)));
+        assert!(fx.contract.is_attestation_valid(witnessed_id, None));
+        assert!(!fx.contract.is_attestation_valid(plain_id, Some(true)));
+        assert!(fx.contract.is_attestation_valid(plain_id, Some(false)));
+        assert!(!fx.contract.is_attestation_valid([0u8; 32], None));
+
+        // Witnessing does not change the signed payload
+        let (encoded, _) = fx.contract.get_attestation_for_evm(witnessed_id).unwrap();
+        assert_eq!(keccak256(&encoded), witnessed_id);
+
+        assert_eq!(fx.contract.try_add_witness(witnessed_id, signature), Err(VeilError::AlreadyWitnessed.into()));
+        fx.assert_no_events_since(events_before + 1);
+
+        // A witness that is no longer trusted no longer counts
+        fx.env.set_caller(fx.admin);
+        fx.contract.set_trusted_witness(WITNESS, false);
+        assert!(!fx.contract.is_attestation_valid(witnessed_id, Some(true)));
+    }
+
+    #[test]
+    fn test_add_witness_rejects_untrusted_or_invalid_signature() {
+        let mut fx = Fixture::new().build();
+        fx.contract.set_trusted_witness(WITNESS, true);
+
+        let (attestation_id, _) = fx.create_default_attestation(fx.user);
+        let events_before = fx.events_count();
+
+        // Signed by the attestation signer, who is not a registered witness
+        assert_eq!(
+            fx.contract.try_add_witness(attestation_id, witness_signature(&TEST_PRIVATE_KEY, &attestation_id)),
+            Err(VeilError::UntrustedWitness.into())
+        );
+        // Trusted witness, but over a different message
+        assert_eq!(
+            fx.contract.try_add_witness(attestation_id, witness_signature(&WITNESS_PRIVATE_KEY, &[0x42; 32])),
+            Err(VeilError::UntrustedWitness.into())
+        );
+        assert_eq!(
+            fx.contract.try_add_witness(attestation_id, Bytes::from(vec![0u8; 64])),
+            Err(VeilError::InvalidWitnessSignature.into())
+        );
+        assert_eq!(fx.contract.get_attestation(attestation_id).unwrap().witness_address, None);
+        fx.assert_no_events_since(events_before);
+
+        assert_eq!(fx.contract.try_set_trusted_witness(WITNESS, false), Err(VeilError::NotAdmin.into()));
+    }
+
+    const CLAIM_PROVIDER_KEY: [u8; 32] = [0x2a; 32];
+
+    fn provider_claim(key: &[u8; 32], attestation_id: &[u8; 32], claim_type: u8, claim_data: &[u8]) -> Bytes {
+        let digest = encoding::claim_digest(attestation_id, claim_type, claim_data);
+        Bytes::from(encoding::sign_message(key, &digest).to_vec())
+    }
+
+    #[test]
+    fn test_attach_claim_folds_provider_claims_into_payload() {
+        let mut fx = Fixture::new().build();
+        let public_key = encoding::derive_public_key(&CLAIM_PROVIDER_KEY).unwrap();
+        let provider = fx.contract.register_claim_provider(public_key);
+        assert_eq!(provider, encoding::public_key_to_address(&public_key));
+        assert!(fx.env.emitted_event(&fx.contract, ClaimProviderUpdated { provider, registered: true }));
+        assert_eq!(fx.contract.get_claim_providers(), vec![provider]);
+        assert_eq!(fx.contract.get_claim_provider_key(provider), Some(public_key));
+
+        let (id, _) = fx.create_default_attestation(fx.user);
+        assert_eq!(fx.contract.get_evm_attestation_id(id), Some(id));
+
+        // Anyone may submit a provider's claim
+        fx.env.set_caller(fx.other);
+        let kyc = Bytes::from(b"kyc-level-2".to_vec());
+        let signature = provider_claim(&CLAIM_PROVIDER_KEY, &id, 2, &kyc);
+        let claim_id = fx.contract.attach_claim(id, 2, kyc.clone(), signature.clone());
+        let claims_hash = encoding::fold_claims_hash(&[0; 32], &encoding::claim_digest(&id, 2, &kyc));
+        assert!(fx.env.emitted_event(
+            &fx.contract,
+            ClaimAttached { claim_id, attestation_id: id, provider, claim_type: 2, claims_hash }
+        ));
+        let claim = fx.contract.get_claim(claim_id).unwrap();
+        assert_eq!((claim.attestation_id, claim.provider, claim.claim_type), (id, provider, 2));
+        assert_eq!((claim.claim_data, claim.provider_signature), (kyc, signature));
+
+        // The EVM payload now carries the claims hash, under a new ID, re-signed
+        let (encoded, evm_signature) = fx.contract.get_attestation_for_evm(id).unwrap();
+        assert_eq!(abi_decode_payload(&encoded).unwrap().claims_hash, claims_hash);
+        let evm_id = keccak256(&encoded);
+        assert_ne!(evm_id, id);
+        assert_eq!(fx.contract.get_evm_attestation_id(id), Some(evm_id));
+        assert_eq!(recover_eth_address(&evm_id, &evm_signature), fx.contract.get_signer_address());
+        assert_eq!(fx.contract.get_attestation_raw_signature(id), Some(evm_signature));
+        fx.env.set_caller(fx.admin);
+        let (_, debug_info) = fx.contract.debug_recover_signer(id).unwrap();
+        assert_eq!(debug_info, "Recovered address matches the current signer");
+
+        // A second claim folds on top of the first
+        let sanctions = Bytes::from(vec![0u8]);
+        let signature = provider_claim(&CLAIM_PROVIDER_KEY, &id, 7, &sanctions);
+        fx.contract.attach_claim(id, 7, sanctions.clone(), signature);
+        let (encoded, _) = fx.contract.get_attestation_for_evm(id).unwrap();
+        assert_eq!(
+            abi_decode_payload(&encoded).unwrap().claims_hash,
+            encoding::fold_claims_hash(&claims_hash, &encoding::claim_digest(&id, 7, &sanctions))
+        );
+        let claims = fx.contract.get_attestation_claims(id);
+        assert_eq!(claims.iter().map(|claim| claim.claim_type).collect::<Vec<_>>(), vec![2, 7]);
+        assert_eq!(fx.contract.get_provider_claims(provider).len(), 2);
+        assert!(fx.contract.get_provider_claims(EXPECTED_SIGNER).is_empty());
+
+        // Removing the provider keeps the claims it signed
+        fx.contract.remove_claim_provider(provider);
+        assert!(fx.env.emitted_event(&fx.contract, ClaimProviderUpdated { provider, registered: false }));
+        assert!(fx.contract.get_claim_providers().is_empty());
+        assert_eq!(fx.contract.get_claim_provider_key(provider), None);
+        assert_eq!(fx.contract.get_attestation_claims(id).len(), 2);
+    }
+
+    #[test]
+    fn test_attach_claim_rejects_invalid_signatures() {
+        let mut fx = Fixture::new().build();
+        let provider = fx.contract.register_claim_provider(encoding::derive_public_key(&CLAIM_PROVIDER_KEY).unwrap());
+        let (id, _) = fx.create_default_attestation(fx.user);
+        let kyc = Bytes::from(b"kyc-level-2".to_vec());
+        let attach = |fx: &mut Fixture, signature: Bytes| fx.contract.try_attach_claim(id, 2, kyc.clone(), signature);
+        let events_before = fx.events_count();
+
+        // Signed by a key that is not a registered provider
+        let unregistered = provider_claim(&TEST_PRIVATE_KEY, &id, 2, b"kyc-level-2");
+        assert_eq!(attach(&mut fx, unregistered), Err(VeilError::UnknownClaimProvider.into()));
+        // Registered provider, but over another claim type or attestation
+        let other_type = provider_claim(&CLAIM_PROVIDER_KEY, &id, 3, b"kyc-level-2");
+        assert_eq!(attach(&mut fx, other_type), Err(VeilError::UnknownClaimProvider.into()));
+        let other_attestation = provider_claim(&CLAIM_PROVIDER_KEY, &[0x42; 32], 2, b"kyc-level-2");
+        assert_eq!(attach(&mut fx, other_attestation), Err(VeilError::UnknownClaimProvider.into()));
+        assert_eq!(attach(&mut fx, Bytes::from(vec![0u8; 64])), Err(VeilError::InvalidClaimSignature.into()));
+
+        let long = vec![0u8; MAX_CLAIM_DATA_LEN + 1];
+        let signature = provider_claim(&CLAIM_PROVIDER_KEY, &id, 2, &long);
+        assert_eq!(
+            fx.contract.try_attach_claim(id, 2, Bytes::from(long), signature),
+            Err(VeilError::ClaimDataTooLong.into())
+        );
+        let signature = provider_claim(&CLAIM_PROVIDER_KEY, &[0x42; 32], 2, b"kyc-level-2");
+        assert_eq!(
+            fx.contract.try_attach_claim([0x42; 32], 2, kyc.clone(), signature),
+            Err(VeilError::AttestationNotFound.into())
+        );
+        assert!(fx.contract.get_attestation_claims(id).is_empty());
+        assert_eq!(fx.contract.get_evm_attestation_id(id), Some(id));
+        fx.assert_no_events_since(events_before);
+
+        // Each signed claim attaches once
+        let signature = provider_claim(&CLAIM_PROVIDER_KEY, &id, 2, b"kyc-level-2");
+        assert_eq!(attach(&mut fx, signature.clone()), Ok(0));
+        assert_eq!(attach(&mut fx, signature), Err(VeilError::ClaimAlreadyAttached.into()));
+
+        // Removed providers can no longer attach claims
+        fx.env.set_caller(fx.admin);
+        fx.contract.remove_claim_provider(provider);
+        let signature = provider_claim(&CLAIM_PROVIDER_KEY, &id, 3, b"kyc-level-2");
+        assert_eq!(
+            fx.contract.try_attach_claim(id, 3, kyc, signature),
+            Err(VeilError::UnknownClaimProvider.into())
+        );
+    }
+
+    const CONFIRMATION_PRIVATE_KEY: [u8; 32] = [0x3c; 32];
+
+    fn evm_confirmation(key: &[u8; 32], attestation_id: &[u8; 32], evm_tx_hash: &[u8; 32]) -> Bytes {
+        let digest = encoding::confirmation_digest(attestation_id, evm_tx_hash);
+        Bytes::from(encoding::sign_message(key, &digest).to_vec())
+    }
+
+    fn set_confirmation_key(fx: &mut Fixture) -> [u8; 20] {
+        let signer = encoding::public_key_to_address(&encoding::derive_public_key(&CONFIRMATION_PRIVATE_KEY).unwrap());
+        fx.env.set_caller(fx.admin);
+        fx.contract.set_confirmation_signer(Some(signer));
+        signer
+    }
+
+    #[test]
+    fn test_confirm_verified_records_evm_consumption() {
+        let mut fx = Fixture::new().with_mock_stake(1, cspr_to_motes(10_000, 0)).build();
+        let signer = set_confirmation_key(&mut fx);
+        assert_eq!(fx.contract.get_confirmation_signer(), Some(signer));
+        let (id, _) = fx.create_default_attestation(fx.user);
+        assert!(fx.contract.check_attestation(fx.user, Tier::Gold, Some(false)));
+        assert!(!fx.contract.check_attestation(fx.user, Tier::Gold, Some(true)));
+
+        // Anyone may relay the confirmation
+        let evm_tx_hash = [0x7e; 32];
+        fx.env.set_caller(fx.other);
+        fx.contract.confirm_verified(id, evm_tx_hash, evm_confirmation(&CONFIRMATION_PRIVATE_KEY, &id, &evm_tx_hash));
+        let verified_on_evm_at = fx.env.block_time();
+        assert!(fx.env.emitted_event(
+            &fx.contract,
+            AttestationConfirmed { attestation_id: id, evm_tx_hash, verified_on_evm_at }
+        ));
+        let attestation = fx.contract.get_attestation(id).unwrap();
+        assert_eq!(attestation.verified_on_evm_at, Some(verified_on_evm_at));
+        assert_eq!(attestation.evm_tx_hash, Some(evm_tx_hash));
+        assert!(fx.contract.check_attestation(fx.user, Tier::Gold, Some(true)));
+
+        // Compressed records keep the confirmation alongside
+        fx.env.set_caller(fx.admin);
+        fx.contract.set_compressed_storage(true);
+        let (compressed_id, _) = fx.create_attestation(fx.other, DEFAULT_CHAIN, DEFAULT_TARGET);
+        let signature = evm_confirmation(&CONFIRMATION_PRIVATE_KEY, &compressed_id, &evm_tx_hash);
+        fx.contract.confirm_verified(compressed_id, evm_tx_hash, signature);
+        let attestation = fx.contract.get_attestation(compressed_id).unwrap();
+        assert_eq!(attestation.evm_tx_hash, Some(evm_tx_hash));
+    }
+
+    #[test]
+    fn test_confirm_verified_rejects_forged_and_repeated_confirmations() {
+        let mut fx = Fixture::new().build();
+        let (id, _) = fx.create_default_attestation(fx.user);
+        let evm_tx_hash = [0x7e; 32];
+        let confirm = |fx: &mut Fixture, signature: Bytes| fx.contract.try_confirm_verified(id, evm_tx_hash, signature);
+        let signature = evm_confirmation(&CONFIRMATION_PRIVATE_KEY, &id, &evm_tx_hash);
+        let events_before = fx.events_count();
+
+        // Nothing is accepted until a confirmation signer is set
+        assert_eq!(confirm(&mut fx, signature.clone()), Err(VeilError::InvalidConfirmationSignature.into()));
+        set_confirmation_key(&mut fx);
+        let forged = evm_confirmation(&TEST_PRIVATE_KEY, &id, &evm_tx_hash);
+        assert_eq!(confirm(&mut fx, forged), Err(VeilError::InvalidConfirmationSignature.into()));
+        let other_tx = evm_confirmation(&CONFIRMATION_PRIVATE_KEY, &id, &[0x7f; 32]);
+        assert_eq!(confirm(&mut fx, other_tx), Err(VeilError::InvalidConfirmationSignature.into()));
+        assert_eq!(confirm(&mut fx, Bytes::from(vec![0u8; 64])), Err(VeilError::InvalidConfirmationSignature.into()));
+        assert_eq!(
+            fx.contract.try_confirm_verified([0x42; 32], evm_tx_hash, signature.clone()),
+            Err(VeilError::AttestationNotFound.into())
+        );
+        assert_eq!(fx.contract.get_attestation(id).unwrap().verified_on_evm_at, None);
+        fx.assert_no_events_since(events_before);
+
+        // An attestation is confirmed once
+        assert_eq!(confirm(&mut fx, signature.clone()), Ok(()));
+        assert_eq!(confirm(&mut fx, signature), Err(VeilError::AlreadyConfirmed.into()));
+    }
+
+    #[test]
+    fn test_mark_stale_attestations_filters_old_attestations() {
+        let mut fx = Fixture::new().with_mock_stake(1, cspr_to_motes(10_000, 0)).build();
+        let (old_id, _) = fx.create_default_attestation(fx.user);
+
+        // Disabled by default: nothing is ever stale
+        fx.env.advance_block_time(2 * 24 * 60 * 60 * 1000);
+        assert_eq!(fx.contract.get_max_attestation_age_secs(), 0);
+        assert_eq!(fx.contract.mark_stale_attestations(fx.user), 0);
+
+        fx.env.set_caller(fx.admin);
+        fx.contract.set_max_attestation_age_secs(24 * 60 * 60);
+        assert_eq!(fx.contract.get_max_attestation_age_secs(), 24 * 60 * 60);
+        let (recent_id, _) = fx.create_default_attestation(fx.user);
+
+        // Anyone may mark; only the attestation past the maximum age goes stale
+        fx.env.set_caller(fx.other);
+        assert_eq!(fx.contract.mark_stale_attestations(fx.user), 1);
+        assert!(fx.env.emitted_event(&fx.contract, AttestationMarkedStale { id: old_id }));
+        assert!(fx.contract.get_attestation(old_id).unwrap().stale);
+        assert!(!fx.contract.get_attestation(recent_id).unwrap().stale);
+        assert_eq!(fx.contract.mark_stale_attestations(fx.user), 0);
+
+        // Stale attestations are invalid before they expire
+        let old = fx.contract.get_attestation(old_id).unwrap();
+        assert!(fx.env.block_time() < old.expires_at);
+        assert!(!fx.contract.is_attestation_valid(old_id, None));
+        assert!(fx.contract.is_attestation_valid(recent_id, None));
+        assert_eq!(fx.contract.get_active_attestation(fx.user).map(|attestation| attestation.id), Some(recent_id));
+
+        // Compressed records keep the flag alongside
+        fx.env.set_caller(fx.admin);
+        fx.contract.set_compressed_storage(true);
+        let (compressed_id, _) = fx.create_attestation(fx.other, DEFAULT_CHAIN, DEFAULT_TARGET);
+        fx.env.advance_block_time(24 * 60 * 60 * 1000 + 1);
+        assert_eq!(fx.contract.mark_stale_attestations(fx.other), 1);
+        assert!(fx.contract.get_attestation(compressed_id).unwrap().stale);
+        assert!(!fx.contract.is_attestation_valid(compressed_id, None));
+    }
+
+    #[test]
+    fn test_freeze_attestation_suspends_until_unfrozen() {
+        let mut fx = Fixture::new().with_mock_stake(1, cspr_to_motes(10_000, 0)).build();
+        let (id, signature) = fx.create_default_attestation(fx.user);
+
+        fx.contract.freeze_attestation(id);
+        assert!(fx.env.emitted_event(&fx.contract, AttestationFrozen { id }));
+        assert!(fx.contract.get_attestation(id).unwrap().frozen);
+        assert_eq!(fx.contract.get_attestation_for_evm(id), None);
+        assert!(!fx.contract.is_attestation_valid(id, None));
+        assert!(!fx.contract.check_attestation(fx.user, Tier::Gold, None));
+        assert_eq!(fx.contract.try_freeze_attestation(id), Err(VeilError::AlreadyFrozen.into()));
+
+        // Unfreezing restores the same ID and signature
+        fx.contract.unfreeze_attestation(id);
+        assert!(fx.env.emitted_event(&fx.contract, AttestationUnfrozen { id }));
+        let (encoded, exported_signature) = fx.contract.get_attestation_for_evm(id).unwrap();
+        assert_eq!((keccak256(&encoded), exported_signature), (id, signature));
+        assert!(fx.contract.is_attestation_valid(id, None));
+        assert!(fx.contract.check_attestation(fx.user, Tier::Gold, None));
+        assert_eq!(fx.contract.try_unfreeze_attestation(id), Err(VeilError::NotFrozen.into()));
+
+        // Compressed records keep the flag alongside, through both transitions
+        fx.env.set_caller(fx.admin);
+        fx.contract.set_compressed_storage(true);
+        let (compressed_id, _) = fx.create_default_attestation(fx.other);
+        fx.contract.freeze_attestation(compressed_id);
+        assert_eq!(fx.contract.get_attestation_for_evm(compressed_id), None);
+        fx.contract.unfreeze_attestation(compressed_id);
+        assert!(!fx.contract.get_attestation(compressed_id).unwrap().frozen);
+    }
+
+    #[test]
+    fn test_freeze_attestation_rejections() {
+        let mut fx = Fixture::new().build();
+        let (id, _) = fx.create_default_attestation(fx.user);
+        let events_before = fx.events_count();
+
+        fx.env.set_caller(fx.other);
+        assert_eq!(fx.contract.try_freeze_attestation(id), Err(VeilError::NotAttestationOwner.into()));
+        assert_eq!(fx.contract.try_unfreeze_attestation(id), Err(VeilError::NotAttestationOwner.into()));
+        assert_eq!(fx.contract.try_freeze_attestation([0x42; 32]), Err(VeilError::AttestationNotFound.into()));
+        fx.assert_no_events_since(events_before);
+
+        fx.env.set_caller(fx.user);
+        fx.contract.freeze_attestation(id);
+        fx.env.set_caller(fx.other);
+        assert_eq!(fx.contract.try_unfreeze_attestation(id), Err(VeilError::NotAttestationOwner.into()));
+        fx.env.set_caller(fx.user);
+        fx.contract.unfreeze_attestation(id);
+
+        // Revoked and expired attestations cannot be frozen
+        fx.contract.revoke_attestation(id);
+        assert_eq!(fx.contract.try_freeze_attestation(id), Err(VeilError::AlreadyRevoked.into()));
+        let (expiring_id, _) = fx.create_default_attestation(fx.user);
+        fx.env.advance_block_time(7 * 24 * 60 * 60 * 1000);
+        assert_eq!(fx.contract.try_freeze_attestation(expiring_id), Err(VeilError::AttestationExpired.into()));
+    }
+
+    #[test]
+    fn test_eip1271_signature_recovers_signer() {
+        let mut fx = Fixture::new().build();
+
+        let (attestation_id, _) = fx.create_default_attestation(fx.user);
+
+        let (signature, magic) = fx.contract.get_eip1271_signature(attestation_id).unwrap();
+        assert_eq!(magic, [0x16, 0x26, 0xba, 0x7e]);
+        assert_eq!(magic, EIP1271_MAGIC_VALUE);
+        // Signed over the bare payload hash, not the EIP-191 prefixed one
+        assert_eq!(encoding::recover_signer(&attestation_id, &signature), Some(EXPECTED_SIGNER));
+        assert_eq!(fx.contract.get_eip1271_signature([0u8; 32]), None);
+    }
+
+    #[test]
+    fn test_signature_components_and_compact_form() {
+        let mut fx = Fixture::new().build();
+
+        // RFC 6979 nonces make the parity per attestation fixed; several cover both
+        for _ in 0..4 {
+            let (id, signature) = fx.create_default_attestation(fx.user);
+            let (r, s, v) = fx.contract.get_attestation_signature_components(id).unwrap();
+            assert_eq!(r[..], signature[..32]);
+            assert_eq!(s[..], signature[32..64]);
+            assert_eq!(v, signature[64]);
+            assert!(v == 27 || v == 28);
+
+            let compact = fx.contract.get_compact_signature(id).unwrap();
+            assert_eq!(compact[..32], r);
+            assert_eq!(compact[32] >> 7, v - 27, "yParity rides in the top bit of s");
+            assert_eq!(compact[32] & 0x7f, s[0]);
+            assert_eq!(compact[33..], s[1..]);
+            assert_eq!(encoding::expand_compact_signature(&compact).to_vec(), signature.to_vec());
+        }
+
+        assert_eq!(fx.contract.get_attestation_signature_components([0u8; 32]), None);
+        assert_eq!(fx.contract.get_compact_signature([0u8; 32]), None);
+    }
+
+    #[test]
+    fn test_abi_decode_payload_round_trips() {
+        let payload = sample_payload();
+        let encoded = abi_encode_payload(&payload);
+
+        assert_eq!(abi_decode_payload(&encoded), Some(payload));
+        assert_eq!(abi_decode_payload(&encoded[..encoded.len() - 32]), None);
+        assert_eq!(abi_decode_payload(&[]), None);
+    }
+
+    #[test]
+    fn test_payload_completeness() {
+        assert_eq!(verify_payload_completeness(&sample_payload()), Ok(()));
+
+        let incomplete: [fn(&mut AttestationPayload); 6] = [
+            |payload| payload.casper_address_hash = [0u8; 32],
+            |payload| payload.target_chain.clear(),
+            |payload| payload.target_address.truncate(41),
+            |payload| payload.expires_at = payload.created_at,
+            |payload| payload.expires_at = payload.created_at - 1,
+            |payload| payload.attestation_type.clear(),
+        ];
+        for mutate in incomplete {
+            let mut payload = sample_payload();
+            mutate(&mut payload);
+            assert_eq!(verify_payload_completeness(&payload), Err(VeilError::IncompletePayload));
+        }
+    }
+
+    #[test]
+    fn test_validate_encoded_payload() {
+        let fx = Fixture::new().build();
+        let mut payload = sample_payload();
+        assert!(fx.contract.validate_encoded_payload(Bytes::from(abi_encode_payload(&payload))));
+
+        payload.casper_address_hash = [0u8; 32];
+        assert!(!fx.contract.validate_encoded_payload(Bytes::from(abi_encode_payload(&payload))));
+        assert!(!fx.contract.validate_encoded_payload(Bytes::from(vec![0xff; 64])));
+    }
+
+    #[test]
+    fn test_attestation_types_registered_and_filtered() {
+        let mut fx = Fixture::new().build();
+        assert_eq!(fx.contract.get_registered_types(), vec![DEFAULT_ATTESTATION_TYPE.to_string()]);
+        let (identity_id, _) = fx.create_default_attestation(fx.user);
+        assert_eq!(fx.contract.get_attestation(identity_id).unwrap().attestation_type, DEFAULT_ATTESTATION_TYPE);
+
+        // Unregistered until the admin registers it
+        let (chain, target) = (DEFAULT_CHAIN.to_string(), DEFAULT_TARGET.to_string());
+        let governance = || Some("governance".to_string());
+        assert_eq!(
+            fx.contract.try_create_attestation(
+                chain.clone(),
+                target.clone(),
+                None,
+                None,
+                None,
+                None,
+                governance(),
+                None,
+            ),
+            Err(VeilError::UnregisteredAttestationType.into())
+        );
+        fx.env.set_caller(fx.admin);
+        fx.contract.register_attestation_type("governance".to_string());
+        assert!(fx.env.emitted_event(
+            &fx.contract,
+            AttestationTypeRegistered { attestation_type: "governance".to_string(), registered_by: fx.admin }
+        ));
+        assert_eq!(
+            fx.contract.try_register_attestation_type("governance".to_string()),
+            Err(VeilError::AttestationTypeAlreadyRegistered.into())
+        );
+        for invalid in [String::new(), "two words".to_string(), "t".repeat(MAX_ATTESTATION_TYPE_LEN + 1)] {
+            assert_eq!(
+                fx.contract.try_register_attestation_type(invalid),
+                Err(VeilError::InvalidAttestationType.into())
+            );
+        }
+        assert_eq!(fx.contract.get_registered_types(), vec![DEFAULT_ATTESTATION_TYPE.to_string(), "governance".into()]);
+
+        fx.env.set_caller(fx.user);
+        let (governance_id, signature) =
+            fx.contract.create_attestation(chain, target, None, None, None, None, governance(), None);
+        fx.assert_signature_valid(&governance_id, &signature);
+        let (encoded, _) = fx.contract.get_attestation_for_evm(governance_id).unwrap();
+        assert_eq!(abi_decode_payload(&encoded).unwrap().attestation_type, "governance");
+
+        let (second_identity_id, _) = fx.create_default_attestation(fx.user);
+        let ids_of = |attestation_type: &str| -> Vec<[u8; 32]> {
+            let attestations = fx.contract.get_attestations_by_type(fx.user, attestation_type.to_string());
+            attestations.iter().map(|attestation| attestation.id).collect()
+        };
+        assert_eq!(ids_of(DEFAULT_ATTESTATION_TYPE), vec![identity_id, second_identity_id]);
+        assert_eq!(ids_of("governance"), vec![governance_id]);
+        assert!(ids_of("unknown").is_empty());
+    }
+
+    #[test]
+    fn test_attestation_templates_constrain_creation() {
+        let mut fx = Fixture::new()
+            .with_mock_stake(1, cspr_to_motes(1_000, 0))
+            .with_mock_stake(2, cspr_to_motes(100, 0))
+            .build();
+        let day = 24 * 60 * 60;
+        let tag = "defi-season-1".to_string();
+        let template_id = fx.contract.create_template(DEFAULT_CHAIN.to_string(), Tier::Silver as u8, day, tag.clone());
+        assert_eq!(template_id, 1);
+        let template = Template {
+            id: template_id,
+            target_chain: DEFAULT_CHAIN.to_string(),
+            min_tier: Tier::Silver as u8,
+            validity_secs: day,
+            tag: "defi-season-1".to_string(),
+            active: true,
+        };
+        assert!(fx.env.emitted_event(
+            &fx.contract,
+            TemplateUpdated {
+                template_id,
+                target_chain: DEFAULT_CHAIN.to_string(),
+                min_tier: Tier::Silver as u8,
+                validity_secs: day,
+                tag: "defi-season-1".to_string(),
+                active: true,
+            }
+        ));
+        assert_eq!(fx.contract.get_templates(), vec![template.clone()]);
+
+        // Silver meets the minimum; the template fills in chain, validity and tag
+        fx.env.set_caller(fx.user);
+        let (id, signature) =
+            fx.contract.create_attestation_from_template(template_id, DEFAULT_TARGET.to_string(), None);
+        fx.assert_signature_valid(&id, &signature);
+        let attestation = fx.contract.get_attestation(id).unwrap();
+        assert_eq!(attestation.target_chain, DEFAULT_CHAIN);
+        assert_eq!(attestation.attestation_validity_secs, day);
+        assert_eq!(attestation.tags, vec!["defi-season-1".to_string()]);
+        assert_eq!(attestation.template_id, template_id);
+        let (encoded, _) = fx.contract.get_attestation_for_evm(id).unwrap();
+        assert_eq!(abi_decode_payload(&encoded).unwrap().template_id, template_id);
+        assert_eq!(fx.contract.find_attestations_by_tag(fx.user, "defi-season-1".to_string()), vec![id]);
+        assert_eq!(fx.contract.get_template_attestations(template_id), vec![id]);
+
+        // Bronze does not
+        fx.env.set_caller(fx.other);
+        assert_eq!(
+            fx.contract.try_create_attestation_from_template(template_id, DEFAULT_TARGET.to_string(), None),
+            Err(VeilError::TierBelowTemplateMinimum.into())
+        );
+
+        // Deactivated templates take no new attestations, but can be updated
+        fx.env.set_caller(fx.admin);
+        fx.contract.set_template_active(template_id, false);
+        fx.contract.update_template(template_id, DEFAULT_CHAIN.to_string(), Tier::Bronze as u8, day, "s2".to_string());
+        let updated = Template { min_tier: Tier::Bronze as u8, tag: "s2".to_string(), active: false, ..template };
+        assert_eq!(fx.contract.get_template(template_id), Some(updated));
+        fx.env.set_caller(fx.other);
+        assert_eq!(
+            fx.contract.try_create_attestation_from_template(template_id, DEFAULT_TARGET.to_string(), None),
+            Err(VeilError::TemplateInactive.into())
+        );
+        assert_eq!(
+            fx.contract.try_create_attestation_from_template(template_id + 1, DEFAULT_TARGET.to_string(), None),
+            Err(VeilError::TemplateNotFound.into())
+        );
+        assert_eq!(fx.contract.get_template_attestations(template_id), vec![id]);
+
+        // Templates must name a tier and a valid tag, and meet the minimum validity
+        fx.env.set_caller(fx.admin);
+        let chain = DEFAULT_CHAIN.to_string();
+        assert_eq!(
+            fx.contract.try_create_template(chain.clone(), Tier::Validator as u8 + 1, day, "s3".to_string()),
+            Err(VeilError::InvalidTemplate.into())
+        );
+        assert_eq!(
+            fx.contract.try_create_template(chain.clone(), 0, DEFAULT_MIN_VALIDITY_SECS - 1, "s3".to_string()),
+            Err(VeilError::ValidityTooShort.into())
+        );
+        assert_eq!(
+            fx.contract.try_create_template(chain, 0, day, "two words".to_string()),
+            Err(VeilError::InvalidTag.into())
+        );
+    }
+
+    #[test]
+    fn test_nonce_commitment_signed_in_place_of_nonce() {
+        let mut fx = Fixture::new().build();
+        fx.create_default_attestation(fx.user);
+        let secret = [0x5e; 32];
+        let (chain, target) = (DEFAULT_CHAIN.to_string(), DEFAULT_TARGET.to_string());
+
+        fx.env.set_caller(fx.user);
+        let (id, signature) =
+            fx.contract.create_attestation_with_nonce_commitment(chain.clone(), target.clone(), secret, None);
+        fx.assert_signature_valid(&id, &signature);
+
+        // Casper keeps the nonce; the payload only carries its commitment
+        let attestation = fx.contract.get_attestation(id).unwrap();
+        assert_eq!(attestation.nonce, 1);
+        assert_eq!(attestation.nonce_commitment, Some(encoding::nonce_commitment(1, &secret)));
+        assert!(!attestation.is_renewable, "the secret is not kept for renewals");
+        let (encoded, _) = fx.contract.get_attestation_for_evm(id).unwrap();
+        assert_eq!(keccak256(&encoded), id);
+        let payload = abi_decode_payload(&encoded).unwrap();
+        assert_eq!(payload.nonce, 0);
+        assert_eq!(payload.nonce_commitment, encoding::nonce_commitment(1, &secret));
+
+        // Compressed records keep the commitment alongside
+        fx.env.set_caller(fx.admin);
+        fx.contract.set_compressed_storage(true);
+        fx.env.set_caller(fx.user);
+        let (compressed_id, _) =
+            fx.contract.create_attestation_with_nonce_commitment(chain, target, [0x5f; 32], None);
+        let commitment = fx.contract.get_attestation(compressed_id).unwrap().nonce_commitment;
+        assert_eq!(commitment, Some(encoding::nonce_commitment(2, &[0x5f; 32])));
+        let (encoded, _) = fx.contract.get_attestation_for_evm(compressed_id).unwrap();
+        assert_eq!(keccak256(&encoded), compressed_id);
+
+        // A retried nonce is committed to, never revealed
+        let (taken, _, _) = find_unused_attestation_id(&mut sample_payload(), Some(&secret), |_| false).unwrap();
+        let mut payload = sample_payload();
+        let (_, encoded, retries) = find_unused_attestation_id(&mut payload, Some(&secret), |id| *id == taken).unwrap();
+        assert_eq!((retries, payload.nonce), (1, 1));
+        let signed = abi_decode_payload(&encoded).unwrap();
+        assert_eq!(signed.nonce, 0);
+        assert_eq!(signed.nonce_commitment, encoding::nonce_commitment(1, &secret));
+    }
+
+    #[test]
+    fn test_hash_algorithm_switch_to_sha3() {
+        let mut fx = Fixture::new().build();
+        assert_eq!(fx.contract.get_hash_algorithm(), encoding::HASH_ALGORITHM_KECCAK256);
+        let (keccak_id, _) = fx.create_default_attestation(fx.user);
+
+        fx.env.set_caller(fx.admin);
+        fx.contract.set_hash_algorithm(encoding::HASH_ALGORITHM_SHA3_256);
+        assert!(fx.env.emitted_event(
+            &fx.contract,
+            HashAlgorithmChanged {
+                old_algorithm: encoding::HASH_ALGORITHM_KECCAK256,
+                new_algorithm: encoding::HASH_ALGORITHM_SHA3_256,
+            }
+        ));
+        assert_eq!(fx.contract.get_hash_algorithm(), encoding::HASH_ALGORITHM_SHA3_256);
+
+        // New IDs are SHA3-256 of an encoding that names it, and sign the same way
+        let (sha3_id, signature) = fx.create_default_attestation(fx.other);
+        fx.assert_signature_valid(&sha3_id, &signature);
+        let (encoded, _) = fx.contract.get_attestation_for_evm(sha3_id).unwrap();
+        assert_eq!(encoding::sha3_256(&encoded), sha3_id);
+        assert_ne!(keccak256(&encoded), sha3_id);
+        assert_eq!(abi_decode_payload(&encoded).unwrap().hash_algorithm_version, encoding::HASH_ALGORITHM_SHA3_256);
+        assert_eq!(fx.contract.get_attestation(sha3_id).unwrap().hash_algorithm_version, 1);
+
+        // Attestations created before the switch keep their Keccak256 IDs
+        let (encoded, signature) = fx.contract.get_attestation_for_evm(keccak_id).unwrap();
+        assert_eq!(keccak256(&encoded), keccak_id);
+        fx.assert_signature_valid(&keccak_id, &signature);
+
+        // Collision retries work the same under either hash
+        let mut payload = AttestationPayload {
+            hash_algorithm_version: encoding::HASH_ALGORITHM_SHA3_256,
+            ..sample_payload()
+        };
+        let (taken, _, _) = find_unused_attestation_id(&mut payload.clone(), None, |_| false).unwrap();
+        assert_eq!(taken, encoding::sha3_256(&abi_encode_payload(&payload)));
+        let (id, _, retries) = find_unused_attestation_id(&mut payload, None, |id| *id == taken).unwrap();
+        assert_eq!((retries, payload.nonce), (1, 1));
+        assert_ne!(id, taken);
+
+        assert_eq!(fx.contract.try_set_hash_algorithm(2), Err(VeilError::UnsupportedHashAlgorithm.into()));
+    }
+
+    #[test]
+    fn test_payload_timestamps_follow_time_unit() {
+        let mut fx = Fixture::new().build();
+        assert_eq!(fx.contract.get_time_unit(), TIME_UNIT_SECONDS);
+        fx.env.advance_block_time(1_500);
+        let (seconds_id, _) = fx.create_default_attestation(fx.user);
+        let seconds_at = fx.env.block_time();
+        let (seconds_encoded, _) = fx.contract.get_attestation_for_evm(seconds_id).unwrap();
+        let payload = abi_decode_payload(&seconds_encoded).unwrap();
+        assert_eq!((payload.created_at, payload.expires_at), (seconds_at / 1000, seconds_at / 1000 + WEEK_SECS));
+        // Storage keeps block time either way
+        let stored = fx.contract.get_attestation(seconds_id).unwrap();
+        assert_eq!((stored.created_at, stored.expires_at), (seconds_at, seconds_at + WEEK_SECS * 1000));
+
+        fx.env.set_caller(fx.admin);
+        let events_before = fx.events_count();
+        fx.contract.set_time_unit(TIME_UNIT_MILLIS);
+        fx.assert_single_event(
+            events_before,
+            TimeUnitChanged { old_unit: TIME_UNIT_SECONDS, new_unit: TIME_UNIT_MILLIS, by: fx.admin },
+        );
+        assert_eq!(fx.contract.try_set_time_unit(2), Err(VeilError::InvalidTimeUnit.into()));
+
+        fx.env.advance_block_time(1_500);
+        let (millis_id, _) = fx.create_attestation(fx.user, "anvil-local", DEFAULT_TARGET);
+        let millis_at = fx.env.block_time();
+        let (encoded, _) = fx.contract.get_attestation_for_evm(millis_id).unwrap();
+        let payload = abi_decode_payload(&encoded).unwrap();
+        assert_eq!((payload.created_at, payload.expires_at), (millis_at, millis_at + WEEK_SECS * 1000));
+
+        // Earlier attestations keep the unit they were signed with
+        assert_eq!(fx.contract.get_attestation_for_evm(seconds_id).unwrap().0, seconds_encoded);
+        assert_eq!(keccak256(&seconds_encoded), seconds_id);
+        fx.env.set_caller(fx.user);
+        assert_eq!(fx.contract.try_set_time_unit(TIME_UNIT_SECONDS), Err(VeilError::NotAdmin.into()));
+    }
+
+    #[test]
+    fn test_era_id_recorded_and_signed() {
+        let mut fx = Fixture::new().build();
+        let (id, _) = fx.create_default_attestation(fx.user);
+
+        // Contracts cannot read the era yet, so it is recorded as unknown
+        let era = fx.contract.get_attestation_era_id(id);
+        assert_eq!(era, Some(0));
+        assert_eq!(fx.contract.get_attestation(id).unwrap().casper_era_id, 0);
+        let (encoded, _) = fx.contract.get_attestation_for_evm(id).unwrap();
+        assert_eq!(Some(abi_decode_payload(&encoded).unwrap().casper_era_id), era);
+
+        assert_eq!(fx.contract.get_attestation_era_id([0xab; 32]), None);
+    }
+
+    #[test]
+    fn test_trusted_verifier_embedded_in_payload() {
+        const VERIFIER: [u8; 20] = [0xaa; 20];
+        const REPLACEMENT: [u8; 20] = [0xbb; 20];
+        let mut fx = Fixture::new().build();
+
+        // No verifier registered: the payload names none
+        let (open_id, _) = fx.create_default_attestation(fx.user);
+        let (encoded, _) = fx.contract.get_attestation_for_evm(open_id).unwrap();
+        assert_eq!(abi_decode_payload(&encoded).unwrap().trusted_verifier, [0; 20]);
+
+        fx.env.set_caller(fx.admin);
+        fx.contract.add_trusted_verifier(DEFAULT_CHAIN.to_string(), VERIFIER);
+        assert!(fx.contract.get_trusted_verifier(DEFAULT_CHAIN.to_string(), VERIFIER));
+        assert!(!fx.contract.get_trusted_verifier("ethereum".to_string(), VERIFIER));
+        assert_eq!(fx.contract.get_chain_verifier(DEFAULT_CHAIN.to_string()), VERIFIER);
+        fx.env.set_caller(fx.user);
+        assert_eq!(
+            fx.contract.try_add_trusted_verifier(DEFAULT_CHAIN.to_string(), REPLACEMENT),
+            Err(VeilError::NotAdmin.into())
+        );
+
+        // bytes20 is left-aligned in head slot 9, right after the nonce
+        let (verified_id, _) = fx.create_default_attestation(fx.user);
+        let (encoded, _) = fx.contract.get_attestation_for_evm(verified_id).unwrap();
+        assert_eq!(&encoded[9 * 32..9 * 32 + 20], &VERIFIER);
+        assert_eq!(&encoded[9 * 32 + 20..10 * 32], &[0u8; 12]);
+        assert_eq!(abi_decode_payload(&encoded).unwrap().trusted_verifier, VERIFIER);
+        assert_eq!(fx.contract.get_attestation(verified_id).unwrap().trusted_verifier, VERIFIER);
+        // Other chains are unaffected
+        let (other_id, _) = fx.create_attestation(fx.user, "ethereum", DEFAULT_TARGET);
+        assert_eq!(fx.contract.get_attestation(other_id).unwrap().trusted_verifier, [0; 20]);
+
+        // Removing the embedded verifier stops naming it; existing payloads keep it
+        fx.env.set_caller(fx.admin);
+        fx.contract.add_trusted_verifier(DEFAULT_CHAIN.to_string(), REPLACEMENT);
+        fx.contract.remove_trusted_verifier(DEFAULT_CHAIN.to_string(), VERIFIER);
+        assert!(!fx.contract.get_trusted_verifier(DEFAULT_CHAIN.to_string(), VERIFIER));
+        assert_eq!(fx.contract.get_chain_verifier(DEFAULT_CHAIN.to_string()), REPLACEMENT);
+        fx.contract.remove_trusted_verifier(DEFAULT_CHAIN.to_string(), REPLACEMENT);
+        assert_eq!(fx.contract.get_chain_verifier(DEFAULT_CHAIN.to_string()), [0; 20]);
+        assert_eq!(fx.contract.get_attestation(verified_id).unwrap().trusted_verifier, VERIFIER);
+    }
+
+    #[test]
+    fn test_registered_chain_names_its_verifier() {
+        const VERIFIER: [u8; 20] = [0xaa; 20];
+        let mut fx = Fixture::new().with_registered_chain(DEFAULT_CHAIN, VERIFIER).build();
+
+        assert_eq!(fx.contract.get_deployment_info().supported_chains, vec![DEFAULT_CHAIN.to_string()]);
+        let (id, _) = fx.create_default_attestation(fx.user);
+        assert_eq!(fx.contract.get_attestation(id).unwrap().trusted_verifier, VERIFIER);
+    }
+
+    #[test]
+    fn test_proof_of_work_required_when_difficulty_set() {
+        let mut fx = Fixture::new().build();
+        let caller_bytes = fx.user.to_bytes().unwrap();
+        assert_eq!(fx.contract.get_pow_difficulty(), 0);
+
+        // Disabled: any nonce, or none at all, is accepted
+        fx.env.set_caller(fx.user);
+        fx.contract.create_attestation(
+            DEFAULT_CHAIN.to_string(),
+            DEFAULT_TARGET.to_string(),
+            None,
+            Some(12_345),
+            None,
+            None,
+            None,
+            None,
+        );
+        fx.create_default_attestation(fx.user);
+
+        fx.env.set_caller(fx.admin);
+        assert_eq!(
+            fx.contract.try_set_pow_difficulty(MAX_POW_DIFFICULTY + 1),
+            Err(VeilError::PowDifficultyTooHigh.into())
+        );
+        fx.contract.set_pow_difficulty(8);
+        assert_eq!(fx.contract.get_pow_difficulty(), 8);
+
+        let work = |nonce| leading_zero_bits(&proof_of_work_hash(&caller_bytes, DEFAULT_CHAIN, nonce));
+        let solved = find_proof_of_work(&caller_bytes, DEFAULT_CHAIN, 8);
+        let unsolved = (0..).find(|nonce| work(*nonce) < 8).unwrap();
+        assert!(work(solved) >= 8);
+
+        fx.env.set_caller(fx.user);
+        let events_before = fx.events_count();
+        for pow_nonce in [None, Some(unsolved)] {
+            assert_eq!(
+                fx.contract.try_create_attestation(
+                    DEFAULT_CHAIN.to_string(),
+                    DEFAULT_TARGET.to_string(),
+                    None,
+                    pow_nonce,
+                    None,
+                    None,
+                    None,
+                    None,
+                ),
+                Err(VeilError::InsufficientProofOfWork.into())
+            );
+        }
+        fx.assert_no_events_since(events_before);
+        let (attestation_id, signature) = fx.contract.create_attestation(
+            DEFAULT_CHAIN.to_string(),
+            DEFAULT_TARGET.to_string(),
+            None,
+            Some(solved),
+            None,
+            None,
+            None,
+            None,
+        );
+        fx.assert_signature_valid(&attestation_id, &signature);
+        fx.assert_created_event(events_before, &attestation_id);
+    }
+
+    #[test]
+    fn test_mock_auction_stake_sets_tier_at_each_boundary() {
+        let cases = [
+            (cspr_to_motes(99, 999_999_999), Tier::None),
+            (cspr_to_motes(100, 0), Tier::Bronze),
+            (cspr_to_motes(1_000, 0), Tier::Silver),
+            (cspr_to_motes(10_000, 0), Tier::Gold),
+            (cspr_to_motes(100_000, 0), Tier::Platinum),
+        ];
+        let mut builder = Fixture::new();
+        for (i, (stake, _)) in cases.iter().enumerate() {
+            builder = builder.with_mock_stake(i + 1, *stake);
+        }
+        let mut fx = builder.build();
+        let auction = fx.auction.as_ref().unwrap().address();
+        assert_eq!(fx.contract.get_stake_source(), Some(auction));
+
+        for (i, (stake, tier)) in cases.into_iter().enumerate() {
+            let account = fx.env.get_account(i + 1);
+            let (attestation_id, _) = fx.create_default_attestation(account);
+
+            let attestation = fx.contract.get_attestation(attestation_id).unwrap();
+            assert_eq!((attestation.stake_amount, attestation.tier), (stake, tier), "Account {}", i + 1);
+            let (encoded, _) = fx.contract.get_attestation_for_evm(attestation_id).unwrap();
+            let payload = abi_decode_payload(&encoded).unwrap();
+            assert_eq!((payload.stake_amount, payload.tier), (stake, tier as u8), "Account {}", i + 1);
+        }
+
+        // Stake is read at creation, so a later change shows in the next attestation
+        fx.auction.as_mut().unwrap().set_delegated_stake(fx.user, cspr_to_motes(100_000, 0));
+        let (attestation_id, _) = fx.create_default_attestation(fx.user);
+        assert_eq!(fx.contract.get_attestation(attestation_id).unwrap().tier, Tier::Platinum);
+    }
+
+    #[test]
+    fn test_stake_zero_without_stake_source() {
+        let mut fx = Fixture::new().with_mock_stake(1, cspr_to_motes(1_000, 0)).build();
+
+        fx.env.set_caller(fx.user);
+        assert_eq!(fx.contract.try_set_stake_source(None), Err(VeilError::NotAdmin.into()));
+
+        fx.env.set_caller(fx.admin);
+        fx.contract.set_stake_source(None);
+        assert_eq!(fx.contract.get_stake_source(), None);
+        let (attestation_id, _) = fx.create_default_attestation(fx.user);
+        let attestation = fx.contract.get_attestation(attestation_id).unwrap();
+        assert_eq!((attestation.stake_amount, attestation.tier), (U512::zero(), Tier::None));
+    }
+
+    #[test]
+    fn test_badge_minted_with_attestation_and_burned_on_revocation() {
+        let mut fx = Fixture::new().with_mock_stake(1, cspr_to_motes(1_000, 0)).with_badges().build();
+        let badge = fx.badge.take().unwrap();
+        assert!(fx.contract.get_mint_badges());
+        assert_eq!(fx.contract.get_badge_collection(), Some(badge.address()));
+
+        let (attestation_id, _) = fx.create_default_attestation(fx.user);
+        let attestation = fx.contract.get_attestation(attestation_id).unwrap();
+        assert_eq!(fx.contract.get_attestation_badge(attestation_id), Some(badge.address()));
+        assert_eq!(badge.owner_of(attestation_id), Some(fx.user));
+        assert_eq!(badge.balance_of(fx.user), 1);
+        assert_eq!(
+            badge.token_metadata(attestation_id),
+            Some(alloc::format!(
+                "{{\"attestation_id\":\"0x{}\",\"tier\":2,\"target_chain\":\"{}\",\"expires_at\":{}}}",
+                hex::encode(attestation_id),
+                DEFAULT_CHAIN,
+                attestation.expires_at
+            ))
+        );
+
+        fx.contract.revoke_attestation(attestation_id);
+        assert_eq!(badge.owner_of(attestation_id), None);
+        assert_eq!(badge.balance_of(fx.user), 0);
+
+        // With the toggle off no badge is minted
+        fx.env.set_caller(fx.admin);
+        fx.contract.set_mint_badges(false);
+        let (attestation_id, _) = fx.create_default_attestation(fx.user);
+        assert_eq!(fx.contract.get_attestation_badge(attestation_id), None);
+        assert_eq!(badge.owner_of(attestation_id), None);
+        fx.contract.revoke_attestation(attestation_id);
+    }
+
+    #[test]
+    fn test_reward_per_tier() {
+        let mut fx = Fixture::new().build();
+        assert_eq!(fx.contract.get_reward_token(), None);
+        assert!(!fx.contract.get_rewards_enabled());
+        assert_eq!(fx.contract.get_reward_multipliers(), RewardMultipliers::default());
+        assert_eq!(fx.contract.get_reward_emission_cap(), U256::MAX);
+
+        fx.contract.set_base_reward(U256::from(100));
+        let rewards: Vec<_> = [Tier::None, Tier::Bronze, Tier::Silver, Tier::Gold, Tier::Platinum, Tier::Validator]
+            .into_iter()
+            .map(|tier| fx.contract.get_reward_for_tier(tier))
+            .collect();
+        let expected: Vec<_> = [0, 100, 200, 300, 400, 500].into_iter().map(|n| Some(U256::from(n))).collect();
+        assert_eq!(rewards, expected);
+
+        fx.contract.set_reward_multipliers(RewardMultipliers { gold: 10, ..RewardMultipliers::default() });
+        assert_eq!(fx.contract.get_reward_for_tier(Tier::Gold), Some(U256::from(1_000)));
+        fx.contract.set_base_reward(U256::MAX);
+        assert_eq!(fx.contract.get_reward_for_tier(Tier::Gold), None);
+    }
+
+    #[test]
+    fn test_attestation_mints_reward() {
+        let mut fx = Fixture::new()
+            .with_mock_stake(1, cspr_to_motes(1_000, 0))
+            .with_rewards(U256::from(50))
+            .build();
+        let token = fx.reward_token.take().unwrap();
+        assert_eq!(fx.contract.get_reward_token(), Some(token.address()));
+
+        // Silver: 50 * 2
+        let (attestation_id, _) = fx.create_default_attestation(fx.user);
+        let amount = U256::from(100);
+        assert_eq!(token.balance_of(fx.user), amount);
+        assert_eq!(fx.contract.get_rewards_emitted(), amount);
+        assert!(fx.env.emitted_event(
+            &fx.contract,
+            RewardMinted { attestation_id, recipient: fx.user, amount }
+        ));
+
+        // With the kill switch off nothing is minted
+        fx.env.set_caller(fx.admin);
+        fx.contract.set_rewards_enabled(false);
+        fx.create_attestation(fx.user, "ethereum", DEFAULT_TARGET);
+        assert_eq!(token.balance_of(fx.user), amount);
+        assert_eq!(fx.contract.get_rewards_emitted(), amount);
+    }
+
+    #[test]
+    fn test_reward_past_emission_cap_skipped() {
+        let mut fx = Fixture::new()
+            .with_mock_stake(1, cspr_to_motes(1_000, 0))
+            .with_rewards(U256::from(50))
+            .build();
+        let token = fx.reward_token.take().unwrap();
+        fx.contract.set_reward_emission_cap(U256::from(150));
+        fx.create_default_attestation(fx.user);
+
+        // A second 100 would pass the cap; the attestation is still created
+        let (attestation_id, _) = fx.create_attestation(fx.user, "ethereum", DEFAULT_TARGET);
+        assert!(fx.contract.get_attestation(attestation_id).is_some());
+        assert_eq!(token.balance_of(fx.user), U256::from(100));
+        assert_eq!(fx.contract.get_rewards_emitted(), U256::from(100));
+        assert!(fx.env.emitted_event(
+            &fx.contract,
+            RewardSkipped {
+                attestation_id,
+                recipient: fx.user,
+                amount: U256::from(100),
+                reason: "Emission cap reached".to_string(),
+            }
+        ));
+
+        // Overflowing rewards are skipped too
+        fx.env.set_caller(fx.admin);
+        fx.contract.set_base_reward(U256::MAX);
+        let (attestation_id, _) = fx.create_attestation(fx.user, "anvil-local", DEFAULT_TARGET);
+        assert!(fx.env.emitted_event(
+            &fx.contract,
+            RewardSkipped {
+                attestation_id,
+                recipient: fx.user,
+                amount: U256::zero(),
+                reason: "Reward overflows".to_string(),
+            }
+        ));
+    }
+
+    #[test]
+    fn test_reward_skipped_when_token_mint_would_revert() {
+        let mut fx = Fixture::new()
+            .with_mock_stake(1, cspr_to_motes(1_000, 0))
+            .with_rewards(U256::from(50))
+            .build();
+        let mut token = fx.reward_token.take().unwrap();
+        let user = fx.user;
+        let skipped = |attestation_id: [u8; 32], reason: &str| RewardSkipped {
+            attestation_id,
+            recipient: user,
+            amount: U256::from(100),
+            reason: reason.to_string(),
+        };
+
+        // A paused token would revert the mint; the attestation is still created
+        token.set_paused(true);
+        let (attestation_id, _) = fx.create_default_attestation(fx.user);
+        assert!(fx.contract.get_attestation(attestation_id).is_some());
+        assert!(fx.env.emitted_event(&fx.contract, skipped(attestation_id, "Reward token paused")));
+
+        token.set_paused(false);
+        token.set_supply_cap(Some(U256::from(50)));
+        let (attestation_id, _) = fx.create_attestation(fx.user, "ethereum", DEFAULT_TARGET);
+        assert!(fx.env.emitted_event(&fx.contract, skipped(attestation_id, "Reward token supply cap reached")));
+
+        let foreign = MockRewardToken::deploy(&fx.env, MockRewardTokenInitArgs { minter: fx.admin });
+        fx.env.set_caller(fx.admin);
+        fx.contract.set_reward_token(Some(foreign.address()));
+        let (attestation_id, _) = fx.create_attestation(fx.user, "anvil-local", DEFAULT_TARGET);
+        assert!(fx.env.emitted_event(&fx.contract, skipped(attestation_id, "Not the reward token's minter")));
+
+        assert_eq!(token.balance_of(fx.user), U256::zero());
+        assert_eq!(foreign.balance_of(fx.user), U256::zero());
+        assert_eq!(fx.contract.get_rewards_emitted(), U256::zero());
+    }
+
+    #[test]
+    fn test_superseding_announces_successor_without_rewards() {
+        let mut fx = Fixture::new()
+            .with_mock_stake(1, cspr_to_motes(1_000, 0))
+            .with_rewards(U256::from(50))
+            .with_badges()
+            .build();
+        let token = fx.reward_token.take().unwrap();
+        let badge = fx.badge.take().unwrap();
+        let (attestation_id, _) = fx.create_default_attestation(fx.user);
+        let reward = token.balance_of(fx.user);
+        assert_eq!(reward, U256::from(100));
+
+        // Revocation, the successor's creation events marked as superseding, then the supersession
+        let events_before = fx.events_count();
+        let (refreshed_id, _) = fx.contract.refresh_attestation(attestation_id);
+        assert_eq!(fx.events_count(), events_before + 4);
+        assert_eq!(
+            fx.event_at::<AttestationRevoked>(-4),
+            AttestationRevoked { id: attestation_id, casper_address: fx.user }
+        );
+        let refreshed = fx.contract.get_attestation(refreshed_id).unwrap();
+        assert_eq!(
+            fx.event_at::<AttestationCreated>(-3),
+            AttestationCreated { supersedes: Some(attestation_id), ..AttestationCreated::from(&refreshed) }
+        );
+        let created_v2 = fx.event_at::<AttestationCreatedV2>(-2);
+        assert_eq!((created_v2.id, created_v2.supersedes), (refreshed_id, Some(attestation_id)));
+        assert_eq!(
+            fx.event_at::<AttestationSuperseded>(-1),
+            AttestationSuperseded { old_id: attestation_id, new_id: refreshed_id, reason: SUPERSEDED_BY_REFRESH }
+        );
+        let (extended_id, _) = fx.contract.extend_attestation(refreshed_id, 60 * 60);
+
+        assert_eq!(token.balance_of(fx.user), reward);
+        assert_eq!(fx.contract.get_rewards_emitted(), reward);
+        assert_eq!((badge.owner_of(refreshed_id), badge.owner_of(extended_id)), (None, None));
+        assert_eq!(badge.balance_of(fx.user), 0);
+        assert!(!fx.contract.get_attestation(extended_id).unwrap().revoked);
+
+        // Successors are indexed and replayed, but are not new attestations
+        assert_eq!(fx.contract.get_total_attestation_count(), 1);
+        let indexed: Vec<[u8; 32]> =
+            (0..3).map(|index| fx.contract.get_attestation_by_global_index(index).unwrap().id).collect();
+        assert_eq!(indexed, vec![attestation_id, refreshed_id, extended_id]);
+        let supersedes: Vec<Option<[u8; 32]>> =
+            fx.contract.get_attestation_events_replay(0, 10).iter().map(|event| event.supersedes).collect();
+        assert_eq!(supersedes, vec![None, Some(attestation_id), Some(refreshed_id)]);
+    }
+
+    /// Fixture with a Gold attestation by the user, disputed by the other account
+    fn disputed_fixture() -> (Fixture, [u8; 32], u64) {
+        let mut fx = Fixture::new().with_mock_stake(1, cspr_to_motes(10_000, 0)).build();
+        let (attestation_id, _) = fx.create_default_attestation(fx.user);
+        assert!(fx.contract.check_attestation(fx.user, Tier::Gold, None));
+
+        fx.env.set_caller(fx.other);
+        let bond = fx.contract.get_dispute_bond();
+        assert_eq!(bond, U512::from(DEFAULT_DISPUTE_BOND_MOTES));
+        assert_eq!(
+            fx.contract.with_tokens(bond - 1).try_open_dispute(attestation_id, "stale stake".to_string()),
+            Err(VeilError::InvalidDisputeBond.into())
+        );
+        let dispute_id = fx.contract.with_tokens(bond).open_dispute(attestation_id, "stale stake".to_string());
+        assert!(fx.env.emitted_event(
+            &fx.contract,
+            DisputeOpened {
+                dispute_id,
+                attestation_id,
+                challenger: fx.other,
+                bond,
+                reason: "stale stake".to_string(),
+            }
+        ));
+        assert_eq!(
+            fx.contract.with_tokens(bond).try_open_dispute(attestation_id, "again".to_string()),
+            Err(VeilError::DisputeAlreadyOpen.into())
+        );
+        (fx, attestation_id, dispute_id)
+    }
+
+    #[test]
+    fn test_open_dispute_flags_attestation() {
+        let (fx, attestation_id, dispute_id) = disputed_fixture();
+        let bond = fx.contract.get_dispute_bond();
+        assert_eq!(fx.env.balance_of(&fx.contract.address()), bond);
+        assert_eq!(fx.contract.get_attestation_dispute(attestation_id), Some(dispute_id));
+        assert!(!fx.contract.check_attestation(fx.user, Tier::Gold, None));
+        // Disputed, not revoked
+        assert!(fx.contract.is_attestation_valid(attestation_id, None));
+
+        let dispute = fx.contract.get_dispute(dispute_id).unwrap();
+        assert_eq!(fx.contract.get_open_disputes(), vec![dispute.clone()]);
+        assert_eq!(
+            dispute,
+            Dispute {
+                id: dispute_id,
+                attestation_id,
+                challenger: fx.other,
+                bond,
+                reason: "stale stake".to_string(),
+                opened_at: fx.env.block_time(),
+                status: DisputeStatus::Open,
+            }
+        );
+    }
+
+    #[test]
+    fn test_upheld_dispute_revokes_and_returns_bond() {
+        let (mut fx, attestation_id, dispute_id) = disputed_fixture();
+        let bond = fx.contract.get_dispute_bond();
+        let challenger_before = fx.env.balance_of(&fx.other);
+
+        // Only the admin or the arbiter resolves
+        assert_eq!(fx.contract.try_resolve_dispute(dispute_id, true), Err(VeilError::NotArbiter.into()));
+        let arbiter = fx.env.get_account(3);
+        fx.env.set_caller(fx.admin);
+        fx.contract.set_arbiter(Some(arbiter));
+        fx.env.set_caller(arbiter);
+        fx.contract.resolve_dispute(dispute_id, true);
+
+        assert!(fx.contract.get_attestation(attestation_id).unwrap().revoked);
+        assert_eq!(fx.env.balance_of(&fx.other), challenger_before + bond);
+        assert_eq!(fx.env.balance_of(&fx.contract.address()), U512::zero());
+        assert_eq!(fx.contract.get_dispute(dispute_id).unwrap().status, DisputeStatus::Upheld);
+        assert!(fx.contract.get_open_disputes().is_empty());
+        assert_eq!(fx.contract.get_attestation_dispute(attestation_id), None);
+        assert!(fx.env.emitted_event(
+            &fx.contract,
+            DisputeResolved { dispute_id, attestation_id, upheld: true, bond_recipient: fx.other }
+        ));
+        assert_eq!(fx.contract.try_resolve_dispute(dispute_id, false), Err(VeilError::DisputeNotOpen.into()));
+        assert_eq!(fx.contract.try_resolve_dispute(dispute_id + 1, true), Err(VeilError::DisputeNotFound.into()));
+
+        // A revoked attestation cannot be disputed
+        fx.env.set_caller(fx.other);
+        assert_eq!(
+            fx.contract.with_tokens(bond).try_open_dispute(attestation_id, "again".to_string()),
+            Err(VeilError::AlreadyRevoked.into())
+        );
+    }
+
+    #[test]
+    fn test_rejected_dispute_forfeits_bond_to_treasury() {
+        let (mut fx, attestation_id, dispute_id) = disputed_fixture();
+        let bond = fx.contract.get_dispute_bond();
+        let treasury = fx.env.get_account(4);
+        fx.env.set_caller(fx.admin);
+        assert_eq!(fx.contract.get_treasury(), fx.admin);
+        fx.contract.set_treasury(Some(treasury));
+        let (challenger_before, treasury_before) = (fx.env.balance_of(&fx.other), fx.env.balance_of(&treasury));
+
+        fx.contract.resolve_dispute(dispute_id, false);
+
+        assert!(!fx.contract.get_attestation(attestation_id).unwrap().revoked);
+        assert!(fx.contract.check_attestation(fx.user, Tier::Gold, None));
+        assert_eq!(fx.env.balance_of(&treasury), treasury_before + bond);
+        assert_eq!(fx.env.balance_of(&fx.other), challenger_before);
+        assert_eq!(fx.env.balance_of(&fx.contract.address()), U512::zero());
+        assert_eq!(fx.contract.get_dispute(dispute_id).unwrap().status, DisputeStatus::Rejected);
+        assert!(fx.env.emitted_event(
+            &fx.contract,
+            DisputeResolved { dispute_id, attestation_id, upheld: false, bond_recipient: treasury }
+        ));
+    }
+
+    #[test]
+    fn test_locked_stake_counts_per_stake_policy() {
+        let mut fx = Fixture::new()
+            .with_mock_stake(1, cspr_to_motes(600, 0))
+            .with_staking(24 * 60 * 60, StakePolicy::Combined)
+            .build();
+        let mut staking = fx.staking.take().unwrap();
+        let (user, other) = (fx.user, fx.other);
+        fx.env.set_caller(user);
+        staking.with_tokens(cspr_to_motes(500, 0)).lock();
+        fx.env.set_caller(other);
+        staking.with_tokens(cspr_to_motes(10_000, 0)).lock();
+
+        // Combined: 600 delegated + 500 locked is Silver; account 2 locks its way to Gold
+        let tier_of = |fx: &mut Fixture, account: Address| {
+            let (attestation_id, _) = fx.create_default_attestation(account);
+            let attestation = fx.contract.get_attestation(attestation_id).unwrap();
+            (attestation.stake_amount, attestation.tier)
+        };
+        assert_eq!(tier_of(&mut fx, user), (cspr_to_motes(1_100, 0), Tier::Silver));
+        assert_eq!(tier_of(&mut fx, other), (cspr_to_motes(10_000, 0), Tier::Gold));
+
+        fx.env.set_caller(fx.admin);
+        fx.contract.set_stake_policy(StakePolicy::LockedOnly);
+        assert_eq!(fx.contract.get_stake_policy(), StakePolicy::LockedOnly);
+        assert_eq!(tier_of(&mut fx, user), (cspr_to_motes(500, 0), Tier::Bronze));
+
+        fx.env.set_caller(fx.admin);
+        fx.contract.set_stake_policy(StakePolicy::DelegatedOnly);
+        assert_eq!(tier_of(&mut fx, user), (cspr_to_motes(600, 0), Tier::Bronze));
+        assert_eq!(tier_of(&mut fx, other), (U512::zero(), Tier::None));
+    }
+
+    #[test]
+    fn test_create_gas_estimate_grows_with_inputs_and_stake_query() {
+        let fx = Fixture::new().build();
+        let estimate = |fx: &Fixture, chain: &str| {
+            fx.contract.estimate_create_attestation_gas(chain.to_string(), DEFAULT_TARGET.to_string())
+        };
+
+        let default_estimate = estimate(&fx, DEFAULT_CHAIN);
+        assert!(estimate(&fx, "") >= CREATE_BASE_GAS);
+        assert!(estimate(&fx, "base-sepolia-with-a-much-longer-name") > default_estimate);
+        // The fixed budget the livenet tools give a create call covers typical inputs
+        assert!(default_estimate <= CALL_GAS);
+
+        let staked = Fixture::new().with_mock_stake(1, cspr_to_motes(100, 0)).build();
+        assert_eq!(estimate(&staked, DEFAULT_CHAIN), default_estimate + STAKE_QUERY_GAS);
+        let combined = Fixture::new()
+            .with_mock_stake(1, cspr_to_motes(100, 0))
+            .with_staking(0, StakePolicy::Combined)
+            .build();
+        assert_eq!(estimate(&combined, DEFAULT_CHAIN), default_estimate + 2 * STAKE_QUERY_GAS);
+    }
+
+    #[test]
+    fn test_min_stake_not_required_by_default() {
+        let mut fx = Fixture::new().build();
+        assert!(!fx.contract.is_min_stake_required());
+        assert_eq!(fx.contract.get_min_attestation_stake_motes(), cspr_to_motes(100, 0));
+
+        fx.create_default_attestation(fx.user);
+    }
+
+    #[test]
+    fn test_min_stake_required_rejects_zero_stake() {
+        let mut fx = Fixture::new().build();
+
+        fx.env.set_caller(fx.user);
+        assert_eq!(fx.contract.try_set_require_min_stake(true), Err(VeilError::NotAdmin.into()));
+
+        fx.env.set_caller(fx.admin);
+        fx.contract.set_require_min_stake(true);
+
+        let events_before = fx.events_count();
+        fx.env.set_caller(fx.user);
+        assert_eq!(
+            fx.contract.try_create_attestation(
+                DEFAULT_CHAIN.to_string(),
+                DEFAULT_TARGET.to_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            ),
+            Err(VeilError::InsufficientStake.into())
+        );
+        fx.assert_no_events_since(events_before);
+
+        // Lowering the minimum lets zero-stake users through again
+        fx.env.set_caller(fx.admin);
+        fx.contract.set_min_attestation_stake_motes(U512::zero());
+        fx.create_default_attestation(fx.user);
+    }
+
+    #[test]
+    fn test_check_min_stake() {
+        let bronze = cspr_to_motes(100, 0);
+
+        assert!(matches!(check_min_stake(U512::zero(), bronze), Err(VeilError::InsufficientStake)));
+        assert!(matches!(check_min_stake(bronze - 1, bronze), Err(VeilError::InsufficientStake)));
+        assert!(check_min_stake(bronze, bronze).is_ok());
+        assert!(check_min_stake(cspr_to_motes(1_000, 0), bronze).is_ok());
+    }
+
+    fn tags(tags: &[&str]) -> Vec<String> {
+        tags.iter().map(|tag| tag.to_string()).collect()
+    }
+
+    #[test]
+    fn test_check_tags() {
+        assert!(check_tags(&tags(&["verified-human", "kyc-level-2"]), DEFAULT_MAX_TAGS).is_ok());
+        assert!(check_tags(&[], DEFAULT_MAX_TAGS).is_ok());
+        assert!(check_tags(&["x".repeat(MAX_TAG_LEN)], DEFAULT_MAX_TAGS).is_ok());
+
+        assert!(matches!(check_tags(&tags(&["a", "b", "c"]), 2), Err(VeilError::TooManyTags)));
+        assert!(matches!(check_tags(&["x".repeat(MAX_TAG_LEN + 1)], 5), Err(VeilError::InvalidTag)));
+        for invalid in ["", "dao contributor", "naïve", "tab\t", "a\u{7f}"] {
+            assert!(matches!(check_tags(&tags(&[invalid]), 5), Err(VeilError::InvalidTag)), "{:?}", invalid);
+        }
+        assert!(matches!(check_tags(&tags(&["dao", "dao"]), 5), Err(VeilError::InvalidTag)));
+    }
+
+    #[test]
+    fn test_attestation_tags_limits() {
+        let mut fx = Fixture::new().build();
+        let user = fx.user;
+        assert_eq!(fx.contract.get_max_tags(), DEFAULT_MAX_TAGS);
+
+        let (id, _) = fx.create_default_attestation(user);
+        assert!(fx.contract.get_attestation_tags(id).is_empty());
+
+        let six = tags(&["a", "b", "c", "d", "e", "f"]);
+        assert_eq!(fx.contract.try_set_attestation_tags(id, six.clone()), Err(VeilError::TooManyTags.into()));
+        assert_eq!(
+            fx.contract.try_set_attestation_tags(id, tags(&["kyc level 2"])),
+            Err(VeilError::InvalidTag.into())
+        );
+
+        // Only the owner or the admin may tag
+        fx.env.set_caller(fx.other);
+        assert_eq!(
+            fx.contract.try_set_attestation_tags(id, tags(&["spam"])),
+            Err(VeilError::NotOwnerOrAdmin.into())
+        );
+
+        fx.env.set_caller(fx.admin);
+        fx.contract.set_max_tags(6);
+        fx.contract.set_attestation_tags(id, six.clone());
+        assert_eq!(fx.contract.get_attestation_tags(id), six);
+
+        fx.env.set_caller(user);
+        assert_eq!(fx.contract.try_set_max_tags(10), Err(VeilError::NotAdmin.into()));
+    }
+
+    #[test]
+    fn test_find_attestations_by_tag() {
+        let mut fx = Fixture::new().build();
+        let user = fx.user;
+
+        let mut ids = Vec::new();
+        for _ in 0..3 {
+            let (id, _) = fx.create_default_attestation(user);
+            ids.push(id);
+        }
+        let (encoded_before, _) = fx.contract.get_attestation_for_evm(ids[0]).unwrap();
+
+        fx.contract.set_attestation_tags(ids[0], tags(&["verified-human", "dao-contributor"]));
+        fx.contract.set_attestation_tags(ids[2], tags(&["dao-contributor"]));
+
+        let find = |tag: &str| fx.contract.find_attestations_by_tag(user, tag.to_string());
+        assert_eq!(find("dao-contributor"), vec![ids[0], ids[2]]);
+        assert_eq!(find("verified-human"), vec![ids[0]]);
+        assert!(find("kyc-level-2").is_empty());
+        assert!(fx.contract.find_attestations_by_tag(fx.other, "dao-contributor".to_string()).is_empty());
+
+        // Replacing tags updates the index
+        fx.contract.set_attestation_tags(ids[0], tags(&["kyc-level-2"]));
+        let find = |tag: &str| fx.contract.find_attestations_by_tag(user, tag.to_string());
+        assert_eq!(find("dao-contributor"), vec![ids[2]]);
+        assert!(find("verified-human").is_empty());
+        assert_eq!(find("kyc-level-2"), vec![ids[0]]);
+
+        // Tags are metadata: the signed payload is unchanged
+        let (encoded_after, _) = fx.contract.get_attestation_for_evm(ids[0]).unwrap();
+        assert_eq!(encoded_after, encoded_before);
+        assert_eq!(keccak256(&encoded_after), ids[0]);
+    }
+
+    #[test]
+    fn test_compressed_attestation_keeps_tags_and_witness() {
+        let mut fx = Fixture::new().build();
+        fx.contract.set_compressed_storage(true);
+        fx.contract.set_trusted_witness(WITNESS, true);
+
+        let (id, _) = fx.create_default_attestation(fx.user);
+        fx.contract.set_attestation_tags(id, tags(&["verified-human"]));
+        assert_eq!(fx.contract.get_attestation(id).unwrap().tags, tags(&["verified-human"]));
+        fx.contract.add_witness(id, witness_signature(&WITNESS_PRIVATE_KEY, &id));
+        assert!(fx.contract.is_attestation_valid(id, Some(true)));
+
+        fx.contract.revoke_attestation(id);
+        let attestation = fx.contract.get_attestation(id).unwrap();
+        assert!(attestation.revoked);
+        assert_eq!(attestation.tags, tags(&["verified-human"]));
+        assert_eq!(attestation.witness_address, Some(WITNESS));
+    }
+
+    fn sample_attestation(env: &HostEnv) -> Attestation {
+        Attestation {
+            id: [0x22; 32],
+            casper_address: env.get_account(1),
+            target_chain: DEFAULT_CHAIN.to_string(),
+            target_address: DEFAULT_TARGET.to_string(),
+            stake_amount: cspr_to_motes(10_000, 5),
+            stake_source: STAKE_SOURCE_LIVE,
+            stake_epoch: 0,
+            tier: Tier::Gold,
+            account_age_days: 0,
+            created_at: 1_000,
+            expires_at: 604_801_000,
+            attestation_validity_secs: 604_800,
+            nonce: 3,
+            revoked: true,
+            tags: Vec::new(),
+            witness_address: None,
+            witness_signature: None,
+            referrer: None,
+            trusted_verifier: [0x33; 20],
+            casper_era_id: 17_204,
+            reputation_score: 35,
+            attestation_type: DEFAULT_ATTESTATION_TYPE.to_string(),
+            template_id: 2,
+            nonce_commitment: None,
+            hash_algorithm_version: encoding::HASH_ALGORITHM_SHA3_256,
+            verified_on_evm_at: None,
+            evm_tx_hash: None,
+            stale: false,
+            frozen: false,
+            region_hint: None,
+            is_renewable: false,
+        }
+    }
+
+    #[test]
+    fn test_compressed_attestation_round_trips() {
+        let env = odra_test::env();
+        let mut attestation = sample_attestation(&env);
+        // Stake above 64 bits exercises the high word
+        attestation.stake_amount = U512::from(u64::MAX) * 3;
+
+        let type_hash = keccak256(DEFAULT_ATTESTATION_TYPE.as_bytes());
+        let compressed = compress_attestation(&attestation, keccak256(b"base-sepolia"), type_hash).unwrap();
+        let (chain, address) = (&attestation.target_chain, &attestation.target_address);
+        let restored = decompress_attestation(&compressed, chain, address, DEFAULT_ATTESTATION_TYPE);
+        assert_eq!(restored, attestation);
+
+        attestation.target_address = "0xnothex".to_string();
+        assert_eq!(compress_attestation(&attestation, [0; 32], [0; 32]), None);
+    }
+
+    #[test]
+    fn test_compressed_attestation_storage_size() {
+        // Storage gas scales with the bytes written, so serialized length is the benchmark
+        let env = odra_test::env();
+        let attestation = sample_attestation(&env);
+        let type_hash = keccak256(DEFAULT_ATTESTATION_TYPE.as_bytes());
+        let compressed = compress_attestation(&attestation, keccak256(b"base-sepolia"), type_hash).unwrap();
+
+        let full_len = attestation.serialized_length();
+        let compressed_len = compressed.serialized_length();
+        assert_eq!(compressed_len, 235, "compressed records are fixed-size");
+        assert!(compressed_len < full_len, "compressed {} vs full {}", compressed_len, full_len);
+
+        // Savings grow with the chain name, which compressed records store once per chain
+        let mut long_chain = attestation.clone();
+        long_chain.target_chain = "arbitrum-one-with-a-name-longer-than-one-slot".to_string();
+        let savings = |record: &Attestation| record.serialized_length() - compressed_len;
+        assert!(savings(&long_chain) > savings(&attestation));
+    }
+
+    #[test]
+    fn test_compressed_storage_round_trips_through_contract() {
+        let mut fx = Fixture::new().build();
+        fx.contract.set_compressed_storage(true);
+        assert!(fx.contract.is_compressed_storage());
+
+        let (compressed_id, signature) =
+            fx.create_attestation(fx.user, DEFAULT_CHAIN, "0x1234567890ABCDEF1234567890abcdef12345678");
+
+        let attestation = fx.contract.get_attestation(compressed_id).unwrap();
+        assert_eq!(attestation.target_chain, DEFAULT_CHAIN);
+        assert_eq!(attestation.target_address, DEFAULT_TARGET);
+        assert_eq!(attestation.attestation_validity_secs, 7 * 24 * 60 * 60);
+
+        // The signed payload uses the normalized address, so re-export matches
+        let (encoded, exported_signature) = fx.contract.get_attestation_for_evm(compressed_id).unwrap();
+        assert_eq!(keccak256(&encoded), compressed_id);
+        assert_eq!(exported_signature, signature);
+
+        // Records stay where they were written after the flag is turned off
+        fx.env.set_caller(fx.admin);
+        fx.contract.set_compressed_storage(false);
+        let (full_id, _) = fx.create_default_attestation(fx.user);
+        fx.contract.revoke_attestation(compressed_id);
+
+        assert!(fx.contract.get_attestation(compressed_id).unwrap().revoked);
+        assert!(!fx.contract.get_attestation(full_id).unwrap().revoked);
+        assert_eq!(fx.contract.get_user_attestations(fx.user).len(), 2);
+    }
+
+    /// A rejected call: `arrange` prepares the contract and returns the
+    /// attestation ID to act on, `act` makes the call that must revert
+    struct NegativeCase {
+        name: &'static str,
+        arrange: fn(&mut Fixture) -> [u8; 32],
+        act: fn(&mut Fixture, [u8; 32]) -> OdraResult<()>,
+        expected: VeilError,
+    }
+
+    const UNKNOWN_ID: [u8; 32] = [0xab; 32];
+
+    fn nothing(_: &mut Fixture) -> [u8; 32] {
+        UNKNOWN_ID
+    }
+
+    /// Default attestation owned by the user, who stays the caller
+    fn attested(fx: &mut Fixture) -> [u8; 32] {
+        fx.create_default_attestation(fx.user).0
+    }
+
+    fn attested_then_revoked(fx: &mut Fixture) -> [u8; 32] {
+        let id = attested(fx);
+        fx.contract.revoke_attestation(id);
+        id
+    }
+
+    fn attested_with_pending_revocation(fx: &mut Fixture) -> [u8; 32] {
+        fx.contract.set_revocation_delay_secs(3600);
+        let id = attested(fx);
+        fx.contract.revoke_attestation(id);
+        id
+    }
+
+    fn paused(fx: &mut Fixture) -> [u8; 32] {
+        fx.contract.emergency_pause();
+        fx.env.set_caller(fx.user);
+        UNKNOWN_ID
+    }
+
+    fn compressed(fx: &mut Fixture) -> [u8; 32] {
+        fx.contract.set_compressed_storage(true);
+        fx.env.set_caller(fx.user);
+        UNKNOWN_ID
+    }
+
+    /// The user is the caller, but not the admin
+    fn as_user(fx: &mut Fixture) -> [u8; 32] {
+        fx.env.set_caller(fx.user);
+        UNKNOWN_ID
+    }
+
+    fn create_for(fx: &mut Fixture, target: &str) -> OdraResult<()> {
+        fx.env.set_caller(fx.user);
+        fx.contract
+            .try_create_attestation(DEFAULT_CHAIN.to_string(), target.to_string(), None, None, None, None, None, None)
+            .map(|_| ())
+    }
+
+    #[test]
+    fn test_negative_paths_revert_with_specific_errors() {
+        let cases = vec![
+            NegativeCase {
+                name: "address without 0x prefix",
+                arrange: nothing,
+                act: |fx, _| create_for(fx, "1234567890abcdef1234567890abcdef1234567890"),
+                expected: VeilError::InvalidTargetAddress,
+            },
+            NegativeCase {
+                name: "address too short",
+                arrange: nothing,
+                act: |fx, _| create_for(fx, "0x1234567890abcdef"),
+                expected: VeilError::InvalidTargetAddress,
+            },
+            NegativeCase {
+                name: "address too long",
+                arrange: nothing,
+                act: |fx, _| create_for(fx, "0x1234567890abcdef1234567890abcdef1234567890"),
+                expected: VeilError::InvalidTargetAddress,
+            },
+            NegativeCase {
+                name: "empty address",
+                arrange: nothing,
+                act: |fx, _| create_for(fx, ""),
+                expected: VeilError::InvalidTargetAddress,
+            },
+            NegativeCase {
+                name: "free-form address",
+                arrange: nothing,
+                act: |fx, _| create_for(fx, "not-an-address"),
+                expected: VeilError::InvalidTargetAddress,
+            },
+            NegativeCase {
+                name: "non-hex address",
+                arrange: nothing,
+                act: |fx, _| create_for(fx, "0xzz34567890abcdef1234567890abcdef12345678"),
+                expected: VeilError::InvalidTargetAddress,
+            },
+            NegativeCase {
+                name: "non-hex address under compressed storage",
+                arrange: compressed,
+                act: |fx, _| create_for(fx, "0xzz34567890abcdef1234567890abcdef12345678"),
+                expected: VeilError::InvalidTargetAddress,
+            },
+            NegativeCase {
+                name: "creation while paused",
+                arrange: paused,
+                act: |fx, _| create_for(fx, DEFAULT_TARGET),
+                expected: VeilError::ContractPaused,
+            },
+            NegativeCase {
+                name: "revoke unknown attestation",
+                arrange: as_user,
+                act: |fx, id| fx.contract.try_revoke_attestation(id),
+                expected: VeilError::AttestationNotFound,
+            },
+            NegativeCase {
+                name: "revoke twice",
+                arrange: attested_then_revoked,
+                act: |fx, id| fx.contract.try_revoke_attestation(id),
+                expected: VeilError::AlreadyRevoked,
+            },
+            NegativeCase {
+                name: "revoke by non-owner",
+                arrange: attested,
+                act: |fx, id| {
+                    fx.env.set_caller(fx.other);
+                    fx.contract.try_revoke_attestation(id)
+                },
+                expected: VeilError::NotAttestationOwner,
+            },
+            NegativeCase {
+                name: "revoke by admin",
+                arrange: attested,
+                act: |fx, id| {
+                    fx.env.set_caller(fx.admin);
+                    fx.contract.try_revoke_attestation(id)
+                },
+                expected: VeilError::NotAttestationOwner,
+            },
+            NegativeCase {
+                name: "request revocation twice",
+                arrange: attested_with_pending_revocation,
+                act: |fx, id| fx.contract.try_revoke_attestation(id),
+                expected: VeilError::RevocationAlreadyPending,
+            },
+            NegativeCase {
+                name: "finalize unknown attestation",
+                arrange: nothing,
+                act: |fx, id| fx.contract.try_finalize_revocation(id),
+                expected: VeilError::NoPendingRevocation,
+            },
+            NegativeCase {
+                name: "make another user's attestation non-renewable",
+                arrange: attested,
+                act: |fx, id| {
+                    fx.env.set_caller(fx.other);
+                    fx.contract.try_make_attestation_non_renewable(id)
+                },
+                expected: VeilError::NotAttestationOwner,
+            },
+            NegativeCase {
+                name: "tag unknown attestation",
+                arrange: as_user,
+                act: |fx, id| fx.contract.try_set_attestation_tags(id, Vec::new()),
+                expected: VeilError::AttestationNotFound,
+            },
+            NegativeCase {
+                name: "witness unknown attestation",
+                arrange: nothing,
+                act: |fx, id| fx.contract.try_add_witness(id, Bytes::from(vec![0u8; 65])),
+                expected: VeilError::AttestationNotFound,
+            },
+            NegativeCase {
+                name: "witness revoked attestation",
+                arrange: attested_then_revoked,
+                act: |fx, id| fx.contract.try_add_witness(id, Bytes::from(vec![0u8; 65])),
+                expected: VeilError::AlreadyRevoked,
+            },
+            NegativeCase {
+                name: "auto-renew by non-relayer",
+                arrange: attested,
+                act: |fx, id| fx.contract.try_execute_auto_renew(fx.user, id).map(|_| ()),
+                expected: VeilError::NotRelayer,
+            },
+            NegativeCase {
+                name: "auto-renew without opt-in",
+                arrange: attested,
+                act: |fx, id| {
+                    fx.env.set_caller(fx.admin);
+                    fx.contract.add_relayer(fx.other);
+                    fx.env.set_caller(fx.other);
+                    fx.contract.try_execute_auto_renew(fx.user, id).map(|_| ())
+                },
+                expected: VeilError::AutoRenewDisabled,
+            },
+            NegativeCase {
+                name: "unauthorized pause",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_emergency_pause(),
+                expected: VeilError::NotEmergencyAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized unpause",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_unpause(),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized signer rotation",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_rotate_signer(ROTATED_PRIVATE_KEY),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized revocation delay",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_revocation_delay_secs(0),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized compressed storage toggle",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_compressed_storage(true),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized verifier registration",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_add_trusted_verifier(DEFAULT_CHAIN.to_string(), [0x11; 20]),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized relayer registration",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_add_relayer(fx.other),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized chain attestation limit",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_chain_attestation_limit(DEFAULT_CHAIN.to_string(), 1),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized admin transfer",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_transfer_admin(fx.user),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized validity period",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_attestation_validity_secs(24 * 60 * 60),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized tier thresholds",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_update_tier_thresholds(TierThresholds::default()),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized attestation fee",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_attestation_fee(U512::one()),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized locked stake source",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_locked_stake_source(Some(fx.other)),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized badge collection",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_badge_collection(Some(fx.other)),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized reward token",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_reward_token(Some(fx.other)),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized base reward",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_base_reward(U256::one()),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized reward multipliers",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_reward_multipliers(RewardMultipliers::default()),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized upgrade lock extension",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_extend_upgrade_lock(60),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized pow difficulty",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_pow_difficulty(1),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized trusted verifier removal",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_remove_trusted_verifier(DEFAULT_CHAIN.to_string(), [0x22; 20]),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized checksum requirement",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_require_checksum(true),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized reuse window",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_reuse_window_secs(600),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized stake cache",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_stake_cache_secs(600),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized relayer removal",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_remove_relayer(fx.other),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized renewal grace",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_renewal_grace_secs(600),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized network name",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_network_name("casper-test".to_string()),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized supported chains",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_supported_chains(vec![DEFAULT_CHAIN.to_string()]),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized duplicate chain address switch",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_allow_duplicate_chain_address(false),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized v1 event switch",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_emit_v1_events(false),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized trusted witness",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_trusted_witness([0x22; 20], true),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized max tags",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_max_tags(2),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized min stake requirement",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_require_min_stake(true),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized min attestation stake",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_min_attestation_stake_motes(U512::one()),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized stake source",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_stake_source(Some(fx.other)),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized chain proposal",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_propose_add_chain("ethereum".to_string()),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized badge toggle",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_mint_badges(true),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized reward kill switch",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_rewards_enabled(true),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized reward emission cap",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_reward_emission_cap(U256::zero()),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized custom tier",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_custom_tier(fx.user, Some(Tier::Gold)),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized attestation type",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_register_attestation_type("governance".to_string()),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized template",
+                arrange: as_user,
+                act: |fx, _| {
+                    let chain = DEFAULT_CHAIN.to_string();
+                    fx.contract.try_create_template(chain, 0, DEFAULT_MIN_VALIDITY_SECS, "s1".to_string()).map(|_| ())
+                },
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized chain allowlist",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_allow_chain(DEFAULT_CHAIN.to_string()),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized allowlist mode",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_allowlist_enabled(true),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized hash algorithm",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_hash_algorithm(encoding::HASH_ALGORITHM_SHA3_256),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unsupported hash algorithm",
+                arrange: nothing,
+                act: |fx, _| fx.contract.try_set_hash_algorithm(2),
+                expected: VeilError::UnsupportedHashAlgorithm,
+            },
+            NegativeCase {
+                name: "unauthorized claim provider",
+                arrange: as_user,
+                act: |fx, _| {
+                    let public_key = encoding::derive_public_key(&CLAIM_PROVIDER_KEY).unwrap();
+                    fx.contract.try_register_claim_provider(public_key).map(|_| ())
+                },
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized claim provider removal",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_remove_claim_provider(EXPECTED_SIGNER),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized confirmation signer",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_confirmation_signer(Some(EXPECTED_SIGNER)),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized max attestation age",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_max_attestation_age_secs(1),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "self-referral",
+                arrange: nothing,
+                act: |fx, _| create_referred(fx, fx.user, fx.user).map(|_| ()),
+                expected: VeilError::SelfReferral,
+            },
+            NegativeCase {
+                name: "unauthorized referrer block",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_referrer_blocked(fx.other, true),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized signature refresh",
+                arrange: as_user,
+                act: |fx, id| fx.contract.try_batch_refresh_signatures(vec![id]).map(|_| ()),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized max batch size",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_max_batch_size(1),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized reputation weights",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_reputation_weights(ReputationWeights::default()),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized dispute bond",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_dispute_bond(U512::zero()),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized arbiter",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_arbiter(None),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized treasury",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_treasury(None),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized stake policy",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_stake_policy(StakePolicy::Combined),
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized chain deployment",
+                arrange: as_user,
+                act: |fx, _| {
+                    fx.contract.try_set_chain_deployment(DEFAULT_CHAIN.to_string(), chain_deployment(true, Tier::None))
+                },
+                expected: VeilError::NotAdmin,
+            },
+            NegativeCase {
+                name: "unauthorized minimum validity",
+                arrange: as_user,
+                act: |fx, _| fx.contract.try_set_min_validity_secs(0),
+                expected: VeilError::NotAdmin,
+            },
+        ];
+
+        for case in cases {
+            let mut fx = Fixture::new().build();
+            let id = (case.arrange)(&mut fx);
+            let events_before = fx.events_count();
+            assert_eq!((case.act)(&mut fx, id), Err(case.expected.into()), "{}", case.name);
+            fx.assert_no_events_since(events_before);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "no-onchain-signing"))]
+mod external_signing_tests {
+    use alloc::string::ToString;
+    use odra::casper_types::bytesrepr::Bytes;
+    use odra::host::Deployer;
+    use crate::encoding::{derive_public_key, sign_message};
+    use crate::errors::VeilError;
+    use crate::test_support::{
+        Fixture, DEFAULT_CHAIN, DEFAULT_TARGET, EXPECTED_SIGNER, ROTATED_PRIVATE_KEY, ROTATED_SIGNER, TEST_PRIVATE_KEY,
+    };
+    use crate::types::AttestationSigned;
+    use crate::veil_attestation::{VeilAttestation, VeilAttestationInitArgs};
+
+    fn signed(key: &[u8; 32], message: &[u8; 32]) -> Bytes {
+        Bytes::from(sign_message(key, message).to_vec())
+    }
+
+    #[test]
+    fn test_attestation_waits_for_external_signature() {
+        let mut fx = Fixture::new().build();
+        assert_eq!(fx.contract.get_signer_address(), EXPECTED_SIGNER);
+
+        fx.env.set_caller(fx.user);
+        let (attestation_id, signature) = fx.contract.create_attestation(
+            DEFAULT_CHAIN.to_string(),
+            DEFAULT_TARGET.to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        assert!(signature.is_empty());
+        assert_eq!(fx.contract.get_attestation_for_evm(attestation_id), None);
+        assert_eq!(fx.contract.check_signer_health().signatures_produced, 0);
+
+        // Only the signer's signature over the attestation is accepted
+        let events_before = fx.events_count();
+        for forged in [signed(&ROTATED_PRIVATE_KEY, &attestation_id), signed(&TEST_PRIVATE_KEY, &[0u8; 32])] {
+            assert_eq!(
+                fx.contract.try_submit_signature(attestation_id, forged),
+                Err(VeilError::SignatureNotFromSigner.into())
+            );
+        }
+        assert_eq!(
+            fx.contract.try_submit_signature([0xee; 32], signed(&TEST_PRIVATE_KEY, &[0xee; 32])),
+            Err(VeilError::AttestationNotFound.into())
+        );
+        fx.assert_no_events_since(events_before);
+
+        let signature = fx.submit_signature(attestation_id);
+        fx.assert_single_event(events_before, AttestationSigned { id: attestation_id, key_version: 1 });
+        fx.assert_signature_valid(&attestation_id, &signature);
+        assert_eq!(fx.contract.get_attestation_raw_signature(attestation_id), Some(signature));
+        assert_eq!(fx.contract.get_signature_produced_at(attestation_id), Some(fx.env.block_time()));
+        assert_eq!(fx.contract.check_signer_health().signatures_produced, 1);
+    }
+
+    #[test]
+    fn test_rotation_installs_public_key() {
+        let mut fx = Fixture::new().build();
+        let (attestation_id, _) = fx.create_default_attestation(fx.user);
+
+        fx.env.set_caller(fx.admin);
+        let rotated_public_key = derive_public_key(&ROTATED_PRIVATE_KEY).unwrap();
+        let mut off_curve = rotated_public_key;
+        off_curve[63] ^= 1;
+        assert_eq!(fx.contract.try_rotate_signer(off_curve), Err(VeilError::InvalidSignerKey.into()));
+        fx.contract.rotate_signer(rotated_public_key);
+        assert_eq!(fx.contract.get_signer_address(), ROTATED_SIGNER);
+
+        // The old key's signature is not exported until the new key signs
+        assert_eq!(fx.contract.get_attestation_for_evm(attestation_id), None);
+        let signature = signed(&ROTATED_PRIVATE_KEY, &attestation_id);
+        fx.contract.submit_signature(attestation_id, signature.clone());
+        assert_eq!(fx.contract.get_attestation_for_evm(attestation_id).unwrap().1, signature);
+    }
+
+    #[test]
+    fn test_attestation_unusable_until_signed_under_current_key() {
+        let mut fx = Fixture::new().build();
+        let (attestation_id, _) = fx.create_default_attestation(fx.user);
+        let usable = |fx: &Fixture| {
+            fx.contract.is_attestation_usable_for_chain(attestation_id, DEFAULT_CHAIN.to_string())
+        };
+        assert_eq!(usable(&fx), (true, "OK".to_string()));
+
+        fx.env.set_caller(fx.admin);
+        fx.contract.rotate_signer(derive_public_key(&ROTATED_PRIVATE_KEY).unwrap());
+        assert_eq!(usable(&fx), (false, "Not signed under the current signing key".to_string()));
+        fx.contract.submit_signature(attestation_id, signed(&ROTATED_PRIVATE_KEY, &attestation_id));
+        assert_eq!(usable(&fx), (true, "OK".to_string()));
+    }
+
+    #[test]
+    fn test_init_rejects_invalid_public_key() {
+        let env = odra_test::env();
+        let init_args = VeilAttestationInitArgs {
+            admin: env.get_account(0),
+            signer_public_key: [0; 64],
+            initial_lock_secs: None,
+            config: None,
+        };
+        assert_eq!(VeilAttestation::try_deploy(&env, init_args).err(), Some(VeilError::InvalidSignerKey.into()));
     }
 }
@@ -0,0 +1,239 @@
+//! Storage migration for deployed contracts
+//!
+//! Drives `migrate_v1_to_v2` against an existing deployment: reads the
+//! storage version, sends the migration while it is still at version 1,
+//! reads the version back and, in debug builds, checks the contract's
+//! invariants. Reads go through [`MigrationTarget`], so the flow runs
+//! against a mock in unit tests and against a node in `veil_migrate`.
+
+use std::fmt;
+use std::format;
+use std::prelude::v1::*;
+
+use serde::Serialize;
+
+use crate::types::DeploymentInfo;
+use crate::veil_attestation::{VeilAttestationHostRef, DEFAULT_ATTESTATION_TYPE, SCHEMA_VERSION, STORAGE_VERSION};
+
+/// Contract views the migration reads
+pub trait MigrationTarget {
+    fn get_contract_version(&self) -> u32;
+    fn get_deployment_info(&self) -> DeploymentInfo;
+    fn is_attestation_type_registered(&self, attestation_type: String) -> bool;
+}
+
+impl MigrationTarget for VeilAttestationHostRef {
+    fn get_contract_version(&self) -> u32 {
+        VeilAttestationHostRef::get_contract_version(self)
+    }
+
+    fn get_deployment_info(&self) -> DeploymentInfo {
+        VeilAttestationHostRef::get_deployment_info(self)
+    }
+
+    fn is_attestation_type_registered(&self, attestation_type: String) -> bool {
+        VeilAttestationHostRef::is_attestation_type_registered(self, attestation_type)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MigrationError {
+    /// The contract reports a storage version this tool does not know
+    UnknownVersion(u32),
+    /// The migration was confirmed but the version did not change
+    NotMigrated { found: u32 },
+    /// A post-migration invariant does not hold
+    Invariant(String),
+}
+
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MigrationError::UnknownVersion(version) => {
+                write!(f, "contract reports storage version {}, newer than {}", version, STORAGE_VERSION)
+            }
+            MigrationError::NotMigrated { found } => {
+                write!(f, "contract still reports storage version {} after migrating", found)
+            }
+            MigrationError::Invariant(message) => write!(f, "invariant violated: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for MigrationError {}
+
+/// What `migrate` found and did, printed by `veil_migrate`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MigrationReport {
+    pub contract: String,
+    pub from_version: u32,
+    pub to_version: u32,
+    /// Whether `migrate_v1_to_v2` was sent; false if already current
+    pub migrated: bool,
+    /// Whether `assert_invariants` ran (debug builds only)
+    pub invariants_checked: bool,
+}
+
+impl fmt::Display for MigrationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Contract:         {}", self.contract)?;
+        writeln!(f, "Storage version:  {} -> {}", self.from_version, self.to_version)?;
+        writeln!(f, "Migrated:         {}", if self.migrated { "yes" } else { "no (already current)" })?;
+        write!(f, "Invariants:       {}", if self.invariants_checked { "checked" } else { "skipped (release build)" })
+    }
+}
+
+/// Migrate `target` to `STORAGE_VERSION`. `send` sends `migrate_v1_to_v2`
+/// and returns once its deploy is confirmed; it is skipped when the contract
+/// is already current.
+pub fn migrate<T: MigrationTarget>(
+    target: &mut T,
+    contract: &str,
+    send: impl FnOnce(&mut T),
+) -> Result<MigrationReport, MigrationError> {
+    let from_version = target.get_contract_version();
+    if from_version > STORAGE_VERSION {
+        return Err(MigrationError::UnknownVersion(from_version));
+    }
+    let migrated = from_version < STORAGE_VERSION;
+    if migrated {
+        send(target);
+    }
+    let to_version = target.get_contract_version();
+    if to_version != STORAGE_VERSION {
+        return Err(MigrationError::NotMigrated { found: to_version });
+    }
+
+    #[cfg(debug_assertions)]
+    assert_invariants(target)?;
+
+    Ok(MigrationReport {
+        contract: contract.to_string(),
+        from_version,
+        to_version,
+        migrated,
+        invariants_checked: cfg!(debug_assertions),
+    })
+}
+
+/// Check what every migrated deployment must satisfy; `migrate` runs this
+/// in debug builds
+pub fn assert_invariants<T: MigrationTarget>(target: &T) -> Result<(), MigrationError> {
+    let version = target.get_contract_version();
+    if version != STORAGE_VERSION {
+        return Err(MigrationError::Invariant(format!("storage version is {}", version)));
+    }
+    let info = target.get_deployment_info();
+    if info.schema_version != SCHEMA_VERSION {
+        return Err(MigrationError::Invariant(format!(
+            "payload schema version is {}, expected {}",
+            info.schema_version, SCHEMA_VERSION
+        )));
+    }
+    if !target.is_attestation_type_registered(DEFAULT_ATTESTATION_TYPE.to_string()) {
+        return Err(MigrationError::Invariant(format!("{:?} is not a registered type", DEFAULT_ATTESTATION_TYPE)));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::vec;
+    use odra::host::Deployer;
+    use crate::cli::signer_init_args;
+    use crate::test_support::TEST_PRIVATE_KEY;
+    use crate::veil_attestation::VeilAttestation;
+
+    /// A deployment as `migrate_v1_to_v2` would see and leave it
+    struct MockContract {
+        version: u32,
+        default_type_registered: bool,
+        migrations_sent: u32,
+    }
+
+    impl MockContract {
+        fn v1() -> Self {
+            MockContract { version: 1, default_type_registered: false, migrations_sent: 0 }
+        }
+
+        fn migrate_v1_to_v2(&mut self) {
+            self.migrations_sent += 1;
+            self.version = 2;
+            self.default_type_registered = true;
+        }
+    }
+
+    impl MigrationTarget for MockContract {
+        fn get_contract_version(&self) -> u32 {
+            self.version
+        }
+
+        fn get_deployment_info(&self) -> DeploymentInfo {
+            DeploymentInfo {
+                contract_address: "hash-00".to_string(),
+                signer_address: "0x00".to_string(),
+                network_name: String::new(),
+                deployed_at: 0,
+                contract_version: "0.1.0".to_string(),
+                schema_version: SCHEMA_VERSION,
+                supported_chains: vec![],
+            }
+        }
+
+        fn is_attestation_type_registered(&self, attestation_type: String) -> bool {
+            self.default_type_registered && attestation_type == DEFAULT_ATTESTATION_TYPE
+        }
+    }
+
+    #[test]
+    fn test_migrates_v1_contract() {
+        let mut contract = MockContract::v1();
+        let report = migrate(&mut contract, "hash-00", MockContract::migrate_v1_to_v2).unwrap();
+        assert_eq!(contract.migrations_sent, 1);
+        assert_eq!(
+            report,
+            MigrationReport {
+                contract: "hash-00".to_string(),
+                from_version: 1,
+                to_version: STORAGE_VERSION,
+                migrated: true,
+                invariants_checked: cfg!(debug_assertions),
+            }
+        );
+    }
+
+    #[test]
+    fn test_current_contract_is_left_alone() {
+        let env = odra_test::env();
+        let init_args = signer_init_args(env.get_account(0), TEST_PRIVATE_KEY, None).unwrap();
+        let mut contract = VeilAttestation::deploy(&env, init_args);
+        let report = migrate(&mut contract, "hash-00", |_| panic!("migration must not be sent")).unwrap();
+        assert!(!report.migrated);
+        assert_eq!((report.from_version, report.to_version), (STORAGE_VERSION, STORAGE_VERSION));
+    }
+
+    #[test]
+    fn test_reports_failed_and_unknown_migrations() {
+        let mut contract = MockContract::v1();
+        assert_eq!(migrate(&mut contract, "hash-00", |_| {}), Err(MigrationError::NotMigrated { found: 1 }));
+
+        let mut contract = MockContract { version: STORAGE_VERSION + 1, ..MockContract::v1() };
+        assert_eq!(
+            migrate(&mut contract, "hash-00", MockContract::migrate_v1_to_v2),
+            Err(MigrationError::UnknownVersion(STORAGE_VERSION + 1))
+        );
+        assert_eq!(contract.migrations_sent, 0);
+    }
+
+    #[test]
+    fn test_invariants_catch_incomplete_migration() {
+        let contract = MockContract { version: 2, ..MockContract::v1() };
+        assert!(matches!(assert_invariants(&contract), Err(MigrationError::Invariant(_))));
+        if cfg!(debug_assertions) {
+            let mut contract = MockContract::v1();
+            let result = migrate(&mut contract, "hash-00", |contract| contract.version = 2);
+            assert!(matches!(result, Err(MigrationError::Invariant(_))));
+        }
+    }
+}
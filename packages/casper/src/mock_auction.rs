@@ -0,0 +1,25 @@
+//! Stand-in for the system auction, so tiers can be exercised in tests and
+//! on localnet. Anyone may set any account's stake; never deploy it as the
+//! stake source of a production contract.
+
+use odra::casper_types::U512;
+use odra::prelude::*;
+
+/// Delegated stake per account, settable by anyone
+#[odra::module]
+pub struct MockAuction {
+    delegated_stakes: Mapping<Address, U512>,
+}
+
+#[odra::module]
+impl MockAuction {
+    /// Set the stake (motes) `delegator` reports as delegated
+    pub fn set_delegated_stake(&mut self, delegator: Address, amount: U512) {
+        self.delegated_stakes.set(&delegator, amount);
+    }
+
+    /// See `stake_source::StakeSource`; zero for unknown accounts
+    pub fn get_delegated_stake(&self, delegator: Address) -> U512 {
+        self.delegated_stakes.get(&delegator).unwrap_or_default()
+    }
+}
@@ -0,0 +1,77 @@
+//! Stand-in for a CEP-18 reward token in tests and on localnet: balances, the
+//! `mint` entry point and the views of `reward_token::RewardToken`, minted on
+//! by a single minter. Anyone can pause it or cap its supply.
+
+use odra::casper_types::U256;
+use odra::prelude::*;
+use crate::errors::VeilError;
+
+#[odra::module]
+pub struct MockRewardToken {
+    /// The only account or contract allowed to mint
+    minter: Var<Address>,
+    balances: Mapping<Address, U256>,
+    total_supply: Var<U256>,
+    paused: Var<bool>,
+    /// Maximum total supply; `None` means uncapped
+    supply_cap: Var<Option<U256>>,
+}
+
+#[odra::module]
+impl MockRewardToken {
+    /// Initialize the token with `minter` holding the mint rights
+    pub fn init(&mut self, minter: Address) {
+        self.minter.set(minter);
+    }
+
+    /// See `reward_token::RewardToken` (minter only; reverts while paused or
+    /// past the supply cap)
+    pub fn mint(&mut self, owner: Address, amount: U256) {
+        if !self.is_minter(self.env().caller()) {
+            self.env().revert(VeilError::NotAdmin);
+        }
+        if self.is_paused() || amount > self.mintable_supply() {
+            self.env().revert(VeilError::ContractPaused);
+        }
+        self.balances.set(&owner, self.balance_of(owner) + amount);
+        self.total_supply.set(self.total_supply() + amount);
+    }
+
+    /// Pause or resume minting
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused.set(paused);
+    }
+
+    /// Set or clear the maximum total supply
+    pub fn set_supply_cap(&mut self, cap: Option<U256>) {
+        self.supply_cap.set(cap);
+    }
+
+    /// See `reward_token::RewardToken`
+    pub fn is_paused(&self) -> bool {
+        self.paused.get_or_default()
+    }
+
+    /// See `reward_token::RewardToken`
+    pub fn is_minter(&self, account: Address) -> bool {
+        Some(account) == self.minter.get()
+    }
+
+    /// See `reward_token::RewardToken`
+    pub fn mintable_supply(&self) -> U256 {
+        match self.supply_cap.get().flatten() {
+            Some(cap) => cap.saturating_sub(self.total_supply()),
+            None => U256::MAX,
+        }
+    }
+
+    /// Tokens held by `owner`
+    pub fn balance_of(&self, owner: Address) -> U256 {
+        self.balances.get(&owner).unwrap_or_default()
+    }
+
+    /// Tokens in existence
+    pub fn total_supply(&self) -> U256 {
+        self.total_supply.get_or_default()
+    }
+}
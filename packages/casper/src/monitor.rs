@@ -0,0 +1,391 @@
+//! Monitor core: flags attestations that are about to lapse
+//!
+//! Reads the database written by `veil_indexer` and reports every
+//! attestation that is not revoked and either expires within the lookahead
+//! window (warning) or has already expired (critical). Findings can be
+//! POSTed to webhooks as JSON:
+//!
+//! ```text
+//! { "event": "attestation.expiring", "severity": "warning", "checked_at": 1760000000000,
+//!   "expires_in_secs": 3600, "attestation": { "attestation_id": "0x..", ... } }
+//! ```
+//!
+//! Delivered findings are marked in the `monitor_findings` table so each is
+//! sent once; without webhooks nothing is marked and every run reports all.
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::format;
+use std::io::Write;
+use std::prelude::v1::*;
+use std::process::{Command, Stdio};
+
+use rusqlite::params;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::indexer::{IndexDb, IndexerError};
+
+/// Default lookahead window: one day
+pub const DEFAULT_LOOKAHEAD_SECS: u64 = 24 * 60 * 60;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS monitor_findings (
+        id TEXT NOT NULL,
+        kind TEXT NOT NULL,
+        notified_at INTEGER NOT NULL,
+        PRIMARY KEY (id, kind)
+    );
+";
+
+/// Errors while scanning or notifying
+#[derive(Debug, PartialEq)]
+pub enum MonitorError {
+    Db(String),
+    /// `curl` is not installed or not on PATH
+    CurlMissing,
+    /// The webhook could not be reached or answered with an error status
+    Webhook { url: String, message: String },
+}
+
+impl fmt::Display for MonitorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MonitorError::Db(message) => write!(f, "index database: {}", message),
+            MonitorError::CurlMissing => write!(f, "`curl` not found on PATH"),
+            MonitorError::Webhook { url, message } => write!(f, "webhook {}: {}", url, message.trim()),
+        }
+    }
+}
+
+impl std::error::Error for MonitorError {}
+
+impl From<rusqlite::Error> for MonitorError {
+    fn from(e: rusqlite::Error) -> Self {
+        MonitorError::Db(e.to_string())
+    }
+}
+
+impl From<IndexerError> for MonitorError {
+    fn from(e: IndexerError) -> Self {
+        MonitorError::Db(e.to_string())
+    }
+}
+
+/// An indexed attestation, as far as the monitor cares
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AttestationRow {
+    pub attestation_id: String,
+    pub casper_address: String,
+    pub target_chain: String,
+    pub target_address: String,
+    /// Block time in ms
+    pub expires_at: u64,
+    pub revoked: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Warning,
+    Critical,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FindingKind {
+    /// Expires within the lookahead window
+    Expiring,
+    /// Already expired but still active
+    Expired,
+}
+
+impl FindingKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FindingKind::Expiring => "expiring",
+            FindingKind::Expired => "expired",
+        }
+    }
+
+    pub fn severity(self) -> Severity {
+        match self {
+            FindingKind::Expiring => Severity::Warning,
+            FindingKind::Expired => Severity::Critical,
+        }
+    }
+}
+
+/// One attestation that needs attention
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    pub kind: FindingKind,
+    pub attestation: AttestationRow,
+}
+
+impl Finding {
+    /// Seconds until expiry; negative once expired
+    pub fn expires_in_secs(&self, now_ms: u64) -> i64 {
+        (self.attestation.expires_at as i64 - now_ms as i64) / 1000
+    }
+}
+
+/// Findings among `rows` at `now_ms`, soonest expiry first. Revoked
+/// attestations and findings already in `marked` (id, kind) are skipped.
+pub fn select_findings(
+    rows: &[AttestationRow],
+    now_ms: u64,
+    lookahead_secs: u64,
+    marked: &BTreeSet<(String, FindingKind)>,
+) -> Vec<Finding> {
+    let horizon = now_ms.saturating_add(lookahead_secs.saturating_mul(1000));
+    let mut findings: Vec<Finding> = rows
+        .iter()
+        .filter(|row| !row.revoked)
+        .filter_map(|row| {
+            let kind = if row.expires_at <= now_ms {
+                FindingKind::Expired
+            } else if row.expires_at <= horizon {
+                FindingKind::Expiring
+            } else {
+                return None;
+            };
+            Some(Finding { kind, attestation: row.clone() })
+        })
+        .filter(|finding| !marked.contains(&(finding.attestation.attestation_id.clone(), finding.kind)))
+        .collect();
+    findings.sort_by_key(|finding| finding.attestation.expires_at);
+    findings
+}
+
+/// Whether any finding should fail a `--once` run
+pub fn has_critical(findings: &[Finding]) -> bool {
+    findings.iter().any(|finding| finding.kind.severity() == Severity::Critical)
+}
+
+/// JSON body POSTed to webhooks for one finding
+pub fn webhook_payload(finding: &Finding, now_ms: u64) -> Value {
+    json!({
+        "event": format!("attestation.{}", finding.kind.as_str()),
+        "severity": finding.kind.severity(),
+        "checked_at": now_ms,
+        "expires_in_secs": finding.expires_in_secs(now_ms),
+        "attestation": finding.attestation,
+    })
+}
+
+/// POST `payload` to `url` with `curl`
+pub fn post_webhook(url: &str, payload: &Value) -> Result<(), MonitorError> {
+    let webhook_error = |message: String| MonitorError::Webhook { url: url.to_string(), message };
+    let mut child = Command::new("curl")
+        .args(["-sS", "--fail", "--max-time", "10", "-X", "POST"])
+        .args(["-H", "Content-Type: application/json", "--data-binary", "@-", url])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => MonitorError::CurlMissing,
+            _ => webhook_error(e.to_string()),
+        })?;
+
+    let body = payload.to_string();
+    child
+        .stdin
+        .take()
+        .expect("curl stdin is piped")
+        .write_all(body.as_bytes())
+        .map_err(|e| webhook_error(e.to_string()))?;
+    let output = child.wait_with_output().map_err(|e| webhook_error(e.to_string()))?;
+    if !output.status.success() {
+        return Err(webhook_error(String::from_utf8_lossy(&output.stderr).to_string()));
+    }
+    Ok(())
+}
+
+/// Monitor state on top of the indexer database
+pub struct MonitorDb<'a> {
+    db: &'a IndexDb,
+}
+
+impl<'a> MonitorDb<'a> {
+    pub fn new(db: &'a IndexDb) -> Result<Self, MonitorError> {
+        db.connection().execute_batch(SCHEMA)?;
+        Ok(MonitorDb { db })
+    }
+
+    /// Every indexed attestation
+    pub fn attestations(&self) -> Result<Vec<AttestationRow>, MonitorError> {
+        let mut statement = self.db.connection().prepare(
+            "SELECT id, casper_address, target_chain, target_address, expires_at, revoked
+             FROM attestations ORDER BY expires_at",
+        )?;
+        let rows = statement.query_map([], |row| {
+            Ok(AttestationRow {
+                attestation_id: row.get(0)?,
+                casper_address: row.get(1)?,
+                target_chain: row.get(2)?,
+                target_address: row.get(3)?,
+                expires_at: row.get(4)?,
+                revoked: row.get(5)?,
+            })
+        })?;
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
+    }
+
+    /// Findings already delivered
+    pub fn marked(&self) -> Result<BTreeSet<(String, FindingKind)>, MonitorError> {
+        let mut statement = self.db.connection().prepare("SELECT id, kind FROM monitor_findings")?;
+        let rows = statement.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?;
+
+        let mut marked = BTreeSet::new();
+        for row in rows {
+            let (id, kind) = row?;
+            let kind = match kind.as_str() {
+                "expiring" => FindingKind::Expiring,
+                _ => FindingKind::Expired,
+            };
+            marked.insert((id, kind));
+        }
+        Ok(marked)
+    }
+
+    /// Record that `finding` was delivered
+    pub fn mark(&self, finding: &Finding, now_ms: u64) -> Result<(), MonitorError> {
+        self.db.connection().execute(
+            "INSERT OR IGNORE INTO monitor_findings (id, kind, notified_at) VALUES (?1, ?2, ?3)",
+            params![finding.attestation.attestation_id, finding.kind.as_str(), now_ms],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::vec;
+
+    use crate::indexer::IndexedEvent;
+    use crate::types::AttestationCreatedV2;
+
+    const HOUR_MS: u64 = 60 * 60 * 1000;
+    const NOW: u64 = 1_000 * HOUR_MS;
+
+    fn row(id: &str, expires_at: u64, revoked: bool) -> AttestationRow {
+        AttestationRow {
+            attestation_id: id.to_string(),
+            casper_address: "account-hash-01".to_string(),
+            target_chain: "base-sepolia".to_string(),
+            target_address: "0x70997970c51812dc3a010c7d01b50e0d17dc79c8".to_string(),
+            expires_at,
+            revoked,
+        }
+    }
+
+    fn ids(findings: &[Finding]) -> Vec<(&str, FindingKind)> {
+        findings.iter().map(|finding| (finding.attestation.attestation_id.as_str(), finding.kind)).collect()
+    }
+
+    #[test]
+    fn test_select_findings() {
+        let rows = vec![
+            row("0xfar", NOW + 48 * HOUR_MS, false),
+            row("0xsoon", NOW + 2 * HOUR_MS, false),
+            row("0xedge", NOW + 24 * HOUR_MS, false),
+            row("0xexpired", NOW - HOUR_MS, false),
+            row("0xnow", NOW, false),
+            row("0xrevoked", NOW - HOUR_MS, true),
+        ];
+
+        let findings = select_findings(&rows, NOW, DEFAULT_LOOKAHEAD_SECS, &BTreeSet::new());
+        assert_eq!(
+            ids(&findings),
+            vec![
+                ("0xexpired", FindingKind::Expired),
+                ("0xnow", FindingKind::Expired),
+                ("0xsoon", FindingKind::Expiring),
+                ("0xedge", FindingKind::Expiring),
+            ]
+        );
+        assert!(has_critical(&findings));
+
+        // A zero window only reports what has already lapsed
+        assert_eq!(select_findings(&rows, NOW, 0, &BTreeSet::new()).len(), 2);
+
+        // Delivered findings are not reported again, but a new kind is
+        let marked = BTreeSet::from([
+            ("0xexpired".to_string(), FindingKind::Expired),
+            ("0xnow".to_string(), FindingKind::Expired),
+            ("0xsoon".to_string(), FindingKind::Expiring),
+        ]);
+        let findings = select_findings(&rows, NOW, DEFAULT_LOOKAHEAD_SECS, &marked);
+        assert_eq!(ids(&findings), vec![("0xedge", FindingKind::Expiring)]);
+        assert!(!has_critical(&findings));
+        let later = select_findings(&rows, NOW + 3 * HOUR_MS, DEFAULT_LOOKAHEAD_SECS, &marked);
+        assert!(ids(&later).contains(&("0xsoon", FindingKind::Expired)));
+    }
+
+    #[test]
+    fn test_webhook_payload() {
+        let expiring = Finding { kind: FindingKind::Expiring, attestation: row("0xsoon", NOW + 2 * HOUR_MS, false) };
+        assert_eq!(
+            webhook_payload(&expiring, NOW),
+            json!({
+                "event": "attestation.expiring",
+                "severity": "warning",
+                "checked_at": NOW,
+                "expires_in_secs": 7200,
+                "attestation": {
+                    "attestation_id": "0xsoon",
+                    "casper_address": "account-hash-01",
+                    "target_chain": "base-sepolia",
+                    "target_address": "0x70997970c51812dc3a010c7d01b50e0d17dc79c8",
+                    "expires_at": NOW + 2 * HOUR_MS,
+                    "revoked": false
+                }
+            })
+        );
+
+        let expired = Finding { kind: FindingKind::Expired, attestation: row("0xold", NOW - HOUR_MS, false) };
+        let payload = webhook_payload(&expired, NOW);
+        assert_eq!(payload["event"], "attestation.expired");
+        assert_eq!(payload["severity"], "critical");
+        assert_eq!(payload["expires_in_secs"], -3600);
+    }
+
+    #[test]
+    fn test_reads_index_and_marks_findings() {
+        let mut db = IndexDb::open_in_memory().unwrap();
+        let created = AttestationCreatedV2 {
+            id: [0xaa; 32],
+            casper_address: odra::prelude::Address::Account(odra::casper_types::account::AccountHash::new([1; 32])),
+            target_chain: "base-sepolia".to_string(),
+            target_address: "0x70997970c51812dc3a010c7d01b50e0d17dc79c8".to_string(),
+            tier: 1,
+            expires_at: NOW - HOUR_MS,
+            created_at: NOW - 2 * HOUR_MS,
+            stake_motes: 100_000_000_000u64.into(),
+            nonce: 0,
+            payload_hash: [0xaa; 32],
+            schema_version: 1,
+            referrer: None,
+            supersedes: None,
+        };
+        db.apply("hash-01", 0, &IndexedEvent::Created(created)).unwrap();
+
+        let monitor = MonitorDb::new(&db).unwrap();
+        let rows = monitor.attestations().unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].attestation_id, format!("0x{}", "aa".repeat(32)));
+        assert_eq!(rows[0].expires_at, NOW - HOUR_MS);
+
+        let findings = select_findings(&rows, NOW, DEFAULT_LOOKAHEAD_SECS, &monitor.marked().unwrap());
+        assert_eq!(findings.len(), 1);
+        monitor.mark(&findings[0], NOW).unwrap();
+        monitor.mark(&findings[0], NOW).unwrap();
+
+        // Reopening the monitor on the same database keeps the marks
+        let monitor = MonitorDb::new(&db).unwrap();
+        assert!(select_findings(&rows, NOW, DEFAULT_LOOKAHEAD_SECS, &monitor.marked().unwrap()).is_empty());
+    }
+}
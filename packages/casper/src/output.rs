@@ -0,0 +1,404 @@
+//! Machine-readable output for the binaries
+//!
+//! Every binary accepts `--json` (or `VEIL_OUTPUT=json`). In JSON mode the
+//! decorative output is suppressed and a single JSON document is printed on
+//! stdout when the run ends. The document types live here so their shape is
+//! pinned by tests; scripts should parse these instead of the human output.
+
+use std::any::Any;
+use std::fmt;
+use std::format;
+use std::panic::{self, AssertUnwindSafe};
+use std::prelude::v1::*;
+use std::println;
+use std::time::Instant;
+
+use serde::Serialize;
+
+use crate::relayer::RelayOutcome;
+use crate::verify::VerifyReport;
+
+/// Env var selecting the output mode (`json` or `human`)
+pub const OUTPUT_ENV: &str = "VEIL_OUTPUT";
+/// Flag selecting JSON output
+pub const JSON_FLAG: &str = "--json";
+
+/// How a binary reports its results
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputMode {
+    Human,
+    Json,
+}
+
+impl OutputMode {
+    /// JSON if the flag is given or the env var is `json`
+    pub fn resolve(json_flag: bool, env_value: Option<&str>) -> Self {
+        if json_flag || env_value.is_some_and(|value| value.eq_ignore_ascii_case("json")) {
+            OutputMode::Json
+        } else {
+            OutputMode::Human
+        }
+    }
+
+    /// Strip `--json` from `args` and resolve the mode against `VEIL_OUTPUT`
+    pub fn from_args(args: Vec<String>) -> (Self, Vec<String>) {
+        let json_flag = args.iter().any(|arg| arg == JSON_FLAG);
+        let rest = args.into_iter().filter(|arg| arg != JSON_FLAG).collect();
+        (Self::resolve(json_flag, std::env::var(OUTPUT_ENV).ok().as_deref()), rest)
+    }
+
+    pub fn is_json(self) -> bool {
+        self == OutputMode::Json
+    }
+
+    /// Print a line of human-readable output; suppressed in JSON mode
+    pub fn say(self, line: impl fmt::Display) {
+        if !self.is_json() {
+            println!("{}", line);
+        }
+    }
+
+    /// Print the run's JSON document; does nothing in human mode
+    pub fn emit<T: Serialize>(self, document: &T) {
+        if self.is_json() {
+            println!("{}", serde_json::to_string_pretty(document).expect("Output serializes to JSON"));
+        }
+    }
+}
+
+fn hex0x(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+/// Result of deploying VeilAttestation
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DeployOutput {
+    pub contract_hash: String,
+    pub signer_address: String,
+    /// Motes charged to the deployer
+    pub gas_used: String,
+}
+
+/// Result of creating an attestation
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AttestOutput {
+    pub attestation_id: String,
+    pub signature: String,
+    pub expires_at: u64,
+    pub encoded: String,
+}
+
+/// Expected cost of a deploy that was not submitted (`--dry-run`)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CostReport {
+    pub operation: String,
+    pub estimated_gas_motes: String,
+    pub estimated_cost_cspr: String,
+    pub gas_limit_motes: String,
+    pub gas_limit_sufficient: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contract_hash: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StepStatus {
+    Passed,
+    Failed,
+}
+
+/// One step of a scripted test run
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StepOutput {
+    pub step: u32,
+    pub title: String,
+    pub status: StepStatus,
+    pub duration_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Result of a scripted test run (integration or e2e)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RunOutput {
+    pub name: String,
+    pub passed: bool,
+    pub steps: Vec<StepOutput>,
+    /// The attestation the run created, once it exists
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attestation: Option<AttestOutput>,
+}
+
+/// Times the steps of a test run and prints their banners in human mode
+pub struct StepLog {
+    mode: OutputMode,
+    steps: Vec<StepOutput>,
+    current: Option<(u32, String, Instant)>,
+}
+
+impl StepLog {
+    pub fn new(mode: OutputMode) -> Self {
+        StepLog { mode, steps: Vec::new(), current: None }
+    }
+
+    /// Start a step, marking the previous one as passed
+    pub fn start(&mut self, step: u32, title: &str) {
+        self.finish(None);
+        self.mode.say("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        self.mode.say(format!("STEP {}: {}", step, title));
+        self.mode.say("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        self.current = Some((step, title.to_string(), Instant::now()));
+    }
+
+    /// Close the current step, failed if `error` is set
+    pub fn finish(&mut self, error: Option<String>) {
+        if let Some((step, title, started)) = self.current.take() {
+            self.steps.push(StepOutput {
+                step,
+                title,
+                status: if error.is_some() { StepStatus::Failed } else { StepStatus::Passed },
+                duration_ms: started.elapsed().as_millis() as u64,
+                error,
+            });
+        }
+    }
+
+    /// Close the current step and build the run document
+    pub fn into_output(mut self, name: &str, error: Option<String>, attestation: Option<AttestOutput>) -> RunOutput {
+        self.finish(error);
+        let passed = self.steps.iter().all(|step| step.status == StepStatus::Passed);
+        RunOutput { name: name.to_string(), passed, steps: self.steps, attestation }
+    }
+}
+
+/// Run a scripted test, turning a panic into a failure of the current step
+pub fn run_steps<F>(mode: OutputMode, name: &str, body: F) -> RunOutput
+where
+    F: FnOnce(&mut StepLog, &mut Option<AttestOutput>),
+{
+    let mut log = StepLog::new(mode);
+    let mut attestation = None;
+    let result = panic::catch_unwind(AssertUnwindSafe(|| body(&mut log, &mut attestation)));
+    let error = result.err().map(|payload| panic_message(payload.as_ref()));
+    log.into_output(name, error, attestation)
+}
+
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    match (payload.downcast_ref::<&str>(), payload.downcast_ref::<String>()) {
+        (Some(message), _) => message.to_string(),
+        (_, Some(message)) => message.clone(),
+        _ => "panicked".to_string(),
+    }
+}
+
+/// Result of generating test vectors
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VectorsOutput {
+    pub count: usize,
+    pub signer_address: String,
+    pub out: String,
+}
+
+/// One checked attestation
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VerificationOutput {
+    pub attestation_id: String,
+    pub eth_signed_hash: String,
+    pub recovered_signer: Option<String>,
+    pub expected_signer: Option<String>,
+    pub target_chain: Option<String>,
+    pub target_address: Option<String>,
+    pub expires_at: Option<u64>,
+    pub valid: bool,
+    pub problems: Vec<String>,
+}
+
+impl From<&VerifyReport> for VerificationOutput {
+    fn from(report: &VerifyReport) -> Self {
+        VerificationOutput {
+            attestation_id: hex0x(&report.attestation_id),
+            eth_signed_hash: hex0x(&report.eth_signed_hash),
+            recovered_signer: report.recovered_signer.map(|signer| hex0x(&signer)),
+            expected_signer: report.expected_signer.map(|signer| hex0x(&signer)),
+            target_chain: report.payload.as_ref().map(|payload| payload.target_chain.clone()),
+            target_address: report.payload.as_ref().map(|payload| payload.target_address.clone()),
+            expires_at: report.payload.as_ref().map(|payload| payload.expires_at),
+            valid: report.is_valid(),
+            problems: report.problems(),
+        }
+    }
+}
+
+/// Result of checking one or more attestations offline
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VerifyOutput {
+    pub valid: bool,
+    pub attestations: Vec<VerificationOutput>,
+}
+
+/// Result of a relayer run
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RelayerOutput {
+    pub next_event_index: u32,
+    pub outcomes: Vec<RelayOutcome>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::vec;
+    use serde_json::json;
+    use crate::relayer::RelayRecord;
+    use crate::vectors::{generate_vectors, DEFAULT_SEED_KEY};
+    use crate::veil_attestation::TIME_UNIT_MILLIS;
+    use crate::verify::verify_attestation;
+
+    fn attest_output() -> AttestOutput {
+        AttestOutput {
+            attestation_id: "0x01".to_string(),
+            signature: "0x02".to_string(),
+            expires_at: 604_800_000,
+            encoded: "0x03".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_mode_from_flag_and_env() {
+        assert_eq!(OutputMode::resolve(false, None), OutputMode::Human);
+        assert_eq!(OutputMode::resolve(true, None), OutputMode::Json);
+        assert_eq!(OutputMode::resolve(false, Some("JSON")), OutputMode::Json);
+        assert_eq!(OutputMode::resolve(false, Some("human")), OutputMode::Human);
+
+        let (mode, rest) = OutputMode::from_args(vec!["--once".to_string(), "--json".to_string()]);
+        assert_eq!(mode, OutputMode::Json);
+        assert_eq!(rest, vec!["--once".to_string()]);
+    }
+
+    #[test]
+    fn test_deploy_and_attest_shape() {
+        let deploy = DeployOutput {
+            contract_hash: "hash-aa".to_string(),
+            signer_address: "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".to_string(),
+            gas_used: "412000000000".to_string(),
+        };
+        assert_eq!(
+            serde_json::to_value(deploy).unwrap(),
+            json!({
+                "contract_hash": "hash-aa",
+                "signer_address": "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+                "gas_used": "412000000000",
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(attest_output()).unwrap(),
+            json!({ "attestation_id": "0x01", "signature": "0x02", "expires_at": 604_800_000u64, "encoded": "0x03" })
+        );
+    }
+
+    #[test]
+    fn test_run_shape() {
+        let mut log = StepLog::new(OutputMode::Json);
+        log.start(1, "Connecting to Casper Localnet");
+        log.start(2, "Creating Attestation on Casper");
+        let mut output = log.into_output("e2e-basic", Some("EVM transaction failed".to_string()), Some(attest_output()));
+        for step in &mut output.steps {
+            step.duration_ms = 0;
+        }
+
+        assert!(!output.passed);
+        assert_eq!(
+            serde_json::to_value(output).unwrap(),
+            json!({
+                "name": "e2e-basic",
+                "passed": false,
+                "steps": [
+                    { "step": 1, "title": "Connecting to Casper Localnet", "status": "passed", "duration_ms": 0 },
+                    {
+                        "step": 2,
+                        "title": "Creating Attestation on Casper",
+                        "status": "failed",
+                        "duration_ms": 0,
+                        "error": "EVM transaction failed",
+                    },
+                ],
+                "attestation": { "attestation_id": "0x01", "signature": "0x02", "expires_at": 604_800_000u64, "encoded": "0x03" },
+            })
+        );
+    }
+
+    #[test]
+    fn test_run_steps_records_panics() {
+        let output = run_steps(OutputMode::Json, "integration", |log, attestation| {
+            log.start(1, "Getting signer address");
+            *attestation = Some(attest_output());
+            log.start(2, "Creating attestation");
+            panic!("Signature should be 65 bytes");
+        });
+
+        assert!(!output.passed);
+        assert_eq!(output.attestation, Some(attest_output()));
+        assert_eq!(output.steps[0].status, StepStatus::Passed);
+        assert_eq!(output.steps[1].status, StepStatus::Failed);
+        assert_eq!(output.steps[1].error.as_deref(), Some("Signature should be 65 bytes"));
+
+        let output = run_steps(OutputMode::Json, "integration", |log, _| log.start(1, "Getting signer address"));
+        assert!(output.passed);
+        assert_eq!(output.steps.len(), 1);
+    }
+
+    #[test]
+    fn test_verify_shape() {
+        let vector = generate_vectors(&DEFAULT_SEED_KEY).unwrap().vectors.remove(0);
+        let decode = |field: &str| hex::decode(field.trim_start_matches("0x")).unwrap();
+        let report =
+            verify_attestation(&decode(&vector.encoded), &decode(&vector.signature), None, 0, TIME_UNIT_MILLIS);
+
+        assert_eq!(
+            serde_json::to_value(VerificationOutput::from(&report)).unwrap(),
+            json!({
+                "attestation_id": vector.attestation_id,
+                "eth_signed_hash": vector.eth_signed_hash,
+                "recovered_signer": vector.signer_address,
+                "expected_signer": null,
+                "target_chain": vector.target_chain,
+                "target_address": vector.target_address,
+                "expires_at": vector.expires_at,
+                "valid": true,
+                "problems": [],
+            })
+        );
+    }
+
+    #[test]
+    fn test_relayer_shape() {
+        let output = RelayerOutput {
+            next_event_index: 3,
+            outcomes: vec![
+                RelayOutcome::Relayed(RelayRecord {
+                    attestation_id: "0x01".to_string(),
+                    target_chain: "base-sepolia".to_string(),
+                    target_address: "0x70997970c51812dc3a010c7d01b50e0d17dc79c8".to_string(),
+                    tx_hash: "0x02".to_string(),
+                }),
+                RelayOutcome::Skipped { attestation_id: "0x03".to_string(), reason: "Attestation revoked".to_string() },
+            ],
+        };
+
+        assert_eq!(
+            serde_json::to_value(output).unwrap(),
+            json!({
+                "next_event_index": 3,
+                "outcomes": [
+                    {
+                        "outcome": "relayed",
+                        "attestation_id": "0x01",
+                        "target_chain": "base-sepolia",
+                        "target_address": "0x70997970c51812dc3a010c7d01b50e0d17dc79c8",
+                        "tx_hash": "0x02",
+                    },
+                    { "outcome": "skipped", "attestation_id": "0x03", "reason": "Attestation revoked" },
+                ],
+            })
+        );
+    }
+}
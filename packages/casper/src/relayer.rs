@@ -0,0 +1,461 @@
+//! Relayer core: forwards `AttestationCreatedV2` events to EVM verifiers
+//!
+//! The relayer walks the contract's event list from a persisted cursor,
+//! fetches `get_attestation_for_evm` for every new attestation and submits it
+//! to the verifier configured for its `target_chain` via `verifyAndStoreFor`.
+//! The cursor is saved after every event, so a restart never re-submits.
+//!
+//! Config file (TOML or JSON by extension):
+//!
+//! ```text
+//! relayer_key_env = "VEIL_RELAYER_KEY"
+//! state_path = "relayer-state.json"     # optional
+//! poll_interval_secs = 10               # optional
+//!
+//! [chains.base-sepolia]
+//! rpc_url = "https://sepolia.base.org"
+//! verifier = "0x..."
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::format;
+use std::path::{Path, PathBuf};
+use std::prelude::v1::*;
+
+use log::{debug, info, warn};
+use odra::host::HostEnv;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{parse_file, ConfigError};
+use crate::evm::{parse_tx_hash, EvmClient, EvmError};
+use crate::types::AttestationCreatedV2;
+use crate::veil_attestation::VeilAttestationHostRef;
+
+/// Default relayer config file, relative to `packages/casper`
+pub const DEFAULT_RELAYER_CONFIG_PATH: &str = "relayer.toml";
+
+fn default_state_path() -> PathBuf {
+    PathBuf::from("relayer-state.json")
+}
+
+fn default_poll_interval_secs() -> u64 {
+    10
+}
+
+/// EVM endpoint and verifier for one target chain
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ChainRoute {
+    pub rpc_url: String,
+    pub verifier: String,
+}
+
+/// Relayer settings
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RelayerConfig {
+    /// Env var holding the relayer's EVM private key
+    pub relayer_key_env: String,
+    /// Where the cursor and relay records are persisted
+    #[serde(default = "default_state_path")]
+    pub state_path: PathBuf,
+    #[serde(default = "default_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+    /// Routes keyed by attestation `target_chain`
+    #[serde(default)]
+    pub chains: BTreeMap<String, ChainRoute>,
+}
+
+impl RelayerConfig {
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| ConfigError::Read { path: path.to_path_buf(), message: e.to_string() })?;
+        parse_file(path, &contents)
+    }
+}
+
+/// One attestation submitted to an EVM verifier
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RelayRecord {
+    pub attestation_id: String,
+    pub target_chain: String,
+    pub target_address: String,
+    pub tx_hash: String,
+}
+
+/// Persistent relayer state
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RelayerState {
+    /// Index of the next contract event to process
+    pub next_event_index: u32,
+    pub relayed: Vec<RelayRecord>,
+}
+
+impl RelayerState {
+    /// Load the state, starting from scratch if the file does not exist yet
+    pub fn load(path: &Path) -> Result<Self, RelayerError> {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents).map_err(|e| RelayerError::state(path, e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(RelayerError::state(path, e)),
+        }
+    }
+
+    /// Write the state atomically (temp file + rename)
+    pub fn save(&self, path: &Path) -> Result<(), RelayerError> {
+        let contents = serde_json::to_string_pretty(self).map_err(|e| RelayerError::state(path, e))?;
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, contents).map_err(|e| RelayerError::state(path, e))?;
+        std::fs::rename(&tmp, path).map_err(|e| RelayerError::state(path, e))
+    }
+}
+
+/// Errors that stop a poll; the event being processed is retried next poll
+#[derive(Debug, PartialEq)]
+pub enum RelayerError {
+    /// The state file could not be read or written
+    State { path: PathBuf, message: String },
+    /// Submitting to the EVM chain failed
+    Evm(EvmError),
+}
+
+impl RelayerError {
+    fn state(path: &Path, e: impl fmt::Display) -> Self {
+        RelayerError::State { path: path.to_path_buf(), message: e.to_string() }
+    }
+}
+
+impl fmt::Display for RelayerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RelayerError::State { path, message } => {
+                write!(f, "relayer state {}: {}", path.display(), message)
+            }
+            RelayerError::Evm(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for RelayerError {}
+
+impl From<EvmError> for RelayerError {
+    fn from(e: EvmError) -> Self {
+        RelayerError::Evm(e)
+    }
+}
+
+/// What happened to one `AttestationCreatedV2` event
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "outcome", rename_all = "lowercase")]
+pub enum RelayOutcome {
+    Relayed(RelayRecord),
+    Skipped { attestation_id: String, reason: String },
+}
+
+/// Bridges one VeilAttestation contract to the configured EVM verifiers
+pub struct Relayer<'a> {
+    env: &'a HostEnv,
+    contract: &'a VeilAttestationHostRef,
+    config: RelayerConfig,
+    relayer_key: String,
+    state: RelayerState,
+}
+
+impl<'a> Relayer<'a> {
+    /// Create a relayer, resuming from the state file if there is one
+    pub fn new(
+        env: &'a HostEnv,
+        contract: &'a VeilAttestationHostRef,
+        config: RelayerConfig,
+        relayer_key: String,
+    ) -> Result<Self, RelayerError> {
+        let state = RelayerState::load(&config.state_path)?;
+        info!(
+            "relayer starting next_event_index={} relayed={} chains={:?}",
+            state.next_event_index,
+            state.relayed.len(),
+            config.chains.keys().collect::<Vec<_>>()
+        );
+        Ok(Relayer { env, contract, config, relayer_key, state })
+    }
+
+    pub fn config(&self) -> &RelayerConfig {
+        &self.config
+    }
+
+    pub fn state(&self) -> &RelayerState {
+        &self.state
+    }
+
+    /// Process every event emitted since the cursor. On error the cursor stays
+    /// on the failed event so it is retried by the next poll.
+    pub fn poll_once(&mut self) -> Result<Vec<RelayOutcome>, RelayerError> {
+        let count = self.env.events_count(self.contract);
+        let mut outcomes = Vec::new();
+
+        while self.state.next_event_index < count {
+            let index = self.state.next_event_index;
+            match self.env.get_event::<AttestationCreatedV2, _>(self.contract, index as i32) {
+                Ok(event) => {
+                    let outcome = self.relay(event)?;
+                    match &outcome {
+                        RelayOutcome::Relayed(record) => {
+                            info!(
+                                "relayed attestation_id={} chain={} target={} tx_hash={}",
+                                record.attestation_id, record.target_chain, record.target_address, record.tx_hash
+                            );
+                            self.state.relayed.push(record.clone());
+                        }
+                        RelayOutcome::Skipped { attestation_id, reason } => {
+                            warn!("skipped attestation_id={} reason={:?}", attestation_id, reason);
+                        }
+                    }
+                    outcomes.push(outcome);
+                }
+                // Some other event type
+                Err(_) => debug!("ignoring event index={}", index),
+            }
+
+            self.state.next_event_index = index + 1;
+            self.state.save(&self.config.state_path)?;
+        }
+
+        Ok(outcomes)
+    }
+
+    fn relay(&self, event: AttestationCreatedV2) -> Result<RelayOutcome, RelayerError> {
+        let attestation_id = format!("0x{}", hex::encode(event.id));
+        let skip = |reason: String| Ok(RelayOutcome::Skipped { attestation_id: attestation_id.clone(), reason });
+
+        let Some(route) = self.config.chains.get(&event.target_chain) else {
+            return skip(format!("No route configured for chain '{}'", event.target_chain));
+        };
+
+        // Revoked or expired since it was created
+        let (usable, reason) = self
+            .contract
+            .is_attestation_usable_for_chain(event.id, event.target_chain.clone());
+        if !usable {
+            return skip(reason);
+        }
+
+        let Some((encoded, signature)) = self.contract.get_attestation_for_evm(event.id) else {
+            return skip("Attestation not found".to_string());
+        };
+
+        let output = EvmClient::new(route.rpc_url.clone()).verify_and_store_for(
+            &route.verifier,
+            &event.target_address,
+            &encoded,
+            &signature,
+            &self.relayer_key,
+        )?;
+
+        Ok(RelayOutcome::Relayed(RelayRecord {
+            attestation_id,
+            tx_hash: parse_tx_hash(&output)?,
+            target_chain: event.target_chain,
+            target_address: event.target_address,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::vec;
+    use odra::host::Deployer;
+    use crate::cli::signer_init_args;
+    use crate::evm::Anvil;
+    use crate::veil_attestation::{VeilAttestation, DEFAULT_RENEWAL_GRACE_SECS, TIME_UNIT_SECONDS};
+
+    // Anvil account[0]: Casper signer key and verifier deployer
+    const SIGNER_PRIVATE_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    const SIGNER_ADDRESS: &str = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+    // Anvil account[1]: attestation target
+    const USER_ADDRESS: &str = "0x70997970c51812dc3a010c7d01b50e0d17dc79c8";
+    // Anvil account[2]: relayer
+    const RELAYER_PRIVATE_KEY: &str = "0x5de4111afa1a4b94908f83103eb1f1706367c2e68ca870fc3fb9a804cdab365a";
+
+    fn state_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("veil-relayer-{}-{}.json", name, std::process::id()))
+    }
+
+    #[test]
+    fn test_config_defaults_and_routes() {
+        let config: RelayerConfig = parse_file(
+            Path::new("relayer.toml"),
+            r#"
+            relayer_key_env = "VEIL_RELAYER_KEY"
+
+            [chains.base-sepolia]
+            rpc_url = "https://sepolia.base.org"
+            verifier = "0x5FbDB2315678afecb367f032d93F642f64180aa3"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.state_path, default_state_path());
+        assert_eq!(config.poll_interval_secs, 10);
+        assert_eq!(config.chains["base-sepolia"].rpc_url, "https://sepolia.base.org");
+    }
+
+    #[test]
+    fn test_state_round_trips_and_defaults_when_missing() {
+        let path = state_path("round-trip");
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(RelayerState::load(&path), Ok(RelayerState::default()));
+
+        let state = RelayerState {
+            next_event_index: 4,
+            relayed: vec![RelayRecord {
+                attestation_id: "0x01".to_string(),
+                target_chain: "base-sepolia".to_string(),
+                target_address: USER_ADDRESS.to_string(),
+                tx_hash: "0x02".to_string(),
+            }],
+        };
+        state.save(&path).unwrap();
+        assert_eq!(RelayerState::load(&path), Ok(state));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    /// Deploy VeilAttestation with the Anvil signer key, its clock lined up
+    /// with Anvil so attestations are not already expired there
+    fn deploy_contract(env: &HostEnv, evm: &EvmClient) -> VeilAttestationHostRef {
+        let init_args = signer_init_args(env.get_account(0), signer_private_key(), None).unwrap();
+        let contract = VeilAttestation::deploy(env, init_args);
+        env.advance_block_time(evm.block_timestamp().unwrap() * 1000);
+        contract
+    }
+
+    fn signer_private_key() -> [u8; 32] {
+        hex::decode(&SIGNER_PRIVATE_KEY[2..]).unwrap().try_into().unwrap()
+    }
+
+    /// Sign `attestation_id` as the external signer would
+    #[cfg(feature = "no-onchain-signing")]
+    fn sign_externally(contract: &mut VeilAttestationHostRef, attestation_id: [u8; 32]) {
+        contract.submit_signature(
+            attestation_id,
+            crate::encoding::sign_message(&signer_private_key(), &attestation_id).to_vec().into(),
+        );
+    }
+
+    fn relayer_config(anvil: &Anvil, verifier: &str, path: &Path) -> RelayerConfig {
+        let mut chains = BTreeMap::new();
+        chains.insert(
+            "anvil".to_string(),
+            ChainRoute { rpc_url: anvil.rpc_url.clone(), verifier: verifier.to_string() },
+        );
+        RelayerConfig {
+            relayer_key_env: "VEIL_RELAYER_KEY".to_string(),
+            state_path: path.to_path_buf(),
+            poll_interval_secs: 1,
+            chains,
+        }
+    }
+
+    #[test]
+    #[ignore = "requires Foundry (anvil, forge, cast) on PATH"]
+    fn test_full_relay_cycle_against_anvil() {
+        let anvil = Anvil::spawn().unwrap();
+        let evm = anvil.client();
+        let verifier = evm.deploy_verifier(SIGNER_PRIVATE_KEY, SIGNER_ADDRESS, TIME_UNIT_SECONDS).unwrap();
+
+        let env = odra_test::env();
+        let mut contract = deploy_contract(&env, &evm);
+        // The relayer follows `AttestationCreatedV2` alone
+        contract.set_emit_v1_events(false);
+        env.set_caller(env.get_account(1));
+        let (attestation_id, _) = contract.create_attestation(
+            "anvil".to_string(),
+            USER_ADDRESS.to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        // Without on-chain signing, sign as the external signer would
+        #[cfg(feature = "no-onchain-signing")]
+        sign_externally(&mut contract, attestation_id);
+
+        let path = state_path("cycle");
+        let _ = std::fs::remove_file(&path);
+        let config = relayer_config(&anvil, &verifier, &path);
+
+        let mut relayer = Relayer::new(&env, &contract, config.clone(), RELAYER_PRIVATE_KEY.to_string()).unwrap();
+        let outcomes = relayer.poll_once().unwrap();
+
+        assert_eq!(outcomes.len(), 1);
+        let RelayOutcome::Relayed(record) = &outcomes[0] else {
+            panic!("expected a relay, got {:?}", outcomes[0]);
+        };
+        assert_eq!(record.attestation_id, format!("0x{}", hex::encode(attestation_id)));
+        assert!(record.tx_hash.starts_with("0x"));
+        assert!(evm.is_verified(&verifier, USER_ADDRESS).unwrap());
+
+        // Nothing new on the next poll, nor after a restart from the saved cursor
+        assert_eq!(relayer.poll_once().unwrap(), vec![]);
+        let mut restarted = Relayer::new(&env, &contract, config, RELAYER_PRIVATE_KEY.to_string()).unwrap();
+        assert_eq!(restarted.state().relayed.len(), 1);
+        assert_eq!(restarted.poll_once().unwrap(), vec![]);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    #[ignore = "requires Foundry (anvil, forge, cast) on PATH"]
+    fn test_relay_cycle_follows_renewal() {
+        let anvil = Anvil::spawn().unwrap();
+        let evm = anvil.client();
+        let verifier = evm.deploy_verifier(SIGNER_PRIVATE_KEY, SIGNER_ADDRESS, TIME_UNIT_SECONDS).unwrap();
+
+        let env = odra_test::env();
+        let mut contract = deploy_contract(&env, &evm);
+        let (user, renewer) = (env.get_account(1), env.get_account(3));
+        contract.add_relayer(renewer);
+        env.set_caller(user);
+        let (attestation_id, _) = contract.create_attestation(
+            "anvil".to_string(),
+            USER_ADDRESS.to_string(),
+            None,
+            None,
+            Some(true),
+            None,
+            None,
+            None,
+        );
+        contract.set_auto_renew(true);
+        #[cfg(feature = "no-onchain-signing")]
+        sign_externally(&mut contract, attestation_id);
+
+        let path = state_path("renewal");
+        let _ = std::fs::remove_file(&path);
+        let config = relayer_config(&anvil, &verifier, &path);
+        let mut relayer = Relayer::new(&env, &contract, config.clone(), RELAYER_PRIVATE_KEY.to_string()).unwrap();
+        assert_eq!(relayer.poll_once().unwrap().len(), 1);
+
+        // The renewed attestation is announced as a successor and bridged in its place
+        let validity_secs = contract.get_attestation(attestation_id).unwrap().attestation_validity_secs;
+        env.advance_block_time((validity_secs - DEFAULT_RENEWAL_GRACE_SECS) * 1000);
+        env.set_caller(renewer);
+        let (renewed_id, _) = contract.execute_auto_renew(user, attestation_id);
+        #[cfg(feature = "no-onchain-signing")]
+        sign_externally(&mut contract, renewed_id);
+
+        let mut relayer = Relayer::new(&env, &contract, config, RELAYER_PRIVATE_KEY.to_string()).unwrap();
+        let outcomes = relayer.poll_once().unwrap();
+        assert_eq!(outcomes.len(), 1);
+        let RelayOutcome::Relayed(record) = &outcomes[0] else {
+            panic!("expected a relay, got {:?}", outcomes[0]);
+        };
+        assert_eq!(record.attestation_id, format!("0x{}", hex::encode(renewed_id)));
+        let stored = evm.call(&verifier, "userAttestations(address)(bytes32)", &[USER_ADDRESS]).unwrap();
+        assert_eq!(stored.trim(), format!("0x{}", hex::encode(renewed_id)));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,25 @@
+//! CEP-18 token `VeilAttestation` mints attestation rewards on
+//!
+//! The contract calls the token's `mint` entry point, so it must hold the
+//! token's mint rights. Casper cannot catch a revert in a called contract,
+//! so before minting `VeilAttestation` reads the token's pause flag, mint
+//! rights and remaining supply, and skips the reward (emitting
+//! `RewardSkipped`) rather than call a mint that would revert attestation
+//! creation with it.
+
+use odra::casper_types::U256;
+use odra::prelude::*;
+
+/// The mint entry point of a CEP-18 token with minting enabled, and the
+/// views that tell whether a mint would succeed
+#[odra::external_contract]
+pub trait RewardToken {
+    /// Mint `amount` to `owner`
+    fn mint(&mut self, owner: Address, amount: U256);
+    /// Whether minting is paused
+    fn is_paused(&self) -> bool;
+    /// Whether `account` may mint
+    fn is_minter(&self, account: Address) -> bool;
+    /// Tokens that can still be minted before the supply cap (`U256::MAX` if uncapped)
+    fn mintable_supply(&self) -> U256;
+}
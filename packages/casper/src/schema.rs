@@ -0,0 +1,173 @@
+//! Schema generation for the contract modules
+//!
+//! Each registered module gets two schemas, written to
+//! `<out_dir>/<Module>/`: Odra's legacy blueprint (`legacy_schema.json`) and
+//! the Casper contract schema (`casper_contract_schema.json`). Register new
+//! modules in the `schema_modules!` invocation below; `bin/build_schema.rs`
+//! picks them up from there. The localnet mocks are not part of the published
+//! interface and are left out.
+
+use std::format;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::prelude::v1::*;
+
+use serde_json::Value;
+
+use crate::badge::VeilBadgeContractRef;
+use crate::inbound_verifier::VeilInboundVerifierContractRef;
+use crate::staking::VeilStakingContractRef;
+use crate::veil_attestation::VeilAttestationContractRef;
+
+/// Default output directory, relative to `packages/casper`
+pub const DEFAULT_OUT_DIR: &str = "resources";
+/// `ODRA_MODULE` value selecting every registered module
+pub const ALL_MODULES: &str = "all";
+pub const LEGACY_SCHEMA_FILE: &str = "legacy_schema.json";
+pub const CASPER_SCHEMA_FILE: &str = "casper_contract_schema.json";
+
+/// A contract module whose schemas can be generated
+pub struct SchemaModule {
+    pub name: &'static str,
+    legacy: fn() -> Option<String>,
+    casper: fn() -> Option<String>,
+}
+
+impl SchemaModule {
+    pub fn legacy_schema(&self) -> Value {
+        parse_schema(self.name, (self.legacy)())
+    }
+
+    pub fn casper_schema(&self) -> Value {
+        parse_schema(self.name, (self.casper)())
+    }
+
+    /// Both schemas with their file names
+    pub fn schemas(&self) -> [(&'static str, Value); 2] {
+        [(LEGACY_SCHEMA_FILE, self.legacy_schema()), (CASPER_SCHEMA_FILE, self.casper_schema())]
+    }
+
+    /// Write both schemas to `<out_dir>/<name>/`, returning the files written
+    pub fn write(&self, out_dir: &Path) -> io::Result<Vec<PathBuf>> {
+        let dir = out_dir.join(self.name);
+        std::fs::create_dir_all(&dir)?;
+
+        let mut written = Vec::new();
+        for (file_name, schema) in self.schemas() {
+            let path = dir.join(file_name);
+            std::fs::write(&path, to_pretty_json(&schema))?;
+            written.push(path);
+        }
+        Ok(written)
+    }
+}
+
+fn parse_schema(module: &str, json: Option<String>) -> Value {
+    json.and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_else(|| panic!("{} schema does not serialize to JSON", module))
+}
+
+/// Pretty JSON with a trailing newline, as written to disk
+pub fn to_pretty_json(schema: &Value) -> String {
+    serde_json::to_string_pretty(schema).expect("Schema serializes to JSON") + "\n"
+}
+
+macro_rules! schema_modules {
+    ($($name:ident => $contract_ref:ty),* $(,)?) => {
+        /// Every module with a schema, in registration order
+        pub const MODULES: &[SchemaModule] = &[$(
+            SchemaModule {
+                name: stringify!($name),
+                legacy: || <$contract_ref>::schema().as_json(),
+                casper: || <$contract_ref>::casper_contract_schema().as_json(),
+            },
+        )*];
+    };
+}
+
+schema_modules! {
+    VeilAttestation => VeilAttestationContractRef,
+    VeilInboundVerifier => VeilInboundVerifierContractRef,
+    VeilStaking => VeilStakingContractRef,
+    VeilBadge => VeilBadgeContractRef,
+}
+
+/// Modules selected by an `ODRA_MODULE` value: a module name or `all`
+pub fn select_modules(selector: &str) -> Option<Vec<&'static SchemaModule>> {
+    if selector == ALL_MODULES {
+        return Some(MODULES.iter().collect());
+    }
+    MODULES.iter().find(|module| module.name == selector).map(|module| vec![module])
+}
+
+/// Names accepted by [`select_modules`], for error messages
+pub fn module_names() -> String {
+    let mut names: Vec<&str> = MODULES.iter().map(|module| module.name).collect();
+    names.push(ALL_MODULES);
+    names.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Snapshots of the generated schemas, relative to `packages/casper`
+    const SNAPSHOT_DIR: &str = "tests/snapshots/schema";
+    /// Set to `1` to rewrite the snapshots after an intended schema change
+    const UPDATE_ENV: &str = "UPDATE_SCHEMA_SNAPSHOTS";
+
+    #[test]
+    fn test_select_modules() {
+        assert_eq!(select_modules("VeilAttestation").unwrap()[0].name, "VeilAttestation");
+        assert_eq!(select_modules(ALL_MODULES).unwrap().len(), MODULES.len());
+        assert!(select_modules("ChainRegistry").is_none());
+        assert!(select_modules("").is_none());
+        assert!(module_names().ends_with(", all"));
+    }
+
+    #[test]
+    fn test_write_uses_stable_paths() {
+        let out_dir = std::env::temp_dir().join(format!("veil-schema-{}", std::process::id()));
+        let written = MODULES[0].write(&out_dir).unwrap();
+
+        assert_eq!(
+            written,
+            vec![
+                out_dir.join("VeilAttestation").join(LEGACY_SCHEMA_FILE),
+                out_dir.join("VeilAttestation").join(CASPER_SCHEMA_FILE),
+            ]
+        );
+        std::fs::remove_dir_all(&out_dir).unwrap();
+    }
+
+    /// Renamed entrypoints, changed argument types and the like show up as
+    /// snapshot diffs. A missing snapshot fails too; rerun with
+    /// `UPDATE_SCHEMA_SNAPSHOTS=1` to record or accept an intended change.
+    #[test]
+    fn test_schemas_match_snapshots() {
+        let update = std::env::var(UPDATE_ENV).is_ok_and(|value| value == "1");
+
+        for module in MODULES {
+            for (file_name, schema) in module.schemas() {
+                let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(SNAPSHOT_DIR).join(module.name).join(file_name);
+                let generated = to_pretty_json(&schema);
+
+                if update {
+                    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+                    std::fs::write(&path, generated).unwrap();
+                    continue;
+                }
+                let snapshot = std::fs::read_to_string(&path).unwrap_or_else(|e| {
+                    panic!("No snapshot at {} ({}); rerun with {}=1 to record it", path.display(), e, UPDATE_ENV)
+                });
+                assert!(
+                    snapshot == generated,
+                    "{} no longer matches {}; rerun with {}=1 if the change is intended",
+                    file_name,
+                    path.display(),
+                    UPDATE_ENV
+                );
+            }
+        }
+    }
+}
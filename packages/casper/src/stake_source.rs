@@ -0,0 +1,24 @@
+//! Where `VeilAttestation` looks up a user's stake
+//!
+//! Delegated stake comes from the address set with `set_stake_source`, via
+//! `get_delegated_stake`: `MockAuction` in tests and on localnet, or on
+//! livenet a contract exposing the same entry point over the system auction.
+//! CSPR locked with us comes from `VeilStaking`, via `get_locked`. How the
+//! two combine is the contract's `StakePolicy`.
+
+use odra::casper_types::U512;
+use odra::prelude::*;
+
+/// A contract reporting delegated stake per account
+#[odra::external_contract]
+pub trait StakeSource {
+    /// Total stake (motes) `delegator` has delegated to validators
+    fn get_delegated_stake(&self, delegator: Address) -> U512;
+}
+
+/// A contract reporting CSPR locked per account
+#[odra::external_contract]
+pub trait LockedStakeSource {
+    /// Motes `account` currently has locked
+    fn get_locked(&self, account: Address) -> U512;
+}
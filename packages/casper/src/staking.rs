@@ -0,0 +1,195 @@
+//! CSPR locked directly with Veil, as a simpler route to a tier than
+//! delegating. Set as `VeilAttestation`'s locked stake source, it counts
+//! towards tiers according to the attestation contract's `StakePolicy`.
+
+use odra::casper_types::U512;
+use odra::prelude::*;
+use crate::errors::VeilError;
+use crate::types::{StakeLock, StakeLocked, StakeUnlocked};
+
+/// Default time locked CSPR must stay locked before it can be withdrawn (7 days)
+pub const DEFAULT_MIN_LOCK_SECS: u64 = 7 * 24 * 60 * 60;
+
+#[odra::module]
+pub struct VeilStaking {
+    admin: Var<Address>,
+    /// How long after its latest lock an account must wait to unlock, in seconds
+    min_lock_secs: Var<u64>,
+    locks: Mapping<Address, StakeLock>,
+}
+
+#[odra::module]
+impl VeilStaking {
+    /// Initialize the contract (`min_lock_secs` default: `DEFAULT_MIN_LOCK_SECS`)
+    pub fn init(&mut self, admin: Address, min_lock_secs: Option<u64>) {
+        self.admin.set(admin);
+        self.min_lock_secs.set(min_lock_secs.unwrap_or(DEFAULT_MIN_LOCK_SECS));
+    }
+
+    /// Lock the attached CSPR for the caller. Locking more restarts the
+    /// minimum lock period for the whole balance.
+    #[odra(payable)]
+    pub fn lock(&mut self) {
+        let amount = self.env().attached_value();
+        if amount.is_zero() {
+            self.env().revert(VeilError::InvalidLockAmount);
+        }
+
+        let account = self.env().caller();
+        let now = self.env().get_block_time();
+        let total = self.get_locked(account) + amount;
+        self.locks.set(&account, StakeLock { amount: total, locked_at: now });
+
+        self.env().emit_event(StakeLocked { account, amount, total, locked_at: now });
+    }
+
+    /// Withdraw `amount` motes of the caller's locked CSPR once the lock has matured
+    pub fn unlock(&mut self, amount: U512) {
+        let account = self.env().caller();
+        let lock = self.locks.get(&account).unwrap_or_default();
+        if amount.is_zero() {
+            self.env().revert(VeilError::InvalidLockAmount);
+        }
+        if amount > lock.amount {
+            self.env().revert(VeilError::InsufficientLockedStake);
+        }
+        if !self.is_lock_matured(account) {
+            self.env().revert(VeilError::LockNotMatured);
+        }
+
+        let remaining = lock.amount - amount;
+        self.locks.set(&account, StakeLock { amount: remaining, locked_at: lock.locked_at });
+        self.env().transfer_tokens(&account, &amount);
+
+        self.env().emit_event(StakeUnlocked { account, amount, remaining });
+    }
+
+    // ============ ADMIN FUNCTIONS ============
+
+    /// Set the minimum lock period; applies to existing locks too (admin only)
+    pub fn set_min_lock_secs(&mut self, secs: u64) {
+        if Some(self.env().caller()) != self.admin.get() {
+            self.env().revert(VeilError::NotAdmin);
+        }
+        self.min_lock_secs.set(secs);
+    }
+
+    // ============ VIEW FUNCTIONS ============
+
+    /// Motes `account` currently has locked; see `stake_source::LockedStakeSource`
+    pub fn get_locked(&self, account: Address) -> U512 {
+        self.locks.get(&account).unwrap_or_default().amount
+    }
+
+    /// Block time (ms) from which `account` may unlock, or `None` with nothing locked
+    pub fn get_lock_matures_at(&self, account: Address) -> Option<u64> {
+        let lock = self.locks.get(&account).filter(|lock| !lock.amount.is_zero())?;
+        Some(lock.locked_at + self.get_min_lock_secs() * 1000)
+    }
+
+    /// Whether `account` has locked CSPR it may unlock now
+    pub fn is_lock_matured(&self, account: Address) -> bool {
+        self.get_lock_matures_at(account).is_some_and(|matures_at| self.env().get_block_time() >= matures_at)
+    }
+
+    /// Get the minimum lock period in seconds
+    pub fn get_min_lock_secs(&self) -> u64 {
+        self.min_lock_secs.get_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use odra::casper_types::U512;
+    use odra::host::{Deployer, HostEnv};
+    use odra::prelude::{Address, Addressable};
+    use super::{VeilStaking, VeilStakingHostRef, VeilStakingInitArgs};
+    use crate::errors::VeilError;
+    use crate::types::{cspr_to_motes, StakeLocked, StakeUnlocked};
+
+    const DAY_SECS: u64 = 24 * 60 * 60;
+
+    fn setup() -> (HostEnv, VeilStakingHostRef, Address) {
+        let env = odra_test::env();
+        let admin = env.get_account(0);
+        let staking = VeilStaking::deploy(&env, VeilStakingInitArgs { admin, min_lock_secs: Some(DAY_SECS) });
+        let user = env.get_account(1);
+        env.set_caller(user);
+        (env, staking, user)
+    }
+
+    #[test]
+    fn test_lock_records_amount_and_restarts_maturity() {
+        let (env, mut staking, user) = setup();
+        assert_eq!(staking.get_locked(user), U512::zero());
+        assert_eq!(staking.get_lock_matures_at(user), None);
+
+        let amount = cspr_to_motes(100, 0);
+        staking.with_tokens(amount).lock();
+        let locked_at = env.block_time();
+        assert_eq!(staking.get_locked(user), amount);
+        assert_eq!(env.balance_of(&staking.address()), amount);
+        assert_eq!(staking.get_lock_matures_at(user), Some(locked_at + DAY_SECS * 1000));
+        assert!(env.emitted_event(&staking, StakeLocked { account: user, amount, total: amount, locked_at }));
+
+        env.advance_block_time(DAY_SECS * 1000);
+        assert!(staking.is_lock_matured(user));
+
+        // Topping up adds to the balance and restarts the lock period
+        staking.with_tokens(amount).lock();
+        assert_eq!(staking.get_locked(user), amount * 2);
+        assert!(!staking.is_lock_matured(user));
+        assert_eq!(staking.get_lock_matures_at(user), Some(env.block_time() + DAY_SECS * 1000));
+
+        assert_eq!(staking.try_lock(), Err(VeilError::InvalidLockAmount.into()));
+    }
+
+    #[test]
+    fn test_unlock_rejected_before_maturity() {
+        let (env, mut staking, user) = setup();
+        let amount = cspr_to_motes(100, 0);
+        staking.with_tokens(amount).lock();
+
+        env.advance_block_time(DAY_SECS * 1000 - 1);
+        assert_eq!(staking.try_unlock(amount), Err(VeilError::LockNotMatured.into()));
+        assert_eq!(staking.get_locked(user), amount);
+
+        // Another account has nothing to unlock
+        env.set_caller(env.get_account(2));
+        assert_eq!(staking.try_unlock(U512::one()), Err(VeilError::InsufficientLockedStake.into()));
+    }
+
+    #[test]
+    fn test_unlock_after_maturity_returns_cspr() {
+        let (env, mut staking, user) = setup();
+        let amount = cspr_to_motes(100, 0);
+        staking.with_tokens(amount).lock();
+        env.advance_block_time(DAY_SECS * 1000);
+
+        assert_eq!(staking.try_unlock(U512::zero()), Err(VeilError::InvalidLockAmount.into()));
+        assert_eq!(staking.try_unlock(amount + 1), Err(VeilError::InsufficientLockedStake.into()));
+
+        let balance_before = env.balance_of(&user);
+        let part = cspr_to_motes(40, 0);
+        staking.unlock(part);
+        assert_eq!(env.balance_of(&user), balance_before + part);
+        assert_eq!(staking.get_locked(user), amount - part);
+        assert!(env.emitted_event(&staking, StakeUnlocked { account: user, amount: part, remaining: amount - part }));
+
+        staking.unlock(amount - part);
+        assert_eq!(staking.get_locked(user), U512::zero());
+        assert_eq!(staking.get_lock_matures_at(user), None);
+        assert_eq!(env.balance_of(&staking.address()), U512::zero());
+    }
+
+    #[test]
+    fn test_min_lock_secs_admin_only() {
+        let (env, mut staking, _) = setup();
+        assert_eq!(staking.get_min_lock_secs(), DAY_SECS);
+        assert_eq!(staking.try_set_min_lock_secs(0), Err(VeilError::NotAdmin.into()));
+
+        env.set_caller(env.get_account(0));
+        staking.set_min_lock_secs(0);
+        assert_eq!(staking.get_min_lock_secs(), 0);
+    }
+}
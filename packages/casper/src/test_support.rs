@@ -0,0 +1,389 @@
+//! Shared setup for the contract's unit tests
+//!
+//! `Fixture::new()` deploys with the test signer key and no upgrade lock;
+//! the `with_*` methods adjust the deployment before `build()`.
+
+// Without on-chain signing only part of the suite is built
+#![cfg_attr(feature = "no-onchain-signing", allow(dead_code))]
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+use odra::casper_event_standard::EventInstance;
+use odra::casper_types::bytesrepr::{Bytes, FromBytes};
+use odra::casper_types::{U256, U512};
+use odra::host::{Deployer, HostEnv, NoArgs};
+use odra::prelude::{Address, Addressable};
+
+use crate::badge::{VeilBadge, VeilBadgeHostRef, VeilBadgeInitArgs};
+use crate::encoding::{self, keccak256};
+use crate::mock_auction::{MockAuction, MockAuctionHostRef};
+use crate::mock_reward_token::{MockRewardToken, MockRewardTokenHostRef, MockRewardTokenInitArgs};
+use crate::staking::{VeilStaking, VeilStakingHostRef, VeilStakingInitArgs};
+use crate::types::{AttestationCreated, AttestationCreatedV2, StakePolicy};
+use crate::veil_attestation::{VeilAttestation, VeilAttestationHostRef, VeilAttestationInitArgs, SCHEMA_VERSION};
+
+// Test private key (matches EVM tests)
+pub const TEST_PRIVATE_KEY: [u8; 32] = [
+    0xac, 0x09, 0x74, 0xbe, 0xc3, 0x9a, 0x17, 0xe3,
+    0x6b, 0xa4, 0xa6, 0xb4, 0xd2, 0x38, 0xff, 0x94,
+    0x4b, 0xac, 0xb4, 0x78, 0xcb, 0xed, 0x5e, 0xfc,
+    0xae, 0x78, 0x4d, 0x7b, 0xf4, 0xf2, 0xff, 0x80,
+];
+
+// Expected signer address (Ethereum format)
+pub const EXPECTED_SIGNER: [u8; 20] = [
+    0xf3, 0x9F, 0xd6, 0xe5, 0x1a, 0xad, 0x88, 0xF6,
+    0xF4, 0xce, 0x6a, 0xB8, 0x82, 0x72, 0x79, 0xcf,
+    0xff, 0xFb, 0x92, 0x26,
+];
+
+// Second test key (Anvil account[1]) and its Ethereum address
+pub const ROTATED_PRIVATE_KEY: [u8; 32] = [
+    0x59, 0xc6, 0x99, 0x5e, 0x99, 0x8f, 0x97, 0xa5,
+    0xa0, 0x04, 0x49, 0x66, 0xf0, 0x94, 0x53, 0x89,
+    0xdc, 0x9e, 0x86, 0xda, 0xe8, 0x8c, 0x7a, 0x84,
+    0x12, 0xf4, 0x60, 0x3b, 0x6b, 0x78, 0x69, 0x0d,
+];
+pub const ROTATED_SIGNER: [u8; 20] = [
+    0x70, 0x99, 0x79, 0x70, 0xC5, 0x18, 0x12, 0xdc,
+    0x3A, 0x01, 0x0C, 0x7d, 0x01, 0xb5, 0x0e, 0x0d,
+    0x17, 0xdc, 0x79, 0xC8,
+];
+
+/// Anvil/Hardhat default accounts 0-4 as (private key, Ethereum address) hex
+pub const KNOWN_KEYPAIRS: [(&str, &str); 5] = [
+    ("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80", "f39fd6e51aad88f6f4ce6ab8827279cfffb92266"),
+    ("59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d", "70997970c51812dc3a010c7d01b50e0d17dc79c8"),
+    ("5de4111afa1a4b94908f83103eb1f1706367c2e68ca870fc3fb9a804cdab365a", "3c44cdddb6a900fa2b585dd299e03d12fa4293bc"),
+    ("7c852118294e51e653712a81e05800f419141751be58f605c371e15141b007a6", "90f79bf6eb2c4f870365e785982e1f101e93b906"),
+    ("47e179ec197488593b187f80a00eb0da91f1b9d0b13f8733639f19c30a34926a", "15d34aaf54267db7d7c367839aaf71a00a2c6a65"),
+];
+
+/// `KNOWN_KEYPAIRS` as bytes
+pub fn known_keypairs() -> Vec<([u8; 32], [u8; 20])> {
+    KNOWN_KEYPAIRS
+        .iter()
+        .map(|(key, address)| {
+            (hex::decode(key).unwrap().try_into().unwrap(), hex::decode(address).unwrap().try_into().unwrap())
+        })
+        .collect()
+}
+
+/// Target chain of `create_default_attestation`
+pub const DEFAULT_CHAIN: &str = "base-sepolia";
+/// Target address of `create_default_attestation`
+pub const DEFAULT_TARGET: &str = "0x1234567890abcdef1234567890abcdef12345678";
+
+/// Builder for a [`Fixture`]
+#[derive(Default)]
+pub struct FixtureBuilder {
+    signer_key: Option<[u8; 32]>,
+    validity_secs: Option<u64>,
+    initial_lock_secs: Option<u64>,
+    registered_chains: Vec<(String, [u8; 20])>,
+    mock_stakes: Vec<(usize, U512)>,
+    staking: Option<(u64, StakePolicy)>,
+    badges: bool,
+    base_reward: Option<U256>,
+}
+
+impl FixtureBuilder {
+    /// Deploy with `key` as the signer instead of `TEST_PRIVATE_KEY`
+    pub fn with_signer_key(mut self, key: [u8; 32]) -> Self {
+        self.signer_key = Some(key);
+        self
+    }
+
+    /// Request `secs` of validity for every attestation the fixture creates
+    pub fn with_validity(mut self, secs: u64) -> Self {
+        self.validity_secs = Some(secs);
+        self
+    }
+
+    /// Deploy with the signer and tier thresholds locked for `secs`
+    pub fn with_initial_lock(mut self, secs: u64) -> Self {
+        self.initial_lock_secs = Some(secs);
+        self
+    }
+
+    /// Register `verifier` as the trusted EVM verifier for `chain` and list
+    /// the chain in the deployment info
+    pub fn with_registered_chain(mut self, chain: &str, verifier: [u8; 20]) -> Self {
+        self.registered_chains.push((chain.to_string(), verifier));
+        self
+    }
+
+    /// Deploy a `MockAuction` as the stake source, reporting `motes` of
+    /// delegated stake for account `account_index`
+    pub fn with_mock_stake(mut self, account_index: usize, motes: U512) -> Self {
+        self.mock_stakes.push((account_index, motes));
+        self
+    }
+
+    /// Deploy a `VeilStaking` with a `min_lock_secs` lock period as the
+    /// locked stake source, counted under `policy`
+    pub fn with_staking(mut self, min_lock_secs: u64, policy: StakePolicy) -> Self {
+        self.staking = Some((min_lock_secs, policy));
+        self
+    }
+
+    /// Deploy a `VeilBadge` collection, minted on by the contract for every
+    /// new attestation
+    pub fn with_badges(mut self) -> Self {
+        self.badges = true;
+        self
+    }
+
+    /// Deploy a `MockRewardToken` minted on by the contract, rewarding each
+    /// new attestation `base_reward` times its tier's multiplier
+    pub fn with_rewards(mut self, base_reward: U256) -> Self {
+        self.base_reward = Some(base_reward);
+        self
+    }
+
+    pub fn build(self) -> Fixture {
+        let env = odra_test::env();
+        let admin = env.get_account(0);
+        let signer_key = self.signer_key.unwrap_or(TEST_PRIVATE_KEY);
+        let mut contract = VeilAttestation::deploy(
+            &env,
+            VeilAttestationInitArgs {
+                admin,
+                #[cfg(not(feature = "no-onchain-signing"))]
+                signer_private_key: signer_key,
+                #[cfg(feature = "no-onchain-signing")]
+                signer_public_key: encoding::derive_public_key(&signer_key).unwrap(),
+                initial_lock_secs: self.initial_lock_secs,
+                config: None,
+            },
+        );
+
+        if !self.registered_chains.is_empty() {
+            for (chain, verifier) in &self.registered_chains {
+                contract.add_trusted_verifier(chain.clone(), *verifier);
+            }
+            contract.set_supported_chains(self.registered_chains.into_iter().map(|(chain, _)| chain).collect());
+        }
+
+        let auction = (!self.mock_stakes.is_empty()).then(|| {
+            let mut auction = MockAuction::deploy(&env, NoArgs);
+            for (account_index, motes) in self.mock_stakes {
+                auction.set_delegated_stake(env.get_account(account_index), motes);
+            }
+            contract.set_stake_source(Some(auction.address()));
+            auction
+        });
+
+        let staking = self.staking.map(|(min_lock_secs, policy)| {
+            let staking = VeilStaking::deploy(&env, VeilStakingInitArgs { admin, min_lock_secs: Some(min_lock_secs) });
+            contract.set_locked_stake_source(Some(staking.address()));
+            contract.set_stake_policy(policy);
+            staking
+        });
+
+        let badge = self.badges.then(|| {
+            let mut badge = VeilBadge::deploy(&env, VeilBadgeInitArgs { admin });
+            badge.set_minter(Some(contract.address()));
+            contract.set_badge_collection(Some(badge.address()));
+            contract.set_mint_badges(true);
+            badge
+        });
+
+        let reward_token = self.base_reward.map(|base_reward| {
+            let token = MockRewardToken::deploy(&env, MockRewardTokenInitArgs { minter: contract.address() });
+            contract.set_reward_token(Some(token.address()));
+            contract.set_base_reward(base_reward);
+            contract.set_rewards_enabled(true);
+            token
+        });
+
+        Fixture {
+            admin,
+            user: env.get_account(1),
+            other: env.get_account(2),
+            env,
+            contract,
+            auction,
+            staking,
+            badge,
+            reward_token,
+            validity_secs: self.validity_secs,
+            signer_key,
+        }
+    }
+}
+
+/// A deployed contract with named accounts. The admin is the caller until a
+/// helper or test switches it.
+pub struct Fixture {
+    pub env: HostEnv,
+    pub contract: VeilAttestationHostRef,
+    /// Account 0, the contract admin
+    pub admin: Address,
+    /// Account 1, the usual attestation owner
+    pub user: Address,
+    /// Account 2, an unrelated user
+    pub other: Address,
+    /// Stake source, deployed when `with_mock_stake` was used
+    pub auction: Option<MockAuctionHostRef>,
+    /// Locked stake source, deployed when `with_staking` was used
+    pub staking: Option<VeilStakingHostRef>,
+    /// Badge collection, deployed when `with_badges` was used
+    pub badge: Option<VeilBadgeHostRef>,
+    /// Reward token, deployed when `with_rewards` was used
+    pub reward_token: Option<MockRewardTokenHostRef>,
+    validity_secs: Option<u64>,
+    /// Key the contract's signer signs with, on chain or (without on-chain
+    /// signing) through the fixture
+    #[cfg_attr(not(feature = "no-onchain-signing"), allow(dead_code))]
+    signer_key: [u8; 32],
+}
+
+impl Fixture {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new() -> FixtureBuilder {
+        FixtureBuilder::default()
+    }
+
+    /// Create an attestation from `account` for the default chain and target,
+    /// checking the returned signature. Leaves `account` as the caller.
+    pub fn create_default_attestation(&mut self, account: Address) -> ([u8; 32], Bytes) {
+        self.create_attestation(account, DEFAULT_CHAIN, DEFAULT_TARGET)
+    }
+
+    /// Create an attestation from `account`, checking the returned signature
+    /// and the creation events. Without on-chain signing the fixture submits
+    /// the signature as the external signer. Leaves `account` as the caller.
+    pub fn create_attestation(&mut self, account: Address, chain: &str, target: &str) -> ([u8; 32], Bytes) {
+        self.env.set_caller(account);
+        let events_before = self.events_count();
+        let (attestation_id, signature) = self.contract.create_attestation(
+            chain.to_string(),
+            target.to_string(),
+            self.validity_secs,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        self.assert_created_event(events_before, &attestation_id);
+        #[cfg(feature = "no-onchain-signing")]
+        let signature = self.submit_signature(attestation_id);
+        self.assert_signature_valid(&attestation_id, &signature);
+        (attestation_id, signature)
+    }
+
+    /// Sign a freshly created attestation with the signer key and submit the
+    /// signature, as the external signer would
+    #[cfg(feature = "no-onchain-signing")]
+    pub fn submit_signature(&mut self, attestation_id: [u8; 32]) -> Bytes {
+        let signature = Bytes::from(encoding::sign_message(&self.signer_key, &attestation_id).to_vec());
+        self.contract.submit_signature(attestation_id, signature.clone());
+        signature
+    }
+
+    /// Number of events the contract has emitted so far
+    pub fn events_count(&self) -> u32 {
+        self.env.events_count(&self.contract)
+    }
+
+    /// Assert that the contract emitted exactly one event since
+    /// `events_before`, and that it is `expected`
+    pub fn assert_single_event<T>(&self, events_before: u32, expected: T)
+    where
+        T: FromBytes + EventInstance + PartialEq + Debug,
+    {
+        assert_eq!(self.events_count(), events_before + 1, "Expected exactly one new event");
+        assert_eq!(self.event_at::<T>(-1), expected);
+    }
+
+    /// Assert that the contract emitted nothing since `events_before`, as a
+    /// reverted call must not
+    pub fn assert_no_events_since(&self, events_before: u32) {
+        assert_eq!(self.events_count(), events_before, "Unexpected event");
+    }
+
+    /// Assert that the events since `events_before` are exactly those for
+    /// creating the stored `attestation_id`: `AttestationCreated` while v1
+    /// events are on, then `AttestationCreatedV2`
+    pub fn assert_created_event(&self, events_before: u32, attestation_id: &[u8; 32]) {
+        let attestation = self.contract.get_attestation(*attestation_id).expect("Attestation should exist");
+        let payload_hash = self.contract.get_evm_attestation_id(*attestation_id).unwrap();
+        let v1_events = u32::from(self.contract.get_emit_v1_events());
+        assert_eq!(self.events_count(), events_before + 1 + v1_events, "Expected only the creation events");
+
+        if v1_events > 0 {
+            assert_eq!(
+                self.event_at::<AttestationCreated>(-2),
+                AttestationCreated {
+                    id: *attestation_id,
+                    casper_address: attestation.casper_address,
+                    target_chain: attestation.target_chain.clone(),
+                    target_address: attestation.target_address.clone(),
+                    tier: attestation.tier as u8,
+                    expires_at: attestation.expires_at,
+                    nonce: attestation.nonce,
+                    stake_amount: attestation.stake_amount,
+                    created_at: attestation.created_at,
+                    referrer: attestation.referrer,
+                    supersedes: None,
+                }
+            );
+        }
+        assert_eq!(
+            self.event_at::<AttestationCreatedV2>(-1),
+            AttestationCreatedV2 {
+                id: *attestation_id,
+                casper_address: attestation.casper_address,
+                target_chain: attestation.target_chain,
+                target_address: attestation.target_address,
+                tier: attestation.tier as u8,
+                expires_at: attestation.expires_at,
+                created_at: attestation.created_at,
+                stake_motes: attestation.stake_amount,
+                nonce: attestation.nonce,
+                payload_hash,
+                schema_version: SCHEMA_VERSION,
+                referrer: attestation.referrer,
+                supersedes: None,
+            }
+        );
+    }
+
+    /// Event `index` of the contract (negative: from the end)
+    pub fn event_at<T: FromBytes + EventInstance>(&self, index: i32) -> T {
+        self.env.get_event(&self.contract, index).expect("Event should be of the expected type")
+    }
+
+    /// Assert that `signature` over `attestation_id`, and the signature
+    /// returned by `get_attestation_for_evm` over its encoding, both recover
+    /// to the contract's current signer
+    pub fn assert_signature_valid(&self, attestation_id: &[u8; 32], signature: &[u8]) {
+        let signer = self.contract.get_signer_address();
+        assert_eq!(recover_eth_address(attestation_id, signature), signer, "Attestation signed by another key");
+
+        let (encoded, evm_signature) = self.contract.get_attestation_for_evm(*attestation_id)
+            .expect("Attestation should export");
+        assert_eq!(recover_eth_address(&encoding::attestation_id(&encoded).unwrap(), &evm_signature), signer,
+            "EVM export signed by another key");
+    }
+}
+
+/// Recover the Ethereum address that produced `signature` over the
+/// EIP-191 hash of `message`, without going through `encoding`
+pub fn recover_eth_address(message: &[u8; 32], signature: &[u8]) -> [u8; 20] {
+    assert_eq!(signature.len(), 65, "Signature should be r || s || v");
+    let mut prefixed = b"\x19Ethereum Signed Message:\n32".to_vec();
+    prefixed.extend_from_slice(message);
+    let prehash = keccak256(&prefixed);
+
+    let recovery_id = RecoveryId::from_byte(signature[64].wrapping_sub(27)).expect("v should be 27 or 28");
+    let signature = Signature::from_slice(&signature[..64]).expect("r || s should be a valid signature");
+    let key = VerifyingKey::recover_from_prehash(&prehash, &signature, recovery_id)
+        .expect("Signature should recover a public key");
+    let point = key.to_encoded_point(false);
+    keccak256(&point.as_bytes()[1..])[12..].try_into().unwrap()
+}
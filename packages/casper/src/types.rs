@@ -1,6 +1,31 @@
+use alloc::format;
 use alloc::string::String;
+use alloc::vec::Vec;
 use odra::prelude::*;
-use odra::casper_types::U512;
+use odra::casper_types::bytesrepr::Bytes;
+use odra::casper_types::{U256, U512};
+
+/// Number of motes in one CSPR
+pub const MOTES_PER_CSPR: u64 = 1_000_000_000;
+
+/// Format a motes amount as CSPR with 6 decimal places, extended up to 9
+/// when needed so no motes are dropped: `"100000.000000"`, `"0.000000001"`
+pub fn motes_to_cspr_string(motes: U512) -> String {
+    let motes_per_cspr = U512::from(MOTES_PER_CSPR);
+    let whole = motes / motes_per_cspr;
+    let fraction = (motes % motes_per_cspr).as_u64();
+
+    let mut decimals = format!("{:09}", fraction);
+    while decimals.len() > 6 && decimals.ends_with('0') {
+        decimals.pop();
+    }
+    format!("{}.{}", whole, decimals)
+}
+
+/// Convert whole CSPR plus a fractional part given in motes (9 decimal places) to motes
+pub fn cspr_to_motes(cspr_whole: u64, decimals: u32) -> U512 {
+    U512::from(cspr_whole) * U512::from(MOTES_PER_CSPR) + U512::from(decimals)
+}
 
 /// Tier levels based on stake amount
 #[odra::odra_type]
@@ -15,6 +40,310 @@ pub enum Tier {
     Validator = 5,  // Active validator
 }
 
+impl Tier {
+    /// Tier for its `u8` discriminant, as carried in the EVM payload
+    pub fn from_u8(value: u8) -> Option<Tier> {
+        match value {
+            0 => Some(Tier::None),
+            1 => Some(Tier::Bronze),
+            2 => Some(Tier::Silver),
+            3 => Some(Tier::Gold),
+            4 => Some(Tier::Platinum),
+            5 => Some(Tier::Validator),
+            _ => None,
+        }
+    }
+}
+
+/// Which stake counts towards a user's tier
+#[odra::odra_type]
+#[derive(Copy, Default)]
+pub enum StakePolicy {
+    /// Stake delegated to validators, from the stake source
+    #[default]
+    DelegatedOnly = 0,
+    /// Delegated stake plus CSPR locked in `VeilStaking`
+    Combined = 1,
+    /// Only CSPR locked in `VeilStaking`
+    LockedOnly = 2,
+}
+
+/// An account's CSPR locked in `VeilStaking`
+#[odra::odra_type]
+#[derive(Default)]
+pub struct StakeLock {
+    /// Locked motes
+    pub amount: U512,
+    /// Block time (ms) of the latest lock, from which the minimum lock period runs
+    pub locked_at: u64,
+}
+
+/// Where a dispute against an attestation stands
+#[odra::odra_type]
+#[derive(Copy)]
+pub enum DisputeStatus {
+    Open = 0,
+    /// The attestation was revoked and the bond returned
+    Upheld = 1,
+    /// The bond was forfeited to the treasury
+    Rejected = 2,
+}
+
+/// A bonded challenge against an attestation
+#[odra::odra_type]
+pub struct Dispute {
+    pub id: u64,
+    pub attestation_id: [u8; 32],
+    pub challenger: Address,
+    /// Motes bonded by the challenger
+    pub bond: U512,
+    pub reason: String,
+    /// Block time (ms) the dispute was opened
+    pub opened_at: u64,
+    pub status: DisputeStatus,
+}
+
+/// Minimum stake in whole CSPR for each tier
+#[odra::odra_type]
+#[derive(Copy)]
+pub struct TierThresholds {
+    pub bronze: u64,
+    pub silver: u64,
+    pub gold: u64,
+    pub platinum: u64,
+}
+
+impl Default for TierThresholds {
+    fn default() -> Self {
+        Self {
+            bronze: 100,
+            silver: 1_000,
+            gold: 10_000,
+            platinum: 100_000,
+        }
+    }
+}
+
+/// Settings `init` applies at deployment instead of the defaults; `None`
+/// keeps the default
+#[odra::odra_type]
+#[derive(Default)]
+pub struct InitConfig {
+    /// Validity of new attestations; at least the minimum validity
+    pub validity_secs: Option<u64>,
+    /// Must be strictly increasing
+    pub tier_thresholds: Option<TierThresholds>,
+    /// Motes each attestation creation pays to the treasury (default: free)
+    pub attestation_fee: Option<U512>,
+    /// Whether target addresses must be EIP-55 checksummed (default: no)
+    pub require_checksum: Option<bool>,
+    /// Whether new attestations may only target allowlisted chains (default: no)
+    pub allowlist_enabled: Option<bool>,
+    /// Unit of new payloads' timestamps, a `TIME_UNIT_*` (default: seconds)
+    pub time_unit: Option<u8>,
+}
+
+/// Reward multiplier applied to the base reward for each tier
+#[odra::odra_type]
+#[derive(Copy)]
+pub struct RewardMultipliers {
+    pub none: u32,
+    pub bronze: u32,
+    pub silver: u32,
+    pub gold: u32,
+    pub platinum: u32,
+    pub validator: u32,
+}
+
+impl RewardMultipliers {
+    /// Multiplier for `tier`
+    pub fn for_tier(&self, tier: Tier) -> u32 {
+        match tier {
+            Tier::None => self.none,
+            Tier::Bronze => self.bronze,
+            Tier::Silver => self.silver,
+            Tier::Gold => self.gold,
+            Tier::Platinum => self.platinum,
+            Tier::Validator => self.validator,
+        }
+    }
+}
+
+impl Default for RewardMultipliers {
+    fn default() -> Self {
+        Self {
+            none: 0,
+            bronze: 1,
+            silver: 2,
+            gold: 3,
+            platinum: 4,
+            validator: 5,
+        }
+    }
+}
+
+/// A user's attested target addresses, one entry per (chain, address)
+/// pair; entry `i` of each vector describes the same pair
+#[odra::odra_type]
+#[derive(Default)]
+pub struct AttestedAddresses {
+    pub addresses: Vec<String>,
+    pub chains: Vec<String>,
+    /// Whether the pair's reported attestation is neither revoked nor expired
+    pub is_active: Vec<bool>,
+}
+
+/// Everything a dApp usually shows about a user, from one call
+#[odra::odra_type]
+pub struct UserProfile {
+    pub address: Address,
+    /// Current stake from the configured stake sources
+    pub stake_motes: U512,
+    /// Tier the current stake (or a custom tier) gives
+    pub tier: Tier,
+    /// Reputation score, saturated to `u32`
+    pub trust_score: u32,
+    /// Attestations neither revoked, expired, stale nor frozen
+    pub active_attestation_count: u32,
+    /// Chains with an active attestation, in order of first attestation
+    pub verified_chains: Vec<String>,
+    /// Age in whole days of the oldest active attestation; `None` without one
+    pub oldest_attestation_days: Option<u64>,
+    pub has_custom_tier: bool,
+}
+
+/// A user's attestation history, accumulated as it happens; the score is
+/// derived from it with the contract's `ReputationWeights`
+#[odra::odra_type]
+#[derive(Default)]
+pub struct ReputationRecord {
+    /// Validity periods the user's attestations ran through to renewal
+    pub completed_periods: u32,
+    /// Distinct target chains the user has attested to
+    pub distinct_chains: u32,
+    /// Attestations revoked by the admin or arbiter (upheld disputes)
+    pub admin_revocations: u32,
+}
+
+/// Points per `ReputationRecord` entry
+#[odra::odra_type]
+#[derive(Copy)]
+pub struct ReputationWeights {
+    pub per_completed_period: u64,
+    pub per_distinct_chain: u64,
+    /// Deducted per admin revocation; the score does not go below zero
+    pub per_admin_revocation: u64,
+}
+
+impl Default for ReputationWeights {
+    fn default() -> Self {
+        Self {
+            per_completed_period: 10,
+            per_distinct_chain: 5,
+            per_admin_revocation: 50,
+        }
+    }
+}
+
+impl ReputationRecord {
+    /// Score of this history under `weights`
+    pub fn score(&self, weights: &ReputationWeights) -> u64 {
+        let earned = u64::from(self.completed_periods)
+            .saturating_mul(weights.per_completed_period)
+            .saturating_add(u64::from(self.distinct_chains).saturating_mul(weights.per_distinct_chain));
+        earned.saturating_sub(u64::from(self.admin_revocations).saturating_mul(weights.per_admin_revocation))
+    }
+}
+
+/// Signer key usage, for monitoring when to rotate it
+#[odra::odra_type]
+pub struct SignerHealthStatus {
+    pub key_version: u32,
+    pub signer_address: [u8; 20],
+    /// Signatures stored under the current key
+    pub signatures_produced: u64,
+    /// Block time (ms) of the last stored signature; 0 if none yet
+    pub last_signature_at: u64,
+    pub key_age_secs: u64,
+    pub recommend_rotation: bool,
+}
+
+/// Deployment metadata, as recorded in `deployed-addresses.json`
+#[odra::odra_type]
+pub struct DeploymentInfo {
+    /// The contract's own Casper address
+    pub contract_address: String,
+    /// Signer's Ethereum address, `0x`-prefixed lowercase hex
+    pub signer_address: String,
+    pub network_name: String,
+    /// Block time (ms) of the deployment
+    pub deployed_at: u64,
+    pub contract_version: String,
+    pub schema_version: u8,
+    /// Target chains the deployment serves
+    pub supported_chains: Vec<String>,
+}
+
+/// Where a target chain's verifier lives, for frontends and relayers
+#[odra::odra_type]
+pub struct ChainDeployment {
+    pub verifier_address: [u8; 20],
+    /// Explorer URL a transaction hash is appended to, e.g. `https://sepolia.basescan.org/tx/`
+    pub explorer_tx_prefix: String,
+    /// Whether attestations may be created for the chain
+    pub enabled: bool,
+    /// Lowest `Tier` (as `u8`) attestations for the chain may carry
+    pub min_tier: u8,
+    /// `SCHEMA_VERSION` the chain's verifier decodes; 0 when unknown
+    pub schema_version: u8,
+}
+
+/// Admin-defined preset for `create_attestation_from_template`: fixes the
+/// target chain, validity and a tag, and requires a minimum tier
+#[odra::odra_type]
+pub struct Template {
+    /// Assigned on creation, starting at 1
+    pub id: u64,
+    pub target_chain: String,
+    /// Lowest `Tier` (as `u8`) the creator must hold
+    pub min_tier: u8,
+    /// Validity of attestations created from the template, in seconds
+    pub validity_secs: u64,
+    /// Tag attached to attestations created from the template
+    pub tag: String,
+    /// Whether new attestations may be created from the template
+    pub active: bool,
+}
+
+/// Statement a registered claim provider (e.g. a KYC partner) signed about
+/// an attestation, such as "passed KYC level 2"
+#[odra::odra_type]
+pub struct Claim {
+    pub id: u64,
+    pub attestation_id: [u8; 32],
+    /// Ethereum address of the provider's signing key
+    pub provider: [u8; 20],
+    /// Provider-defined kind of statement, e.g. the KYC level
+    pub claim_type: u8,
+    pub claim_data: Bytes,
+    /// Provider's 65-byte EIP-191 signature over `encoding::claim_digest`
+    pub provider_signature: Bytes,
+    /// Block time (ms) the claim was attached
+    pub attached_at: u64,
+}
+
+/// `Attestation::stake_source`: read from the stake sources at creation
+pub const STAKE_SOURCE_LIVE: u8 = 0;
+/// `Attestation::stake_source`: the owner's earlier live reading, reused
+/// within `get_stake_cache_secs`
+pub const STAKE_SOURCE_CACHED: u8 = 1;
+/// `Attestation::stake_source`: reported by a stake oracle; reserved, not
+/// produced yet
+pub const STAKE_SOURCE_ORACLE: u8 = 2;
+/// `Attestation::stake_source`: the owner's tier was set by the admin with
+/// `set_custom_tier`; the stake is still read but did not decide the tier
+pub const STAKE_SOURCE_CUSTOM: u8 = 3;
+
 /// Attestation record stored on Casper
 #[odra::odra_type]
 pub struct Attestation {
@@ -23,12 +352,203 @@ pub struct Attestation {
     pub target_chain: String,
     pub target_address: String,
     pub stake_amount: U512,
+    /// Where `stake_amount` was read from, one of the `STAKE_SOURCE_*` constants. Signed
+    pub stake_source: u8,
+    /// Casper era `stake_amount` was measured in; 0 when unknown. Signed
+    pub stake_epoch: u64,
     pub tier: Tier,
     pub account_age_days: u64,
     pub created_at: u64,
     pub expires_at: u64,
+    /// Validity the attestation was issued with, in seconds
+    pub attestation_validity_secs: u64,
     pub nonce: u64,
     pub revoked: bool,
+    /// Categorization labels; metadata only, never part of the signed payload
+    pub tags: Vec<String>,
+    /// Trusted third party that co-signed the attestation, if any
+    pub witness_address: Option<[u8; 20]>,
+    /// Witness's 65-byte signature over the attestation ID (EIP-191)
+    pub witness_signature: Option<Bytes>,
+    /// Account that referred the owner; metadata only, like `tags`
+    pub referrer: Option<Address>,
+    /// EVM verifier the attestation is intended for; zero if none was
+    /// registered for the target chain at creation
+    pub trusted_verifier: [u8; 20],
+    /// Casper era at creation, for finality checks; 0 when unknown
+    pub casper_era_id: u64,
+    /// Owner's reputation score at creation, see `ReputationRecord`
+    pub reputation_score: u64,
+    /// Registered category of the attestation, e.g. `identity`; signed
+    pub attestation_type: String,
+    /// `Template` the attestation was created from; 0 if none. Signed
+    pub template_id: u64,
+    /// Signed in place of the nonce if set, see `encoding::nonce_commitment`
+    pub nonce_commitment: Option<[u8; 32]>,
+    /// Hash the attestation ID was taken with, see `encoding::hash`. Signed
+    pub hash_algorithm_version: u8,
+    /// Block time (ms) an EVM verifier was confirmed to have consumed the
+    /// attestation, see `confirm_verified`
+    pub verified_on_evm_at: Option<u64>,
+    /// EVM transaction that consumed the attestation
+    pub evm_tx_hash: Option<[u8; 32]>,
+    /// Older than the maximum attestation age when last checked, see
+    /// `mark_stale_attestations`
+    pub stale: bool,
+    /// Suspended by its owner, see `freeze_attestation`; unusable until unfrozen
+    pub frozen: bool,
+    /// Region the holder reported at creation, e.g. "EU"; self-declared, not verified
+    pub region_hint: Option<String>,
+    /// Whether relayers may renew the attestation; can only be cleared
+    pub is_renewable: bool,
+}
+
+/// Summary of an attestation for history lists, kept in step with the full
+/// record by the contract
+#[odra::odra_type]
+pub struct AttestationListItem {
+    pub id: [u8; 32],
+    pub target_chain: String,
+    pub tier: Tier,
+    pub expires_at: u64,
+    pub revoked: bool,
+    /// Block time (ms) the current signature was produced; 0 if unknown.
+    /// Filled in when the list is read, not stored.
+    pub signature_produced_at: u64,
+}
+
+impl From<&Attestation> for AttestationListItem {
+    fn from(attestation: &Attestation) -> Self {
+        Self {
+            id: attestation.id,
+            target_chain: attestation.target_chain.clone(),
+            tier: attestation.tier,
+            expires_at: attestation.expires_at,
+            revoked: attestation.revoked,
+            signature_produced_at: 0,
+        }
+    }
+}
+
+/// Fixed-size attestation record used when compressed storage is enabled.
+/// The target chain and attestation type are kept by hash (their names are
+/// stored once) and the target address as raw bytes, rendered back as
+/// lowercase hex.
+#[odra::odra_type]
+pub struct CompressedAttestation {
+    pub id: [u8; 32],
+    pub casper_address: Address,
+    pub target_chain_hash: [u8; 32],
+    pub target_address: [u8; 20],
+    /// Low 64 bits of the stake in motes
+    pub stake_motes_lo: u64,
+    /// Bits 64..128 of the stake in motes
+    pub stake_motes_hi: u64,
+    /// Tier in the low 4 bits, `REVOKED_FLAG` and `NON_RENEWABLE_FLAG` in the top bits
+    pub tier_and_flags: u8,
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub nonce: u64,
+    pub trusted_verifier: [u8; 20],
+    pub casper_era_id: u64,
+    pub reputation_score: u64,
+    pub attestation_type_hash: [u8; 32],
+    pub template_id: u64,
+    pub hash_algorithm_version: u8,
+}
+
+/// `tier_and_flags` bit marking a revoked compressed attestation
+pub const REVOKED_FLAG: u8 = 0x80;
+
+/// `tier_and_flags` bit marking a compressed attestation that may not be renewed
+pub const NON_RENEWABLE_FLAG: u8 = 0x40;
+
+/// Parse a `0x`-prefixed 20-byte hex address
+pub fn parse_evm_address(address: &str) -> Option<[u8; 20]> {
+    let hex_digits = address.strip_prefix("0x")?;
+    hex::decode(hex_digits).ok()?.try_into().ok()
+}
+
+/// Compress an attestation, or `None` if its target address is not valid hex.
+/// Account age is not kept (always 0 for now); the validity is recomputed
+/// from the timestamps on decompression.
+pub fn compress_attestation(
+    attestation: &Attestation,
+    target_chain_hash: [u8; 32],
+    attestation_type_hash: [u8; 32],
+) -> Option<CompressedAttestation> {
+    let stake = attestation.stake_amount;
+    let mut tier_and_flags = attestation.tier as u8;
+    if attestation.revoked {
+        tier_and_flags |= REVOKED_FLAG;
+    }
+    if !attestation.is_renewable {
+        tier_and_flags |= NON_RENEWABLE_FLAG;
+    }
+
+    Some(CompressedAttestation {
+        id: attestation.id,
+        casper_address: attestation.casper_address,
+        target_chain_hash,
+        target_address: parse_evm_address(&attestation.target_address)?,
+        stake_motes_lo: stake.low_u64(),
+        stake_motes_hi: (stake >> 64).low_u64(),
+        tier_and_flags,
+        created_at: attestation.created_at,
+        expires_at: attestation.expires_at,
+        nonce: attestation.nonce,
+        trusted_verifier: attestation.trusted_verifier,
+        casper_era_id: attestation.casper_era_id,
+        reputation_score: attestation.reputation_score,
+        attestation_type_hash,
+        template_id: attestation.template_id,
+        hash_algorithm_version: attestation.hash_algorithm_version,
+    })
+}
+
+/// Rebuild the full record from a compressed one and its looked-up strings.
+/// Tags, witnesses, referrers, nonce commitments, EVM confirmations, stale
+/// and frozen flags, region hints and stake origins are stored alongside
+/// compressed records, so come back empty (or live, era 0) here.
+pub fn decompress_attestation(
+    ca: &CompressedAttestation,
+    chain: &str,
+    address: &str,
+    attestation_type: &str,
+) -> Attestation {
+    Attestation {
+        id: ca.id,
+        casper_address: ca.casper_address,
+        target_chain: chain.to_string(),
+        target_address: address.to_string(),
+        stake_amount: (U512::from(ca.stake_motes_hi) << 64) | U512::from(ca.stake_motes_lo),
+        stake_source: STAKE_SOURCE_LIVE,
+        stake_epoch: 0,
+        tier: Tier::from_u8(ca.tier_and_flags & 0x0f).unwrap_or_default(),
+        account_age_days: 0,
+        created_at: ca.created_at,
+        expires_at: ca.expires_at,
+        attestation_validity_secs: (ca.expires_at - ca.created_at) / 1000,
+        nonce: ca.nonce,
+        revoked: ca.tier_and_flags & REVOKED_FLAG != 0,
+        tags: Vec::new(),
+        witness_address: None,
+        witness_signature: None,
+        referrer: None,
+        trusted_verifier: ca.trusted_verifier,
+        casper_era_id: ca.casper_era_id,
+        reputation_score: ca.reputation_score,
+        attestation_type: attestation_type.to_string(),
+        template_id: ca.template_id,
+        nonce_commitment: None,
+        hash_algorithm_version: ca.hash_algorithm_version,
+        verified_on_evm_at: None,
+        evm_tx_hash: None,
+        stale: false,
+        frozen: false,
+        region_hint: None,
+        is_renewable: ca.tier_and_flags & NON_RENEWABLE_FLAG == 0,
+    }
 }
 
 /// Payload that gets signed and sent to EVM
@@ -43,9 +563,89 @@ pub struct AttestationPayload {
     pub created_at: u64,
     pub expires_at: u64,
     pub nonce: u64,
+    /// Intended EVM verifier (`bytes20`), zero when any verifier may accept it
+    pub trusted_verifier: [u8; 20],
+    /// Casper era at creation; 0 when unknown
+    pub casper_era_id: u64,
+    /// Owner's reputation score at creation
+    pub reputation_score: u64,
+    /// Registered category of the attestation
+    pub attestation_type: String,
+    /// `Template` the attestation was created from; 0 if none
+    pub template_id: u64,
+    /// `encoding::nonce_commitment` of the nonce, which is then zero; zero if
+    /// the nonce is in the clear
+    pub nonce_commitment: [u8; 32],
+    /// `encoding::HASH_ALGORITHM_*` the attestation ID is taken with
+    pub hash_algorithm_version: u8,
+    /// `encoding::fold_claims_hash` over the attached claims in order; zero
+    /// when there are none, as in the payload the ID is taken from
+    pub claims_hash: [u8; 32],
+    /// Attestation's region hint; empty if none
+    pub region_hint: String,
+    /// `STAKE_SOURCE_*` the stake was read from
+    pub stake_source: u8,
+    /// Casper era the stake was measured in; 0 when unknown
+    pub stake_epoch: u64,
+}
+
+/// Everything needed to reproduce an EVM-side signature recovery off-chain
+#[odra::odra_type]
+pub struct AttestationExport {
+    /// ABI-encoded payload, as submitted to VeilVerifier
+    pub encoded: Bytes,
+    /// r || s || v over `signed_digest`
+    pub signature: Bytes,
+    pub attestation_id: [u8; 32],
+    /// EIP-191 prefixed hash of the encoding, the digest actually signed
+    pub signed_digest: [u8; 32],
+    /// Signer's Ethereum address the signature recovers to
+    pub signer_address: [u8; 20],
+    /// `SCHEMA_VERSION` of the encoding
+    pub schema_version: u8,
+}
+
+/// What `create_attestation` would produce for a user right now, see
+/// `preview_attestation`
+#[odra::odra_type]
+pub struct AttestationPreview {
+    pub attestation_id: [u8; 32],
+    /// ABI-encoded payload the ID is taken from
+    pub encoded: Bytes,
+    pub tier: Tier,
+    pub stake_amount: U512,
+    pub expires_at: u64,
+    /// Whether this is an existing attestation the reuse window hands back
+    pub reused: bool,
+    /// Motes creation has to attach
+    pub fee: U512,
+    /// Leading zero bits the proof of work needs; 0 when none is required
+    pub pow_difficulty: u8,
 }
 
-/// Event emitted when attestation is created
+/// Payload an EVM-side signer issues to link an EVM address to a Casper
+/// account, ABI-encoded as `(address, bytes32, uint64, uint64)`
+#[odra::odra_type]
+pub struct EvmLinkPayload {
+    pub evm_address: [u8; 20],
+    /// Account hash of the Casper account being linked
+    pub casper_account_hash: [u8; 32],
+    /// Block time (ms) after which the link no longer verifies
+    pub expires_at: u64,
+    pub nonce: u64,
+}
+
+/// A Casper account's verified EVM identity
+#[odra::odra_type]
+pub struct EvmLink {
+    pub evm_address: [u8; 20],
+    pub expires_at: u64,
+    /// `keccak256` of the submitted encoding
+    pub attestation_id: [u8; 32],
+}
+
+/// Event emitted when attestation is created. Deprecated in favour of
+/// `AttestationCreatedV2`; only emitted while `emit_v1_events` is set.
 #[odra::event]
 pub struct AttestationCreated {
     pub id: [u8; 32],
@@ -54,6 +654,57 @@ pub struct AttestationCreated {
     pub target_address: String,
     pub tier: u8,
     pub expires_at: u64,
+    pub nonce: u64,
+    /// Stake snapshot (motes) the tier was derived from
+    pub stake_amount: U512,
+    /// Block time (ms) of creation
+    pub created_at: u64,
+    /// Account that referred the owner, if any
+    pub referrer: Option<Address>,
+    /// Attestation this one replaced by renewal, extension or refresh; `None` if it is new
+    pub supersedes: Option<[u8; 32]>,
+}
+
+impl From<&Attestation> for AttestationCreated {
+    /// The event as emitted when `attestation` was created, if it replaced nothing
+    fn from(attestation: &Attestation) -> Self {
+        Self {
+            id: attestation.id,
+            casper_address: attestation.casper_address,
+            target_chain: attestation.target_chain.clone(),
+            target_address: attestation.target_address.clone(),
+            tier: attestation.tier as u8,
+            expires_at: attestation.expires_at,
+            nonce: attestation.nonce,
+            stake_amount: attestation.stake_amount,
+            created_at: attestation.created_at,
+            referrer: attestation.referrer,
+            supersedes: None,
+        }
+    }
+}
+
+/// Event emitted when attestation is created, with what indexers need to
+/// match it on EVM chains without re-encoding the payload
+#[odra::event]
+pub struct AttestationCreatedV2 {
+    pub id: [u8; 32],
+    pub casper_address: Address,
+    pub target_chain: String,
+    pub target_address: String,
+    pub tier: u8,
+    pub expires_at: u64,
+    /// Block time (ms) of creation
+    pub created_at: u64,
+    pub stake_motes: U512,
+    pub nonce: u64,
+    /// Hash of the ABI-encoded payload under the attestation's hash
+    /// algorithm (Keccak256 or SHA3-256), i.e. exactly what was signed
+    pub payload_hash: [u8; 32],
+    pub schema_version: u8,
+    pub referrer: Option<Address>,
+    /// Attestation this one replaced by renewal, extension or refresh; `None` if it is new
+    pub supersedes: Option<[u8; 32]>,
 }
 
 /// Event emitted when attestation is revoked
@@ -62,3 +713,389 @@ pub struct AttestationRevoked {
     pub id: [u8; 32],
     pub casper_address: Address,
 }
+
+/// Event emitted when an attestation is found older than the maximum attestation age
+#[odra::event]
+pub struct AttestationMarkedStale {
+    pub id: [u8; 32],
+}
+
+/// Event emitted when an attestation's owner suspends it
+#[odra::event]
+pub struct AttestationFrozen {
+    pub id: [u8; 32],
+}
+
+/// Event emitted when an attestation's owner lifts a suspension
+#[odra::event]
+pub struct AttestationUnfrozen {
+    pub id: [u8; 32],
+}
+
+/// Event emitted when an attestation's owner makes it non-renewable
+#[odra::event]
+pub struct AttestationRenewabilityChanged {
+    pub id: [u8; 32],
+    pub is_renewable: bool,
+}
+
+/// Event emitted when an attestation badge is minted
+#[odra::event]
+pub struct BadgeMinted {
+    /// The attestation ID
+    pub token_id: [u8; 32],
+    pub owner: Address,
+}
+
+/// Event emitted when an attestation badge is burned
+#[odra::event]
+pub struct BadgeBurned {
+    pub token_id: [u8; 32],
+    pub owner: Address,
+}
+
+/// Event emitted when an attestation's owner is minted a reward
+#[odra::event]
+pub struct RewardMinted {
+    pub attestation_id: [u8; 32],
+    pub recipient: Address,
+    pub amount: U256,
+}
+
+/// Event emitted when an attestation's reward is not minted
+#[odra::event]
+pub struct RewardSkipped {
+    pub attestation_id: [u8; 32],
+    pub recipient: Address,
+    pub amount: U256,
+    pub reason: String,
+}
+
+/// Event emitted when the admin registers a new attestation type
+#[odra::event]
+pub struct AttestationTypeRegistered {
+    pub attestation_type: String,
+    pub registered_by: Address,
+}
+
+/// Event emitted when a template is created, changed, or (de)activated
+#[odra::event]
+pub struct TemplateUpdated {
+    pub template_id: u64,
+    pub target_chain: String,
+    pub min_tier: u8,
+    pub validity_secs: u64,
+    pub tag: String,
+    pub active: bool,
+}
+
+/// Event emitted when the admin switches the hash new attestation IDs are taken with
+#[odra::event]
+pub struct HashAlgorithmChanged {
+    pub old_algorithm: u8,
+    pub new_algorithm: u8,
+}
+
+/// Event emitted when the admin changes how long new attestations are valid
+#[odra::event]
+pub struct ValidityPeriodChanged {
+    pub old_secs: u64,
+    pub new_secs: u64,
+    pub by: Address,
+}
+
+/// Event emitted when the admin updates the tier thresholds
+#[odra::event]
+pub struct TierThresholdsChanged {
+    pub old_thresholds: TierThresholds,
+    pub new_thresholds: TierThresholds,
+    pub by: Address,
+}
+
+/// Event emitted when the external signer's signature of an attestation is
+/// stored (`no-onchain-signing` builds)
+#[odra::event]
+pub struct AttestationSigned {
+    pub id: [u8; 32],
+    /// Version of the key that signed
+    pub key_version: u32,
+}
+
+/// Event emitted when the signing key is replaced, by the admin or in an emergency
+#[odra::event]
+pub struct SignerRotated {
+    pub old_signer: [u8; 20],
+    pub new_signer: [u8; 20],
+    /// Version of the new key
+    pub key_version: u32,
+    pub by: Address,
+}
+
+/// Event emitted when the admin hands the role to another account
+#[odra::event]
+pub struct AdminTransferred {
+    /// The admin that made the transfer
+    pub old_admin: Address,
+    pub new_admin: Address,
+}
+
+/// Event emitted when the admin changes a chain's per-user attestation limit
+#[odra::event]
+pub struct ChainConfigChanged {
+    pub chain: String,
+    pub old_attestation_limit: u32,
+    pub new_attestation_limit: u32,
+    pub by: Address,
+}
+
+/// Event emitted when the admin changes a setting that has no dedicated
+/// event. Values are rendered as text: numbers in decimal, flags as
+/// `true`/`false`, EVM addresses as `0x` hex and unset options as `none`.
+#[odra::event]
+pub struct ConfigChanged {
+    /// Setting that changed, e.g. `require_checksum`; settings held per
+    /// account, chain or key append it, e.g. `relayer:<address>`
+    pub key: String,
+    pub old_value: String,
+    pub new_value: String,
+    pub by: Address,
+}
+
+/// Event emitted when the admin switches the unit of new payloads' timestamps
+#[odra::event]
+pub struct TimeUnitChanged {
+    pub old_unit: u8,
+    pub new_unit: u8,
+    pub by: Address,
+}
+
+/// Event emitted when the admin sets or clears a user's custom tier
+#[odra::event]
+pub struct CustomTierSet {
+    pub user: Address,
+    pub tier: Option<Tier>,
+    pub set_by: Address,
+}
+
+/// Event emitted when the admin re-signs a batch of attestations under the
+/// current signer
+#[odra::event]
+pub struct SignaturesBatchRefreshed {
+    pub count: u32,
+    pub key_version: u32,
+}
+
+/// Event emitted when a dispute is opened against an attestation
+#[odra::event]
+pub struct DisputeOpened {
+    pub dispute_id: u64,
+    pub attestation_id: [u8; 32],
+    pub challenger: Address,
+    pub bond: U512,
+    pub reason: String,
+}
+
+/// Event emitted when a dispute is resolved
+#[odra::event]
+pub struct DisputeResolved {
+    pub dispute_id: u64,
+    pub attestation_id: [u8; 32],
+    pub upheld: bool,
+    /// Where the bond went: the challenger if upheld, the treasury otherwise
+    pub bond_recipient: Address,
+}
+
+/// Event emitted when a chain is proposed for the supported chains
+#[odra::event]
+pub struct ChainProposed {
+    pub chain: String,
+    /// Block time (ms) from which the chain can be confirmed
+    pub activates_at: u64,
+}
+
+/// Event emitted when a proposed chain is confirmed and becomes supported
+#[odra::event]
+pub struct ChainActivated {
+    pub chain: String,
+}
+
+/// Event emitted when a chain is added to or removed from the allowlist
+#[odra::event]
+pub struct ChainAllowlistUpdated {
+    pub chain: String,
+    pub allowed: bool,
+}
+
+/// Event emitted when a region is added to or removed from the region allowlist
+#[odra::event]
+pub struct RegionAllowlistUpdated {
+    pub region: String,
+    pub allowed: bool,
+}
+
+/// Event emitted when `migrate_v1_to_v2` upgrades the storage layout
+#[odra::event]
+pub struct ContractMigrated {
+    pub from_version: u32,
+    pub to_version: u32,
+}
+
+/// Event emitted when a chain proposal is withdrawn
+#[odra::event]
+pub struct ChainProposalCancelled {
+    pub chain: String,
+}
+
+/// Event emitted when a chain's deployment metadata is set
+#[odra::event]
+pub struct ChainDeploymentUpdated {
+    pub chain: String,
+    pub verifier_address: [u8; 20],
+    pub enabled: bool,
+    pub min_tier: u8,
+}
+
+/// Event emitted when a chain's deployment metadata is removed
+#[odra::event]
+pub struct ChainDeploymentRemoved {
+    pub chain: String,
+}
+
+/// Event emitted when the confirmation signer reports an attestation consumed on EVM
+#[odra::event]
+pub struct AttestationConfirmed {
+    pub attestation_id: [u8; 32],
+    pub evm_tx_hash: [u8; 32],
+    /// Block time (ms) the confirmation was recorded
+    pub verified_on_evm_at: u64,
+}
+
+/// Event emitted when a trusted witness co-signs an attestation
+#[odra::event]
+pub struct WitnessAdded {
+    pub attestation_id: [u8; 32],
+    pub witness: [u8; 20],
+}
+
+/// Event emitted when the admin registers or removes a claim provider
+#[odra::event]
+pub struct ClaimProviderUpdated {
+    pub provider: [u8; 20],
+    pub registered: bool,
+}
+
+/// Event emitted when a provider claim is attached to an attestation
+#[odra::event]
+pub struct ClaimAttached {
+    pub claim_id: u64,
+    pub attestation_id: [u8; 32],
+    pub provider: [u8; 20],
+    pub claim_type: u8,
+    /// Claims hash now signed in the attestation's EVM payload
+    pub claims_hash: [u8; 32],
+}
+
+/// Event emitted when a revocation is requested and waits for the revocation delay
+#[odra::event]
+pub struct RevocationPending {
+    pub id: [u8; 32],
+    /// Block time (ms) from which the revocation can be finalized
+    pub effective_at: u64,
+}
+
+/// `AttestationSuperseded::reason`: replaced by a renewal
+pub const SUPERSEDED_BY_RENEWAL: u8 = 0;
+/// `AttestationSuperseded::reason`: replaced by an extension
+pub const SUPERSEDED_BY_EXTENSION: u8 = 1;
+/// `AttestationSuperseded::reason`: replaced by a refresh
+pub const SUPERSEDED_BY_REFRESH: u8 = 2;
+
+/// Event emitted when an attestation is revoked and reissued in its place;
+/// follow `new_id` (or `get_successor`) to the current attestation
+#[odra::event]
+pub struct AttestationSuperseded {
+    pub old_id: [u8; 32],
+    pub new_id: [u8; 32],
+    /// One of the `SUPERSEDED_BY_*` constants
+    pub reason: u8,
+}
+
+/// Event emitted when an attestation is renewed for another full validity period
+#[odra::event]
+pub struct AttestationRenewed {
+    pub old_id: [u8; 32],
+    pub new_id: [u8; 32],
+    pub casper_address: Address,
+}
+
+/// Event emitted when an attestation's expiry is pushed out. The ID commits
+/// to the expiry, so the extended attestation has a new one.
+#[odra::event]
+pub struct AttestationExtended {
+    pub id: [u8; 32],
+    pub new_id: [u8; 32],
+    pub new_expires_at: u64,
+}
+
+/// Event emitted when a relayer renews an attestation on its owner's behalf
+#[odra::event]
+pub struct AutoRenewExecuted {
+    pub user: Address,
+    /// The renewed attestation, now revoked
+    pub attestation_id: [u8; 32],
+    /// The attestation issued in its place
+    pub renewed_id: [u8; 32],
+    pub renewed_by: Address,
+}
+
+/// Event emitted when an EVM-originated attestation links an EVM address to a Casper account
+#[odra::event]
+pub struct EvmIdentityLinked {
+    pub evm_address: [u8; 20],
+    pub casper_account: Address,
+    pub expires_at: u64,
+}
+
+/// Event emitted when CSPR is locked in `VeilStaking`
+#[odra::event]
+pub struct StakeLocked {
+    pub account: Address,
+    pub amount: U512,
+    /// The account's locked motes after this lock
+    pub total: U512,
+    pub locked_at: u64,
+}
+
+/// Event emitted when locked CSPR is withdrawn from `VeilStaking`
+#[odra::event]
+pub struct StakeUnlocked {
+    pub account: Address,
+    pub amount: U512,
+    pub remaining: U512,
+}
+
+/// Event emitted when a derived attestation ID was already taken and the nonce was bumped
+#[odra::event]
+pub struct AttestationIdRetried {
+    pub user: Address,
+    pub attempt: u8,
+}
+
+/// Event emitted when the admin contact metadata changes
+#[odra::event]
+pub struct AdminContactUpdated {
+    pub new_contact_hash: [u8; 32],
+}
+
+/// Event emitted when the emergency admin is set or cleared
+#[odra::event]
+pub struct EmergencyAdminSet {
+    pub address: Option<Address>,
+}
+
+/// Event emitted when an emergency function is used
+#[odra::event]
+pub struct EmergencyActionTaken {
+    pub action: String,
+    pub by: Address,
+}
@@ -0,0 +1,234 @@
+//! Attestation test vectors for the EVM verifier's Foundry tests
+//!
+//! Vectors are built with the same encoder and signer as the contract
+//! (`crate::encoding`), so the Solidity tests exercise exactly the bytes
+//! Casper produces. Regenerate the fixture with the `veil_vectors` binary.
+
+use std::format;
+use std::prelude::v1::*;
+
+use odra::casper_types::U512;
+use serde::Serialize;
+
+use crate::encoding::{
+    abi_encode_payload, derive_public_key, eth_signed_message_hash, keccak256, public_key_to_address,
+    sign_message, HASH_ALGORITHM_KECCAK256,
+};
+use crate::types::{AttestationPayload, STAKE_SOURCE_LIVE};
+
+/// Fixture consumed by `packages/evm/test/VeilVerifierVectors.t.sol`, relative to `packages/casper`
+pub const DEFAULT_VECTORS_PATH: &str = "../evm/test/fixtures/attestation_vectors.json";
+
+/// Default seed key (Anvil account[0], the signer the Foundry tests use)
+pub const DEFAULT_SEED_KEY: [u8; 32] = [
+    0xac, 0x09, 0x74, 0xbe, 0xc3, 0x9a, 0x17, 0xe3,
+    0x6b, 0xa4, 0xa6, 0xb4, 0xd2, 0x38, 0xff, 0x94,
+    0x4b, 0xac, 0xb4, 0x78, 0xcb, 0xed, 0x5e, 0xfc,
+    0xae, 0x78, 0x4d, 0x7b, 0xf4, 0xf2, 0xff, 0x80,
+];
+
+/// Target chains: short, typical, exactly one 32-byte slot, and spilling into a second slot
+pub const VECTOR_CHAINS: [&str; 4] = [
+    "base",
+    "base-sepolia",
+    "abcdefghijklmnopqrstuvwxyz012345",
+    "arbitrum-one-with-a-name-longer-than-one-slot",
+];
+
+/// Target address for each entry of `VECTOR_CHAINS`
+pub const VECTOR_TARGETS: [&str; 4] = [
+    "0x70997970c51812dc3a010c7d01b50e0d17dc79c8",
+    "0x3c44cdddb6a900fa2b585dd299e03d12fa4293bc",
+    "0x90f79bf6eb2c4f870365e785982e1f101e93b906",
+    "0x0000000000000000000000000000000000001234",
+];
+
+/// (tier, stake in motes): None, Silver and Validator
+pub const VECTOR_TIERS: [(u8, u64); 3] = [
+    (0, 0),
+    (2, 1_000_000_000_000),
+    (5, 1_000_000_000_000_000),
+];
+
+/// (created_at, expires_at) in milliseconds: epoch start and a realistic timestamp, 7 days each
+pub const VECTOR_TIMESTAMPS: [(u64, u64); 2] = [
+    (0, 604_800_000),
+    (1_700_000_000_000, 1_700_604_800_000),
+];
+
+/// Era of vector 0; each later vector is one era on
+pub const VECTOR_BASE_ERA: u64 = 15_000;
+
+/// Reputation score step between consecutive vectors, from 0 at vector 0
+pub const VECTOR_REPUTATION_STEP: u64 = 5;
+
+/// Attestation types, alternating between consecutive vectors
+pub const VECTOR_ATTESTATION_TYPES: [&str; 2] = ["identity", "defi-season-1"];
+
+/// One attestation with its encoding and signature, hex fields `0x`-prefixed
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TestVector {
+    pub casper_address_hash: String,
+    pub target_chain: String,
+    pub target_address: String,
+    pub stake_amount: u64,
+    pub tier: u8,
+    pub account_age_days: u64,
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub nonce: u64,
+    /// Always zero, so any verifier deployment accepts the vectors
+    pub trusted_verifier: String,
+    pub casper_era_id: u64,
+    pub reputation_score: u64,
+    pub attestation_type: String,
+    /// Always zero: the vectors are not created from a template
+    pub template_id: u64,
+    /// Always zero: the vectors sign their nonces in the clear
+    pub nonce_commitment: String,
+    /// Always zero: the vector IDs are Keccak256 hashes
+    pub hash_algorithm_version: u8,
+    /// Always zero: the vectors carry no provider claims
+    pub claims_hash: String,
+    /// Always empty: the vectors carry no region hint
+    pub region_hint: String,
+    /// Always live (`STAKE_SOURCE_LIVE`)
+    pub stake_source: u8,
+    /// Era the stake was measured in, the vector's `casper_era_id`
+    pub stake_epoch: u64,
+    pub encoded: String,
+    pub attestation_id: String,
+    pub eth_signed_hash: String,
+    pub signature: String,
+    pub signer_address: String,
+}
+
+/// The fixture file
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VectorFile {
+    pub signer_address: String,
+    pub count: usize,
+    pub vectors: Vec<TestVector>,
+}
+
+fn hex0x(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+/// Build every combination of chain, tier and timestamps, signed with `seed_key`.
+/// Returns `None` if `seed_key` is not a valid secp256k1 private key.
+pub fn generate_vectors(seed_key: &[u8; 32]) -> Option<VectorFile> {
+    let signer_address = hex0x(&public_key_to_address(&derive_public_key(seed_key)?));
+    let mut vectors = Vec::new();
+
+    for (chain, target) in VECTOR_CHAINS.iter().zip(VECTOR_TARGETS.iter()) {
+        for (tier, stake) in VECTOR_TIERS {
+            for (created_at, expires_at) in VECTOR_TIMESTAMPS {
+                let index = vectors.len() as u64;
+                let payload = AttestationPayload {
+                    casper_address_hash: keccak256(format!("veil-vector-{}", index).as_bytes()),
+                    target_chain: chain.to_string(),
+                    target_address: target.to_string(),
+                    stake_amount: U512::from(stake),
+                    tier,
+                    account_age_days: index * 30,
+                    created_at,
+                    expires_at,
+                    nonce: index,
+                    trusted_verifier: [0u8; 20],
+                    casper_era_id: VECTOR_BASE_ERA + index,
+                    reputation_score: VECTOR_REPUTATION_STEP * index,
+                    attestation_type: VECTOR_ATTESTATION_TYPES[index as usize % 2].to_string(),
+                    template_id: 0,
+                    nonce_commitment: [0u8; 32],
+                    hash_algorithm_version: HASH_ALGORITHM_KECCAK256,
+                    claims_hash: [0u8; 32],
+                    region_hint: String::new(),
+                    stake_source: STAKE_SOURCE_LIVE,
+                    stake_epoch: VECTOR_BASE_ERA + index,
+                };
+
+                let encoded = abi_encode_payload(&payload);
+                let attestation_id = keccak256(&encoded);
+                let signature = sign_message(seed_key, &attestation_id);
+
+                vectors.push(TestVector {
+                    casper_address_hash: hex0x(&payload.casper_address_hash),
+                    target_chain: payload.target_chain,
+                    target_address: payload.target_address,
+                    stake_amount: stake,
+                    tier,
+                    account_age_days: payload.account_age_days,
+                    created_at,
+                    expires_at,
+                    nonce: payload.nonce,
+                    trusted_verifier: hex0x(&payload.trusted_verifier),
+                    casper_era_id: payload.casper_era_id,
+                    reputation_score: payload.reputation_score,
+                    attestation_type: payload.attestation_type,
+                    template_id: payload.template_id,
+                    nonce_commitment: hex0x(&payload.nonce_commitment),
+                    hash_algorithm_version: payload.hash_algorithm_version,
+                    claims_hash: hex0x(&payload.claims_hash),
+                    region_hint: payload.region_hint.clone(),
+                    stake_source: payload.stake_source,
+                    stake_epoch: payload.stake_epoch,
+                    encoded: hex0x(&encoded),
+                    attestation_id: hex0x(&attestation_id),
+                    eth_signed_hash: hex0x(&eth_signed_message_hash(&attestation_id)),
+                    signature: hex0x(&signature),
+                    signer_address: signer_address.clone(),
+                });
+            }
+        }
+    }
+
+    Some(VectorFile { signer_address, count: vectors.len(), vectors })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::recover_signer;
+
+    fn decode(hex_field: &str) -> Vec<u8> {
+        hex::decode(hex_field.trim_start_matches("0x")).unwrap()
+    }
+
+    #[test]
+    fn test_every_signature_recovers_to_signer() {
+        let file = generate_vectors(&DEFAULT_SEED_KEY).unwrap();
+
+        assert_eq!(file.signer_address, "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266");
+        assert_eq!(file.count, VECTOR_CHAINS.len() * VECTOR_TIERS.len() * VECTOR_TIMESTAMPS.len());
+
+        for vector in &file.vectors {
+            let attestation_id: [u8; 32] = decode(&vector.attestation_id).try_into().unwrap();
+            let eth_signed_hash: [u8; 32] = decode(&vector.eth_signed_hash).try_into().unwrap();
+
+            assert_eq!(keccak256(&decode(&vector.encoded)), attestation_id);
+            assert_eq!(eth_signed_message_hash(&attestation_id), eth_signed_hash);
+
+            let recovered = recover_signer(&eth_signed_hash, &decode(&vector.signature))
+                .unwrap_or_else(|| panic!("signature for {} does not recover", vector.attestation_id));
+            assert_eq!(hex0x(&recovered), vector.signer_address);
+        }
+    }
+
+    #[test]
+    fn test_committed_fixture_matches_generator() {
+        let committed: serde_json::Value =
+            serde_json::from_str(include_str!("../../evm/test/fixtures/attestation_vectors.json")).unwrap();
+        let generated = serde_json::to_value(generate_vectors(&DEFAULT_SEED_KEY).unwrap()).unwrap();
+
+        assert!(
+            committed == generated,
+            "attestation_vectors.json is stale; regenerate it with `cargo run --bin veil_vectors`"
+        );
+    }
+
+    #[test]
+    fn test_invalid_seed_key_rejected() {
+        assert_eq!(generate_vectors(&[0u8; 32]), None);
+    }
+}
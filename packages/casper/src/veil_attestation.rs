@@ -1,31 +1,353 @@
+use alloc::format;
 use alloc::string::String;
 use alloc::vec::Vec;
 use odra::prelude::*;
-use odra::casper_types::U512;
-use odra::casper_types::bytesrepr::Bytes;
-use sha3::{Keccak256, Digest};
-use k256::ecdsa::SigningKey;
-
-use crate::types::{Attestation, AttestationCreated, AttestationPayload, AttestationRevoked, Tier};
-
-// Helper: left-pad bytes to 32 bytes
-fn pad_left_32(data: &[u8]) -> [u8; 32] {
-    let mut padded = [0u8; 32];
-    let start = 32 - data.len();
-    padded[start..].copy_from_slice(data);
-    padded
+use odra::casper_types::{U256, U512};
+use odra::casper_types::bytesrepr::{Bytes, ToBytes};
+use crate::badge::{badge_metadata, BadgeCollectionContractRef};
+use crate::claims::Claims;
+use crate::disputes::Disputes;
+use crate::encoding::{
+    self, abi_decode_payload, abi_encode_payload, eth_signed_message_hash, is_eip55_checksummed, keccak256,
+    leading_zero_bits, proof_of_work_hash, public_key_to_address,
+};
+#[cfg(not(feature = "no-onchain-signing"))]
+use crate::encoding::derive_public_key;
+#[cfg(feature = "no-onchain-signing")]
+use crate::encoding::is_valid_public_key;
+use crate::errors::VeilError;
+use crate::reward_token::RewardTokenContractRef;
+use crate::stake_source::{LockedStakeSourceContractRef, StakeSourceContractRef};
+use crate::types::{
+    compress_attestation, decompress_attestation, parse_evm_address, AdminContactUpdated, AdminTransferred, Attestation,
+    AttestationConfirmed, AttestationCreated, AttestationCreatedV2, AttestationExport, AttestationExtended,
+    AttestationFrozen, AttestationIdRetried, AttestationListItem, AttestationMarkedStale, AttestationPayload,
+    AttestationRenewabilityChanged, AttestationRenewed, AttestationRevoked, AttestationSuperseded,
+    AttestationTypeRegistered, AttestationUnfrozen, AttestedAddresses, AutoRenewExecuted, ChainActivated,
+    ChainAllowlistUpdated, ChainConfigChanged, ChainDeployment, ChainDeploymentRemoved, ChainDeploymentUpdated,
+    ChainProposalCancelled, ChainProposed, Claim, ClaimAttached, ClaimProviderUpdated, CompressedAttestation,
+    ConfigChanged, ContractMigrated, CustomTierSet, DeploymentInfo, Dispute, DisputeOpened, DisputeResolved,
+    EmergencyActionTaken, EmergencyAdminSet, HashAlgorithmChanged, InitConfig, RegionAllowlistUpdated, ReputationRecord,
+    ReputationWeights, RevocationPending, RewardMinted, RewardMultipliers, RewardSkipped, SignerHealthStatus,
+    SignerRotated, StakePolicy, Template, TemplateUpdated, Tier, TierThresholds, TierThresholdsChanged, TimeUnitChanged,
+    UserProfile, ValidityPeriodChanged, WitnessAdded, MOTES_PER_CSPR, STAKE_SOURCE_CACHED, STAKE_SOURCE_CUSTOM,
+    STAKE_SOURCE_LIVE, SUPERSEDED_BY_EXTENSION, SUPERSEDED_BY_REFRESH, SUPERSEDED_BY_RENEWAL,
+};
+#[cfg(not(feature = "no-onchain-signing"))]
+use crate::types::SignaturesBatchRefreshed;
+#[cfg(feature = "no-onchain-signing")]
+use crate::types::AttestationSigned;
+
+/// Maximum number of nonce bumps when a derived attestation ID is already taken
+pub const MAX_ATTESTATION_ID_RETRIES: u8 = 3;
+
+/// Maximum length of the admin contact string in bytes
+pub const MAX_ADMIN_CONTACT_LEN: usize = 512;
+
+/// Default shortest validity a user may request for an attestation (1 hour)
+pub const DEFAULT_MIN_VALIDITY_SECS: u64 = 60 * 60;
+
+/// Default window before expiry in which a relayer may renew an attestation (1 day)
+pub const DEFAULT_RENEWAL_GRACE_SECS: u64 = 24 * 60 * 60;
+
+/// Default CSPR bonded to open a dispute (100 CSPR)
+pub const DEFAULT_DISPUTE_BOND_MOTES: u64 = 100 * MOTES_PER_CSPR;
+
+/// Active attestations per user allowed on a chain without its own limit (no limit)
+pub const DEFAULT_CHAIN_ATTESTATION_LIMIT: u32 = u32::MAX;
+
+/// Gas (motes) `estimate_create_attestation_gas` charges every creation:
+/// hashing, signing and the storage writes
+pub const CREATE_BASE_GAS: u64 = 20_000_000_000;
+
+/// Gas (motes) per byte of target chain or target address, which is stored
+/// and hashed more than once
+pub const CREATE_GAS_PER_BYTE: u64 = 25_000_000;
+
+/// Gas (motes) per stake source the stake policy queries
+pub const STAKE_QUERY_GAS: u64 = 5_000_000_000;
+
+/// How long a proposed chain waits before it can be confirmed as supported (24 hours)
+pub const CHAIN_PROPOSAL_DELAY_SECS: u64 = 24 * 60 * 60;
+
+/// Default maximum number of tags per attestation
+pub const DEFAULT_MAX_TAGS: u8 = 5;
+
+/// Maximum length of a single tag in bytes
+pub const MAX_TAG_LEN: usize = 32;
+
+/// Attestation type used when none is given; registered on deployment
+pub const DEFAULT_ATTESTATION_TYPE: &str = "identity";
+
+/// Maximum length of an attestation type in bytes
+pub const MAX_ATTESTATION_TYPE_LEN: usize = 64;
+
+/// Maximum length of a region hint in bytes
+pub const MAX_REGION_HINT_LEN: usize = 10;
+
+/// Admin actions kept by `get_admin_audit_log`; older entries are dropped
+pub const ADMIN_AUDIT_LOG_LEN: usize = 100;
+
+/// Default cap on the IDs passed to a batch call
+pub const DEFAULT_MAX_BATCH_SIZE: u32 = 50;
+
+/// Crate version reported by `get_deployment_info`
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// `get_time_unit`: payload timestamps in milliseconds, Casper's block time
+/// unit; what deployments from before the setting existed sign in
+pub const TIME_UNIT_MILLIS: u8 = 0;
+
+/// `get_time_unit`: payload timestamps in seconds, comparable with an EVM
+/// `block.timestamp` directly; the default for new deployments
+pub const TIME_UNIT_SECONDS: u8 = 1;
+
+/// Storage layout version set by `init`; deployments from before versioning
+/// report 1 until `migrate_v1_to_v2` runs
+pub const STORAGE_VERSION: u32 = 2;
+
+/// Version of the attestation payload encoding, bumped on breaking changes
+pub const SCHEMA_VERSION: u8 = 11;
+
+/// Highest proof-of-work difficulty the admin may set, keeping a solve to ~1M hashes
+pub const MAX_POW_DIFFICULTY: u8 = 20;
+
+/// `bytes4(keccak256("isValidSignature(bytes32,bytes)"))`, returned by EIP-1271 wallets
+pub const EIP1271_MAGIC_VALUE: [u8; 4] = [0x16, 0x26, 0xba, 0x7e];
+
+/// Signatures under one key after which `check_signer_health` recommends rotating it
+pub const SIGNER_ROTATION_SIGNATURES: u64 = 10_000;
+
+/// Key age in seconds after which `check_signer_health` recommends rotating it (90 days)
+pub const SIGNER_ROTATION_AGE_SECS: u64 = 90 * 24 * 60 * 60;
+
+/// Whether a signer key has been used enough, or held long enough, to rotate
+pub(crate) fn recommends_rotation(signatures_produced: u64, key_age_secs: u64) -> bool {
+    signatures_produced > SIGNER_ROTATION_SIGNATURES || key_age_secs > SIGNER_ROTATION_AGE_SECS
+}
+
+/// A setting's value as carried by `ConfigChanged`
+pub(crate) trait ConfigValue {
+    fn render(&self) -> String;
+}
+
+macro_rules! config_value_to_string {
+    ($($ty:ty),*) => {$(
+        impl ConfigValue for $ty {
+            fn render(&self) -> String {
+                self.to_string()
+            }
+        }
+    )*};
+}
+
+config_value_to_string!(bool, u8, u32, u64, U256, U512, String, Address);
+
+impl ConfigValue for [u8; 20] {
+    fn render(&self) -> String {
+        format!("0x{}", hex::encode(self))
+    }
+}
+
+impl ConfigValue for Vec<String> {
+    fn render(&self) -> String {
+        self.join(",")
+    }
+}
+
+impl ConfigValue for ReputationWeights {
+    fn render(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+impl ConfigValue for RewardMultipliers {
+    fn render(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+impl ConfigValue for StakePolicy {
+    fn render(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+impl<T: ConfigValue> ConfigValue for Option<T> {
+    fn render(&self) -> String {
+        self.as_ref().map_or_else(|| "none".to_string(), ConfigValue::render)
+    }
+}
+
+/// Block time `ms` expressed in `time_unit` (`TIME_UNIT_*`) for a payload
+pub(crate) fn payload_timestamp(ms: u64, time_unit: u8) -> u64 {
+    if time_unit == TIME_UNIT_SECONDS {
+        ms / 1000
+    } else {
+        ms
+    }
+}
+
+/// Check `stake` against the minimum required to create an attestation
+pub(crate) fn check_min_stake(stake: U512, required: U512) -> Result<(), VeilError> {
+    if stake < required {
+        Err(VeilError::InsufficientStake)
+    } else {
+        Ok(())
+    }
+}
+
+/// Check a tag list: at most `max_tags` distinct, non-empty tags of up to
+/// `MAX_TAG_LEN` printable ASCII characters (no spaces)
+pub(crate) fn check_tags(tags: &[String], max_tags: u8) -> Result<(), VeilError> {
+    if tags.len() > max_tags as usize {
+        return Err(VeilError::TooManyTags);
+    }
+    for (i, tag) in tags.iter().enumerate() {
+        if tag.is_empty() || tag.len() > MAX_TAG_LEN || !tag.bytes().all(|b| b.is_ascii_graphic()) {
+            return Err(VeilError::InvalidTag);
+        }
+        if tags[..i].contains(tag) {
+            return Err(VeilError::InvalidTag);
+        }
+    }
+    Ok(())
+}
+
+/// Check an attestation type name: non-empty, at most
+/// `MAX_ATTESTATION_TYPE_LEN` printable ASCII characters (no spaces)
+pub(crate) fn check_attestation_type(attestation_type: &str) -> Result<(), VeilError> {
+    if attestation_type.is_empty()
+        || attestation_type.len() > MAX_ATTESTATION_TYPE_LEN
+        || !attestation_type.bytes().all(|b| b.is_ascii_graphic())
+    {
+        return Err(VeilError::InvalidAttestationType);
+    }
+    Ok(())
+}
+
+/// Check a region hint: non-empty, at most `MAX_REGION_HINT_LEN` printable
+/// ASCII characters (no spaces)
+pub(crate) fn check_region_hint(region: &str) -> Result<(), VeilError> {
+    if region.is_empty() || region.len() > MAX_REGION_HINT_LEN || !region.bytes().all(|b| b.is_ascii_graphic()) {
+        return Err(VeilError::InvalidRegionHint);
+    }
+    Ok(())
+}
+
+/// Check that every field an EVM verifier relies on is set: a non-zero
+/// address hash, a target chain, a 42-character target address, an expiry
+/// after creation and an attestation type
+pub fn verify_payload_completeness(payload: &AttestationPayload) -> Result<(), VeilError> {
+    if payload.casper_address_hash == [0u8; 32]
+        || payload.target_chain.is_empty()
+        || payload.target_address.len() != 42
+        || payload.expires_at <= payload.created_at
+        || payload.attestation_type.is_empty()
+    {
+        return Err(VeilError::IncompletePayload);
+    }
+    Ok(())
 }
 
-// Helper: convert U512 to 32-byte big-endian array
-fn u512_to_bytes32(value: &U512) -> [u8; 32] {
-    let mut bytes = [0u8; 64];
-    value.to_big_endian(&mut bytes);
-    // Take the lower 32 bytes (U512 is 64 bytes but stake fits in U256)
-    let mut result = [0u8; 32];
-    result.copy_from_slice(&bytes[32..64]);
-    result
+/// Tag index key for a user's attestations carrying `tag`
+fn tag_index_key(user: &Address, tag: &str) -> [u8; 32] {
+    keccak256(format!("{}:{}", user, tag).as_bytes())
 }
 
+/// Derive the attestation ID for `payload`, bumping its nonce while the ID is
+/// already taken. With `user_secret`, the nonce is encoded as its commitment,
+/// see `committed_payload`. The ID is hashed with the payload's
+/// `hash_algorithm_version`. Returns the ID, the encoded payload and the
+/// number of retries used, or `None` if every retry collided or the
+/// algorithm is unknown.
+pub(crate) fn find_unused_attestation_id(
+    payload: &mut AttestationPayload,
+    user_secret: Option<&[u8; 32]>,
+    is_taken: impl Fn(&[u8; 32]) -> bool,
+) -> Option<([u8; 32], Vec<u8>, u8)> {
+    for attempt in 0..=MAX_ATTESTATION_ID_RETRIES {
+        let encoded = match user_secret {
+            Some(user_secret) => abi_encode_payload(&committed_payload(payload, user_secret)),
+            None => abi_encode_payload(payload),
+        };
+        let attestation_id = encoding::hash(payload.hash_algorithm_version, &encoded)?;
+        if !is_taken(&attestation_id) {
+            return Some((attestation_id, encoded, attempt));
+        }
+        payload.nonce += 1;
+    }
+    None
+}
+
+/// `payload` with its nonce replaced by `encoding::nonce_commitment` of it
+pub(crate) fn committed_payload(payload: &AttestationPayload, user_secret: &[u8; 32]) -> AttestationPayload {
+    AttestationPayload {
+        nonce: 0,
+        nonce_commitment: encoding::nonce_commitment(payload.nonce, user_secret),
+        ..payload.clone()
+    }
+}
+
+/// What `issue_attestation` attests to, besides the owner
+struct IssueRequest {
+    target_chain: String,
+    target_address: String,
+    attestation_type: String,
+    custom_validity_secs: Option<u64>,
+    /// Whether `custom_validity_secs` is carried over from a superseded
+    /// attestation rather than chosen by the user, so the minimum does not apply
+    carried_validity: bool,
+    is_renewable: bool,
+    referrer: Option<Address>,
+    /// Template whose minimum tier, tag and ID the attestation gets
+    template: Option<Template>,
+    /// Secret the nonce is committed to instead of being signed in the clear
+    user_secret: Option<[u8; 32]>,
+    region_hint: Option<String>,
+}
+
+/// Checked, encoded attestation from `prepare_attestation`
+struct PreparedAttestation {
+    /// Payload at the nonce actually used, signed in the clear. Its
+    /// timestamps are in `time_unit`; `created_at` and `expires_at` here are
+    /// block time (ms), as stored
+    payload: AttestationPayload,
+    time_unit: u8,
+    created_at: u64,
+    expires_at: u64,
+    attestation_id: [u8; 32],
+    /// Encoding the ID was taken from
+    encoded: Vec<u8>,
+    /// Nonce bumps it took to find an unused ID
+    retries: u8,
+    tier: Tier,
+    validity: u64,
+    chain_address_key: [u8; 72],
+    user_chain_key: [u8; 52],
+    /// Owner's still-active attestations to the target chain
+    chain_attestations: Vec<[u8; 32]>,
+    /// Whether this is the owner's first attestation to the target chain
+    first_on_chain: bool,
+}
+
+/// A user's stake and where it came from, see `query_user_stake`
+struct StakeReading {
+    amount: U512,
+    /// One of the `STAKE_SOURCE_*` constants
+    source: u8,
+    /// Era the stake was measured in; 0 when unknown
+    epoch: u64,
+}
+
+/// Key `rotate_signer` installs: the new private key
+#[cfg(not(feature = "no-onchain-signing"))]
+pub type SignerKey = [u8; 32];
+/// Key `rotate_signer` installs: the external signer's new public key
+/// (uncompressed, no 0x04 prefix)
+#[cfg(feature = "no-onchain-signing")]
+pub type SignerKey = [u8; 64];
+
 /// Veil Attestation Contract
 /// Creates cryptographically signed attestations of user's Casper identity
 /// that can be verified on EVM chains.
@@ -33,341 +355,3694 @@ fn u512_to_bytes32(value: &U512) -> [u8; 32] {
 pub struct VeilAttestation {
     /// Attestation storage by ID
     attestations: Mapping<[u8; 32], Attestation>,
+    /// Attestations created while compressed storage was enabled
+    compressed_attestations: Mapping<[u8; 32], CompressedAttestation>,
+    /// List summary of every attestation, updated whenever it is stored
+    attestation_list_items: Mapping<[u8; 32], AttestationListItem>,
+    /// Target chain names by hash, for compressed attestations
+    chain_names: Mapping<[u8; 32], String>,
+    /// Whether an attestation type is registered, keyed by `keccak256(type)`
+    registered_types: Mapping<[u8; 32], bool>,
+    /// Registered attestation types by hash, for compressed attestations
+    attestation_type_names: Mapping<[u8; 32], String>,
+    /// Registered attestation types, in registration order
+    registered_type_list: Var<Vec<String>>,
+    /// Attestation templates by ID, see `create_attestation_from_template`
+    templates: Mapping<u64, Template>,
+    /// Number of templates created, which is also the last template's ID
+    template_count: Var<u64>,
+    /// IDs of the attestations created from each template, in creation order
+    template_attestations: Mapping<u64, Vec<[u8; 32]>>,
+    /// Whether new attestations are stored compressed
+    compressed_storage: Var<bool>,
+    /// Tags of compressed attestations, which have no room for them
+    compressed_tags: Mapping<[u8; 32], Vec<String>>,
+    /// Witness (address, signature) of compressed attestations
+    compressed_witnesses: Mapping<[u8; 32], ([u8; 20], Bytes)>,
+    /// Referrers of compressed attestations
+    compressed_referrers: Mapping<[u8; 32], Address>,
+    /// Nonce commitments of compressed attestations
+    compressed_nonce_commitments: Mapping<[u8; 32], [u8; 32]>,
+    /// EVM confirmation (block time in ms, transaction hash) of compressed attestations
+    compressed_evm_confirmations: Mapping<[u8; 32], (u64, [u8; 32])>,
+    /// Compressed attestations marked stale
+    compressed_stale: Mapping<[u8; 32], bool>,
+    /// Frozen flag of compressed attestations, once ever frozen
+    compressed_frozen: Mapping<[u8; 32], bool>,
+    /// Region hints of compressed attestations
+    compressed_region_hints: Mapping<[u8; 32], String>,
+    /// (stake source, stake epoch) of compressed attestations, unless live at era 0
+    compressed_stake_origins: Mapping<[u8; 32], (u8, u64)>,
+    /// Attestation IDs by (owner, tag) key, see `tag_index_key`
+    tag_index: Mapping<[u8; 32], Vec<[u8; 32]>>,
+    /// Maximum number of tags per attestation
+    max_tags: Var<u8>,
+    /// Whether a user holds an unrevoked attestation for a (chain, target address), see `chain_address_key`
+    user_chain_address_attested: Mapping<[u8; 72], bool>,
+    /// Whether several attestations may target the same (chain, target address)
+    allow_duplicate_chain_address: Var<bool>,
+    /// Maximum active attestations per user, keyed by `keccak256(chain)`
+    chain_attestation_limits: Mapping<[u8; 32], u32>,
+    /// User's attestation IDs for a chain that may still be active, see `user_chain_key`
+    user_chain_attestations: Mapping<[u8; 52], Vec<[u8; 32]>>,
+    /// Whether the deprecated `AttestationCreated` is emitted next to `AttestationCreatedV2`
+    emit_v1_events: Var<bool>,
+    /// Ethereum addresses allowed to co-sign attestations as witnesses
+    trusted_witnesses: Mapping<[u8; 20], bool>,
     /// User's attestation IDs
     user_attestations: Mapping<Address, Vec<[u8; 32]>>,
+    /// ID of each user's first attestation, never updated
+    first_attestation_id: Mapping<Address, [u8; 32]>,
     /// User nonces for replay protection
     user_nonces: Mapping<Address, u64>,
-
-    /// Signer private key (secp256k1)
+    /// Attestation created with each (user, nonce), see `nonce_key`
+    nonce_to_attestation: Mapping<[u8; 40], [u8; 32]>,
+    /// Number of entries in `attestation_ids_by_index`
+    global_attestation_count: Var<u64>,
+    /// Attestation issued at each global index, successors included, in issue order
+    attestation_ids_by_index: Mapping<u64, [u8; 32]>,
+    /// Number of successors in `attestation_ids_by_index`, which are not new attestations
+    global_successor_count: Var<u64>,
+    /// Tier changes per user: (tier, block time, attestation that changed it)
+    tier_history: Mapping<Address, Vec<(Tier, u64, [u8; 32])>>,
+    /// Last `ADMIN_AUDIT_LOG_LEN` admin actions: (entrypoint, caller, block time)
+    admin_audit_log: Var<Vec<(String, Address, u64)>>,
+
+    /// Signer private key (secp256k1); `no-onchain-signing` builds only know
+    /// the public key
+    #[cfg(not(feature = "no-onchain-signing"))]
     signer_private_key: Var<[u8; 32]>,
     /// Signer public key (uncompressed, 64 bytes)
     signer_public_key: Var<[u8; 64]>,
+    /// Bumped whenever the signing key changes
+    signer_key_version: Var<u32>,
+    /// Block time (ms) the current signing key was set
+    signer_set_at: Var<u64>,
+    /// Signatures stored under the current signing key
+    signatures_produced: Var<u64>,
+    /// Block time (ms) of the last stored signature
+    last_signature_at: Var<u64>,
+    /// Signature issued for each attestation
+    attestation_signatures: Mapping<[u8; 32], Bytes>,
+    /// Signer key version each cached signature was made with
+    attestation_signature_versions: Mapping<[u8; 32], u32>,
+    /// Block time (ms) each cached signature was produced; absent for
+    /// signatures from before it was recorded
+    signature_produced_at: Mapping<[u8; 32], u64>,
+    /// Maximum number of IDs per batch call
+    max_batch_size: Var<u32>,
 
     /// Admin address
     admin: Var<Address>,
+    /// Opaque admin contact metadata (email, DID, JSON) for compliance
+    admin_contact: Var<String>,
+    /// Break-glass admin allowed to pause and rotate the signer only
+    emergency_admin: Var<Option<Address>>,
+    /// Contract queried for users' delegated stake; stake is zero without one
+    stake_source: Var<Option<Address>>,
+    /// `VeilStaking` contract queried for locked CSPR; none locked without one
+    locked_stake_source: Var<Option<Address>>,
+    /// Which of delegated and locked stake count towards tiers
+    stake_policy: Var<StakePolicy>,
+    /// Collection badges are minted on; this contract must be its minter
+    badge_collection: Var<Option<Address>>,
+    /// Whether new attestations get a badge
+    mint_badges: Var<bool>,
+    /// Collection each badged attestation's badge was minted on
+    attestation_badges: Mapping<[u8; 32], Address>,
+    /// CEP-18 token rewards are minted on; this contract must hold its mint rights
+    reward_token: Var<Option<Address>>,
+    /// Kill switch: whether new attestations earn a reward
+    rewards_enabled: Var<bool>,
+    /// Reward before the tier multiplier
+    base_reward: Var<U256>,
+    reward_multipliers: Var<RewardMultipliers>,
+    /// Most rewards ever minted (unlimited by default)
+    reward_emission_cap: Var<U256>,
+    /// Rewards minted so far
+    rewards_emitted: Var<U256>,
+    /// Each user's attestation history, scored by `reputation_weights`
+    reputation: Mapping<Address, ReputationRecord>,
+    /// Attestations created with each referrer
+    referral_counts: Mapping<Address, u64>,
+    /// IDs of the attestations each referrer referred, in creation order
+    referred_attestations: Mapping<Address, Vec<[u8; 32]>>,
+    /// Referrers the admin no longer accepts
+    blocked_referrers: Mapping<Address, bool>,
+    /// Whether a user has attested to a chain before, see `user_chain_key`
+    reputation_chains: Mapping<[u8; 52], bool>,
+    /// Chains each user has attested to, by (user, index) in order of first attestation
+    user_chains: Mapping<(Address, u32), String>,
+    /// Number of entries in `user_chains` per user
+    user_chain_counts: Mapping<Address, u32>,
+    reputation_weights: Var<ReputationWeights>,
+    /// Admin-set tiers that replace the stake-based tier, e.g. after off-chain verification
+    custom_tiers: Mapping<Address, Option<Tier>>,
+    /// Disputes against attestations
+    disputes: SubModule<Disputes>,
+    /// Provider registry and claims attached to attestations
+    claims: SubModule<Claims>,
+    /// Motes bonded to open a dispute
+    dispute_bond: Var<U512>,
+    /// Account that may resolve disputes besides the admin
+    arbiter: Var<Option<Address>>,
+    /// Recipient of forfeited dispute bonds and attestation fees (defaults to the admin)
+    treasury: Var<Option<Address>>,
+    /// Motes each attestation creation pays to the treasury (unset: free)
+    attestation_fee: Var<U512>,
+
+    /// Whether attestation creation is paused
+    paused: Var<bool>,
+    /// Minimum stake (whole CSPR) for each tier
+    tier_thresholds: Var<TierThresholds>,
+    /// Whether `create_attestation` enforces `min_attestation_stake_motes`
+    require_min_stake: Var<bool>,
+    /// Minimum stake to create an attestation (defaults to the Bronze threshold)
+    min_attestation_stake_motes: Var<U512>,
 
     /// Attestation validity period in seconds
     attestation_validity_secs: Var<u64>,
+    /// Shortest custom validity a user may request, in seconds
+    min_validity_secs: Var<u64>,
+    /// Wait between requesting and finalizing a revocation, in seconds (0 = immediate)
+    revocation_delay_secs: Var<u64>,
+    /// Age in seconds under which re-attesting to the same (chain, address)
+    /// returns the existing attestation (0 = disabled)
+    reuse_window_secs: Var<u64>,
+    /// Age in seconds under which a user's last live stake reading is reused
+    /// instead of querying the stake sources (0 = disabled)
+    stake_cache_secs: Var<u64>,
+    /// Each user's last live stake reading: (motes, block time, era)
+    stake_cache: Mapping<Address, (U512, u64, u64)>,
+    /// Age in seconds past which `mark_stale_attestations` marks attestations stale (0 = disabled)
+    max_attestation_age_secs: Var<u64>,
+    /// Attestation that replaced each renewed, extended or refreshed one
+    successors: Mapping<[u8; 32], [u8; 32]>,
+    /// Attestation each successor replaced; the inverse of `successors`
+    predecessors: Mapping<[u8; 32], [u8; 32]>,
+    /// Block time (ms) at which each requested revocation takes effect
+    pending_revocations: Mapping<[u8; 32], u64>,
+    /// Users who let relayers renew their attestations
+    auto_renew: Mapping<Address, bool>,
+    /// Accounts allowed to call `execute_auto_renew`
+    relayers: Mapping<Address, bool>,
+    /// How long before expiry an attestation may be auto-renewed, in seconds
+    renewal_grace_secs: Var<u64>,
+
+    /// Network this deployment lives on (e.g. `casper-testnet`)
+    network_name: Var<String>,
+    /// Block time (ms) of the deployment
+    deployed_at: Var<u64>,
+    /// Storage layout version; unset on deployments from before versioning
+    storage_version: Var<u32>,
+    /// Target chains the deployment serves, for integrators
+    supported_chains: Var<Vec<String>>,
+    /// Block time (ms) each pending chain was proposed at; 0 once confirmed or cancelled
+    proposed_chains: Mapping<String, u64>,
+    /// Chains with a pending proposal, in proposal order
+    pending_chain_names: Var<Vec<String>>,
+    /// Block time (ms) before which security parameters cannot change
+    upgrade_lock_until: Var<u64>,
+    /// Leading zero bits `create_attestation` requires of the proof-of-work hash (0: disabled)
+    pow_difficulty: Var<u8>,
+    /// `encoding::HASH_ALGORITHM_*` new attestation IDs are hashed with (0: Keccak256)
+    hash_algorithm: Var<u8>,
+    /// `TIME_UNIT_*` of new payloads' timestamps; unset (milliseconds) on
+    /// deployments from before it was configurable
+    time_unit: Var<u8>,
+    /// `TIME_UNIT_*` each attestation's payload was signed with; absent: milliseconds
+    attestation_time_units: Mapping<[u8; 32], u8>,
+    /// Ethereum address whose signatures `confirm_verified` accepts
+    confirmation_signer: Var<Option<[u8; 20]>>,
+    /// Registered EVM verifiers, keyed by (target chain, verifier address)
+    trusted_verifiers: Mapping<(String, [u8; 20]), bool>,
+    /// Verifier embedded in new payloads for each chain: the last one registered
+    chain_verifiers: Mapping<String, [u8; 20]>,
+    /// Deployment metadata per target chain, for clients; gates creation when set
+    chain_deployments: Mapping<String, Option<ChainDeployment>>,
+    /// Chains with deployment metadata, in the order they were first set
+    chain_deployment_names: Var<Vec<String>>,
+    /// Whether new attestations may only target allowlisted chains
+    allowlist_enabled: Var<bool>,
+    /// Whether target addresses must be EIP-55 checksummed
+    require_checksum: Var<bool>,
+    /// Whether a chain is on the allowlist
+    allowed_chains: Mapping<String, bool>,
+    /// Allowlisted chains, in the order they were added
+    allowed_chain_names: Var<Vec<String>>,
+    /// Whether a region hint is on the region allowlist, keyed by `keccak256(region)`
+    allowed_regions: Mapping<[u8; 32], bool>,
+    /// Allowlisted regions, in the order they were added; new attestations
+    /// must name one while any are listed
+    allowed_region_names: Var<Vec<String>>,
 }
 
 #[odra::module]
 impl VeilAttestation {
-    /// Initialize the contract
+    /// Initialize the contract. `initial_lock_secs` freezes the signer and
+    /// tier thresholds for that long after deployment (`None` or 0: no lock).
+    /// `config` replaces the defaults of the settings it names, validated as
+    /// their setters would, so none of the defaults is ever live.
+    #[cfg(not(feature = "no-onchain-signing"))]
     #[odra(init)]
-    pub fn init(&mut self, admin: Address, signer_private_key: [u8; 32]) {
+    pub fn init(
+        &mut self,
+        admin: Address,
+        signer_private_key: [u8; 32],
+        initial_lock_secs: Option<u64>,
+        config: Option<InitConfig>,
+    ) {
         self.admin.set(admin);
-        self.signer_private_key.set(signer_private_key);
-
-        // Derive public key from private key using k256
-        let signing_key = SigningKey::from_bytes(&signer_private_key.into())
-            .expect("Invalid private key");
-        let verifying_key = signing_key.verifying_key();
-        let public_key_point = verifying_key.to_encoded_point(false);
-
-        // Take 64 bytes (skip 0x04 prefix)
-        let mut pubkey = [0u8; 64];
-        pubkey.copy_from_slice(&public_key_point.as_bytes()[1..65]);
-        self.signer_public_key.set(pubkey);
+        self.set_signer_key(signer_private_key);
+        self.apply_init_settings(initial_lock_secs, config.unwrap_or_default());
+    }
 
-        // 7 days default validity
-        self.attestation_validity_secs.set(7 * 24 * 60 * 60);
+    /// Initialize the contract for an external signer, known by its
+    /// uncompressed `signer_public_key` (64 bytes, no 0x04 prefix), which
+    /// signs off-chain and hands signatures to `submit_signature`. Otherwise
+    /// as in default builds.
+    #[cfg(feature = "no-onchain-signing")]
+    #[odra(init)]
+    pub fn init(
+        &mut self,
+        admin: Address,
+        signer_public_key: [u8; 64],
+        initial_lock_secs: Option<u64>,
+        config: Option<InitConfig>,
+    ) {
+        self.admin.set(admin);
+        self.set_signer_key(signer_public_key);
+        self.apply_init_settings(initial_lock_secs, config.unwrap_or_default());
     }
 
-    /// Create a new attestation for the caller
+    /// Create a new attestation for the caller.
+    /// `custom_validity_secs` may shorten (never extend) the configured validity.
+    /// `pow_nonce` is required while a proof-of-work difficulty is set; see
+    /// `encoding::proof_of_work_hash`. `renewable: Some(false)` rules out
+    /// auto-renewal for good (default: renewable). `referrer` attributes the
+    /// attestation to another, unblocked account. `attestation_type` must be
+    /// registered (default: `DEFAULT_ATTESTATION_TYPE`). `region_hint` is the
+    /// caller's self-reported region, e.g. "EU"; required to be allowlisted
+    /// while the region allowlist is non-empty. Within the reuse window (see
+    /// `set_reuse_window_secs`) an existing attestation to the same pair is
+    /// returned instead of a new one. Each call attaches exactly
+    /// `get_attestation_fee` motes, reused or not.
+    #[odra(payable)]
+    #[allow(clippy::too_many_arguments)]
     pub fn create_attestation(
         &mut self,
         target_chain: String,
         target_address: String,
+        custom_validity_secs: Option<u64>,
+        pow_nonce: Option<u64>,
+        renewable: Option<bool>,
+        referrer: Option<Address>,
+        attestation_type: Option<String>,
+        region_hint: Option<String>,
     ) -> ([u8; 32], Bytes) {
         let caller = self.env().caller();
 
-        // Validate target address format
-        assert!(
-            target_address.starts_with("0x") && target_address.len() == 42,
-            "Invalid EVM address format"
-        );
+        if self.paused.get_or_default() {
+            self.env().revert(VeilError::ContractPaused);
+        }
 
-        // Query user's stake
-        let stake_amount = self.query_user_stake(caller);
+        if let Some(referrer) = referrer {
+            if referrer == caller {
+                self.env().revert(VeilError::SelfReferral);
+            }
+            if self.is_referrer_blocked(referrer) {
+                self.env().revert(VeilError::ReferrerBlocked);
+            }
+        }
 
-        // Calculate tier based on stake
-        let tier = self.calculate_tier(stake_amount);
+        let target_address =
+            self.check_creation_request(caller, &target_chain, target_address, pow_nonce, region_hint.as_deref());
 
-        // Get and increment nonce
-        let nonce = self.user_nonces.get(&caller).unwrap_or(0);
-        self.user_nonces.set(&caller, nonce + 1);
+        let attestation_type = attestation_type.unwrap_or_else(|| DEFAULT_ATTESTATION_TYPE.to_string());
+        if !self.is_attestation_type_registered(attestation_type.clone()) {
+            self.env().revert(VeilError::UnregisteredAttestationType);
+        }
 
-        // Timestamps
-        let now = self.env().get_block_time();
-        let validity = self.attestation_validity_secs.get().unwrap_or(604800);
-        let expires_at = now + (validity * 1000);
+        if let Some(existing) = self.find_reusable_attestation(caller, &target_chain, &target_address) {
+            return existing;
+        }
 
-        // Create payload
-        let payload = AttestationPayload {
-            casper_address_hash: self.hash_address(caller),
-            target_chain: target_chain.clone(),
-            target_address: target_address.clone(),
-            stake_amount,
-            tier: tier as u8,
-            account_age_days: 0, // Skipped for MVP
-            created_at: now,
-            expires_at,
-            nonce,
+        let request = IssueRequest {
+            target_chain,
+            target_address,
+            attestation_type,
+            custom_validity_secs,
+            carried_validity: false,
+            is_renewable: renewable.unwrap_or(true),
+            referrer,
+            template: None,
+            user_secret: None,
+            region_hint,
         };
+        let (attestation_id, signature) = self.issue_attestation(caller, request);
+
+        if let Some(referrer) = referrer {
+            self.referral_counts.set(&referrer, self.get_referral_count(referrer) + 1);
+            let mut referred = self.referred_attestations.get(&referrer).unwrap_or_default();
+            referred.push(attestation_id);
+            self.referred_attestations.set(&referrer, referred);
+        }
+        (attestation_id, signature)
+    }
+
+    /// Create an attestation for the caller from an active template, which
+    /// fixes the target chain, validity and tag, and requires at least its
+    /// `min_tier`. `pow_nonce` is as for `create_attestation`. The
+    /// attestation is renewable, of `DEFAULT_ATTESTATION_TYPE` and has no
+    /// referrer. Attaches exactly `get_attestation_fee` motes.
+    #[odra(payable)]
+    pub fn create_attestation_from_template(
+        &mut self,
+        template_id: u64,
+        target_address: String,
+        pow_nonce: Option<u64>,
+    ) -> ([u8; 32], Bytes) {
+        let caller = self.env().caller();
 
-        // Encode and hash payload
-        let encoded = self.abi_encode_payload(&payload);
-        let attestation_id = self.keccak256(&encoded);
+        if self.paused.get_or_default() {
+            self.env().revert(VeilError::ContractPaused);
+        }
 
-        // Sign the message
-        let signature = self.sign_message(&attestation_id);
+        let template = self.get_template(template_id)
+            .unwrap_or_else(|| self.env().revert(VeilError::TemplateNotFound));
+        if !template.active {
+            self.env().revert(VeilError::TemplateInactive);
+        }
 
-        // Store attestation
-        let attestation = Attestation {
-            id: attestation_id,
-            casper_address: caller,
-            target_chain: target_chain.clone(),
-            target_address: target_address.clone(),
-            stake_amount,
-            tier,
-            account_age_days: 0,
-            created_at: now,
-            expires_at,
-            nonce,
-            revoked: false,
+        let target_address =
+            self.check_creation_request(caller, &template.target_chain, target_address, pow_nonce, None);
+        let request = IssueRequest {
+            target_chain: template.target_chain.clone(),
+            target_address,
+            attestation_type: DEFAULT_ATTESTATION_TYPE.to_string(),
+            custom_validity_secs: Some(template.validity_secs),
+            carried_validity: false,
+            is_renewable: true,
+            referrer: None,
+            template: Some(template),
+            user_secret: None,
+            region_hint: None,
         };
+        self.issue_attestation(caller, request)
+    }
 
-        self.attestations.set(&attestation_id, attestation);
+    /// Create an attestation for the caller whose payload carries a
+    /// commitment to the nonce (`encoding::nonce_commitment`) instead of the
+    /// nonce, which would reveal how many attestations the caller has made.
+    /// Whoever knows `user_secret` can check the commitment against the
+    /// nonce. The secret is not stored, so the attestation cannot be renewed.
+    /// `pow_nonce` is as for `create_attestation`. Attaches exactly
+    /// `get_attestation_fee` motes.
+    #[odra(payable)]
+    pub fn create_attestation_with_nonce_commitment(
+        &mut self,
+        target_chain: String,
+        target_address: String,
+        user_secret: [u8; 32],
+        pow_nonce: Option<u64>,
+    ) -> ([u8; 32], Bytes) {
+        let caller = self.env().caller();
 
-        // Track user's attestations
-        let mut user_atts = self.user_attestations.get(&caller).unwrap_or_default();
-        user_atts.push(attestation_id);
-        self.user_attestations.set(&caller, user_atts);
+        if self.paused.get_or_default() {
+            self.env().revert(VeilError::ContractPaused);
+        }
 
-        // Emit event
-        self.env().emit_event(AttestationCreated {
-            id: attestation_id,
-            casper_address: caller,
+        let target_address = self.check_creation_request(caller, &target_chain, target_address, pow_nonce, None);
+        let request = IssueRequest {
             target_chain,
             target_address,
-            tier: tier as u8,
-            expires_at,
-        });
-
-        (attestation_id, signature)
+            attestation_type: DEFAULT_ATTESTATION_TYPE.to_string(),
+            custom_validity_secs: None,
+            carried_validity: false,
+            is_renewable: false,
+            referrer: None,
+            template: None,
+            user_secret: Some(user_secret),
+            region_hint: None,
+        };
+        self.issue_attestation(caller, request)
     }
 
-    /// Revoke an attestation
-    pub fn revoke_attestation(&mut self, attestation_id: [u8; 32]) {
+    /// Let relayers renew the caller's attestations, or stop them; see
+    /// `execute_auto_renew`
+    pub fn set_auto_renew(&mut self, enabled: bool) {
         let caller = self.env().caller();
+        self.auto_renew.set(&caller, enabled);
+    }
 
-        let mut attestation = self.attestations.get(&attestation_id)
-            .expect("Attestation not found");
+    /// Renew `user`'s attestation within `renewal_grace_secs` of its expiry
+    /// (relayers only, for users who opted in). The attestation ID commits to
+    /// the expiry, so renewal revokes it and issues a new attestation for the
+    /// same chain and target address, with the same validity and the user's
+    /// current stake. Returns the new attestation's ID and signature.
+    pub fn execute_auto_renew(&mut self, user: Address, attestation_id: [u8; 32]) -> ([u8; 32], Bytes) {
+        let caller = self.env().caller();
+        if !self.is_relayer(caller) {
+            self.env().revert(VeilError::NotRelayer);
+        }
+        if !self.get_auto_renew(user) {
+            self.env().revert(VeilError::AutoRenewDisabled);
+        }
+        if self.paused.get_or_default() {
+            self.env().revert(VeilError::ContractPaused);
+        }
 
-        assert!(attestation.casper_address == caller, "Not your attestation");
-        assert!(!attestation.revoked, "Already revoked");
+        let attestation = self.load_attestation(&attestation_id)
+            .unwrap_or_else(|| self.env().revert(VeilError::AttestationNotFound));
+        if attestation.casper_address != user {
+            self.env().revert(VeilError::NotAttestationOwner);
+        }
+        if attestation.revoked {
+            self.env().revert(VeilError::AlreadyRevoked);
+        }
+        if !attestation.is_renewable {
+            self.env().revert(VeilError::AttestationNotRenewable);
+        }
+        if self.pending_revocations.get(&attestation_id).is_some() {
+            self.env().revert(VeilError::RevocationAlreadyPending);
+        }
 
-        attestation.revoked = true;
-        self.attestations.set(&attestation_id, attestation);
+        let now = self.env().get_block_time();
+        let window_opens = attestation.expires_at.saturating_sub(self.get_renewal_grace_secs() * 1000);
+        if now < window_opens || now >= attestation.expires_at {
+            self.env().revert(VeilError::OutsideRenewalWindow);
+        }
 
-        self.env().emit_event(AttestationRevoked {
-            id: attestation_id,
-            casper_address: caller,
+        let mut record = self.get_reputation_record(user);
+        record.completed_periods += 1;
+        self.reputation.set(&user, record);
+        let validity_secs = attestation.attestation_validity_secs;
+        let (renewed_id, signature) =
+            self.supersede(attestation_id, attestation, validity_secs, SUPERSEDED_BY_RENEWAL);
+
+        self.env().emit_event(AttestationRenewed {
+            old_id: attestation_id,
+            new_id: renewed_id,
+            casper_address: user,
         });
+        self.env().emit_event(AutoRenewExecuted {
+            user,
+            attestation_id,
+            renewed_id,
+            renewed_by: caller,
+        });
+
+        (renewed_id, signature)
     }
 
-    // ============ VIEW FUNCTIONS ============
+    /// Push one of the caller's attestations' expiry out by `additional_secs`,
+    /// capped at the configured validity from now. The ID commits to the
+    /// expiry, so this revokes the attestation and issues a new one with the
+    /// current stake. Returns the new attestation's ID and signature.
+    pub fn extend_attestation(&mut self, id: [u8; 32], additional_secs: u64) -> ([u8; 32], Bytes) {
+        let attestation = self.load_supersedable_attestation(id);
+        let remaining_secs = (attestation.expires_at - self.env().get_block_time()) / 1000;
+        let (new_id, signature) =
+            self.supersede(id, attestation, remaining_secs + additional_secs, SUPERSEDED_BY_EXTENSION);
+
+        let new_expires_at = self.load_attestation(&new_id).map(|extended| extended.expires_at).unwrap_or_default();
+        self.env().emit_event(AttestationExtended { id, new_id, new_expires_at });
+        (new_id, signature)
+    }
 
-    /// Get attestation by ID
-    pub fn get_attestation(&self, id: [u8; 32]) -> Option<Attestation> {
-        self.attestations.get(&id)
+    /// Reissue one of the caller's attestations with their current stake and
+    /// tier, keeping its expiry. Revokes the attestation; returns the new
+    /// attestation's ID and signature.
+    pub fn refresh_attestation(&mut self, id: [u8; 32]) -> ([u8; 32], Bytes) {
+        let attestation = self.load_supersedable_attestation(id);
+        let remaining_secs = (attestation.expires_at - self.env().get_block_time()) / 1000;
+        self.supersede(id, attestation, remaining_secs, SUPERSEDED_BY_REFRESH)
     }
 
-    /// Get all attestations for a user
-    pub fn get_user_attestations(&self, user: Address) -> Vec<Attestation> {
-        let ids = self.user_attestations.get(&user).unwrap_or_default();
-        ids.iter()
-            .filter_map(|id| self.attestations.get(id))
-            .collect()
+    /// Rule out renewal of one of the caller's attestations. There is no way
+    /// back; doing it again changes nothing.
+    pub fn make_attestation_non_renewable(&mut self, id: [u8; 32]) {
+        let mut attestation = self.load_owned_attestation(id);
+        if !attestation.is_renewable {
+            return;
+        }
+
+        attestation.is_renewable = false;
+        self.store_attestation(attestation);
+        self.env().emit_event(AttestationRenewabilityChanged {
+            id,
+            is_renewable: false,
+        });
     }
 
-    /// Get user's current tier
-    pub fn get_user_tier(&self, user: Address) -> Tier {
-        let stake = self.query_user_stake(user);
-        self.calculate_tier(stake)
+    /// Suspend one of the caller's attestations: it is invalid and not
+    /// exported to EVM until `unfreeze_attestation`. Revoked and expired
+    /// attestations cannot be frozen.
+    pub fn freeze_attestation(&mut self, id: [u8; 32]) {
+        let mut attestation = self.load_owned_attestation(id);
+        if attestation.frozen {
+            self.env().revert(VeilError::AlreadyFrozen);
+        }
+        if self.is_revocation_effective(&attestation) {
+            self.env().revert(VeilError::AlreadyRevoked);
+        }
+        if self.env().get_block_time() >= attestation.expires_at {
+            self.env().revert(VeilError::AttestationExpired);
+        }
+
+        attestation.frozen = true;
+        self.store_attestation(attestation);
+        self.env().emit_event(AttestationFrozen { id });
     }
 
-    /// Get the signer's Ethereum-style address
-    pub fn get_signer_address(&self) -> [u8; 20] {
-        let pubkey = self.signer_public_key.get().expect("Signer not set");
-        let hash = self.keccak256(&pubkey);
-        let mut addr = [0u8; 20];
-        addr.copy_from_slice(&hash[12..32]);
-        addr
+    /// Lift the suspension of one of the caller's frozen attestations
+    pub fn unfreeze_attestation(&mut self, id: [u8; 32]) {
+        let mut attestation = self.load_owned_attestation(id);
+        if !attestation.frozen {
+            self.env().revert(VeilError::NotFrozen);
+        }
+
+        attestation.frozen = false;
+        self.store_attestation(attestation);
+        self.env().emit_event(AttestationUnfrozen { id });
     }
 
-    /// Get ABI-encoded attestation data for EVM submission
-    /// Returns (encoded_attestation, signature) that can be directly submitted to VeilVerifier
-    pub fn get_attestation_for_evm(&self, id: [u8; 32]) -> Option<(Bytes, Bytes)> {
-        let attestation = self.attestations.get(&id)?;
+    /// Revoke an attestation. With a revocation delay set, this only requests
+    /// the revocation; `finalize_revocation` completes it once the delay passes.
+    pub fn revoke_attestation(&mut self, attestation_id: [u8; 32]) {
+        let caller = self.env().caller();
 
-        // Reconstruct the payload
-        let payload = AttestationPayload {
-            casper_address_hash: self.hash_address(attestation.casper_address),
-            target_chain: attestation.target_chain.clone(),
-            target_address: attestation.target_address.clone(),
-            stake_amount: attestation.stake_amount,
-            tier: attestation.tier as u8,
-            account_age_days: attestation.account_age_days,
-            created_at: attestation.created_at,
-            expires_at: attestation.expires_at,
-            nonce: attestation.nonce,
-        };
+        let attestation = self.load_attestation(&attestation_id)
+            .unwrap_or_else(|| self.env().revert(VeilError::AttestationNotFound));
+
+        if attestation.casper_address != caller {
+            self.env().revert(VeilError::NotAttestationOwner);
+        }
+        if attestation.revoked {
+            self.env().revert(VeilError::AlreadyRevoked);
+        }
 
-        // ABI encode
-        let encoded = self.abi_encode_payload(&payload);
+        let delay_secs = self.revocation_delay_secs.get_or_default();
+        if delay_secs == 0 {
+            self.revoke(attestation_id, attestation);
+            return;
+        }
 
-        // Sign
-        let attestation_id = self.keccak256(&encoded);
-        let signature = self.sign_message(&attestation_id);
+        if self.pending_revocations.get(&attestation_id).is_some() {
+            self.env().revert(VeilError::RevocationAlreadyPending);
+        }
+        let effective_at = self.env().get_block_time() + delay_secs * 1000;
+        self.pending_revocations.set(&attestation_id, effective_at);
 
-        Some((Bytes::from(encoded), signature))
+        self.env().emit_event(RevocationPending {
+            id: attestation_id,
+            effective_at,
+        });
     }
 
-    // ============ INTERNAL FUNCTIONS ============
+    /// Complete a requested revocation once its delay has passed (any caller)
+    pub fn finalize_revocation(&mut self, attestation_id: [u8; 32]) {
+        let Some(effective_at) = self.pending_revocations.get(&attestation_id) else {
+            self.env().revert(VeilError::NoPendingRevocation);
+        };
+        if self.env().get_block_time() < effective_at {
+            self.env().revert(VeilError::RevocationNotYetEffective);
+        }
+
+        let attestation = self.load_attestation(&attestation_id)
+            .unwrap_or_else(|| self.env().revert(VeilError::AttestationNotFound));
+        if attestation.revoked {
+            self.env().revert(VeilError::AlreadyRevoked);
+        }
 
-    fn query_user_stake(&self, _user: Address) -> U512 {
-        // TODO: Query System Auction for user's delegated stake
-        // For MVP, return placeholder
-        U512::zero()
+        self.revoke(attestation_id, attestation);
     }
 
-    fn calculate_tier(&self, stake_motes: U512) -> Tier {
-        let stake_cspr = stake_motes / U512::from(1_000_000_000u64);
+    /// Replace an attestation's tags (owner or admin). Tags are metadata for
+    /// search only and do not change the signed payload.
+    pub fn set_attestation_tags(&mut self, id: [u8; 32], tags: Vec<String>) {
+        let caller = self.env().caller();
+        let mut attestation = self.load_attestation(&id)
+            .unwrap_or_else(|| self.env().revert(VeilError::AttestationNotFound));
 
-        if stake_cspr >= U512::from(100_000u64) {
-            Tier::Platinum
-        } else if stake_cspr >= U512::from(10_000u64) {
-            Tier::Gold
-        } else if stake_cspr >= U512::from(1_000u64) {
-            Tier::Silver
-        } else if stake_cspr >= U512::from(100u64) {
-            Tier::Bronze
-        } else {
-            Tier::None
+        if caller != attestation.casper_address && Some(caller) != self.admin.get() {
+            self.env().revert(VeilError::NotOwnerOrAdmin);
+        }
+        if let Err(error) = check_tags(&tags, self.get_max_tags()) {
+            self.env().revert(error);
         }
-    }
 
-    fn hash_address(&self, address: Address) -> [u8; 32] {
-        // Get the account hash bytes from the Address
-        // Address in Odra wraps an AccountHash
-        let bytes = address.to_string().into_bytes();
-        self.keccak256(&bytes)
+        let owner = attestation.casper_address;
+        for tag in attestation.tags.iter().filter(|tag| !tags.contains(tag)) {
+            let key = tag_index_key(&owner, tag);
+            let mut ids = self.tag_index.get(&key).unwrap_or_default();
+            ids.retain(|tagged| *tagged != id);
+            self.tag_index.set(&key, ids);
+        }
+        for tag in tags.iter().filter(|tag| !attestation.tags.contains(tag)) {
+            let key = tag_index_key(&owner, tag);
+            let mut ids = self.tag_index.get(&key).unwrap_or_default();
+            ids.push(id);
+            self.tag_index.set(&key, ids);
+        }
+
+        attestation.tags = tags;
+        self.store_attestation(attestation);
     }
 
-    fn keccak256(&self, data: &[u8]) -> [u8; 32] {
-        let mut hasher = Keccak256::new();
-        hasher.update(data);
-        let result = hasher.finalize();
-        let mut output = [0u8; 32];
-        output.copy_from_slice(&result);
-        output
+    /// Attach a witness co-signature (any caller). The signature must be an
+    /// EIP-191 signature over the attestation ID, i.e. the keccak256 of the
+    /// ABI-encoded payload, by a trusted witness.
+    pub fn add_witness(&mut self, attestation_id: [u8; 32], witness_signature: Bytes) {
+        let mut attestation = self.load_attestation(&attestation_id)
+            .unwrap_or_else(|| self.env().revert(VeilError::AttestationNotFound));
+        if attestation.revoked {
+            self.env().revert(VeilError::AlreadyRevoked);
+        }
+        if attestation.witness_address.is_some() {
+            self.env().revert(VeilError::AlreadyWitnessed);
+        }
+
+        let witness = encoding::recover_signer(&eth_signed_message_hash(&attestation_id), &witness_signature)
+            .unwrap_or_else(|| self.env().revert(VeilError::InvalidWitnessSignature));
+        if !self.is_trusted_witness(witness) {
+            self.env().revert(VeilError::UntrustedWitness);
+        }
+
+        attestation.witness_address = Some(witness);
+        attestation.witness_signature = Some(witness_signature);
+        self.store_attestation(attestation);
+
+        self.env().emit_event(WitnessAdded { attestation_id, witness });
     }
 
-    fn abi_encode_payload(&self, payload: &AttestationPayload) -> Vec<u8> {
-        // Layout:
-        // [0]    bytes32 casperAddressHash   - 32 bytes
-        // [1]    offset to targetChain       - 32 bytes (pointer)
-        // [2]    offset to targetAddress     - 32 bytes (pointer)
-        // [3]    uint256 stake               - 32 bytes
-        // [4]    uint8 tier (as uint256)     - 32 bytes
-        // [5]    uint64 accountAgeDays       - 32 bytes
-        // [6]    uint64 createdAt            - 32 bytes
-        // [7]    uint64 expiresAt            - 32 bytes
-        // [8]    uint64 nonce                - 32 bytes
-        // [9+]   dynamic data for strings
+    /// Attach a claim signed by a registered provider (any caller): an
+    /// EIP-191 signature over `encoding::claim_digest(attestation_id,
+    /// claim_type, claim_data)`. The claim is folded into the claims hash of
+    /// the attestation's EVM payload, which is re-signed; its EVM ID then
+    /// differs from `attestation_id`, see `get_evm_attestation_id`. Returns
+    /// the claim ID.
+    pub fn attach_claim(
+        &mut self,
+        attestation_id: [u8; 32],
+        claim_type: u8,
+        claim_data: Bytes,
+        provider_sig: Bytes,
+    ) -> u64 {
+        let attestation = self.load_attestation(&attestation_id)
+            .unwrap_or_else(|| self.env().revert(VeilError::AttestationNotFound));
+        if attestation.revoked {
+            self.env().revert(VeilError::AlreadyRevoked);
+        }
 
-        let mut encoded = Vec::new();
+        let (claim, claims_hash) = self.claims.attach(attestation_id, claim_type, claim_data, provider_sig);
+
+        // Re-sign the payload now carrying the new claims hash; an external
+        // signer has to submit a new signature, the cached one is stale
+        #[cfg(not(feature = "no-onchain-signing"))]
+        self.sign_payload(&attestation);
+        #[cfg(feature = "no-onchain-signing")]
+        self.attestation_signature_versions.set(&attestation_id, 0);
+
+        self.env().emit_event(ClaimAttached {
+            claim_id: claim.id,
+            attestation_id,
+            provider: claim.provider,
+            claim_type,
+            claims_hash,
+        });
+        claim.id
+    }
 
-        // [0] bytes32 casperAddressHash
-        encoded.extend_from_slice(&payload.casper_address_hash);
+    /// Record that an EVM verifier consumed an attestation in `evm_tx_hash`
+    /// (anyone). `confirmation_sig` is the confirmation signer's EIP-191
+    /// signature over `encoding::confirmation_digest`.
+    pub fn confirm_verified(&mut self, attestation_id: [u8; 32], evm_tx_hash: [u8; 32], confirmation_sig: Bytes) {
+        let mut attestation = self.load_attestation(&attestation_id)
+            .unwrap_or_else(|| self.env().revert(VeilError::AttestationNotFound));
+        if attestation.verified_on_evm_at.is_some() {
+            self.env().revert(VeilError::AlreadyConfirmed);
+        }
+        let digest = encoding::confirmation_digest(&attestation_id, &evm_tx_hash);
+        let signer = encoding::recover_signer(&eth_signed_message_hash(&digest), &confirmation_sig);
+        if signer.is_none() || signer != self.get_confirmation_signer() {
+            self.env().revert(VeilError::InvalidConfirmationSignature);
+        }
 
-        // Calculate offsets for dynamic data
-        // Head size = 9 slots × 32 bytes = 288 bytes
-        let head_size = 9 * 32;
-        let chain_offset = head_size;
-        let chain_len = payload.target_chain.len();
-        let chain_padded = ((chain_len + 31) / 32) * 32;
-        let address_offset = chain_offset + 32 + chain_padded; // length slot + padded data
+        let verified_on_evm_at = self.env().get_block_time();
+        attestation.verified_on_evm_at = Some(verified_on_evm_at);
+        attestation.evm_tx_hash = Some(evm_tx_hash);
+        self.store_attestation(attestation);
+        self.env().emit_event(AttestationConfirmed { attestation_id, evm_tx_hash, verified_on_evm_at });
+    }
 
-        // [1] offset to targetChain
-        encoded.extend_from_slice(&pad_left_32(&chain_offset.to_be_bytes()));
+    /// Mark `user`'s attestations older than `max_attestation_age_secs` stale
+    /// (anyone), so they are no longer valid even before expiring. Returns
+    /// the number newly marked.
+    pub fn mark_stale_attestations(&mut self, user: Address) -> u32 {
+        let max_age_secs = self.get_max_attestation_age_secs();
+        if max_age_secs == 0 {
+            return 0;
+        }
+        let now = self.env().get_block_time();
+        let mut marked = 0;
+        for mut attestation in self.get_user_attestations(user) {
+            if attestation.stale || now.saturating_sub(attestation.created_at) <= max_age_secs.saturating_mul(1000) {
+                continue;
+            }
+            attestation.stale = true;
+            let id = attestation.id;
+            self.store_attestation(attestation);
+            self.env().emit_event(AttestationMarkedStale { id });
+            marked += 1;
+        }
+        marked
+    }
 
-        // [2] offset to targetAddress
-        encoded.extend_from_slice(&pad_left_32(&address_offset.to_be_bytes()));
+    /// Challenge an unrevoked attestation, bonding exactly `get_dispute_bond`
+    /// motes (anyone). Returns the dispute ID.
+    #[odra(payable)]
+    pub fn open_dispute(&mut self, attestation_id: [u8; 32], reason: String) -> u64 {
+        let bond = self.env().attached_value();
+        if bond != self.get_dispute_bond() {
+            self.env().revert(VeilError::InvalidDisputeBond);
+        }
+        let attestation = self.load_attestation(&attestation_id)
+            .unwrap_or_else(|| self.env().revert(VeilError::AttestationNotFound));
+        if attestation.revoked {
+            self.env().revert(VeilError::AlreadyRevoked);
+        }
 
-        // [3] uint256 stake - convert U512 to 32 bytes big-endian
-        let stake_bytes = u512_to_bytes32(&payload.stake_amount);
-        encoded.extend_from_slice(&stake_bytes);
+        let challenger = self.env().caller();
+        let dispute_id = self.disputes.open(attestation_id, challenger, bond, reason.clone());
+        self.env().emit_event(DisputeOpened { dispute_id, attestation_id, challenger, bond, reason });
+        dispute_id
+    }
 
-        // [4] uint8 tier (encoded as uint256)
-        encoded.extend_from_slice(&pad_left_32(&[payload.tier]));
+    /// Resolve an open dispute (admin or arbiter). Upheld: the attestation
+    /// is revoked and the bond returned to the challenger. Rejected: the
+    /// bond is forfeited to the treasury.
+    pub fn resolve_dispute(&mut self, dispute_id: u64, upheld: bool) {
+        let caller = self.env().caller();
+        if Some(caller) != self.admin.get() && Some(caller) != self.get_arbiter() {
+            self.env().revert(VeilError::NotArbiter);
+        }
+        self.record_admin_action("resolve_dispute", caller);
+
+        let dispute = self.disputes.close(dispute_id, upheld);
+        let bond_recipient = if upheld {
+            if let Some(attestation) = self.load_attestation(&dispute.attestation_id).filter(|a| !a.revoked) {
+                let owner = attestation.casper_address;
+                self.revoke(dispute.attestation_id, attestation);
+                let mut record = self.get_reputation_record(owner);
+                record.admin_revocations += 1;
+                self.reputation.set(&owner, record);
+            }
+            dispute.challenger
+        } else {
+            self.get_treasury()
+        };
+        self.env().transfer_tokens(&bond_recipient, &dispute.bond);
 
-        // [5] uint64 accountAgeDays
-        encoded.extend_from_slice(&pad_left_32(&payload.account_age_days.to_be_bytes()));
+        self.env().emit_event(DisputeResolved {
+            dispute_id,
+            attestation_id: dispute.attestation_id,
+            upheld,
+            bond_recipient,
+        });
+    }
 
-        // [6] uint64 createdAt
-        encoded.extend_from_slice(&pad_left_32(&payload.created_at.to_be_bytes()));
+    // ============ ADMIN FUNCTIONS ============
 
-        // [7] uint64 expiresAt
-        encoded.extend_from_slice(&pad_left_32(&payload.expires_at.to_be_bytes()));
+    /// Set the admin contact metadata (admin only)
+    pub fn set_admin_contact(&mut self, contact: String) {
+        self.admin_action("set_admin_contact");
 
-        // [8] uint64 nonce
-        encoded.extend_from_slice(&pad_left_32(&payload.nonce.to_be_bytes()));
+        if contact.len() > MAX_ADMIN_CONTACT_LEN {
+            self.env().revert(VeilError::ContactTooLong);
+        }
+        if !contact.bytes().all(|b| (0x20..=0x7E).contains(&b)) {
+            self.env().revert(VeilError::InvalidContact);
+        }
 
-        // Dynamic data: targetChain
-        let chain_bytes = payload.target_chain.as_bytes();
-        encoded.extend_from_slice(&pad_left_32(&chain_bytes.len().to_be_bytes()));
-        encoded.extend_from_slice(chain_bytes);
-        // Pad to 32-byte boundary
-        let padding = chain_padded - chain_len;
-        encoded.extend_from_slice(&alloc::vec![0u8; padding]);
+        let new_contact_hash = keccak256(contact.as_bytes());
+        self.admin_contact.set(contact);
 
-        // Dynamic data: targetAddress
-        let addr_bytes = payload.target_address.as_bytes();
-        let addr_padded = ((addr_bytes.len() + 31) / 32) * 32;
-        encoded.extend_from_slice(&pad_left_32(&addr_bytes.len().to_be_bytes()));
-        encoded.extend_from_slice(addr_bytes);
-        let addr_padding = addr_padded - addr_bytes.len();
-        encoded.extend_from_slice(&alloc::vec![0u8; addr_padding]);
+        self.env().emit_event(AdminContactUpdated { new_contact_hash });
+    }
 
-        encoded
+    /// Set or clear the emergency admin (admin only)
+    pub fn set_emergency_admin(&mut self, addr: Option<Address>) {
+        self.admin_action("set_emergency_admin");
+        self.emergency_admin.set(addr);
+        self.env().emit_event(EmergencyAdminSet { address: addr });
     }
 
-    fn sign_message(&self, message_hash: &[u8; 32]) -> Bytes {
-        // Ethereum personal_sign prefix
-        let prefix = b"\x19Ethereum Signed Message:\n32";
-        let mut prefixed = Vec::with_capacity(prefix.len() + 32);
-        prefixed.extend_from_slice(prefix);
-        prefixed.extend_from_slice(message_hash);
+    /// Register a new attestation type users may create attestations of
+    /// (admin only)
+    pub fn register_attestation_type(&mut self, attestation_type: String) {
+        self.admin_action("register_attestation_type");
+        if let Err(error) = check_attestation_type(&attestation_type) {
+            self.env().revert(error);
+        }
+        if self.is_attestation_type_registered(attestation_type.clone()) {
+            self.env().revert(VeilError::AttestationTypeAlreadyRegistered);
+        }
+        self.add_attestation_type(attestation_type.clone());
+        self.env().emit_event(AttestationTypeRegistered {
+            attestation_type,
+            registered_by: self.env().caller(),
+        });
+    }
 
-        // Hash the prefixed message
-        let eth_hash = self.keccak256(&prefixed);
+    /// Add an active template for `create_attestation_from_template` (admin
+    /// only). Returns its ID; IDs start at 1.
+    pub fn create_template(&mut self, target_chain: String, min_tier: u8, validity_secs: u64, tag: String) -> u64 {
+        self.admin_action("create_template");
+        let template_id = self.template_count.get_or_default() + 1;
+        self.template_count.set(template_id);
+        self.save_template(Template {
+            id: template_id,
+            target_chain,
+            min_tier,
+            validity_secs,
+            tag,
+            active: true,
+        });
+        template_id
+    }
 
-        // Get private key and sign
-        let private_key = self.signer_private_key.get().expect("Signer not set");
-        let signing_key = SigningKey::from_bytes(&private_key.into()).expect("Invalid key");
+    /// Change a template's constraints (admin only). Attestations already
+    /// created from it are unchanged.
+    pub fn update_template(
+        &mut self,
+        template_id: u64,
+        target_chain: String,
+        min_tier: u8,
+        validity_secs: u64,
+        tag: String,
+    ) {
+        self.admin_action("update_template");
+        let template = self.get_template(template_id)
+            .unwrap_or_else(|| self.env().revert(VeilError::TemplateNotFound));
+        self.save_template(Template {
+            target_chain,
+            min_tier,
+            validity_secs,
+            tag,
+            ..template
+        });
+    }
 
-        // Sign with recoverable signature
-        let (signature, recovery_id) = signing_key
-            .sign_prehash_recoverable(&eth_hash)
-            .expect("Signing failed");
+    /// Activate or deactivate a template (admin only). Deactivated templates
+    /// take no new attestations; renewals of earlier ones still go through.
+    pub fn set_template_active(&mut self, template_id: u64, active: bool) {
+        self.admin_action("set_template_active");
+        let template = self.get_template(template_id)
+            .unwrap_or_else(|| self.env().revert(VeilError::TemplateNotFound));
+        self.save_template(Template { active, ..template });
+    }
 
-        // Return 65-byte signature: r (32) + s (32) + v (1)
-        let mut sig_bytes = Vec::with_capacity(65);
-        sig_bytes.extend_from_slice(&signature.to_bytes());
-        sig_bytes.push(recovery_id.to_byte() + 27); // v = recovery_id + 27
+    /// Block or unblock `referrer` from being named on new attestations;
+    /// existing referrals are kept (admin only)
+    pub fn set_referrer_blocked(&mut self, referrer: Address, blocked: bool) {
+        self.admin_action("set_referrer_blocked");
+        let old_value = self.is_referrer_blocked(referrer).render();
+        self.blocked_referrers.set(&referrer, blocked);
+        self.config_changed(format!("referrer_blocked:{}", referrer.render()), old_value, blocked.render());
+    }
+
+    /// Set the reputation scoring weights; scores are recomputed with them (admin only)
+    pub fn set_reputation_weights(&mut self, weights: ReputationWeights) {
+        self.admin_action("set_reputation_weights");
+        let old_value = self.get_reputation_weights().render();
+        self.reputation_weights.set(weights);
+        self.config_changed("reputation_weights".to_string(), old_value, weights.render());
+    }
+
+    /// Set or clear a tier for `user` that overrides their stake-based tier (admin only)
+    pub fn set_custom_tier(&mut self, user: Address, tier: Option<Tier>) {
+        self.admin_action("set_custom_tier");
+        self.custom_tiers.set(&user, tier);
+        self.env().emit_event(CustomTierSet { user, tier, set_by: self.env().caller() });
+    }
+
+    /// Set the motes bonded to open a dispute; open disputes keep their bond (admin only)
+    pub fn set_dispute_bond(&mut self, motes: U512) {
+        self.admin_action("set_dispute_bond");
+        let old_value = self.get_dispute_bond().render();
+        self.dispute_bond.set(motes);
+        self.config_changed("dispute_bond".to_string(), old_value, motes.render());
+    }
+
+    /// Set the motes each attestation creation pays to the treasury; 0 makes
+    /// creation free (admin only, not while upgrade-locked)
+    pub fn set_attestation_fee(&mut self, motes: U512) {
+        self.admin_action("set_attestation_fee");
+        self.check_upgrade_lock();
+        let old_value = self.get_attestation_fee().render();
+        self.attestation_fee.set(motes);
+        self.config_changed("attestation_fee".to_string(), old_value, motes.render());
+    }
+
+    /// Set or clear the dispute arbiter (admin only)
+    pub fn set_arbiter(&mut self, arbiter: Option<Address>) {
+        self.admin_action("set_arbiter");
+        let old_value = self.get_arbiter().render();
+        self.arbiter.set(arbiter);
+        self.config_changed("arbiter".to_string(), old_value, arbiter.render());
+    }
+
+    /// Set or clear the recipient of forfeited dispute bonds (admin only)
+    pub fn set_treasury(&mut self, treasury: Option<Address>) {
+        self.admin_action("set_treasury");
+        let old_value = self.get_treasury().render();
+        self.treasury.set(treasury);
+        self.config_changed("treasury".to_string(), old_value, treasury.render());
+    }
+
+    /// Set or clear the contract queried for delegated stake, see
+    /// `stake_source::StakeSource` (admin only)
+    pub fn set_stake_source(&mut self, source: Option<Address>) {
+        self.admin_action("set_stake_source");
+        let old_value = self.get_stake_source().render();
+        self.stake_source.set(source);
+        self.config_changed("stake_source".to_string(), old_value, source.render());
+    }
+
+    /// Set or clear the `VeilStaking` contract queried for locked CSPR (admin only)
+    pub fn set_locked_stake_source(&mut self, source: Option<Address>) {
+        self.admin_action("set_locked_stake_source");
+        let old_value = self.get_locked_stake_source().render();
+        self.locked_stake_source.set(source);
+        self.config_changed("locked_stake_source".to_string(), old_value, source.render());
+    }
+
+    /// Set or clear the badge collection, see `badge::BadgeCollection` (admin only)
+    pub fn set_badge_collection(&mut self, collection: Option<Address>) {
+        self.admin_action("set_badge_collection");
+        let old_value = self.get_badge_collection().render();
+        self.badge_collection.set(collection);
+        self.config_changed("badge_collection".to_string(), old_value, collection.render());
+    }
+
+    /// Mint a badge to the owner of each new attestation while a badge
+    /// collection is set (admin only)
+    pub fn set_mint_badges(&mut self, enabled: bool) {
+        self.admin_action("set_mint_badges");
+        let old_value = self.get_mint_badges().render();
+        self.mint_badges.set(enabled);
+        self.config_changed("mint_badges".to_string(), old_value, enabled.render());
+    }
+
+    /// Set or clear the reward token, see `reward_token::RewardToken` (admin only)
+    pub fn set_reward_token(&mut self, token: Option<Address>) {
+        self.admin_action("set_reward_token");
+        let old_value = self.get_reward_token().render();
+        self.reward_token.set(token);
+        self.config_changed("reward_token".to_string(), old_value, token.render());
+    }
+
+    /// Turn rewards for new attestations on or off (admin only)
+    pub fn set_rewards_enabled(&mut self, enabled: bool) {
+        self.admin_action("set_rewards_enabled");
+        let old_value = self.get_rewards_enabled().render();
+        self.rewards_enabled.set(enabled);
+        self.config_changed("rewards_enabled".to_string(), old_value, enabled.render());
+    }
+
+    /// Set the reward before the tier multiplier (admin only)
+    pub fn set_base_reward(&mut self, amount: U256) {
+        self.admin_action("set_base_reward");
+        let old_value = self.get_base_reward().render();
+        self.base_reward.set(amount);
+        self.config_changed("base_reward".to_string(), old_value, amount.render());
+    }
+
+    /// Set the per-tier reward multipliers (admin only)
+    pub fn set_reward_multipliers(&mut self, multipliers: RewardMultipliers) {
+        self.admin_action("set_reward_multipliers");
+        let old_value = self.get_reward_multipliers().render();
+        self.reward_multipliers.set(multipliers);
+        self.config_changed("reward_multipliers".to_string(), old_value, multipliers.render());
+    }
+
+    /// Cap the total rewards ever minted; rewards past it are skipped (admin only)
+    pub fn set_reward_emission_cap(&mut self, cap: U256) {
+        self.admin_action("set_reward_emission_cap");
+        let old_value = self.get_reward_emission_cap().render();
+        self.reward_emission_cap.set(cap);
+        self.config_changed("reward_emission_cap".to_string(), old_value, cap.render());
+    }
+
+    /// Choose which stake counts towards tiers (admin only)
+    pub fn set_stake_policy(&mut self, policy: StakePolicy) {
+        self.admin_action("set_stake_policy");
+        let old_value = self.get_stake_policy().render();
+        self.stake_policy.set(policy);
+        self.config_changed("stake_policy".to_string(), old_value, policy.render());
+    }
+
+    /// Resume attestation creation (admin only)
+    pub fn unpause(&mut self) {
+        self.admin_action("unpause");
+        let old_value = self.is_paused().render();
+        self.paused.set(false);
+        self.config_changed("paused".to_string(), old_value, false.render());
+    }
+
+    /// Replace the signing key (admin only); see `SignerKey`
+    pub fn rotate_signer(&mut self, key: SignerKey) {
+        self.admin_action("rotate_signer");
+        self.check_upgrade_lock();
+        self.rotate_signer_key(key);
+    }
+
+    /// Hand the admin role to `new_admin` (admin only). Takes effect at once;
+    /// the caller loses every admin permission.
+    pub fn transfer_admin(&mut self, new_admin: Address) {
+        self.admin_action("transfer_admin");
+        let old_admin = self.env().caller();
+        self.admin.set(new_admin);
+        self.env().emit_event(AdminTransferred { old_admin, new_admin });
+    }
+
+    /// Set how long new attestations are valid, in seconds (admin only).
+    /// Must be at least `get_min_validity_secs`; existing attestations keep
+    /// their expiry.
+    pub fn set_attestation_validity_secs(&mut self, secs: u64) {
+        self.admin_action("set_attestation_validity_secs");
+        if secs < self.get_min_validity_secs() {
+            self.env().revert(VeilError::ValidityTooShort);
+        }
+        let old_secs = self.get_attestation_validity_secs();
+        self.attestation_validity_secs.set(secs);
+        self.env().emit_event(ValidityPeriodChanged { old_secs, new_secs: secs, by: self.env().caller() });
+    }
+
+    /// Hash new attestation IDs with `encoding::HASH_ALGORITHM_*` `algo`
+    /// (admin only). Existing attestations keep the algorithm they were
+    /// created with, which their payload names.
+    pub fn set_hash_algorithm(&mut self, algo: u8) {
+        self.admin_action("set_hash_algorithm");
+        self.check_upgrade_lock();
+        if encoding::hash(algo, &[]).is_none() {
+            self.env().revert(VeilError::UnsupportedHashAlgorithm);
+        }
+        let old_algorithm = self.get_hash_algorithm();
+        self.hash_algorithm.set(algo);
+        self.env().emit_event(HashAlgorithmChanged { old_algorithm, new_algorithm: algo });
+    }
+
+    /// Sign new payloads' `created_at`/`expires_at` in `TIME_UNIT_*` `unit`
+    /// (admin only). Must match the target verifier: VeilVerifier's
+    /// `timeUnit` uses the same values. Existing attestations keep the unit they
+    /// were signed with. Storage always keeps block time (ms).
+    pub fn set_time_unit(&mut self, unit: u8) {
+        self.admin_action("set_time_unit");
+        if unit != TIME_UNIT_MILLIS && unit != TIME_UNIT_SECONDS {
+            self.env().revert(VeilError::InvalidTimeUnit);
+        }
+        let old_unit = self.get_time_unit();
+        self.time_unit.set(unit);
+        self.env().emit_event(TimeUnitChanged { old_unit, new_unit: unit, by: self.env().caller() });
+    }
+
+    /// Accept EVM confirmations signed by `signer`, or none (admin only)
+    pub fn set_confirmation_signer(&mut self, signer: Option<[u8; 20]>) {
+        self.admin_action("set_confirmation_signer");
+        self.check_upgrade_lock();
+        let old_value = self.get_confirmation_signer().render();
+        self.confirmation_signer.set(signer);
+        self.config_changed("confirmation_signer".to_string(), old_value, signer.render());
+    }
+
+    /// Store the external signer's signature of attestation `id`: over its
+    /// current payload, personal_sign style, as `get_attestation_for_evm`
+    /// exports it (anyone; the signature must recover to the signer). Needed
+    /// after creation, after `attach_claim` and after a key rotation.
+    #[cfg(feature = "no-onchain-signing")]
+    pub fn submit_signature(&mut self, id: [u8; 32], signature: Bytes) {
+        let attestation = self.load_attestation(&id)
+            .unwrap_or_else(|| self.env().revert(VeilError::AttestationNotFound));
+        let encoded = abi_encode_payload(&self.evm_payload(&attestation));
+        let payload_hash = self.hash(attestation.hash_algorithm_version, &encoded);
+        let recovered = encoding::recover_signer(&eth_signed_message_hash(&payload_hash), &signature);
+        if recovered != Some(self.get_signer_address()) {
+            self.env().revert(VeilError::SignatureNotFromSigner);
+        }
+        self.store_signature(id, signature);
+        self.env().emit_event(AttestationSigned { id, key_version: self.signer_key_version.get_or_default() });
+    }
+
+    /// Re-sign attestations under the current signer after a rotation, so
+    /// their raw signatures verify again (admin only). At most
+    /// `max_batch_size` IDs per call; returns the new signatures in order.
+    #[cfg(not(feature = "no-onchain-signing"))]
+    pub fn batch_refresh_signatures(&mut self, ids: Vec<[u8; 32]>) -> Vec<Bytes> {
+        self.admin_action("batch_refresh_signatures");
+        if ids.len() > self.get_max_batch_size() as usize {
+            self.env().revert(VeilError::BatchTooLarge);
+        }
+
+        let key_version = self.signer_key_version.get_or_default();
+        let mut signatures = Vec::with_capacity(ids.len());
+        for id in ids {
+            let attestation = self.load_attestation(&id)
+                .unwrap_or_else(|| self.env().revert(VeilError::AttestationNotFound));
+            signatures.push(self.sign_payload(&attestation));
+        }
+
+        self.env().emit_event(SignaturesBatchRefreshed {
+            count: signatures.len() as u32,
+            key_version,
+        });
+        signatures
+    }
+
+    /// Set the maximum number of IDs per batch call (admin only)
+    pub fn set_max_batch_size(&mut self, max_batch_size: u32) {
+        self.admin_action("set_max_batch_size");
+        let old_value = self.get_max_batch_size().render();
+        self.max_batch_size.set(max_batch_size);
+        self.config_changed("max_batch_size".to_string(), old_value, max_batch_size.render());
+    }
+
+    /// Push the upgrade lock further out (admin only). The lock can only be
+    /// extended: the new end is measured from the later of now and the
+    /// current end.
+    pub fn extend_upgrade_lock(&mut self, additional_secs: u64) {
+        self.admin_action("extend_upgrade_lock");
+        let old_until = self.get_upgrade_lock_until();
+        let new_until = old_until.max(self.env().get_block_time()) + additional_secs * 1000;
+        self.upgrade_lock_until.set(new_until);
+        self.config_changed("upgrade_lock_until".to_string(), old_until.render(), new_until.render());
+    }
+
+    /// Require a proof-of-work with this many leading zero bits to create an
+    /// attestation, at most `MAX_POW_DIFFICULTY`; 0 disables it (admin only)
+    pub fn set_pow_difficulty(&mut self, bits: u8) {
+        self.admin_action("set_pow_difficulty");
+        if bits > MAX_POW_DIFFICULTY {
+            self.env().revert(VeilError::PowDifficultyTooHigh);
+        }
+        let old_value = self.get_pow_difficulty().render();
+        self.pow_difficulty.set(bits);
+        self.config_changed("pow_difficulty".to_string(), old_value, bits.render());
+    }
+
+    /// Register an EVM verifier for `chain` (admin only). It becomes the
+    /// verifier embedded in attestations created for that chain from now on.
+    pub fn add_trusted_verifier(&mut self, chain: String, verifier: [u8; 20]) {
+        self.admin_action("add_trusted_verifier");
+        let old_value = self.get_trusted_verifier(chain.clone(), verifier).render();
+        self.trusted_verifiers.set(&(chain.clone(), verifier), true);
+        self.chain_verifiers.set(&chain, verifier);
+        self.config_changed(format!("trusted_verifier:{}:{}", chain, verifier.render()), old_value, true.render());
+    }
+
+    /// Deregister an EVM verifier for `chain` (admin only). If it was the one
+    /// being embedded, new attestations for the chain name no verifier.
+    pub fn remove_trusted_verifier(&mut self, chain: String, verifier: [u8; 20]) {
+        self.admin_action("remove_trusted_verifier");
+        let old_value = self.get_trusted_verifier(chain.clone(), verifier).render();
+        self.trusted_verifiers.set(&(chain.clone(), verifier), false);
+        if self.get_chain_verifier(chain.clone()) == verifier {
+            self.chain_verifiers.set(&chain, [0u8; 20]);
+        }
+        self.config_changed(format!("trusted_verifier:{}:{}", chain, verifier.render()), old_value, false.render());
+    }
+
+    /// Set `chain`'s deployment metadata (admin only). While set, creation
+    /// for the chain requires `enabled` and a tier of at least `min_tier`.
+    /// The embedded verifier is still the one registered with `add_trusted_verifier`.
+    pub fn set_chain_deployment(&mut self, chain: String, deployment: ChainDeployment) {
+        self.admin_action("set_chain_deployment");
+        if self.get_chain_deployment(chain.clone()).is_none() {
+            let mut names = self.chain_deployment_names.get_or_default();
+            names.push(chain.clone());
+            self.chain_deployment_names.set(names);
+        }
+        self.env().emit_event(ChainDeploymentUpdated {
+            chain: chain.clone(),
+            verifier_address: deployment.verifier_address,
+            enabled: deployment.enabled,
+            min_tier: deployment.min_tier,
+        });
+        self.chain_deployments.set(&chain, Some(deployment));
+    }
+
+    /// Remove `chain`'s deployment metadata, lifting its gates (admin only)
+    pub fn remove_chain_deployment(&mut self, chain: String) {
+        self.admin_action("remove_chain_deployment");
+        if self.get_chain_deployment(chain.clone()).is_none() {
+            return;
+        }
+        let mut names = self.chain_deployment_names.get_or_default();
+        names.retain(|name| *name != chain);
+        self.chain_deployment_names.set(names);
+        self.chain_deployments.set(&chain, None);
+        self.env().emit_event(ChainDeploymentRemoved { chain });
+    }
+
+    /// Restrict new attestations to allowlisted chains, or lift the
+    /// restriction (admin only). Off by default.
+    pub fn set_allowlist_enabled(&mut self, enabled: bool) {
+        self.admin_action("set_allowlist_enabled");
+        let old_value = self.get_allowlist_enabled().render();
+        self.allowlist_enabled.set(enabled);
+        self.config_changed("allowlist_enabled".to_string(), old_value, enabled.render());
+    }
+
+    /// Require target addresses to be EIP-55 checksummed, or stop requiring
+    /// it (admin only). Existing attestations are unaffected.
+    pub fn set_require_checksum(&mut self, required: bool) {
+        self.admin_action("set_require_checksum");
+        let old_value = self.get_require_checksum().render();
+        self.require_checksum.set(required);
+        self.config_changed("require_checksum".to_string(), old_value, required.render());
+    }
+
+    /// Add `chain` to the allowlist (admin only)
+    pub fn allow_chain(&mut self, chain: String) {
+        self.admin_action("allow_chain");
+        if self.allowed_chains.get(&chain).unwrap_or_default() {
+            return;
+        }
+        self.allowed_chains.set(&chain, true);
+        let mut names = self.allowed_chain_names.get_or_default();
+        names.push(chain.clone());
+        self.allowed_chain_names.set(names);
+        self.env().emit_event(ChainAllowlistUpdated { chain, allowed: true });
+    }
+
+    /// Remove `chain` from the allowlist (admin only). Existing attestations
+    /// for it are unaffected.
+    pub fn disallow_chain(&mut self, chain: String) {
+        self.admin_action("disallow_chain");
+        if !self.allowed_chains.get(&chain).unwrap_or_default() {
+            return;
+        }
+        self.allowed_chains.set(&chain, false);
+        let mut names = self.allowed_chain_names.get_or_default();
+        names.retain(|name| *name != chain);
+        self.allowed_chain_names.set(names);
+        self.env().emit_event(ChainAllowlistUpdated { chain, allowed: false });
+    }
+
+    /// Add `region` to the region allowlist (admin only). While any region
+    /// is listed, new attestations must carry an allowlisted region hint.
+    pub fn add_allowed_region(&mut self, region: String) {
+        self.admin_action("add_allowed_region");
+        if let Err(error) = check_region_hint(&region) {
+            self.env().revert(error);
+        }
+        let key = keccak256(region.as_bytes());
+        if self.allowed_regions.get(&key).unwrap_or_default() {
+            return;
+        }
+        self.allowed_regions.set(&key, true);
+        let mut names = self.allowed_region_names.get_or_default();
+        names.push(region.clone());
+        self.allowed_region_names.set(names);
+        self.env().emit_event(RegionAllowlistUpdated { region, allowed: true });
+    }
+
+    /// Remove `region` from the region allowlist (admin only). Removing the
+    /// last one lifts the restriction; existing attestations are unaffected.
+    pub fn remove_allowed_region(&mut self, region: String) {
+        self.admin_action("remove_allowed_region");
+        let key = keccak256(region.as_bytes());
+        if !self.allowed_regions.get(&key).unwrap_or_default() {
+            return;
+        }
+        self.allowed_regions.set(&key, false);
+        let mut names = self.allowed_region_names.get_or_default();
+        names.retain(|name| *name != region);
+        self.allowed_region_names.set(names);
+        self.env().emit_event(RegionAllowlistUpdated { region, allowed: false });
+    }
+
+    /// Bring a pre-versioning deployment's storage to version 2 (admin only).
+    /// Pins the settings v1 left to code defaults, so later default changes
+    /// cannot silently alter a live deployment, and registers the default
+    /// attestation type. Reverts with `AlreadyMigrated` unless at version 1.
+    pub fn migrate_v1_to_v2(&mut self) {
+        self.admin_action("migrate_v1_to_v2");
+        let from_version = self.get_contract_version();
+        if from_version != 1 {
+            self.env().revert(VeilError::AlreadyMigrated);
+        }
+        self.min_validity_secs.set(self.get_min_validity_secs());
+        self.renewal_grace_secs.set(self.get_renewal_grace_secs());
+        self.max_tags.set(self.get_max_tags());
+        self.emit_v1_events.set(self.emit_v1_events.get().unwrap_or(true));
+        if !self.is_attestation_type_registered(DEFAULT_ATTESTATION_TYPE.to_string()) {
+            self.add_attestation_type(DEFAULT_ATTESTATION_TYPE.to_string());
+        }
+        self.storage_version.set(2);
+        self.env().emit_event(ContractMigrated { from_version, to_version: 2 });
+    }
+
+    /// Store new attestations compressed (admin only). Existing records stay
+    /// in the format they were written in.
+    pub fn set_compressed_storage(&mut self, enabled: bool) {
+        self.admin_action("set_compressed_storage");
+        let old_value = self.is_compressed_storage().render();
+        self.compressed_storage.set(enabled);
+        self.config_changed("compressed_storage".to_string(), old_value, enabled.render());
+    }
+
+    /// Set the wait between requesting and finalizing a revocation (admin only)
+    pub fn set_revocation_delay_secs(&mut self, secs: u64) {
+        self.admin_action("set_revocation_delay_secs");
+        let old_value = self.get_revocation_delay_secs().render();
+        self.revocation_delay_secs.set(secs);
+        self.config_changed("revocation_delay_secs".to_string(), old_value, secs.render());
+    }
+
+    /// Set the attestation reuse window in seconds (admin only, 0 = disabled).
+    /// Within it, `create_attestation` for a (chain, address) pair the caller
+    /// holds a usable attestation to returns that one instead.
+    pub fn set_reuse_window_secs(&mut self, secs: u64) {
+        self.admin_action("set_reuse_window_secs");
+        let old_value = self.get_reuse_window_secs().render();
+        self.reuse_window_secs.set(secs);
+        self.config_changed("reuse_window_secs".to_string(), old_value, secs.render());
+    }
+
+    /// Set how long a user's live stake reading is reused, in seconds (admin
+    /// only, 0 = disabled). Within it, new attestations carry the cached
+    /// stake with `STAKE_SOURCE_CACHED` instead of querying the stake sources.
+    pub fn set_stake_cache_secs(&mut self, secs: u64) {
+        self.admin_action("set_stake_cache_secs");
+        let old_value = self.get_stake_cache_secs().render();
+        self.stake_cache_secs.set(secs);
+        self.config_changed("stake_cache_secs".to_string(), old_value, secs.render());
+    }
+
+    /// Set the age past which attestations can be marked stale, in seconds
+    /// (admin only, 0 = disabled)
+    pub fn set_max_attestation_age_secs(&mut self, secs: u64) {
+        self.admin_action("set_max_attestation_age_secs");
+        let old_value = self.get_max_attestation_age_secs().render();
+        self.max_attestation_age_secs.set(secs);
+        self.config_changed("max_attestation_age_secs".to_string(), old_value, secs.render());
+    }
+
+    /// Allow `relayer` to call `execute_auto_renew` (admin only)
+    pub fn add_relayer(&mut self, relayer: Address) {
+        self.admin_action("add_relayer");
+        let old_value = self.is_relayer(relayer).render();
+        self.relayers.set(&relayer, true);
+        self.config_changed(format!("relayer:{}", relayer.render()), old_value, true.render());
+    }
+
+    /// Revoke `relayer`'s permission to call `execute_auto_renew` (admin only)
+    pub fn remove_relayer(&mut self, relayer: Address) {
+        self.admin_action("remove_relayer");
+        let old_value = self.is_relayer(relayer).render();
+        self.relayers.set(&relayer, false);
+        self.config_changed(format!("relayer:{}", relayer.render()), old_value, false.render());
+    }
+
+    /// Set how long before expiry relayers may renew attestations (admin only)
+    pub fn set_renewal_grace_secs(&mut self, secs: u64) {
+        self.admin_action("set_renewal_grace_secs");
+        let old_value = self.get_renewal_grace_secs().render();
+        self.renewal_grace_secs.set(secs);
+        self.config_changed("renewal_grace_secs".to_string(), old_value, secs.render());
+    }
+
+    /// Limit how many active (unrevoked, unexpired) attestations each user
+    /// may hold for `chain`; 0 blocks new ones (admin only)
+    pub fn set_chain_attestation_limit(&mut self, chain: String, limit: u32) {
+        self.admin_action("set_chain_attestation_limit");
+        let old_attestation_limit = self.get_chain_attestation_limit(chain.clone());
+        self.chain_attestation_limits.set(&keccak256(chain.as_bytes()), limit);
+        self.env().emit_event(ChainConfigChanged {
+            chain,
+            old_attestation_limit,
+            new_attestation_limit: limit,
+            by: self.env().caller(),
+        });
+    }
+
+    /// Set the network name reported by `get_deployment_info` (admin only)
+    pub fn set_network_name(&mut self, name: String) {
+        self.admin_action("set_network_name");
+        let old_value = self.network_name.get_or_default();
+        self.config_changed("network_name".to_string(), old_value, name.clone());
+        self.network_name.set(name);
+    }
+
+    /// Propose supporting `chain`; it can be confirmed after
+    /// `CHAIN_PROPOSAL_DELAY_SECS` (admin only)
+    pub fn propose_add_chain(&mut self, chain: String) {
+        self.admin_action("propose_add_chain");
+        if self.supported_chains.get_or_default().contains(&chain) {
+            self.env().revert(VeilError::ChainAlreadySupported);
+        }
+        let mut pending = self.pending_chain_names.get_or_default();
+        if pending.contains(&chain) {
+            self.env().revert(VeilError::ChainAlreadyProposed);
+        }
+
+        let now = self.env().get_block_time();
+        self.proposed_chains.set(&chain, now);
+        pending.push(chain.clone());
+        self.pending_chain_names.set(pending);
+
+        self.env().emit_event(ChainProposed { chain, activates_at: now + CHAIN_PROPOSAL_DELAY_SECS * 1000 });
+    }
+
+    /// Add a proposed `chain` to the supported chains once its proposal
+    /// period has passed (admin only)
+    pub fn confirm_add_chain(&mut self, chain: String) {
+        self.admin_action("confirm_add_chain");
+        let activates_at = self.chain_activates_at(&chain);
+        self.drop_chain_proposal(&chain);
+        if self.env().get_block_time() < activates_at {
+            self.env().revert(VeilError::ChainProposalNotReady);
+        }
+
+        let mut chains = self.supported_chains.get_or_default();
+        chains.push(chain.clone());
+        self.supported_chains.set(chains);
+
+        self.env().emit_event(ChainActivated { chain });
+    }
+
+    /// Withdraw a pending chain proposal (admin only)
+    pub fn cancel_chain_proposal(&mut self, chain: String) {
+        self.admin_action("cancel_chain_proposal");
+        self.drop_chain_proposal(&chain);
+        self.env().emit_event(ChainProposalCancelled { chain });
+    }
+
+    /// Set the target chains reported by `get_deployment_info` (admin only)
+    pub fn set_supported_chains(&mut self, chains: Vec<String>) {
+        self.admin_action("set_supported_chains");
+        let old_value = self.supported_chains.get_or_default().render();
+        self.config_changed("supported_chains".to_string(), old_value, chains.render());
+        self.supported_chains.set(chains);
+    }
+
+    /// Allow or forbid several unrevoked attestations for the same
+    /// (chain, target address) per user (admin only)
+    pub fn set_allow_duplicate_chain_address(&mut self, allowed: bool) {
+        self.admin_action("set_allow_duplicate_chain_address");
+        let old_value = self.get_allow_duplicate_chain_address().render();
+        self.allow_duplicate_chain_address.set(allowed);
+        self.config_changed("allow_duplicate_chain_address".to_string(), old_value, allowed.render());
+    }
+
+    /// Keep emitting the deprecated `AttestationCreated` next to
+    /// `AttestationCreatedV2`, or stop once indexers have moved (admin only).
+    /// The bundled relayer and indexer only follow `AttestationCreatedV2`.
+    pub fn set_emit_v1_events(&mut self, enabled: bool) {
+        self.admin_action("set_emit_v1_events");
+        let old_value = self.get_emit_v1_events().render();
+        self.emit_v1_events.set(enabled);
+        self.config_changed("emit_v1_events".to_string(), old_value, enabled.render());
+    }
+
+    /// Add or remove a trusted witness (admin only)
+    pub fn set_trusted_witness(&mut self, witness: [u8; 20], trusted: bool) {
+        self.admin_action("set_trusted_witness");
+        let old_value = self.is_trusted_witness(witness).render();
+        self.trusted_witnesses.set(&witness, trusted);
+        self.config_changed(format!("trusted_witness:{}", witness.render()), old_value, trusted.render());
+    }
+
+    /// Register a claim provider by its uncompressed secp256k1 public key
+    /// (64 bytes, without the 0x04 prefix), returning its Ethereum address
+    /// (admin only)
+    pub fn register_claim_provider(&mut self, public_key: [u8; 64]) -> [u8; 20] {
+        self.admin_action("register_claim_provider");
+        let provider = self.claims.register_provider(public_key);
+        self.env().emit_event(ClaimProviderUpdated { provider, registered: true });
+        provider
+    }
+
+    /// Stop accepting claims from `provider` (admin only). Claims it already
+    /// signed stay attached.
+    pub fn remove_claim_provider(&mut self, provider: [u8; 20]) {
+        self.admin_action("remove_claim_provider");
+        if self.claims.remove_provider(provider) {
+            self.env().emit_event(ClaimProviderUpdated { provider, registered: false });
+        }
+    }
+
+    /// Set the maximum number of tags per attestation (admin only)
+    pub fn set_max_tags(&mut self, max_tags: u8) {
+        self.admin_action("set_max_tags");
+        let old_value = self.get_max_tags().render();
+        self.max_tags.set(max_tags);
+        self.config_changed("max_tags".to_string(), old_value, max_tags.render());
+    }
+
+    /// Set the shortest custom validity users may request (admin only)
+    pub fn set_min_validity_secs(&mut self, secs: u64) {
+        self.admin_action("set_min_validity_secs");
+        let old_value = self.get_min_validity_secs().render();
+        self.min_validity_secs.set(secs);
+        self.config_changed("min_validity_secs".to_string(), old_value, secs.render());
+    }
+
+    /// Require a minimum stake to create attestations (admin only)
+    pub fn set_require_min_stake(&mut self, required: bool) {
+        self.admin_action("set_require_min_stake");
+        let old_value = self.is_min_stake_required().render();
+        self.require_min_stake.set(required);
+        self.config_changed("require_min_stake".to_string(), old_value, required.render());
+    }
+
+    /// Set the minimum stake to create attestations, in motes (admin only)
+    pub fn set_min_attestation_stake_motes(&mut self, motes: U512) {
+        self.admin_action("set_min_attestation_stake_motes");
+        let old_value = self.get_min_attestation_stake_motes().render();
+        self.min_attestation_stake_motes.set(motes);
+        self.config_changed("min_attestation_stake_motes".to_string(), old_value, motes.render());
+    }
+
+    /// Update the minimum stake (whole CSPR) for each tier (admin only)
+    pub fn update_tier_thresholds(&mut self, thresholds: TierThresholds) {
+        self.admin_action("update_tier_thresholds");
+        self.check_upgrade_lock();
+        self.check_tier_thresholds(&thresholds);
+
+        let old_thresholds = self.get_tier_thresholds();
+        self.tier_thresholds.set(thresholds);
+        self.env().emit_event(TierThresholdsChanged {
+            old_thresholds,
+            new_thresholds: thresholds,
+            by: self.env().caller(),
+        });
+    }
+
+    // ============ EMERGENCY FUNCTIONS ============
+
+    /// Pause attestation creation (admin or emergency admin)
+    pub fn emergency_pause(&mut self) {
+        let caller = self.assert_emergency_caller();
+        self.record_admin_action("emergency_pause", caller);
+        self.paused.set(true);
+        self.env().emit_event(EmergencyActionTaken {
+            action: "pause".to_string(),
+            by: caller,
+        });
+    }
+
+    /// Replace the signing key (admin or emergency admin); see `SignerKey`.
+    /// Unlike `rotate_signer` this ignores the upgrade lock on purpose, so a
+    /// compromised key can be replaced while the lock runs.
+    pub fn emergency_rotate_signer(&mut self, key: SignerKey) {
+        let caller = self.assert_emergency_caller();
+        self.record_admin_action("emergency_rotate_signer", caller);
+        self.rotate_signer_key(key);
+        self.env().emit_event(EmergencyActionTaken {
+            action: "rotate_signer".to_string(),
+            by: caller,
+        });
+    }
+
+    // ============ VIEW FUNCTIONS ============
+
+    /// Get attestation by ID
+    pub fn get_attestation(&self, id: [u8; 32]) -> Option<Attestation> {
+        self.load_attestation(&id)
+    }
+
+    /// Get an attestation's stake as (motes, `STAKE_SOURCE_*`, era measured in).
+    /// The era is a placeholder: Odra does not expose it to contracts yet, so
+    /// it is 0 (unknown) until it does.
+    pub fn get_attestation_stake_info(&self, id: [u8; 32]) -> Option<(U512, u8, u64)> {
+        self.load_attestation(&id)
+            .map(|attestation| (attestation.stake_amount, attestation.stake_source, attestation.stake_epoch))
+    }
+
+    /// Check whether an attestation can be submitted to `target_chain`'s verifier right now.
+    /// The chain must be among the supported chains (when any are set) and its
+    /// verifier, if its schema version is known, must decode `SCHEMA_VERSION`,
+    /// and the stored signature must come from the current signing key (after
+    /// a rotation, re-sign with `batch_refresh_signatures` or `submit_signature`).
+    /// Returns `(true, "OK")` or `(false, reason)` naming the first failed check.
+    pub fn is_attestation_usable_for_chain(&self, id: [u8; 32], target_chain: String) -> (bool, String) {
+        let Some(attestation) = self.load_attestation(&id) else {
+            return (false, "Attestation not found".to_string());
+        };
+
+        if let Some(problem) = self.lifecycle_problem(&attestation) {
+            return (false, problem.to_string());
+        }
+        if attestation.target_chain != target_chain {
+            return (false, "Attestation issued for a different chain".to_string());
+        }
+        let supported_chains = self.supported_chains.get_or_default();
+        if !supported_chains.is_empty() && !supported_chains.contains(&target_chain) {
+            return (false, "Chain not supported".to_string());
+        }
+        let verifier_schema = self.get_chain_deployment(target_chain).map(|deployment| deployment.schema_version);
+        if verifier_schema.is_some_and(|version| version != 0 && version != SCHEMA_VERSION) {
+            return (false, "Verifier expects a different schema version".to_string());
+        }
+        if self.attestation_signature_versions.get(&id) != Some(self.signer_key_version.get_or_default()) {
+            return (false, "Not signed under the current signing key".to_string());
+        }
+
+        (true, "OK".to_string())
+    }
+
+    /// Check what an EVM verifier reports for an attestation's user against
+    /// the Casper record: verified exactly while unrevoked, same tier, same stake.
+    /// Returns `(true, "OK")` or `(false, reason)` naming the first mismatch.
+    pub fn cross_validate_attestation(
+        &self,
+        id: [u8; 32],
+        evm_verified: bool,
+        evm_tier: u8,
+        evm_stake_motes: U512,
+    ) -> (bool, String) {
+        let Some(attestation) = self.load_attestation(&id) else {
+            return (false, "Attestation not found".to_string());
+        };
+
+        if evm_verified == attestation.revoked {
+            let reason = if attestation.revoked {
+                "Verified on EVM but revoked on Casper"
+            } else {
+                "Not verified on EVM but not revoked on Casper"
+            };
+            return (false, reason.to_string());
+        }
+        if evm_tier != attestation.tier as u8 {
+            return (false, format!("Tier mismatch: Casper {}, EVM {}", attestation.tier as u8, evm_tier));
+        }
+        if evm_stake_motes != attestation.stake_amount {
+            return (
+                false,
+                format!("Stake mismatch: Casper {} motes, EVM {} motes", attestation.stake_amount, evm_stake_motes),
+            );
+        }
+
+        (true, "OK".to_string())
+    }
+
+    /// Whether an attestation exists and is neither revoked, expired nor stale.
+    /// With `require_witness`, it must also be co-signed by a witness that is
+    /// still trusted.
+    pub fn is_attestation_valid(&self, id: [u8; 32], require_witness: Option<bool>) -> bool {
+        let Some(attestation) = self.load_attestation(&id) else {
+            return false;
+        };
+        if self.lifecycle_problem(&attestation).is_some() {
+            return false;
+        }
+        !require_witness.unwrap_or(false)
+            || attestation.witness_address.is_some_and(|witness| self.is_trusted_witness(witness))
+    }
+
+    /// Whether `user` holds an active attestation of at least `min_tier`
+    /// with no open dispute against it. With `require_evm_confirmation`, an
+    /// EVM verifier must also have been confirmed to consume it.
+    pub fn check_attestation(
+        &self,
+        user: Address,
+        min_tier: Tier,
+        require_evm_confirmation: Option<bool>,
+    ) -> bool {
+        self.get_active_attestation(user).is_some_and(|attestation| {
+            attestation.tier as u8 >= min_tier as u8
+                && self.get_attestation_dispute(attestation.id).is_none()
+                && (!require_evm_confirmation.unwrap_or(false) || attestation.verified_on_evm_at.is_some())
+        })
+    }
+
+    /// `user`'s highest-tier attestation that is neither revoked, expired
+    /// nor stale, the most recent one on a tie
+    pub fn get_active_attestation(&self, user: Address) -> Option<Attestation> {
+        self.get_user_attestations(user)
+            .into_iter()
+            .filter(|attestation| self.lifecycle_problem(attestation).is_none())
+            .max_by_key(|attestation| attestation.tier as u8)
+    }
+
+    /// `user`'s attestations that are neither revoked, expired, stale nor
+    /// frozen, in creation order
+    pub fn get_active_attestations(&self, user: Address) -> Vec<Attestation> {
+        self.get_user_attestations(user)
+            .into_iter()
+            .filter(|attestation| self.lifecycle_problem(attestation).is_none())
+            .collect()
+    }
+
+    /// Chains `user` holds an active attestation for, in order of first attestation
+    pub fn get_user_verified_chains(&self, user: Address) -> Vec<String> {
+        let mut chains: Vec<String> = Vec::new();
+        for attestation in self.get_active_attestations(user) {
+            if !chains.contains(&attestation.target_chain) {
+                chains.push(attestation.target_chain);
+            }
+        }
+        chains
+    }
+
+    /// Chains `user` has ever attested to, in order of first attestation;
+    /// unlike `get_user_verified_chains`, revoked and expired ones stay
+    pub fn get_user_chains(&self, user: Address) -> Vec<String> {
+        (0..self.get_user_chain_count(user))
+            .filter_map(|index| self.user_chains.get(&(user, index)))
+            .collect()
+    }
+
+    /// Number of distinct chains `user` has ever attested to
+    pub fn get_user_chain_count(&self, user: Address) -> u32 {
+        self.user_chain_counts.get(&user).unwrap_or_default()
+    }
+
+    /// `user`'s reputation score (see `get_reputation`), saturated to `u32`
+    pub fn get_user_trust_score(&self, user: Address) -> u32 {
+        u32::try_from(self.get_reputation(user)).unwrap_or(u32::MAX)
+    }
+
+    /// `user`'s stake, tier, trust score and active attestations in one call
+    pub fn get_user_profile(&self, user: Address) -> UserProfile {
+        let stake_motes = self.query_user_stake(user).amount;
+        let active = self.get_active_attestations(user);
+        let now = self.env().get_block_time();
+        let oldest_attestation_days = active
+            .iter()
+            .map(|attestation| attestation.created_at)
+            .min()
+            .map(|created_at| now.saturating_sub(created_at) / (24 * 60 * 60 * 1000));
+        UserProfile {
+            address: user,
+            stake_motes,
+            tier: self.calculate_tier(user, stake_motes),
+            trust_score: self.get_user_trust_score(user),
+            active_attestation_count: active.len() as u32,
+            verified_chains: self.get_user_verified_chains(user),
+            oldest_attestation_days,
+            has_custom_tier: self.has_custom_tier(user),
+        }
+    }
+
+    /// Whether `witness` may co-sign attestations
+    pub fn is_trusted_witness(&self, witness: [u8; 20]) -> bool {
+        self.trusted_witnesses.get(&witness).unwrap_or_default()
+    }
+
+    /// Registered claim providers' Ethereum addresses, in registration order
+    pub fn get_claim_providers(&self) -> Vec<[u8; 20]> {
+        self.claims.providers()
+    }
+
+    /// Public key a claim provider was registered with
+    pub fn get_claim_provider_key(&self, provider: [u8; 20]) -> Option<[u8; 64]> {
+        self.claims.provider_key(provider)
+    }
+
+    pub fn get_claim(&self, claim_id: u64) -> Option<Claim> {
+        self.claims.get(claim_id)
+    }
+
+    /// Claims attached to an attestation, in attachment order
+    pub fn get_attestation_claims(&self, attestation_id: [u8; 32]) -> Vec<Claim> {
+        self.claims.for_attestation(attestation_id)
+    }
+
+    /// Claims signed by a provider, in attachment order
+    pub fn get_provider_claims(&self, provider: [u8; 20]) -> Vec<Claim> {
+        self.claims.for_provider(provider)
+    }
+
+    /// ID the EVM side knows the attestation by: the hash of its current EVM
+    /// payload. Equals `id` until claims are attached.
+    pub fn get_evm_attestation_id(&self, id: [u8; 32]) -> Option<[u8; 32]> {
+        let attestation = self.load_attestation(&id)?;
+        Some(self.hash(attestation.hash_algorithm_version, &abi_encode_payload(&self.evm_payload(&attestation))))
+    }
+
+    /// Get all attestations for a user
+    pub fn get_user_attestations(&self, user: Address) -> Vec<Attestation> {
+        let ids = self.user_attestations.get(&user).unwrap_or_default();
+        ids.iter()
+            .filter_map(|id| self.load_attestation(id))
+            .collect()
+    }
+
+    /// Summaries of all of `user`'s attestations, in creation order, read
+    /// from the list index rather than the full records. Each carries when
+    /// its current signature was produced.
+    pub fn get_attestation_list(&self, user: Address) -> Vec<AttestationListItem> {
+        let ids = self.user_attestations.get(&user).unwrap_or_default();
+        ids.iter()
+            .filter_map(|id| {
+                let item = self.attestation_list_items
+                    .get(id)
+                    .or_else(|| self.load_attestation(id).map(|attestation| AttestationListItem::from(&attestation)));
+                item.map(|item| AttestationListItem {
+                    signature_produced_at: self.get_signature_produced_at(*id).unwrap_or_default(),
+                    ..item
+                })
+            })
+            .collect()
+    }
+
+    /// Every (chain, address) pair `user` has attested, in order of first
+    /// attestation, each reported by its most recent active attestation, or
+    /// its most recent one if none is active. Addresses compare case-insensitively.
+    pub fn get_all_attested_addresses(&self, user: Address) -> AttestedAddresses {
+        let mut book = AttestedAddresses::default();
+        for attestation in self.get_user_attestations(user) {
+            let is_active = self.lifecycle_problem(&attestation).is_none();
+            let existing = book.chains.iter().zip(&book.addresses).position(|(chain, address)| {
+                *chain == attestation.target_chain && address.eq_ignore_ascii_case(&attestation.target_address)
+            });
+            match existing {
+                // Newer wins unless it would replace an active entry with an inactive one
+                Some(i) if is_active || !book.is_active[i] => {
+                    book.addresses[i] = attestation.target_address;
+                    book.is_active[i] = is_active;
+                }
+                Some(_) => {}
+                None => {
+                    book.addresses.push(attestation.target_address);
+                    book.chains.push(attestation.target_chain);
+                    book.is_active.push(is_active);
+                }
+            }
+        }
+        book
+    }
+
+    /// Identity anchor for `user` that survives renewals and revocations:
+    /// `keccak256(casper_address_hash ++ first_attestation_id ++ nonce)`,
+    /// with the nonce of the first attestation as 8 big-endian bytes (as
+    /// `abi.encodePacked(bytes32, bytes32, uint64)`). Zero before the user's
+    /// first attestation.
+    pub fn get_identity_fingerprint(&self, user: Address) -> [u8; 32] {
+        let first_id = self.first_attestation_id.get(&user)
+            .or_else(|| self.user_attestations.get(&user).unwrap_or_default().first().copied());
+        let Some(first) = first_id.and_then(|id| self.load_attestation(&id)) else {
+            return [0u8; 32];
+        };
+
+        let mut preimage = Vec::with_capacity(72);
+        preimage.extend_from_slice(&self.hash_address(user));
+        preimage.extend_from_slice(&first.id);
+        preimage.extend_from_slice(&first.nonce.to_be_bytes());
+        keccak256(&preimage)
+    }
+
+    /// ID of the attestation `user` created with `nonce`. Nonces skipped on
+    /// an ID collision have none.
+    pub fn get_attestation_by_nonce(&self, user: Address, nonce: u64) -> Option<[u8; 32]> {
+        self.nonce_to_attestation.get(&self.nonce_key(user, nonce))
+    }
+
+    /// Whether the admin set a custom tier for `user`
+    pub fn has_custom_tier(&self, user: Address) -> bool {
+        self.custom_tiers.get(&user).flatten().is_some()
+    }
+
+    /// Get user's current tier
+    pub fn get_user_tier(&self, user: Address) -> Tier {
+        let stake = self.query_user_stake(user).amount;
+        self.calculate_tier(user, stake)
+    }
+
+    /// Whether `verifier` is registered for `chain`
+    pub fn get_trusted_verifier(&self, chain: String, verifier: [u8; 20]) -> bool {
+        self.trusted_verifiers.get(&(chain, verifier)).unwrap_or_default()
+    }
+
+    /// Get the verifier new attestations for `chain` are issued to (zero: any)
+    pub fn get_chain_verifier(&self, chain: String) -> [u8; 20] {
+        self.chain_verifiers.get(&chain).unwrap_or_default()
+    }
+
+    /// List pending chain proposals with the block time (ms) each can be confirmed from
+    pub fn get_pending_chain_additions(&self) -> Vec<(String, u64)> {
+        self.pending_chain_names
+            .get_or_default()
+            .into_iter()
+            .map(|chain| (chain.clone(), self.chain_activates_at(&chain)))
+            .collect()
+    }
+
+    /// Get `chain`'s deployment metadata, if set
+    pub fn get_chain_deployment(&self, chain: String) -> Option<ChainDeployment> {
+        self.chain_deployments.get(&chain).flatten()
+    }
+
+    /// Whether new attestations may only target allowlisted chains
+    pub fn get_allowlist_enabled(&self) -> bool {
+        self.allowlist_enabled.get_or_default()
+    }
+
+    /// Whether target addresses must be EIP-55 checksummed
+    pub fn get_require_checksum(&self) -> bool {
+        self.require_checksum.get_or_default()
+    }
+
+    /// Allowlisted chains, in the order they were added
+    pub fn get_allowed_chains(&self) -> Vec<String> {
+        self.allowed_chain_names.get_or_default()
+    }
+
+    /// Whether new attestations may target `chain`: always while the
+    /// allowlist is off
+    pub fn is_chain_allowed(&self, chain: String) -> bool {
+        !self.get_allowlist_enabled() || self.allowed_chains.get(&chain).unwrap_or_default()
+    }
+
+    /// Allowlisted regions, in the order they were added
+    pub fn get_allowed_regions(&self) -> Vec<String> {
+        self.allowed_region_names.get_or_default()
+    }
+
+    /// Whether new attestations may carry `region_hint`: any, or none, while
+    /// the region allowlist is empty
+    pub fn is_region_allowed(&self, region_hint: Option<String>) -> bool {
+        if self.allowed_region_names.get_or_default().is_empty() {
+            return true;
+        }
+        region_hint.is_some_and(|region| self.allowed_regions.get(&keccak256(region.as_bytes())).unwrap_or_default())
+    }
+
+    /// List every chain's deployment metadata, in the order chains were first set
+    pub fn list_chain_deployments(&self) -> Vec<(String, ChainDeployment)> {
+        self.chain_deployment_names
+            .get_or_default()
+            .into_iter()
+            .filter_map(|chain| self.get_chain_deployment(chain.clone()).map(|deployment| (chain, deployment)))
+            .collect()
+    }
+
+    /// Get the proof-of-work difficulty in leading zero bits (0: disabled)
+    pub fn get_pow_difficulty(&self) -> u8 {
+        self.pow_difficulty.get_or_default()
+    }
+
+    /// Get the `encoding::HASH_ALGORITHM_*` new attestation IDs are hashed with
+    pub fn get_hash_algorithm(&self) -> u8 {
+        self.hash_algorithm.get().unwrap_or(encoding::HASH_ALGORITHM_KECCAK256)
+    }
+
+    /// `TIME_UNIT_*` new payloads' timestamps are signed in; milliseconds on
+    /// deployments from before the setting existed
+    pub fn get_time_unit(&self) -> u8 {
+        self.time_unit.get().unwrap_or(TIME_UNIT_MILLIS)
+    }
+
+    /// Get the age in seconds past which attestations can be marked stale (0 = disabled)
+    pub fn get_max_attestation_age_secs(&self) -> u64 {
+        self.max_attestation_age_secs.get_or_default()
+    }
+
+    /// Get the Ethereum address whose signatures `confirm_verified` accepts
+    pub fn get_confirmation_signer(&self) -> Option<[u8; 20]> {
+        self.confirmation_signer.get().flatten()
+    }
+
+    /// Whether security parameters are still locked
+    pub fn is_upgrade_locked(&self) -> bool {
+        self.env().get_block_time() < self.upgrade_lock_until.get_or_default()
+    }
+
+    /// Get the block time (ms) at which the upgrade lock ends
+    pub fn get_upgrade_lock_until(&self) -> u64 {
+        self.upgrade_lock_until.get_or_default()
+    }
+
+    /// Get the tier changes recorded for a user, oldest first. An entry is
+    /// added only when an attestation is created at a different tier.
+    pub fn get_tier_change_log(&self, user: Address) -> Vec<(Tier, u64, [u8; 32])> {
+        self.tier_history.get(&user).unwrap_or_default()
+    }
+
+    /// Get the number of tier changes recorded for a user
+    pub fn get_tier_change_count(&self, user: Address) -> u32 {
+        self.get_tier_change_log(user).len() as u32
+    }
+
+    /// Get the admin contact metadata
+    pub fn get_admin_contact(&self) -> String {
+        self.admin_contact.get_or_default()
+    }
+
+    /// Get the motes bonded to open a dispute
+    pub fn get_dispute_bond(&self) -> U512 {
+        self.dispute_bond.get().unwrap_or(U512::from(DEFAULT_DISPUTE_BOND_MOTES))
+    }
+
+    /// Get the motes each attestation creation pays to the treasury
+    pub fn get_attestation_fee(&self) -> U512 {
+        self.attestation_fee.get_or_default()
+    }
+
+    /// Get the dispute arbiter, if set
+    pub fn get_arbiter(&self) -> Option<Address> {
+        self.arbiter.get().flatten()
+    }
+
+    /// Get the recipient of forfeited dispute bonds and attestation fees: the
+    /// treasury, or the admin if unset
+    pub fn get_treasury(&self) -> Address {
+        self.treasury.get().flatten().or(self.admin.get()).expect("Admin not set")
+    }
+
+    /// Get a dispute, open or resolved
+    pub fn get_dispute(&self, dispute_id: u64) -> Option<Dispute> {
+        self.disputes.get(dispute_id)
+    }
+
+    /// Disputes not yet resolved, oldest first
+    pub fn get_open_disputes(&self) -> Vec<Dispute> {
+        self.disputes.open_disputes()
+    }
+
+    /// ID of the open dispute against the attestation, if any
+    pub fn get_attestation_dispute(&self, id: [u8; 32]) -> Option<u64> {
+        self.disputes.open_dispute_for(id)
+    }
+
+    /// Get the emergency admin, if set
+    pub fn get_emergency_admin(&self) -> Option<Address> {
+        self.emergency_admin.get().flatten()
+    }
+
+    /// Get the contract queried for delegated stake, if any
+    pub fn get_stake_source(&self) -> Option<Address> {
+        self.stake_source.get().flatten()
+    }
+
+    /// Get the `VeilStaking` contract queried for locked CSPR, if any
+    pub fn get_locked_stake_source(&self) -> Option<Address> {
+        self.locked_stake_source.get().flatten()
+    }
+
+    /// Get which stake counts towards tiers
+    pub fn get_stake_policy(&self) -> StakePolicy {
+        self.stake_policy.get_or_default()
+    }
+
+    /// Get the badge collection, if set
+    pub fn get_badge_collection(&self) -> Option<Address> {
+        self.badge_collection.get().flatten()
+    }
+
+    /// Whether new attestations get a badge
+    pub fn get_mint_badges(&self) -> bool {
+        self.mint_badges.get_or_default()
+    }
+
+    /// Collection holding the attestation's badge (token ID: the attestation ID), if one was minted
+    pub fn get_attestation_badge(&self, id: [u8; 32]) -> Option<Address> {
+        self.attestation_badges.get(&id)
+    }
+
+    /// Get the reward token, if set
+    pub fn get_reward_token(&self) -> Option<Address> {
+        self.reward_token.get().flatten()
+    }
+
+    /// Whether new attestations earn a reward while a reward token is set
+    pub fn get_rewards_enabled(&self) -> bool {
+        self.rewards_enabled.get_or_default()
+    }
+
+    /// Get the reward before the tier multiplier
+    pub fn get_base_reward(&self) -> U256 {
+        self.base_reward.get_or_default()
+    }
+
+    /// Get the per-tier reward multipliers
+    pub fn get_reward_multipliers(&self) -> RewardMultipliers {
+        self.reward_multipliers.get().unwrap_or_default()
+    }
+
+    /// Get the cap on total rewards minted
+    pub fn get_reward_emission_cap(&self) -> U256 {
+        self.reward_emission_cap.get().unwrap_or(U256::MAX)
+    }
+
+    /// Get the total rewards minted so far
+    pub fn get_rewards_emitted(&self) -> U256 {
+        self.rewards_emitted.get_or_default()
+    }
+
+    /// Reward an attestation of `tier` earns: the base reward times the
+    /// tier's multiplier, `None` on overflow
+    pub fn get_reward_for_tier(&self, tier: Tier) -> Option<U256> {
+        self.get_base_reward().checked_mul(U256::from(self.get_reward_multipliers().for_tier(tier)))
+    }
+
+    /// Whether attestation creation is paused
+    pub fn is_paused(&self) -> bool {
+        self.paused.get_or_default()
+    }
+
+    /// Whether new attestations are stored compressed
+    pub fn is_compressed_storage(&self) -> bool {
+        self.compressed_storage.get_or_default()
+    }
+
+    /// Get the revocation delay in seconds
+    pub fn get_revocation_delay_secs(&self) -> u64 {
+        self.revocation_delay_secs.get_or_default()
+    }
+
+    /// Get the attestation reuse window in seconds
+    pub fn get_reuse_window_secs(&self) -> u64 {
+        self.reuse_window_secs.get_or_default()
+    }
+
+    /// Get how long a live stake reading is reused, in seconds (0 = disabled)
+    pub fn get_stake_cache_secs(&self) -> u64 {
+        self.stake_cache_secs.get_or_default()
+    }
+
+    /// Get the block time (ms) at which a requested revocation takes effect
+    pub fn get_pending_revocation(&self, id: [u8; 32]) -> Option<u64> {
+        self.pending_revocations.get(&id)
+    }
+
+    /// Get the maximum active attestations per user for `chain`
+    pub fn get_chain_attestation_limit(&self, chain: String) -> u32 {
+        self.chain_attestation_limits.get(&keccak256(chain.as_bytes())).unwrap_or(DEFAULT_CHAIN_ATTESTATION_LIMIT)
+    }
+
+    /// Conservative gas (motes) for `create_attestation` with these inputs,
+    /// for budgeting `set_gas`; nothing is computed or written
+    pub fn estimate_create_attestation_gas(&self, target_chain: String, target_address: String) -> u64 {
+        let input_bytes = (target_chain.len() + target_address.len()) as u64;
+        let (delegated, locked) = self.stake_sources_queried();
+        let stake_queries = u64::from(delegated.is_some()) + u64::from(locked.is_some());
+        CREATE_BASE_GAS
+            .saturating_add(input_bytes.saturating_mul(CREATE_GAS_PER_BYTE))
+            .saturating_add(stake_queries * STAKE_QUERY_GAS)
+    }
+
+    /// Whether `user` lets relayers renew their attestations
+    pub fn get_auto_renew(&self, user: Address) -> bool {
+        self.auto_renew.get(&user).unwrap_or_default()
+    }
+
+    /// Whether `account` may call `execute_auto_renew`
+    pub fn is_relayer(&self, account: Address) -> bool {
+        self.relayers.get(&account).unwrap_or_default()
+    }
+
+    /// Get how long before expiry attestations may be auto-renewed, in seconds
+    pub fn get_renewal_grace_secs(&self) -> u64 {
+        self.renewal_grace_secs.get().unwrap_or(DEFAULT_RENEWAL_GRACE_SECS)
+    }
+
+    /// Get the shortest custom validity users may request, in seconds
+    pub fn get_min_validity_secs(&self) -> u64 {
+        self.min_validity_secs.get().unwrap_or(DEFAULT_MIN_VALIDITY_SECS)
+    }
+
+    /// Whether a minimum stake is required to create attestations
+    pub fn is_min_stake_required(&self) -> bool {
+        self.require_min_stake.get_or_default()
+    }
+
+    /// Get the minimum stake to create attestations, in motes
+    pub fn get_min_attestation_stake_motes(&self) -> U512 {
+        self.min_attestation_stake_motes.get().unwrap_or_else(|| {
+            U512::from(self.get_tier_thresholds().bronze) * U512::from(MOTES_PER_CSPR)
+        })
+    }
+
+    /// Get the minimum stake (whole CSPR) for each tier
+    pub fn get_tier_thresholds(&self) -> TierThresholds {
+        self.tier_thresholds.get().unwrap_or_default()
+    }
+
+    /// `user`'s reputation score: points for validity periods completed
+    /// through renewal and for distinct chains, less a penalty per admin
+    /// revocation. New attestations carry the score at creation.
+    pub fn get_reputation(&self, user: Address) -> u64 {
+        self.get_reputation_record(user).score(&self.get_reputation_weights())
+    }
+
+    /// Every registered attestation type, in registration order
+    pub fn get_registered_types(&self) -> Vec<String> {
+        self.registered_type_list.get_or_default()
+    }
+
+    /// Whether attestations of `attestation_type` may be created
+    pub fn is_attestation_type_registered(&self, attestation_type: String) -> bool {
+        self.registered_types.get(&keccak256(attestation_type.as_bytes())).unwrap_or_default()
+    }
+
+    /// All of `user`'s attestations of `attestation_type`, in creation order
+    pub fn get_attestations_by_type(&self, user: Address, attestation_type: String) -> Vec<Attestation> {
+        self.get_user_attestations(user)
+            .into_iter()
+            .filter(|attestation| attestation.attestation_type == attestation_type)
+            .collect()
+    }
+
+    /// Get a template by ID
+    pub fn get_template(&self, template_id: u64) -> Option<Template> {
+        self.templates.get(&template_id)
+    }
+
+    /// All templates, active or not, in creation order
+    pub fn get_templates(&self) -> Vec<Template> {
+        (1..=self.template_count.get_or_default())
+            .filter_map(|template_id| self.get_template(template_id))
+            .collect()
+    }
+
+    /// IDs of the attestations created from a template (renewals included),
+    /// in creation order
+    pub fn get_template_attestations(&self, template_id: u64) -> Vec<[u8; 32]> {
+        self.template_attestations.get(&template_id).unwrap_or_default()
+    }
+
+    /// Get the number of attestations created with `referrer`
+    pub fn get_referral_count(&self, referrer: Address) -> u64 {
+        self.referral_counts.get(&referrer).unwrap_or_default()
+    }
+
+    /// Get up to `limit` IDs of attestations `referrer` referred, starting
+    /// at the `offset`-th in creation order
+    pub fn get_referred_attestations(&self, referrer: Address, offset: u32, limit: u32) -> Vec<[u8; 32]> {
+        let referred = self.referred_attestations.get(&referrer).unwrap_or_default();
+        referred.into_iter().skip(offset as usize).take(limit as usize).collect()
+    }
+
+    /// Whether the admin has blocked `referrer`
+    pub fn is_referrer_blocked(&self, referrer: Address) -> bool {
+        self.blocked_referrers.get(&referrer).unwrap_or_default()
+    }
+
+    /// Get the history `user`'s reputation score is derived from
+    pub fn get_reputation_record(&self, user: Address) -> ReputationRecord {
+        self.reputation.get(&user).unwrap_or_default()
+    }
+
+    /// Get the reputation scoring weights
+    pub fn get_reputation_weights(&self) -> ReputationWeights {
+        self.reputation_weights.get().unwrap_or_default()
+    }
+
+    /// Get the Casper era an attestation was created in (0: unknown)
+    pub fn get_attestation_era_id(&self, id: [u8; 32]) -> Option<u64> {
+        self.load_attestation(&id).map(|attestation| attestation.casper_era_id)
+    }
+
+    /// Whether an attestation may still be auto-renewed
+    pub fn get_attestation_renewable(&self, id: [u8; 32]) -> Option<bool> {
+        self.load_attestation(&id).map(|attestation| attestation.is_renewable)
+    }
+
+    /// Get an attestation's tags (empty if it has none or does not exist)
+    pub fn get_attestation_tags(&self, id: [u8; 32]) -> Vec<String> {
+        self.load_attestation(&id).map(|attestation| attestation.tags).unwrap_or_default()
+    }
+
+    /// IDs of `user`'s attestations tagged with `tag`, in tagging order
+    pub fn find_attestations_by_tag(&self, user: Address, tag: String) -> Vec<[u8; 32]> {
+        self.tag_index.get(&tag_index_key(&user, &tag)).unwrap_or_default()
+    }
+
+    /// Whether several attestations may target the same (chain, target address)
+    pub fn get_allow_duplicate_chain_address(&self) -> bool {
+        self.allow_duplicate_chain_address.get().unwrap_or(true)
+    }
+
+    /// Whether the deprecated `AttestationCreated` is still emitted
+    pub fn get_emit_v1_events(&self) -> bool {
+        self.emit_v1_events.get().unwrap_or(true)
+    }
+
+    /// Get the maximum number of tags per attestation
+    pub fn get_max_tags(&self) -> u8 {
+        self.max_tags.get().unwrap_or(DEFAULT_MAX_TAGS)
+    }
+
+    /// Number of attestations ever created, revoked ones included; successors
+    /// from refresh, extension or renewal are not new attestations
+    pub fn get_total_attestation_count(&self) -> u64 {
+        self.global_attestation_count.get_or_default() - self.global_successor_count.get_or_default()
+    }
+
+    /// The attestation issued `index`-th (from 0) across all users, successors
+    /// from refresh, extension or renewal included
+    pub fn get_attestation_by_global_index(&self, index: u64) -> Option<Attestation> {
+        let id = self.attestation_ids_by_index.get(&index)?;
+        self.load_attestation(&id)
+    }
+
+    /// `AttestationCreated` for up to `limit` attestations (capped at
+    /// `get_max_batch_size`) from global index `from_attestation_index` on,
+    /// in issue order and successors included, for indexers that missed
+    /// events. Rebuilt from stored data, so available whether or not v1
+    /// events are on.
+    pub fn get_attestation_events_replay(&self, from_attestation_index: u64, limit: u32) -> Vec<AttestationCreated> {
+        let end = self
+            .global_attestation_count
+            .get_or_default()
+            .min(from_attestation_index.saturating_add(u64::from(limit.min(self.get_max_batch_size()))));
+        (from_attestation_index..end)
+            .filter_map(|index| self.get_attestation_by_global_index(index))
+            .map(|attestation| AttestationCreated {
+                supersedes: self.predecessors.get(&attestation.id),
+                ..AttestationCreated::from(&attestation)
+            })
+            .collect()
+    }
+
+    /// Get the contract admin
+    pub fn get_admin(&self) -> Address {
+        self.admin.get().expect("Admin not set")
+    }
+
+    /// Get how long new attestations are valid, in seconds
+    pub fn get_attestation_validity_secs(&self) -> u64 {
+        self.attestation_validity_secs.get().unwrap_or(604800)
+    }
+
+    /// Get the maximum number of IDs per batch call
+    pub fn get_max_batch_size(&self) -> u32 {
+        self.max_batch_size.get().unwrap_or(DEFAULT_MAX_BATCH_SIZE)
+    }
+
+    /// Storage layout version: `STORAGE_VERSION` for new deployments, 1 for
+    /// deployments from before versioning that have not been migrated
+    pub fn get_contract_version(&self) -> u32 {
+        self.storage_version.get().unwrap_or(1)
+    }
+
+    /// The attestation that replaced `id` by renewal, extension or refresh,
+    /// if any; follow it repeatedly to reach the current one
+    pub fn get_successor(&self, id: [u8; 32]) -> Option<[u8; 32]> {
+        self.successors.get(&id)
+    }
+
+    /// Deployment metadata for documentation and integrations, ready to be
+    /// written to `deployed-addresses.json`
+    pub fn get_deployment_info(&self) -> DeploymentInfo {
+        DeploymentInfo {
+            contract_address: self.env().self_address().to_string(),
+            signer_address: format!("0x{}", hex::encode(self.get_signer_address())),
+            network_name: self.network_name.get_or_default(),
+            deployed_at: self.deployed_at.get_or_default(),
+            contract_version: CONTRACT_VERSION.to_string(),
+            schema_version: SCHEMA_VERSION,
+            supported_chains: self.supported_chains.get_or_default(),
+        }
+    }
+
+    /// Usage and age of the current signing key, with whether it is due for
+    /// rotation (more than `SIGNER_ROTATION_SIGNATURES` signatures or older
+    /// than `SIGNER_ROTATION_AGE_SECS`)
+    pub fn check_signer_health(&self) -> SignerHealthStatus {
+        let signatures_produced = self.signatures_produced.get_or_default();
+        let set_at = self.signer_set_at.get().unwrap_or_else(|| self.deployed_at.get_or_default());
+        let key_age_secs = self.env().get_block_time().saturating_sub(set_at) / 1000;
+        SignerHealthStatus {
+            key_version: self.signer_key_version.get_or_default(),
+            signer_address: self.get_signer_address(),
+            signatures_produced,
+            last_signature_at: self.last_signature_at.get_or_default(),
+            key_age_secs,
+            recommend_rotation: recommends_rotation(signatures_produced, key_age_secs),
+        }
+    }
+
+    /// Get the signer's Ethereum-style address
+    pub fn get_signer_address(&self) -> [u8; 20] {
+        let pubkey = self.signer_public_key.get().expect("Signer not set");
+        public_key_to_address(&pubkey)
+    }
+
+    /// Get ABI-encoded attestation data for EVM submission
+    /// Returns (encoded_attestation, signature) that can be directly submitted to VeilVerifier,
+    /// or `None` once the attestation has expired, as VeilVerifier would reject it,
+    /// and while it is frozen. Without on-chain signing also `None` until the
+    /// external signer has submitted a signature under the current key.
+    pub fn get_attestation_for_evm(&self, id: [u8; 32]) -> Option<(Bytes, Bytes)> {
+        let attestation = self.load_attestation(&id)?;
+        if self.env().get_block_time() >= attestation.expires_at || attestation.frozen {
+            return None;
+        }
+
+        let encoded = abi_encode_payload(&self.evm_payload(&attestation));
+
+        // Use the cached signature unless the signer has been rotated since
+        let cached_version = self.attestation_signature_versions.get(&id);
+        let signature = match self.attestation_signatures.get(&id) {
+            Some(signature) if cached_version == Some(self.signer_key_version.get_or_default()) => signature,
+            #[cfg(not(feature = "no-onchain-signing"))]
+            _ => self.sign_message(&self.hash(attestation.hash_algorithm_version, &encoded)),
+            #[cfg(feature = "no-onchain-signing")]
+            _ => return None,
+        };
+
+        Some((Bytes::from(encoded), signature))
+    }
+
+    /// The attestation `create_attestation(target_chain, target_address)`
+    /// would give `user` if called now with `region_hint` and no other
+    /// options: same checks, reuse window, stake query, tier and encoding at
+    /// the user's current nonce, but nothing is written or signed. The fee
+    /// to attach and the proof-of-work difficulty are reported rather than
+    /// checked.
+    pub fn preview_attestation(
+        &self,
+        user: Address,
+        target_chain: String,
+        target_address: String,
+        region_hint: Option<String>,
+    ) -> AttestationPreview {
+        if self.paused.get_or_default() {
+            self.env().revert(VeilError::ContractPaused);
+        }
+        let target_address = self.check_creation_target(&target_chain, target_address, region_hint.as_deref());
+        let attestation_type = DEFAULT_ATTESTATION_TYPE.to_string();
+        if !self.is_attestation_type_registered(attestation_type.clone()) {
+            self.env().revert(VeilError::UnregisteredAttestationType);
+        }
+        let fee = self.get_attestation_fee();
+        let pow_difficulty = self.get_pow_difficulty();
+
+        if let Some((attestation_id, _)) = self.find_reusable_attestation(user, &target_chain, &target_address) {
+            let attestation = self.load_attestation(&attestation_id).expect("Reusable attestation exists");
+            return AttestationPreview {
+                attestation_id,
+                encoded: Bytes::from(abi_encode_payload(&self.evm_payload(&attestation))),
+                tier: attestation.tier,
+                stake_amount: attestation.stake_amount,
+                expires_at: attestation.expires_at,
+                reused: true,
+                fee,
+                pow_difficulty,
+            };
+        }
+
+        let request = IssueRequest {
+            target_chain,
+            target_address,
+            attestation_type,
+            custom_validity_secs: None,
+            carried_validity: false,
+            is_renewable: true,
+            referrer: None,
+            template: None,
+            user_secret: None,
+            region_hint,
+        };
+        let prepared = self.prepare_attestation(user, &request);
+        AttestationPreview {
+            attestation_id: prepared.attestation_id,
+            encoded: Bytes::from(prepared.encoded),
+            tier: prepared.tier,
+            stake_amount: prepared.payload.stake_amount,
+            expires_at: prepared.expires_at,
+            reused: false,
+            fee,
+            pow_difficulty,
+        }
+    }
+
+    /// `get_attestation_for_evm` with the ID, signed digest, signer address
+    /// and schema version alongside, for debugging reverted EVM submissions.
+    /// `None` in the same cases.
+    pub fn get_attestation_export(&self, id: [u8; 32]) -> Option<AttestationExport> {
+        let (encoded, signature) = self.get_attestation_for_evm(id)?;
+        let payload_hash = encoding::attestation_id(&encoded)?;
+        Some(AttestationExport {
+            encoded,
+            signature,
+            attestation_id: id,
+            signed_digest: eth_signed_message_hash(&payload_hash),
+            signer_address: self.get_signer_address(),
+            schema_version: SCHEMA_VERSION,
+        })
+    }
+
+    /// Recover the address behind the cached signature of `attestation_id`,
+    /// over the hash re-derived from the stored attestation, with a note on
+    /// whether it is the current signer. `None` if no signature is cached or
+    /// it does not recover (admin only).
+    pub fn debug_recover_signer(&self, attestation_id: [u8; 32]) -> Option<([u8; 20], String)> {
+        self.assert_admin();
+        let attestation = self.load_attestation(&attestation_id)?;
+        let signature = self.attestation_signatures.get(&attestation_id)?;
+        let payload = self.evm_payload(&attestation);
+        let payload_hash = self.hash(attestation.hash_algorithm_version, &abi_encode_payload(&payload));
+        let recovered = encoding::recover_signer(&eth_signed_message_hash(&payload_hash), &signature)?;
+
+        // The ID is taken before any claims are folded in
+        let unclaimed = AttestationPayload { claims_hash: [0u8; 32], ..payload };
+        let id_hash = self.hash(attestation.hash_algorithm_version, &abi_encode_payload(&unclaimed));
+        let signer = self.get_signer_address();
+        let debug_info = if id_hash != attestation_id {
+            format!(
+                "Stored attestation re-encodes to 0x{}, not its ID; the recovered address is meaningless",
+                hex::encode(id_hash)
+            )
+        } else if recovered == signer {
+            String::from("Recovered address matches the current signer")
+        } else {
+            format!(
+                "Recovered 0x{} but the current signer is 0x{}; the signer was rotated since signing",
+                hex::encode(recovered),
+                hex::encode(signer)
+            )
+        };
+        Some((recovered, debug_info))
+    }
+
+    /// Get the last `ADMIN_AUDIT_LOG_LEN` admin actions as (entrypoint,
+    /// caller, block time), oldest first (admin only)
+    pub fn get_admin_audit_log(&self) -> Vec<(String, Address, u64)> {
+        self.assert_admin();
+        self.admin_audit_log.get_or_default()
+    }
+
+    /// Get the most recent admin action, if any
+    pub fn get_last_admin_action(&self) -> Option<(String, Address, u64)> {
+        self.admin_audit_log.get_or_default().pop()
+    }
+
+    /// Ethereum address behind a 65-byte r || s || v `signature` over
+    /// `message_hash`, with the EIP-191 prefix applied first if `prefixed`.
+    /// Casper exposes no secp256k1 recovery to contracts, so this runs k256
+    /// in wasm.
+    pub fn recover_signer(&self, message_hash: [u8; 32], signature: Bytes, prefixed: bool) -> [u8; 20] {
+        let prehash = if prefixed { eth_signed_message_hash(&message_hash) } else { message_hash };
+        encoding::recover_signer(&prehash, &signature)
+            .unwrap_or_else(|| self.env().revert(VeilError::UnrecoverableSignature))
+    }
+
+    /// Whether the cached signature of `attestation_id` recovers to the
+    /// current signer over the attestation's current payload; false if there
+    /// is none, or after a signer rotation until it is refreshed
+    pub fn verify_own_signature(&self, attestation_id: [u8; 32]) -> bool {
+        let (Some(attestation), Some(signature)) =
+            (self.load_attestation(&attestation_id), self.attestation_signatures.get(&attestation_id))
+        else {
+            return false;
+        };
+        let encoded = abi_encode_payload(&self.evm_payload(&attestation));
+        let prehash = eth_signed_message_hash(&self.hash(attestation.hash_algorithm_version, &encoded));
+        encoding::recover_signer(&prehash, &signature) == Some(self.get_signer_address())
+    }
+
+    /// Whether `encoded` is a well-formed ABI payload encoding whose fields
+    /// pass `verify_payload_completeness`
+    pub fn validate_encoded_payload(&self, encoded: Bytes) -> bool {
+        abi_decode_payload(&encoded).is_some_and(|payload| verify_payload_completeness(&payload).is_ok())
+    }
+
+    /// Get the 65-byte signature issued when the attestation was created
+    /// (or last had a claim attached). After a signer rotation this is stale
+    /// until `batch_refresh_signatures` re-signs it; `get_attestation_for_evm`
+    /// re-signs on the fly.
+    pub fn get_attestation_raw_signature(&self, id: [u8; 32]) -> Option<Bytes> {
+        self.attestation_signatures.get(&id)
+    }
+
+    /// Block time (ms) the cached signature of `id` was produced: at
+    /// creation, or when last re-signed by `attach_claim` or
+    /// `batch_refresh_signatures`
+    pub fn get_signature_produced_at(&self, id: [u8; 32]) -> Option<u64> {
+        self.signature_produced_at.get(&id)
+    }
+
+    /// Whole seconds since the cached signature of `id` was produced; `None`
+    /// if unknown
+    pub fn get_signature_age_secs(&self, id: [u8; 32]) -> Option<u64> {
+        let produced_at = self.get_signature_produced_at(id)?;
+        Some(self.env().get_block_time().saturating_sub(produced_at) / 1000)
+    }
+
+    /// Whether the cached signature of `id` is at most `max_age_secs` old;
+    /// false if its age is unknown
+    pub fn is_signature_fresh(&self, id: [u8; 32], max_age_secs: u64) -> bool {
+        self.get_signature_age_secs(id).is_some_and(|age| age <= max_age_secs)
+    }
+
+    /// `get_attestation_raw_signature` split into `(r, s, v)`, for verifiers
+    /// that take the components separately
+    pub fn get_attestation_signature_components(&self, id: [u8; 32]) -> Option<([u8; 32], [u8; 32], u8)> {
+        encoding::signature_components(&self.get_attestation_raw_signature(id)?)
+    }
+
+    /// `get_attestation_raw_signature` in EIP-2098 compact form,
+    /// `r ++ (yParity << 255 | s)`
+    pub fn get_compact_signature(&self, id: [u8; 32]) -> Option<[u8; 64]> {
+        encoding::compact_signature(&self.get_attestation_raw_signature(id)?)
+    }
+
+    /// Signature and magic value for smart contract wallets (e.g. AA wallets)
+    /// implementing EIP-1271. The hash is the attestation ID (the ABI-encoded
+    /// payload hashed with its `hash_algorithm_version`), signed without the
+    /// EIP-191 prefix, so an `isValidSignature(hash, sig)` check passes when it
+    /// recovers the Veil signer and returns the magic value.
+    #[cfg(not(feature = "no-onchain-signing"))]
+    pub fn get_eip1271_signature(&self, attestation_id: [u8; 32]) -> Option<(Bytes, [u8; 4])> {
+        let (encoded, _) = self.get_attestation_for_evm(attestation_id)?;
+        let hash = encoding::attestation_id(&encoded)?;
+        let private_key = self.signer_private_key.get().expect("Signer not set");
+        let signature = Bytes::from(encoding::sign_prehash(&private_key, &hash).to_vec());
+        Some((signature, EIP1271_MAGIC_VALUE))
+    }
+
+    // ============ INTERNAL FUNCTIONS ============
+
+    /// Issue and sign an attestation for `owner`, whose target address has
+    /// already been validated: record it, mint its badge and reward and
+    /// announce it
+    fn issue_attestation(&mut self, owner: Address, request: IssueRequest) -> ([u8; 32], Bytes) {
+        let (attestation, signature) = self.record_attestation(owner, request);
+        let attestation_id = attestation.id;
+
+        if let Some(collection) = self.get_badge_collection().filter(|_| self.get_mint_badges()) {
+            let metadata =
+                badge_metadata(&attestation_id, attestation.tier, &attestation.target_chain, attestation.expires_at);
+            BadgeCollectionContractRef::new(self.env(), collection).mint(owner, attestation_id, metadata);
+            self.attestation_badges.set(&attestation_id, collection);
+        }
+        self.reward_attestation(owner, attestation_id, attestation.tier);
+        self.announce_attestation(attestation, None);
+
+        (attestation_id, signature)
+    }
+
+    /// List a recorded attestation in the global index and emit its creation
+    /// events, which relayers and indexers follow; `supersedes` marks a
+    /// successor and names the attestation it replaced
+    fn announce_attestation(&mut self, attestation: Attestation, supersedes: Option<[u8; 32]>) {
+        let global_index = self.global_attestation_count.get_or_default();
+        self.attestation_ids_by_index.set(&global_index, attestation.id);
+        self.global_attestation_count.set(global_index + 1);
+
+        if self.get_emit_v1_events() {
+            self.env().emit_event(AttestationCreated { supersedes, ..AttestationCreated::from(&attestation) });
+        }
+        self.env().emit_event(AttestationCreatedV2 {
+            id: attestation.id,
+            casper_address: attestation.casper_address,
+            target_chain: attestation.target_chain,
+            target_address: attestation.target_address,
+            tier: attestation.tier as u8,
+            expires_at: attestation.expires_at,
+            created_at: attestation.created_at,
+            stake_motes: attestation.stake_amount,
+            nonce: attestation.nonce,
+            payload_hash: attestation.id,
+            schema_version: SCHEMA_VERSION,
+            referrer: attestation.referrer,
+            supersedes,
+        });
+    }
+
+    /// Sign and store an attestation for `owner` and index it under the
+    /// owner, without the badge, reward or announcement that
+    /// `issue_attestation` and `supersede` add
+    fn record_attestation(&mut self, owner: Address, request: IssueRequest) -> (Attestation, Bytes) {
+        let PreparedAttestation {
+            payload,
+            time_unit,
+            created_at: now,
+            expires_at,
+            attestation_id,
+            retries,
+            tier,
+            validity,
+            chain_address_key,
+            user_chain_key,
+            mut chain_attestations,
+            first_on_chain,
+            ..
+        } = self.prepare_attestation(owner, &request);
+        let IssueRequest {
+            target_chain,
+            target_address,
+            attestation_type,
+            is_renewable,
+            referrer,
+            template,
+            user_secret,
+            region_hint,
+            ..
+        } = request;
+        let AttestationPayload {
+            stake_amount,
+            stake_source,
+            stake_epoch,
+            nonce,
+            trusted_verifier,
+            casper_era_id,
+            reputation_score,
+            template_id,
+            hash_algorithm_version,
+            ..
+        } = payload;
+        let tags: Vec<String> = template.map(|template| template.tag).into_iter().collect();
+
+        // A first attestation to this chain counts towards reputation and
+        // lists the chain for `get_user_chains`
+        if first_on_chain {
+            self.reputation_chains.set(&user_chain_key, true);
+            let chain_count = self.get_user_chain_count(owner);
+            self.user_chains.set(&(owner, chain_count), target_chain.clone());
+            self.user_chain_counts.set(&owner, chain_count + 1);
+            let mut record = self.get_reputation_record(owner);
+            record.distinct_chains += 1;
+            self.reputation.set(&owner, record);
+        }
+
+        for attempt in 1..=retries {
+            self.env().emit_event(AttestationIdRetried {
+                user: owner,
+                attempt,
+            });
+        }
+
+        // Increment nonce past the one actually used
+        let nonce_commitment = user_secret.map(|user_secret| encoding::nonce_commitment(nonce, &user_secret));
+        self.user_nonces.set(&owner, nonce + 1);
+        self.nonce_to_attestation.set(&self.nonce_key(owner, nonce), attestation_id);
+        self.attestation_time_units.set(&attestation_id, time_unit);
+        if stake_source == STAKE_SOURCE_LIVE && self.stake_cache_secs.get_or_default() > 0 {
+            self.stake_cache.set(&owner, (stake_amount, now, stake_epoch));
+        }
+
+        // Sign the message and cache the signature; an external signer
+        // submits it later instead, so none is returned yet
+        #[cfg(not(feature = "no-onchain-signing"))]
+        let signature = {
+            let signature = self.sign_message(&attestation_id);
+            self.store_signature(attestation_id, signature.clone());
+            signature
+        };
+        #[cfg(feature = "no-onchain-signing")]
+        let signature = Bytes::new();
+
+        // Store attestation
+        let attestation = Attestation {
+            id: attestation_id,
+            casper_address: owner,
+            target_chain,
+            target_address,
+            stake_amount,
+            stake_source,
+            stake_epoch,
+            tier,
+            account_age_days: 0,
+            created_at: now,
+            expires_at,
+            attestation_validity_secs: validity,
+            nonce,
+            revoked: false,
+            tags: tags.clone(),
+            witness_address: None,
+            witness_signature: None,
+            referrer,
+            trusted_verifier,
+            casper_era_id,
+            reputation_score,
+            attestation_type,
+            template_id,
+            nonce_commitment,
+            hash_algorithm_version,
+            verified_on_evm_at: None,
+            evm_tx_hash: None,
+            stale: false,
+            frozen: false,
+            region_hint,
+            is_renewable,
+        };
+
+        self.store_attestation(attestation.clone());
+        for tag in &tags {
+            let key = tag_index_key(&owner, tag);
+            let mut ids = self.tag_index.get(&key).unwrap_or_default();
+            ids.push(attestation_id);
+            self.tag_index.set(&key, ids);
+        }
+        if template_id != 0 {
+            let mut created = self.template_attestations.get(&template_id).unwrap_or_default();
+            created.push(attestation_id);
+            self.template_attestations.set(&template_id, created);
+        }
+        self.user_chain_address_attested.set(&chain_address_key, true);
+        chain_attestations.push(attestation_id);
+        self.user_chain_attestations.set(&user_chain_key, chain_attestations);
+
+        // Track user's attestations
+        let mut user_atts = self.user_attestations.get(&owner).unwrap_or_default();
+        if user_atts.is_empty() {
+            self.first_attestation_id.set(&owner, attestation_id);
+        }
+        user_atts.push(attestation_id);
+        self.user_attestations.set(&owner, user_atts);
+        self.record_tier_change(owner, tier, now, attestation_id);
+        (attestation, signature)
+    }
+
+    /// Run every check `issue_attestation` makes for `owner` and build the
+    /// payload and ID it would store, without writing anything. The
+    /// reputation score already counts a first attestation to the chain.
+    fn prepare_attestation(&self, owner: Address, request: &IssueRequest) -> PreparedAttestation {
+        let target_chain = &request.target_chain;
+        let chain_address_key = self.chain_address_key(owner, target_chain, &request.target_address);
+        if !self.get_allow_duplicate_chain_address()
+            && self.user_chain_address_attested.get(&chain_address_key).unwrap_or_default()
+        {
+            self.env().revert(VeilError::DuplicateAttestation);
+        }
+
+        let chain_deployment = self.get_chain_deployment(target_chain.clone());
+        if chain_deployment.as_ref().is_some_and(|deployment| !deployment.enabled) {
+            self.env().revert(VeilError::ChainDisabled);
+        }
+
+        // Revoked and expired attestations never count again, so drop them
+        // while checking the chain limit
+        let user_chain_key = self.user_chain_key(owner, target_chain);
+        let mut chain_attestations = self.user_chain_attestations.get(&user_chain_key).unwrap_or_default();
+        chain_attestations.retain(|id| {
+            self.load_attestation(id).is_some_and(|attestation| self.lifecycle_problem(&attestation).is_none())
+        });
+        if chain_attestations.len() >= self.get_chain_attestation_limit(target_chain.clone()) as usize {
+            self.env().revert(VeilError::ChainAttestationLimitReached);
+        }
+
+        // Query user's stake
+        let stake = self.query_user_stake(owner);
+        let stake_amount = stake.amount;
+        if self.require_min_stake.get_or_default() {
+            if let Err(error) = check_min_stake(stake_amount, self.get_min_attestation_stake_motes()) {
+                self.env().revert(error);
+            }
+        }
+
+        // Calculate tier based on stake
+        let tier = self.calculate_tier(owner, stake_amount);
+        if chain_deployment.is_some_and(|deployment| (tier as u8) < deployment.min_tier) {
+            self.env().revert(VeilError::TierBelowChainMinimum);
+        }
+        let template = request.template.as_ref();
+        if template.is_some_and(|template| (tier as u8) < template.min_tier) {
+            self.env().revert(VeilError::TierBelowTemplateMinimum);
+        }
+
+        let first_on_chain = !self.reputation_chains.get(&user_chain_key).unwrap_or_default();
+        let mut record = self.get_reputation_record(owner);
+        if first_on_chain {
+            record.distinct_chains += 1;
+        }
+
+        // Timestamps
+        let now = self.env().get_block_time();
+        let validity = self.resolve_validity_secs(request.custom_validity_secs, request.carried_validity);
+        let expires_at = now + validity * 1000;
+        let time_unit = self.get_time_unit();
+
+        // Create payload, at the current nonce (may be bumped below on ID collision)
+        let mut payload = AttestationPayload {
+            casper_address_hash: self.hash_address(owner),
+            target_chain: target_chain.clone(),
+            target_address: request.target_address.clone(),
+            stake_amount,
+            tier: tier as u8,
+            account_age_days: 0, // Skipped for MVP
+            created_at: payload_timestamp(now, time_unit),
+            expires_at: payload_timestamp(expires_at, time_unit),
+            nonce: self.user_nonces.get(&owner).unwrap_or(0),
+            trusted_verifier: self.get_chain_verifier(target_chain.clone()),
+            casper_era_id: self.current_era_id(),
+            reputation_score: record.score(&self.get_reputation_weights()),
+            attestation_type: request.attestation_type.clone(),
+            template_id: template.map_or(0, |template| template.id),
+            nonce_commitment: [0u8; 32],
+            hash_algorithm_version: self.get_hash_algorithm(),
+            claims_hash: [0u8; 32],
+            region_hint: request.region_hint.clone().unwrap_or_default(),
+            stake_source: stake.source,
+            stake_epoch: stake.epoch,
+        };
+        if let Err(error) = verify_payload_completeness(&payload) {
+            self.env().revert(error);
+        }
+
+        // Encode and hash payload, retrying with a bumped nonce on collision
+        let (attestation_id, encoded, retries) =
+            find_unused_attestation_id(&mut payload, request.user_secret.as_ref(), |id| self.attestation_exists(id))
+                .unwrap_or_else(|| self.env().revert(VeilError::AttestationIdCollision));
+
+        PreparedAttestation {
+            payload,
+            time_unit,
+            created_at: now,
+            expires_at,
+            attestation_id,
+            encoded,
+            retries,
+            tier,
+            validity,
+            chain_address_key,
+            user_chain_key,
+            chain_attestations,
+            first_on_chain,
+        }
+    }
+
+    /// Mint `owner` the reward for a new attestation of `tier`, if rewards
+    /// are on. A reward that would overflow or pass the emission cap is
+    /// skipped with an event rather than reverting the attestation.
+    fn reward_attestation(&mut self, owner: Address, attestation_id: [u8; 32], tier: Tier) {
+        let Some(token) = self.get_reward_token().filter(|_| self.get_rewards_enabled()) else {
+            return;
+        };
+        let Some(amount) = self.get_reward_for_tier(tier) else {
+            self.env().emit_event(RewardSkipped {
+                attestation_id,
+                recipient: owner,
+                amount: U256::zero(),
+                reason: String::from("Reward overflows"),
+            });
+            return;
+        };
+        if amount.is_zero() {
+            return;
+        }
+
+        let emitted = self.get_rewards_emitted();
+        let within_cap = emitted.checked_add(amount).is_some_and(|total| total <= self.get_reward_emission_cap());
+        if !within_cap {
+            self.env().emit_event(RewardSkipped {
+                attestation_id,
+                recipient: owner,
+                amount,
+                reason: String::from("Emission cap reached"),
+            });
+            return;
+        }
+
+        // A reverting mint would revert creation with it
+        let mut reward_token = RewardTokenContractRef::new(self.env(), token);
+        let token_problem = if reward_token.is_paused() {
+            Some("Reward token paused")
+        } else if !reward_token.is_minter(self.env().self_address()) {
+            Some("Not the reward token's minter")
+        } else if reward_token.mintable_supply() < amount {
+            Some("Reward token supply cap reached")
+        } else {
+            None
+        };
+        if let Some(reason) = token_problem {
+            self.env().emit_event(RewardSkipped {
+                attestation_id,
+                recipient: owner,
+                amount,
+                reason: String::from(reason),
+            });
+            return;
+        }
+
+        reward_token.mint(owner, amount);
+        self.rewards_emitted.set(emitted + amount);
+        self.env().emit_event(RewardMinted { attestation_id, recipient: owner, amount });
+    }
+
+    fn revoke(&mut self, attestation_id: [u8; 32], mut attestation: Attestation) {
+        let casper_address = attestation.casper_address;
+        let chain_address_key =
+            self.chain_address_key(casper_address, &attestation.target_chain, &attestation.target_address);
+        attestation.revoked = true;
+        self.store_attestation(attestation);
+        self.user_chain_address_attested.set(&chain_address_key, false);
+        if let Some(collection) = self.attestation_badges.get(&attestation_id) {
+            BadgeCollectionContractRef::new(self.env(), collection).burn(attestation_id);
+        }
+
+        self.env().emit_event(AttestationRevoked {
+            id: attestation_id,
+            casper_address,
+        });
+    }
+
+    /// Why an attestation cannot be used right now: revoked (including a
+    /// pending revocation that took effect), expired, stale or frozen
+    fn lifecycle_problem(&self, attestation: &Attestation) -> Option<&'static str> {
+        if self.is_revocation_effective(attestation) {
+            return Some("Attestation revoked");
+        }
+        if self.env().get_block_time() >= attestation.expires_at {
+            return Some("Attestation expired");
+        }
+        if attestation.stale {
+            return Some("Attestation stale");
+        }
+        if attestation.frozen {
+            return Some("Attestation frozen");
+        }
+        None
+    }
+
+    /// `owner`'s usable attestation to `target_address` on `target_chain`
+    /// created within the reuse window, with its signature; `None` while the
+    /// window is disabled
+    fn find_reusable_attestation(
+        &self,
+        owner: Address,
+        target_chain: &str,
+        target_address: &str,
+    ) -> Option<([u8; 32], Bytes)> {
+        let window_ms = self.reuse_window_secs.get_or_default().saturating_mul(1000);
+        if window_ms == 0 {
+            return None;
+        }
+        let now = self.env().get_block_time();
+        let ids = self.user_chain_attestations.get(&self.user_chain_key(owner, target_chain)).unwrap_or_default();
+        let attestation = ids.iter().rev().filter_map(|id| self.load_attestation(id)).find(|attestation| {
+            attestation.target_address.eq_ignore_ascii_case(target_address)
+                && now < attestation.created_at.saturating_add(window_ms)
+                && self.lifecycle_problem(attestation).is_none()
+        })?;
+        let (_, signature) = self.get_attestation_for_evm(attestation.id)?;
+        Some((attestation.id, signature))
+    }
+
+    /// Whether an attestation is revoked, or its pending revocation took effect
+    fn is_revocation_effective(&self, attestation: &Attestation) -> bool {
+        let now = self.env().get_block_time();
+        attestation.revoked
+            || self.pending_revocations.get(&attestation.id).is_some_and(|effective_at| now >= effective_at)
+    }
+
+    /// Load one of the caller's attestations that may be extended or
+    /// refreshed: live, not frozen, with no revocation pending
+    fn load_supersedable_attestation(&self, id: [u8; 32]) -> Attestation {
+        if self.paused.get_or_default() {
+            self.env().revert(VeilError::ContractPaused);
+        }
+        let attestation = self.load_owned_attestation(id);
+        if attestation.revoked {
+            self.env().revert(VeilError::AlreadyRevoked);
+        }
+        if self.pending_revocations.get(&id).is_some() {
+            self.env().revert(VeilError::RevocationAlreadyPending);
+        }
+        if attestation.frozen {
+            self.env().revert(VeilError::AlreadyFrozen);
+        }
+        if self.env().get_block_time() >= attestation.expires_at {
+            self.env().revert(VeilError::AttestationExpired);
+        }
+        attestation
+    }
+
+    /// Revoke `old_id` and issue its replacement for the same chain and
+    /// target address with `validity_secs` and the owner's current stake,
+    /// recording it as the successor. The successor is announced like a new
+    /// attestation, marked as superseding `old_id`, but gets no badge or
+    /// reward and does not count towards `get_total_attestation_count`.
+    fn supersede(
+        &mut self,
+        old_id: [u8; 32],
+        attestation: Attestation,
+        validity_secs: u64,
+        reason: u8,
+    ) -> ([u8; 32], Bytes) {
+        let owner = attestation.casper_address;
+        let request = IssueRequest {
+            target_chain: attestation.target_chain.clone(),
+            target_address: attestation.target_address.clone(),
+            attestation_type: attestation.attestation_type.clone(),
+            custom_validity_secs: Some(validity_secs),
+            carried_validity: true,
+            is_renewable: attestation.is_renewable,
+            referrer: attestation.referrer,
+            template: self.get_template(attestation.template_id),
+            user_secret: None,
+            region_hint: attestation.region_hint.clone(),
+        };
+        self.revoke(old_id, attestation);
+        let (successor, signature) = self.record_attestation(owner, request);
+        let new_id = successor.id;
+        self.successors.set(&old_id, new_id);
+        self.predecessors.set(&new_id, old_id);
+        self.global_successor_count.set(self.global_successor_count.get_or_default() + 1);
+        self.announce_attestation(successor, Some(old_id));
+        self.env().emit_event(AttestationSuperseded { old_id, new_id, reason });
+        (new_id, signature)
+    }
+
+    /// Load one of the caller's attestations, reverting if it is missing or not theirs
+    fn load_owned_attestation(&self, id: [u8; 32]) -> Attestation {
+        let attestation = self.load_attestation(&id)
+            .unwrap_or_else(|| self.env().revert(VeilError::AttestationNotFound));
+        if attestation.casper_address != self.env().caller() {
+            self.env().revert(VeilError::NotAttestationOwner);
+        }
+        attestation
+    }
+
+    fn attestation_exists(&self, id: &[u8; 32]) -> bool {
+        self.attestations.get(id).is_some() || self.compressed_attestations.get(id).is_some()
+    }
+
+    /// Read an attestation from whichever storage holds it
+    fn load_attestation(&self, id: &[u8; 32]) -> Option<Attestation> {
+        if let Some(attestation) = self.attestations.get(id) {
+            return Some(attestation);
+        }
+        let compressed = self.compressed_attestations.get(id)?;
+        let chain = self.chain_names.get(&compressed.target_chain_hash).unwrap_or_default();
+        let address = format!("0x{}", hex::encode(compressed.target_address));
+        let attestation_type =
+            self.attestation_type_names.get(&compressed.attestation_type_hash).unwrap_or_default();
+        let mut attestation = decompress_attestation(&compressed, &chain, &address, &attestation_type);
+        attestation.tags = self.compressed_tags.get(id).unwrap_or_default();
+        if let Some((witness, signature)) = self.compressed_witnesses.get(id) {
+            attestation.witness_address = Some(witness);
+            attestation.witness_signature = Some(signature);
+        }
+        attestation.referrer = self.compressed_referrers.get(id);
+        attestation.nonce_commitment = self.compressed_nonce_commitments.get(id);
+        if let Some((verified_on_evm_at, evm_tx_hash)) = self.compressed_evm_confirmations.get(id) {
+            attestation.verified_on_evm_at = Some(verified_on_evm_at);
+            attestation.evm_tx_hash = Some(evm_tx_hash);
+        }
+        attestation.stale = self.compressed_stale.get(id).unwrap_or_default();
+        attestation.frozen = self.compressed_frozen.get(id).unwrap_or_default();
+        attestation.region_hint = self.compressed_region_hints.get(id);
+        if let Some((stake_source, stake_epoch)) = self.compressed_stake_origins.get(id) {
+            attestation.stake_source = stake_source;
+            attestation.stake_epoch = stake_epoch;
+        }
+        Some(attestation)
+    }
+
+    /// Write an attestation to the storage that already holds it; new ones
+    /// follow `compressed_storage`
+    fn store_attestation(&mut self, attestation: Attestation) {
+        let id = attestation.id;
+        self.attestation_list_items.set(&id, AttestationListItem::from(&attestation));
+        let compressed = self.attestations.get(&id).is_none()
+            && (self.compressed_attestations.get(&id).is_some() || self.compressed_storage.get_or_default());
+        if !compressed {
+            self.attestations.set(&id, attestation);
+            return;
+        }
+
+        let chain_hash = keccak256(attestation.target_chain.as_bytes());
+        if self.chain_names.get(&chain_hash).is_none() {
+            self.chain_names.set(&chain_hash, attestation.target_chain.clone());
+        }
+        let type_hash = keccak256(attestation.attestation_type.as_bytes());
+        // Successors of records from before hex was checked can still carry a bad address
+        let record = compress_attestation(&attestation, chain_hash, type_hash)
+            .unwrap_or_else(|| self.env().revert(VeilError::InvalidTargetAddress));
+        self.compressed_attestations.set(&id, record);
+        if let (Some(witness), Some(signature)) = (attestation.witness_address, attestation.witness_signature.clone()) {
+            self.compressed_witnesses.set(&id, (witness, signature));
+        }
+        if let Some(referrer) = attestation.referrer {
+            self.compressed_referrers.set(&id, referrer);
+        }
+        if let Some(nonce_commitment) = attestation.nonce_commitment {
+            self.compressed_nonce_commitments.set(&id, nonce_commitment);
+        }
+        if let (Some(verified_on_evm_at), Some(evm_tx_hash)) =
+            (attestation.verified_on_evm_at, attestation.evm_tx_hash)
+        {
+            self.compressed_evm_confirmations.set(&id, (verified_on_evm_at, evm_tx_hash));
+        }
+        if attestation.stale {
+            self.compressed_stale.set(&id, true);
+        }
+        if attestation.frozen || self.compressed_frozen.get(&id).is_some() {
+            self.compressed_frozen.set(&id, attestation.frozen);
+        }
+        if let Some(region_hint) = attestation.region_hint {
+            self.compressed_region_hints.set(&id, region_hint);
+        }
+        if (attestation.stake_source, attestation.stake_epoch) != (STAKE_SOURCE_LIVE, 0) {
+            self.compressed_stake_origins.set(&id, (attestation.stake_source, attestation.stake_epoch));
+        }
+        if !attestation.tags.is_empty() || self.compressed_tags.get(&id).is_some() {
+            self.compressed_tags.set(&id, attestation.tags);
+        }
+    }
+
+    /// Check the target chain, region hint, target address and proof of work
+    /// of a new attestation and collect its fee, returning the address in the
+    /// form it is stored in
+    fn check_creation_request(
+        &self,
+        caller: Address,
+        target_chain: &str,
+        target_address: String,
+        pow_nonce: Option<u64>,
+        region_hint: Option<&str>,
+    ) -> String {
+        let target_address = self.check_creation_target(target_chain, target_address, region_hint);
+        self.check_proof_of_work(caller, target_chain, pow_nonce);
+        self.collect_attestation_fee();
+        target_address
+    }
+
+    /// The checks of `check_creation_request` a preview can run too: target
+    /// chain, region hint and target address, returning the address in the
+    /// form it is stored in
+    fn check_creation_target(&self, target_chain: &str, target_address: String, region_hint: Option<&str>) -> String {
+        self.check_chain_and_region(target_chain, region_hint);
+        self.normalize_target_address(target_address)
+    }
+
+    /// Check the attached motes match the attestation fee and forward them to the treasury
+    fn collect_attestation_fee(&self) {
+        let fee = self.get_attestation_fee();
+        if self.env().attached_value() != fee {
+            self.env().revert(VeilError::IncorrectAttestationFee);
+        }
+        if !fee.is_zero() {
+            self.env().transfer_tokens(&self.get_treasury(), &fee);
+        }
+    }
+
+    /// Revert unless `thresholds` strictly increase from bronze to platinum
+    fn check_tier_thresholds(&self, thresholds: &TierThresholds) {
+        let ordered = thresholds.bronze < thresholds.silver
+            && thresholds.silver < thresholds.gold
+            && thresholds.gold < thresholds.platinum;
+        if !ordered {
+            self.env().revert(VeilError::InvalidTierThresholds);
+        }
+    }
+
+    /// Check that `target_chain` and `region_hint` are allowlisted
+    fn check_chain_and_region(&self, target_chain: &str, region_hint: Option<&str>) {
+        if !self.is_chain_allowed(target_chain.to_string()) {
+            self.env().revert(VeilError::ChainNotAllowed);
+        }
+        if let Some(Err(error)) = region_hint.map(check_region_hint) {
+            self.env().revert(error);
+        }
+        if !self.is_region_allowed(region_hint.map(String::from)) {
+            self.env().revert(VeilError::RegionNotAllowed);
+        }
+    }
+
+    /// Check `pow_nonce` against the proof-of-work difficulty, if one is set
+    fn check_proof_of_work(&self, caller: Address, target_chain: &str, pow_nonce: Option<u64>) {
+        let difficulty = self.pow_difficulty.get_or_default();
+        if difficulty > 0 {
+            let solved = pow_nonce.is_some_and(|nonce| {
+                let caller_bytes = caller.to_bytes().unwrap_or_default();
+                let hash = proof_of_work_hash(&caller_bytes, target_chain, nonce);
+                leading_zero_bits(&hash) >= u32::from(difficulty)
+            });
+            if !solved {
+                self.env().revert(VeilError::InsufficientProofOfWork);
+            }
+        }
+    }
+
+    /// Validate a target address, returning it in the form it is stored in
+    fn normalize_target_address(&self, target_address: String) -> String {
+        // `0x` and 20 bytes of hex, whichever storage the record ends up in
+        if parse_evm_address(&target_address).is_none() {
+            self.env().revert(VeilError::InvalidTargetAddress);
+        }
+        if self.get_require_checksum() && !is_eip55_checksummed(&target_address) {
+            self.env().revert(VeilError::InvalidAddressChecksum);
+        }
+
+        // Compressed records keep the address as bytes and render it back in lowercase
+        if self.compressed_storage.get_or_default() {
+            target_address.to_ascii_lowercase()
+        } else {
+            target_address
+        }
+    }
+
+    /// Validate and store `template`, announcing the change
+    fn save_template(&mut self, template: Template) {
+        if Tier::from_u8(template.min_tier).is_none() {
+            self.env().revert(VeilError::InvalidTemplate);
+        }
+        if template.validity_secs < self.get_min_validity_secs() {
+            self.env().revert(VeilError::ValidityTooShort);
+        }
+        if let Err(error) = check_tags(core::slice::from_ref(&template.tag), self.get_max_tags()) {
+            self.env().revert(error);
+        }
+        self.env().emit_event(TemplateUpdated {
+            template_id: template.id,
+            target_chain: template.target_chain.clone(),
+            min_tier: template.min_tier,
+            validity_secs: template.validity_secs,
+            tag: template.tag.clone(),
+            active: template.active,
+        });
+        self.templates.set(&template.id, template);
+    }
+
+    fn add_attestation_type(&mut self, attestation_type: String) {
+        let type_hash = keccak256(attestation_type.as_bytes());
+        self.registered_types.set(&type_hash, true);
+        self.attestation_type_names.set(&type_hash, attestation_type.clone());
+        let mut registered = self.registered_type_list.get_or_default();
+        registered.push(attestation_type);
+        self.registered_type_list.set(registered);
+    }
+
+    fn assert_admin(&self) {
+        if Some(self.env().caller()) != self.admin.get() {
+            self.env().revert(VeilError::NotAdmin);
+        }
+    }
+
+    /// Allow the admin only and record `action` in the audit log
+    fn admin_action(&mut self, action: &str) {
+        self.assert_admin();
+        self.record_admin_action(action, self.env().caller());
+    }
+
+    /// Announce a setting change that has no dedicated event
+    fn config_changed(&self, key: String, old_value: String, new_value: String) {
+        self.env().emit_event(ConfigChanged { key, old_value, new_value, by: self.env().caller() });
+    }
+
+    fn record_admin_action(&mut self, action: &str, actor: Address) {
+        let mut log = self.admin_audit_log.get_or_default();
+        if log.len() >= ADMIN_AUDIT_LOG_LEN {
+            log.remove(0);
+        }
+        log.push((action.to_string(), actor, self.env().get_block_time()));
+        self.admin_audit_log.set(log);
+    }
+
+    fn check_upgrade_lock(&self) {
+        if self.is_upgrade_locked() {
+            self.env().revert(VeilError::UpgradeLocked);
+        }
+    }
+
+    fn is_emergency_admin(&self, caller: Address) -> bool {
+        self.get_emergency_admin() == Some(caller)
+    }
+
+    /// Settings `init` applies once the admin and signer are set
+    fn apply_init_settings(&mut self, initial_lock_secs: Option<u64>, config: InitConfig) {
+        // 7 days default validity
+        let validity_secs = config.validity_secs.unwrap_or(7 * 24 * 60 * 60);
+        if validity_secs < DEFAULT_MIN_VALIDITY_SECS {
+            self.env().revert(VeilError::ValidityTooShort);
+        }
+        self.attestation_validity_secs.set(validity_secs);
+        self.min_validity_secs.set(DEFAULT_MIN_VALIDITY_SECS);
+        if let Some(thresholds) = config.tier_thresholds {
+            self.check_tier_thresholds(&thresholds);
+            self.tier_thresholds.set(thresholds);
+        }
+        if let Some(fee) = config.attestation_fee {
+            self.attestation_fee.set(fee);
+        }
+        self.require_checksum.set(config.require_checksum.unwrap_or_default());
+        self.allowlist_enabled.set(config.allowlist_enabled.unwrap_or_default());
+        let time_unit = config.time_unit.unwrap_or(TIME_UNIT_SECONDS);
+        if time_unit != TIME_UNIT_MILLIS && time_unit != TIME_UNIT_SECONDS {
+            self.env().revert(VeilError::InvalidTimeUnit);
+        }
+        self.time_unit.set(time_unit);
+        self.renewal_grace_secs.set(DEFAULT_RENEWAL_GRACE_SECS);
+        self.max_tags.set(DEFAULT_MAX_TAGS);
+        self.add_attestation_type(DEFAULT_ATTESTATION_TYPE.to_string());
+        self.allow_duplicate_chain_address.set(true);
+        self.emit_v1_events.set(true);
+        let now = self.env().get_block_time();
+        self.deployed_at.set(now);
+        self.upgrade_lock_until.set(now + initial_lock_secs.unwrap_or_default() * 1000);
+        self.storage_version.set(STORAGE_VERSION);
+    }
+
+    /// Allow the admin or the emergency admin, returning the caller
+    fn assert_emergency_caller(&self) -> Address {
+        let caller = self.env().caller();
+        if Some(caller) != self.admin.get() && !self.is_emergency_admin(caller) {
+            self.env().revert(VeilError::NotEmergencyAdmin);
+        }
+        caller
+    }
+
+    /// Install the signing key `key` and its public key
+    #[cfg(not(feature = "no-onchain-signing"))]
+    fn set_signer_key(&mut self, key: [u8; 32]) {
+        let pubkey = derive_public_key(&key)
+            .unwrap_or_else(|| self.env().revert(VeilError::InvalidSignerKey));
+        self.signer_private_key.set(key);
+        self.set_signer_public_key(pubkey);
+    }
+
+    /// Install the external signer's public key `key`
+    #[cfg(feature = "no-onchain-signing")]
+    fn set_signer_key(&mut self, key: [u8; 64]) {
+        if !is_valid_public_key(&key) {
+            self.env().revert(VeilError::InvalidSignerKey);
+        }
+        self.set_signer_public_key(key);
+    }
+
+    /// Start a new key version for `pubkey`, with no signatures yet
+    fn set_signer_public_key(&mut self, pubkey: [u8; 64]) {
+        self.signer_public_key.set(pubkey);
+        self.signer_key_version.set(self.signer_key_version.get_or_default() + 1);
+        self.signer_set_at.set(self.env().get_block_time());
+        self.signatures_produced.set(0);
+    }
+
+    /// Replace the signing key of a running deployment and record the rotation
+    fn rotate_signer_key(&mut self, key: SignerKey) {
+        let old_signer = self.get_signer_address();
+        self.set_signer_key(key);
+        self.env().emit_event(SignerRotated {
+            old_signer,
+            new_signer: self.get_signer_address(),
+            key_version: self.signer_key_version.get_or_default(),
+            by: self.env().caller(),
+        });
+    }
+
+    /// Cache `signature` for `id` under the current key version and count it
+    /// towards the key's usage
+    fn store_signature(&mut self, id: [u8; 32], signature: Bytes) {
+        self.attestation_signatures.set(&id, signature);
+        self.attestation_signature_versions.set(&id, self.signer_key_version.get_or_default());
+        self.signatures_produced.set(self.signatures_produced.get_or_default() + 1);
+        let now = self.env().get_block_time();
+        self.signature_produced_at.set(&id, now);
+        self.last_signature_at.set(now);
+    }
+
+    /// Validity for a new attestation: the configured period, shortened to
+    /// `custom_validity_secs` if given (which must be at least the minimum
+    /// unless `carried` over from a superseded attestation)
+    fn resolve_validity_secs(&self, custom_validity_secs: Option<u64>, carried: bool) -> u64 {
+        let global = self.get_attestation_validity_secs();
+        match custom_validity_secs {
+            None => global,
+            Some(custom) if custom < self.get_min_validity_secs() && !carried => {
+                self.env().revert(VeilError::ValidityTooShort)
+            }
+            Some(custom) => custom.min(global),
+        }
+    }
+
+    fn record_tier_change(&mut self, user: Address, tier: Tier, now: u64, attestation_id: [u8; 32]) {
+        let mut history = self.tier_history.get(&user).unwrap_or_default();
+        if history.last().map(|(last, _, _)| *last) != Some(tier) {
+            history.push((tier, now, attestation_id));
+            self.tier_history.set(&user, history);
+        }
+    }
+
+    /// `user`'s stake, marked `STAKE_SOURCE_CUSTOM` when an admin-set tier
+    /// overrides it, see `read_user_stake`
+    fn query_user_stake(&self, user: Address) -> StakeReading {
+        let reading = self.read_user_stake(user);
+        if self.has_custom_tier(user) {
+            // The stake is still reported, but it did not decide the tier
+            return StakeReading { source: STAKE_SOURCE_CUSTOM, ..reading };
+        }
+        reading
+    }
+
+    /// `user`'s stake: their last live reading while it is younger than
+    /// `get_stake_cache_secs`, else read live from the stake sources
+    fn read_user_stake(&self, user: Address) -> StakeReading {
+        let cache_ms = self.stake_cache_secs.get_or_default().saturating_mul(1000);
+        if let Some((amount, measured_at, epoch)) = self.stake_cache.get(&user) {
+            if self.env().get_block_time() < measured_at.saturating_add(cache_ms) {
+                return StakeReading { amount, source: STAKE_SOURCE_CACHED, epoch };
+            }
+        }
+
+        let (delegated, locked) = self.stake_sources_queried();
+        let delegated = delegated
+            .map(|source| StakeSourceContractRef::new(self.env(), source).get_delegated_stake(user))
+            .unwrap_or_default();
+        let locked = locked
+            .map(|source| LockedStakeSourceContractRef::new(self.env(), source).get_locked(user))
+            .unwrap_or_default();
+        StakeReading { amount: delegated + locked, source: STAKE_SOURCE_LIVE, epoch: self.current_era_id() }
+    }
+
+    /// The delegated and locked stake sources the stake policy reads
+    fn stake_sources_queried(&self) -> (Option<Address>, Option<Address>) {
+        match self.get_stake_policy() {
+            StakePolicy::DelegatedOnly => (self.get_stake_source(), None),
+            StakePolicy::Combined => (self.get_stake_source(), self.get_locked_stake_source()),
+            StakePolicy::LockedOnly => (None, self.get_locked_stake_source()),
+        }
+    }
+
+    /// `user`'s custom tier if the admin set one, else the tier `stake_motes` reaches
+    fn calculate_tier(&self, user: Address, stake_motes: U512) -> Tier {
+        if let Some(tier) = self.custom_tiers.get(&user).flatten() {
+            return tier;
+        }
+        let stake_cspr = stake_motes / U512::from(MOTES_PER_CSPR);
+        let thresholds = self.get_tier_thresholds();
+
+        if stake_cspr >= U512::from(thresholds.platinum) {
+            Tier::Platinum
+        } else if stake_cspr >= U512::from(thresholds.gold) {
+            Tier::Gold
+        } else if stake_cspr >= U512::from(thresholds.silver) {
+            Tier::Silver
+        } else if stake_cspr >= U512::from(thresholds.bronze) {
+            Tier::Bronze
+        } else {
+            Tier::None
+        }
+    }
+
+    /// Era of the current block. Odra gives contracts no access to the era,
+    /// so this is 0 (unknown) until it does; verifiers must then skip the
+    /// finality check rather than treat the attestation as final.
+    fn current_era_id(&self) -> u64 {
+        0
+    }
+
+    fn hash_address(&self, address: Address) -> [u8; 32] {
+        // Get the account hash bytes from the Address
+        // Address in Odra wraps an AccountHash
+        let bytes = address.to_string().into_bytes();
+        keccak256(&bytes)
+    }
+
+    /// `user(20) ++ keccak256(chain) ++ target_address(20)`. The user part is
+    /// the tail of the address hash; the target address is compared as bytes,
+    /// so its case does not matter.
+    fn chain_address_key(&self, user: Address, target_chain: &str, target_address: &str) -> [u8; 72] {
+        let target = parse_evm_address(&target_address.to_ascii_lowercase()).unwrap_or_else(|| {
+            let hash = keccak256(target_address.as_bytes());
+            hash[12..].try_into().expect("20-byte slice")
+        });
+
+        let mut key = [0u8; 72];
+        key[..20].copy_from_slice(&self.hash_address(user)[12..]);
+        key[20..52].copy_from_slice(&keccak256(target_chain.as_bytes()));
+        key[52..].copy_from_slice(&target);
+        key
+    }
+
+    /// `user(20) ++ keccak256(chain)`, with the user part as in `chain_address_key`
+    fn user_chain_key(&self, user: Address, target_chain: &str) -> [u8; 52] {
+        let mut key = [0u8; 52];
+        key[..20].copy_from_slice(&self.hash_address(user)[12..]);
+        key[20..].copy_from_slice(&keccak256(target_chain.as_bytes()));
+        key
+    }
+
+    /// `user(20) ++ nonce(8, big-endian)`, zero-padded to 40 bytes. The user
+    /// part is the tail of the address hash, as in `chain_address_key`.
+    fn nonce_key(&self, user: Address, nonce: u64) -> [u8; 40] {
+        let mut key = [0u8; 40];
+        key[..20].copy_from_slice(&self.hash_address(user)[12..]);
+        key[20..28].copy_from_slice(&nonce.to_be_bytes());
+        key
+    }
+
+    /// Block time (ms) from which a proposed `chain` can be confirmed
+    fn chain_activates_at(&self, chain: &str) -> u64 {
+        self.proposed_chains.get(&chain.to_string()).unwrap_or_default() + CHAIN_PROPOSAL_DELAY_SECS * 1000
+    }
+
+    /// Remove `chain`'s pending proposal, reverting if there is none
+    fn drop_chain_proposal(&mut self, chain: &str) {
+        let mut pending = self.pending_chain_names.get_or_default();
+        let Some(index) = pending.iter().position(|name| name == chain) else {
+            self.env().revert(VeilError::ChainNotProposed);
+        };
+        pending.remove(index);
+        self.pending_chain_names.set(pending);
+        self.proposed_chains.set(&chain.to_string(), 0);
+    }
+
+    /// Payload signed for `attestation`, as sent to EVM
+    fn evm_payload(&self, attestation: &Attestation) -> AttestationPayload {
+        let time_unit = self.attestation_time_units.get(&attestation.id).unwrap_or(TIME_UNIT_MILLIS);
+        AttestationPayload {
+            casper_address_hash: self.hash_address(attestation.casper_address),
+            target_chain: attestation.target_chain.clone(),
+            target_address: attestation.target_address.clone(),
+            stake_amount: attestation.stake_amount,
+            tier: attestation.tier as u8,
+            account_age_days: attestation.account_age_days,
+            created_at: payload_timestamp(attestation.created_at, time_unit),
+            expires_at: payload_timestamp(attestation.expires_at, time_unit),
+            // A committed nonce is signed as zero next to its commitment
+            nonce: if attestation.nonce_commitment.is_some() { 0 } else { attestation.nonce },
+            trusted_verifier: attestation.trusted_verifier,
+            casper_era_id: attestation.casper_era_id,
+            reputation_score: attestation.reputation_score,
+            attestation_type: attestation.attestation_type.clone(),
+            template_id: attestation.template_id,
+            nonce_commitment: attestation.nonce_commitment.unwrap_or_default(),
+            hash_algorithm_version: attestation.hash_algorithm_version,
+            claims_hash: self.claims.claims_hash(attestation.id),
+            region_hint: attestation.region_hint.clone().unwrap_or_default(),
+            stake_source: attestation.stake_source,
+            stake_epoch: attestation.stake_epoch,
+        }
+    }
+
+    /// Hash `data` with `hash_algorithm_version`, see `encoding::hash`
+    fn hash(&self, hash_algorithm_version: u8, data: &[u8]) -> [u8; 32] {
+        encoding::hash(hash_algorithm_version, data)
+            .unwrap_or_else(|| self.env().revert(VeilError::UnsupportedHashAlgorithm))
+    }
 
-        Bytes::from(sig_bytes)
+    /// Sign and cache `attestation`'s current EVM payload, returning the signature
+    #[cfg(not(feature = "no-onchain-signing"))]
+    fn sign_payload(&mut self, attestation: &Attestation) -> Bytes {
+        let encoded = abi_encode_payload(&self.evm_payload(attestation));
+        let signature = self.sign_message(&self.hash(attestation.hash_algorithm_version, &encoded));
+        self.store_signature(attestation.id, signature.clone());
+        signature
+    }
+
+    #[cfg(not(feature = "no-onchain-signing"))]
+    fn sign_message(&self, message_hash: &[u8; 32]) -> Bytes {
+        let private_key = self.signer_private_key.get().expect("Signer not set");
+        Bytes::from(encoding::sign_message(&private_key, message_hash).to_vec())
     }
 }
@@ -0,0 +1,307 @@
+//! Offline attestation verification
+//!
+//! Checks an encoded attestation and its signature the way the VeilVerifier
+//! would, without touching any chain: decodes the payload, recomputes the
+//! attestation ID with the hash it names, applies the EIP-191 prefix, recovers the signer and checks
+//! expiry against a given time, read in the verifier's time unit.
+
+use std::fmt;
+use std::format;
+use std::prelude::v1::*;
+
+use crate::encoding::{
+    abi_decode_payload, attestation_id, eth_signed_message_hash, keccak256, recover_signer, HASH_ALGORITHM_KECCAK256,
+    HASH_ALGORITHM_SHA3_256,
+};
+use crate::types::{
+    motes_to_cspr_string, AttestationPayload, Tier, STAKE_SOURCE_CACHED, STAKE_SOURCE_CUSTOM, STAKE_SOURCE_LIVE,
+    STAKE_SOURCE_ORACLE,
+};
+use crate::veil_attestation::{payload_timestamp, TIME_UNIT_SECONDS};
+
+/// Outcome of verifying one attestation
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerifyReport {
+    /// Decoded payload, or `None` if the bytes are not a valid encoding
+    pub payload: Option<AttestationPayload>,
+    pub attestation_id: [u8; 32],
+    pub eth_signed_hash: [u8; 32],
+    /// Address the signature recovers to, if it is well-formed
+    pub recovered_signer: Option<[u8; 20]>,
+    pub expected_signer: Option<[u8; 20]>,
+    /// Verification time in milliseconds
+    pub now_ms: u64,
+    /// `TIME_UNIT_*` the payload timestamps are read in, as VeilVerifier's `timeUnit`
+    pub time_unit: u8,
+}
+
+impl VerifyReport {
+    /// Every reason the attestation would be rejected; empty if valid
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        match &self.payload {
+            None => problems.push("Payload is not a valid attestation encoding".to_string()),
+            Some(payload) => {
+                if Tier::from_u8(payload.tier).is_none() {
+                    problems.push(format!("Unknown tier {}", payload.tier));
+                }
+                if payload.hash_algorithm_version > HASH_ALGORITHM_SHA3_256 {
+                    problems.push(format!("Unknown hash algorithm {}", payload.hash_algorithm_version));
+                }
+                if self.now() >= payload.expires_at {
+                    problems.push("Attestation expired".to_string());
+                }
+            }
+        }
+
+        match (self.recovered_signer, self.expected_signer) {
+            (None, _) => problems.push("Signature is malformed or does not recover".to_string()),
+            (Some(recovered), Some(expected)) if recovered != expected => problems.push(format!(
+                "Signed by 0x{}, expected 0x{}",
+                hex::encode(recovered),
+                hex::encode(expected)
+            )),
+            _ => {}
+        }
+
+        problems
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.problems().is_empty()
+    }
+
+    /// Verification time in the payload's unit
+    fn now(&self) -> u64 {
+        payload_timestamp(self.now_ms, self.time_unit)
+    }
+
+    fn unit_label(&self) -> &'static str {
+        if self.time_unit == TIME_UNIT_SECONDS {
+            "s"
+        } else {
+            "ms"
+        }
+    }
+}
+
+impl fmt::Display for VerifyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(payload) = &self.payload {
+            let tier = Tier::from_u8(payload.tier)
+                .map(|tier| format!("{:?}", tier))
+                .unwrap_or_else(|| "unknown".to_string());
+            writeln!(f, "  Casper Address Hash: 0x{}", hex::encode(payload.casper_address_hash))?;
+            writeln!(f, "  Target Chain:        {}", payload.target_chain)?;
+            writeln!(f, "  Target Address:      {}", payload.target_address)?;
+            writeln!(f, "  Stake:               {} CSPR", motes_to_cspr_string(payload.stake_amount))?;
+            writeln!(f, "  Tier:                {} ({})", payload.tier, tier)?;
+            writeln!(f, "  Account Age:         {} days", payload.account_age_days)?;
+            let unit = self.unit_label();
+            writeln!(f, "  Created At:          {} {}", payload.created_at, unit)?;
+            writeln!(
+                f,
+                "  Expires At:          {} {} ({})",
+                payload.expires_at,
+                unit,
+                describe_expiry(payload.expires_at, self.now_ms, self.time_unit)
+            )?;
+            writeln!(f, "  Nonce:               {}", payload.nonce)?;
+            if payload.trusted_verifier == [0u8; 20] {
+                writeln!(f, "  Trusted Verifier:    (any)")?;
+            } else {
+                writeln!(f, "  Trusted Verifier:    0x{}", hex::encode(payload.trusted_verifier))?;
+            }
+            if payload.casper_era_id == 0 {
+                writeln!(f, "  Casper Era:          (unknown)")?;
+            } else {
+                writeln!(f, "  Casper Era:          {}", payload.casper_era_id)?;
+            }
+            writeln!(f, "  Reputation Score:    {}", payload.reputation_score)?;
+            writeln!(f, "  Attestation Type:    {}", payload.attestation_type)?;
+            if payload.template_id == 0 {
+                writeln!(f, "  Template:            (none)")?;
+            } else {
+                writeln!(f, "  Template:            {}", payload.template_id)?;
+            }
+            if payload.nonce_commitment != [0u8; 32] {
+                writeln!(f, "  Nonce Commitment:    0x{}", hex::encode(payload.nonce_commitment))?;
+            }
+            match payload.hash_algorithm_version {
+                HASH_ALGORITHM_KECCAK256 => writeln!(f, "  Hash Algorithm:      Keccak256")?,
+                HASH_ALGORITHM_SHA3_256 => writeln!(f, "  Hash Algorithm:      SHA3-256")?,
+                version => writeln!(f, "  Hash Algorithm:      {} (unknown)", version)?,
+            }
+            if payload.claims_hash != [0u8; 32] {
+                writeln!(f, "  Claims Hash:         0x{}", hex::encode(payload.claims_hash))?;
+            }
+            if !payload.region_hint.is_empty() {
+                writeln!(f, "  Region Hint:         {}", payload.region_hint)?;
+            }
+            let stake_source = match payload.stake_source {
+                STAKE_SOURCE_LIVE => "live",
+                STAKE_SOURCE_CACHED => "cached",
+                STAKE_SOURCE_ORACLE => "oracle",
+                STAKE_SOURCE_CUSTOM => "custom",
+                _ => "unknown",
+            };
+            if payload.stake_epoch == 0 {
+                writeln!(f, "  Stake Source:        {} (era unknown)", stake_source)?;
+            } else {
+                writeln!(f, "  Stake Source:        {} (era {})", stake_source, payload.stake_epoch)?;
+            }
+        }
+        writeln!(f, "  Attestation ID:      0x{}", hex::encode(self.attestation_id))?;
+        writeln!(f, "  EIP-191 Hash:        0x{}", hex::encode(self.eth_signed_hash))?;
+        match self.recovered_signer {
+            Some(signer) => writeln!(f, "  Recovered Signer:    0x{}", hex::encode(signer))?,
+            None => writeln!(f, "  Recovered Signer:    (none)")?,
+        }
+        if let Some(expected) = self.expected_signer {
+            writeln!(f, "  Expected Signer:     0x{}", hex::encode(expected))?;
+        }
+
+        let problems = self.problems();
+        if problems.is_empty() {
+            write!(f, "VALID")
+        } else {
+            write!(f, "INVALID: {}", problems.join("; "))
+        }
+    }
+}
+
+fn describe_expiry(expires_at: u64, now_ms: u64, time_unit: u8) -> String {
+    // Compare in milliseconds so both units report whole seconds
+    let expires_at_ms = if time_unit == TIME_UNIT_SECONDS { expires_at.saturating_mul(1000) } else { expires_at };
+    if now_ms >= expires_at_ms {
+        format!("expired {}s ago", (now_ms - expires_at_ms) / 1000)
+    } else {
+        format!("expires in {}s", (expires_at_ms - now_ms) / 1000)
+    }
+}
+
+/// Verify `encoded` + `signature` at time `now_ms`, optionally against an expected signer,
+/// reading payload timestamps in `time_unit` (a `TIME_UNIT_*`)
+pub fn verify_attestation(
+    encoded: &[u8],
+    signature: &[u8],
+    expected_signer: Option<[u8; 20]>,
+    now_ms: u64,
+    time_unit: u8,
+) -> VerifyReport {
+    // Garbage that names no known algorithm still gets an ID to report
+    let attestation_id = attestation_id(encoded).unwrap_or_else(|| keccak256(encoded));
+    let eth_signed_hash = eth_signed_message_hash(&attestation_id);
+
+    VerifyReport {
+        payload: abi_decode_payload(encoded),
+        attestation_id,
+        eth_signed_hash,
+        recovered_signer: recover_signer(&eth_signed_hash, signature),
+        expected_signer,
+        now_ms,
+        time_unit,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::vec;
+    use crate::encoding::abi_encode_payload;
+    use crate::vectors::{generate_vectors, TestVector, DEFAULT_SEED_KEY};
+    use crate::veil_attestation::TIME_UNIT_MILLIS;
+
+    fn decode(hex_field: &str) -> Vec<u8> {
+        hex::decode(hex_field.trim_start_matches("0x")).unwrap()
+    }
+
+    fn signer(vector: &TestVector) -> [u8; 20] {
+        decode(&vector.signer_address).try_into().unwrap()
+    }
+
+    fn first_vector() -> TestVector {
+        generate_vectors(&DEFAULT_SEED_KEY).unwrap().vectors.remove(0)
+    }
+
+    #[test]
+    fn test_valid_vector() {
+        let vector = first_vector();
+        let report = verify_attestation(
+            &decode(&vector.encoded),
+            &decode(&vector.signature),
+            Some(signer(&vector)),
+            vector.created_at,
+            TIME_UNIT_MILLIS,
+        );
+
+        assert_eq!(report.problems(), Vec::<String>::new());
+        assert_eq!(report.recovered_signer, Some(signer(&vector)));
+        let payload = report.payload.unwrap();
+        assert_eq!(payload.target_chain, vector.target_chain);
+        assert_eq!(payload.target_address, vector.target_address);
+        assert_eq!(payload.expires_at, vector.expires_at);
+        assert_eq!(format!("0x{}", hex::encode(report.attestation_id)), vector.attestation_id);
+    }
+
+    #[test]
+    fn test_tampered_payload() {
+        let vector = first_vector();
+        let mut encoded = decode(&vector.encoded);
+        // Bump the stake slot
+        encoded[3 * 32 + 31] ^= 1;
+
+        let signature = decode(&vector.signature);
+        let report = verify_attestation(&encoded, &signature, Some(signer(&vector)), 0, TIME_UNIT_MILLIS);
+
+        assert!(!report.is_valid());
+        assert!(report.payload.is_some());
+        assert_ne!(report.recovered_signer, Some(signer(&vector)));
+    }
+
+    #[test]
+    fn test_wrong_signer() {
+        let vector = first_vector();
+        let other = [0x11u8; 20];
+        let report =
+            verify_attestation(&decode(&vector.encoded), &decode(&vector.signature), Some(other), 0, TIME_UNIT_MILLIS);
+
+        assert_eq!(
+            report.problems(),
+            vec![format!("Signed by {}, expected 0x{}", vector.signer_address, hex::encode(other))]
+        );
+    }
+
+    #[test]
+    fn test_expired_and_garbage_input() {
+        let vector = first_vector();
+        let (encoded, signature) = (decode(&vector.encoded), decode(&vector.signature));
+        let report = verify_attestation(&encoded, &signature, None, vector.expires_at, TIME_UNIT_MILLIS);
+        assert_eq!(report.problems(), vec!["Attestation expired".to_string()]);
+
+        let report = verify_attestation(&[0u8; 10], &[0u8; 65], None, 0, TIME_UNIT_MILLIS);
+        assert_eq!(report.payload, None);
+        assert_eq!(report.recovered_signer, None);
+        assert_eq!(report.problems().len(), 2);
+    }
+
+    #[test]
+    fn test_expiry_in_seconds() {
+        let mut payload = abi_decode_payload(&decode(&first_vector().encoded)).unwrap();
+        // Seconds, as a default deployment signs them
+        payload.created_at = 1_700_000_000;
+        payload.expires_at = payload.created_at + 60;
+        let encoded = abi_encode_payload(&payload);
+
+        let before = verify_attestation(&encoded, &[0u8; 65], None, 1_700_000_059_999, TIME_UNIT_SECONDS);
+        assert!(!before.problems().contains(&"Attestation expired".to_string()));
+        assert!(before.to_string().contains("Expires At:          1700000060 s (expires in 0s)"));
+        let after = verify_attestation(&encoded, &[0u8; 65], None, 1_700_000_060_000, TIME_UNIT_SECONDS);
+        assert!(after.problems().contains(&"Attestation expired".to_string()));
+
+        // Read as milliseconds the same payload expired long ago
+        let as_millis = verify_attestation(&encoded, &[0u8; 65], None, 1_700_000_059_999, TIME_UNIT_MILLIS);
+        assert!(as_millis.problems().contains(&"Attestation expired".to_string()));
+    }
+}
@@ -12,14 +12,18 @@ contract DeployVeilVerifier is Script {
             address(0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266)
         );
 
+        // Must match the Casper contract's time unit: 0 milliseconds, 1 seconds
+        uint8 timeUnit = uint8(vm.envOr("CASPER_TIME_UNIT", uint256(1)));
+
         uint256 deployerPrivateKey = vm.envUint("PRIVATE_KEY");
 
         vm.startBroadcast(deployerPrivateKey);
 
-        VeilVerifier verifier = new VeilVerifier(casperSigner);
+        VeilVerifier verifier = new VeilVerifier(casperSigner, timeUnit);
 
         console.log("VeilVerifier deployed to:", address(verifier));
         console.log("Casper signer address:", casperSigner);
+        console.log("Time unit:", timeUnit);
 
         vm.stopBroadcast();
     }
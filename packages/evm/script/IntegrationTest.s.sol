@@ -50,7 +50,18 @@ contract IntegrationTest is Script {
                 accountAgeDays,
                 createdAt,
                 expiresAt,
-                nonce
+                nonce,
+                bytes20(0),
+                uint64(0),
+                uint64(0),
+                "identity",
+                uint64(0),
+                bytes32(0),
+                uint8(0),
+                bytes32(0),
+                "",
+                uint8(0),
+                uint64(0)
             );
 
             console.log("Attestation encoded, length:", attestation.length);
@@ -0,0 +1,99 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.24;
+
+/// @title Sha3
+/// @notice SHA3-256 as standardized in FIPS 202
+/// @dev The `keccak256` builtin is the original Keccak, which pads differently, so this runs
+///      the Keccak-f[1600] permutation itself. Lane (x, y) of the state is `state[x + 5 * y]`.
+library Sha3 {
+    /// @dev Bytes absorbed per permutation: (1600 - 2 * 256) / 8
+    uint256 private constant RATE = 136;
+
+    function sha3_256(bytes memory data) internal pure returns (bytes32 digest) {
+        uint64[25] memory state;
+        uint256 length = data.length;
+        // Padding always adds at least one byte, so there is always a final block
+        uint256 blocks = length / RATE + 1;
+
+        for (uint256 b = 0; b < blocks; b++) {
+            for (uint256 lane = 0; lane < RATE / 8; lane++) {
+                uint64 word = 0;
+                for (uint256 k = 0; k < 8; k++) {
+                    uint256 i = b * RATE + lane * 8 + k;
+                    uint8 value = 0;
+                    if (i < length) {
+                        value = uint8(data[i]);
+                    } else if (i == length) {
+                        value = 0x06;
+                    }
+                    if (i == blocks * RATE - 1) {
+                        value |= 0x80;
+                    }
+                    word |= uint64(value) << (8 * k);
+                }
+                state[lane] ^= word;
+            }
+            _keccakF(state);
+        }
+
+        // Squeeze the first 32 bytes, lanes little-endian
+        uint256 out = 0;
+        for (uint256 i = 0; i < 32; i++) {
+            uint256 value = uint8(state[i / 8] >> (8 * (i % 8)));
+            out |= value << (8 * (31 - i));
+        }
+        digest = bytes32(out);
+    }
+
+    function _keccakF(uint64[25] memory a) private pure {
+        uint64[24] memory roundConstants = [
+            uint64(0x0000000000000001), 0x0000000000008082, 0x800000000000808A, 0x8000000080008000,
+            0x000000000000808B, 0x0000000080000001, 0x8000000080008081, 0x8000000000008009,
+            0x000000000000008A, 0x0000000000000088, 0x0000000080008009, 0x000000008000000A,
+            0x000000008000808B, 0x800000000000008B, 0x8000000000008089, 0x8000000000008003,
+            0x8000000000008002, 0x8000000000000080, 0x000000000000800A, 0x800000008000000A,
+            0x8000000080008081, 0x8000000000008080, 0x0000000080000001, 0x8000000080008008
+        ];
+        uint8[25] memory rotations = [
+            uint8(0), 1, 62, 28, 27,
+            36, 44, 6, 55, 20,
+            3, 10, 43, 25, 39,
+            41, 45, 15, 21, 8,
+            18, 2, 61, 56, 14
+        ];
+        uint64[5] memory c;
+        uint64[25] memory b;
+
+        for (uint256 round = 0; round < 24; round++) {
+            // Theta
+            for (uint256 x = 0; x < 5; x++) {
+                c[x] = a[x] ^ a[x + 5] ^ a[x + 10] ^ a[x + 15] ^ a[x + 20];
+            }
+            for (uint256 x = 0; x < 5; x++) {
+                uint64 d = c[(x + 4) % 5] ^ _rotl(c[(x + 1) % 5], 1);
+                for (uint256 y = 0; y < 25; y += 5) {
+                    a[x + y] ^= d;
+                }
+            }
+            // Rho and pi
+            for (uint256 x = 0; x < 5; x++) {
+                for (uint256 y = 0; y < 5; y++) {
+                    b[y + 5 * ((2 * x + 3 * y) % 5)] = _rotl(a[x + 5 * y], rotations[x + 5 * y]);
+                }
+            }
+            // Chi
+            for (uint256 x = 0; x < 5; x++) {
+                for (uint256 y = 0; y < 25; y += 5) {
+                    a[x + y] = b[x + y] ^ (~b[(x + 1) % 5 + y] & b[(x + 2) % 5 + y]);
+                }
+            }
+            // Iota
+            a[0] ^= roundConstants[round];
+        }
+    }
+
+    function _rotl(uint64 x, uint256 n) private pure returns (uint64) {
+        if (n == 0) return x;
+        return (x << n) | (x >> (64 - n));
+    }
+}
@@ -4,6 +4,7 @@ pragma solidity ^0.8.24;
 import "@openzeppelin/contracts/utils/cryptography/ECDSA.sol";
 import "@openzeppelin/contracts/utils/cryptography/MessageHashUtils.sol";
 import "@openzeppelin/contracts/access/Ownable.sol";
+import "./Sha3.sol";
 
 /// @title VeilVerifier
 /// @notice Verifies Casper identity attestations on EVM chains
@@ -33,6 +34,28 @@ contract VeilVerifier is Ownable {
         uint64 createdAt;
         uint64 expiresAt;
         uint64 nonce;
+        bytes20 trustedVerifier;
+        /// @dev Casper era at creation, for finality checks; 0 when unknown
+        uint64 casperEraId;
+        /// @dev Holder's Veil reputation score at creation
+        uint64 reputationScore;
+        /// @dev Registered attestation category, e.g. "identity"
+        string attestationType;
+        /// @dev Veil template the attestation was created from; 0 if none
+        uint64 templateId;
+        /// @dev keccak256(abi.encodePacked(nonce, userSecret)), signed with a zero nonce;
+        ///      zero if the nonce is in the clear
+        bytes32 nonceCommitment;
+        /// @dev Hash the attestation ID is taken with, see HASH_KECCAK256 and HASH_SHA3_256
+        uint8 hashAlgorithmVersion;
+        /// @dev Fold of the provider claims attached on Casper, see `checkClaims`; zero if none
+        bytes32 claimsHash;
+        /// @dev Region the holder reported on Casper, e.g. "EU"; self-declared, empty if none
+        string regionHint;
+        /// @dev Where the stake was read from: 0 live, 1 cached, 2 oracle, 3 custom
+        uint8 stakeSource;
+        /// @dev Casper era the stake was measured in; 0 when unknown
+        uint64 stakeEpoch;
     }
 
     /// @notice Verified identity data
@@ -45,6 +68,21 @@ contract VeilVerifier is Ownable {
         uint64 expiresAt;
     }
 
+    /// @notice `hashAlgorithmVersion` of attestations identified by their keccak256
+    uint8 public constant HASH_KECCAK256 = 0;
+
+    /// @notice `hashAlgorithmVersion` of attestations identified by their SHA3-256 (FIPS 202)
+    uint8 public constant HASH_SHA3_256 = 1;
+
+    /// @notice `timeUnit` of a Casper contract that writes payload timestamps in milliseconds
+    uint8 public constant TIME_UNIT_MILLIS = 0;
+
+    /// @notice `timeUnit` of a Casper contract that writes payload timestamps in seconds
+    uint8 public constant TIME_UNIT_SECONDS = 1;
+
+    /// @notice Unit of `createdAt`/`expiresAt` in payloads, matching the Casper contract's time unit
+    uint8 public immutable timeUnit;
+
     /// @notice Casper attestation signer address
     address public casperSigner;
 
@@ -57,6 +95,12 @@ contract VeilVerifier is Ownable {
     /// @notice Revoked attestations
     mapping(bytes32 => bool) public revokedAttestations;
 
+    /// @notice Attestation backing each verified user's identity
+    mapping(address => bytes32) public userAttestations;
+
+    /// @notice Claims hash of the attestation backing each verified user's identity
+    mapping(address => bytes32) public userClaimsHashes;
+
     /// @notice Emitted when identity is verified
     event IdentityVerified(
         address indexed user, bytes32 casperAddressHash, Tier tier, uint256 stake
@@ -73,9 +117,16 @@ contract VeilVerifier is Ownable {
     error AttestationExpired();
     error TargetAddressMismatch();
     error InvalidSignature();
-
-    constructor(address _casperSigner) Ownable(msg.sender) {
+    error UnintendedVerifier();
+    error UnsupportedHashAlgorithm();
+    error UnsupportedTimeUnit();
+
+    /// @param _casperSigner Address the Casper contract signs attestations with
+    /// @param _timeUnit TIME_UNIT_MILLIS or TIME_UNIT_SECONDS, as set on the Casper contract
+    constructor(address _casperSigner, uint8 _timeUnit) Ownable(msg.sender) {
+        if (_timeUnit > TIME_UNIT_SECONDS) revert UnsupportedTimeUnit();
         casperSigner = _casperSigner;
+        timeUnit = _timeUnit;
     }
 
     /// @notice Verify attestation and store identity
@@ -85,27 +136,19 @@ contract VeilVerifier is Ownable {
         external
         returns (bool)
     {
-        AttestationData memory data = _decodeAttestation(attestation);
-        bytes32 attestationId = keccak256(attestation);
-
-        _validateAttestation(attestationId, data.expiresAt);
-        _validateTargetAddress(data.targetAddress);
-        _validateSignature(attestation, signature);
-
-        usedAttestations[attestationId] = true;
-
-        verifiedUsers[msg.sender] = VerifiedIdentity({
-            casperAddressHash: data.casperAddressHash,
-            tier: Tier(data.tier),
-            stake: data.stake,
-            accountAgeDays: data.accountAgeDays,
-            verifiedAt: uint64(block.timestamp),
-            expiresAt: data.expiresAt
-        });
-
-        emit IdentityVerified(msg.sender, data.casperAddressHash, Tier(data.tier), data.stake);
+        return _verifyAndStore(msg.sender, attestation, signature);
+    }
 
-        return true;
+    /// @notice Verify attestation and store identity on behalf of its target address
+    /// @dev Lets relayers submit attestations; the signed target address still has to match `user`
+    /// @param user EVM address the attestation was issued to
+    /// @param attestation ABI-encoded attestation data
+    /// @param signature 65-byte secp256k1 signature (r, s, v)
+    function verifyAndStoreFor(address user, bytes calldata attestation, bytes calldata signature)
+        external
+        returns (bool)
+    {
+        return _verifyAndStore(user, attestation, signature);
     }
 
     /// @notice Verify attestation without storing
@@ -115,14 +158,15 @@ contract VeilVerifier is Ownable {
         returns (bool valid, Tier tier, uint256 stake)
     {
         AttestationData memory data = _decodeAttestation(attestation);
-        bytes32 attestationId = keccak256(attestation);
+        if (data.hashAlgorithmVersion > HASH_SHA3_256) return (false, Tier.None, 0);
+        bytes32 attestationId = _attestationId(attestation, data.hashAlgorithmVersion);
 
         if (usedAttestations[attestationId]) return (false, Tier.None, 0);
         if (revokedAttestations[attestationId]) return (false, Tier.None, 0);
-        if (block.timestamp * 1000 >= data.expiresAt) return (false, Tier.None, 0);
+        if (_now() >= data.expiresAt) return (false, Tier.None, 0);
+        if (!_isIntendedVerifier(data.trustedVerifier)) return (false, Tier.None, 0);
 
-        bytes32 messageHash = keccak256(attestation);
-        bytes32 ethSignedHash = messageHash.toEthSignedMessageHash();
+        bytes32 ethSignedHash = attestationId.toEthSignedMessageHash();
         address recovered = ethSignedHash.recover(signature);
 
         if (recovered != casperSigner) return (false, Tier.None, 0);
@@ -135,7 +179,10 @@ contract VeilVerifier is Ownable {
     /// @notice Get user's tier
     function getTier(address user) external view returns (Tier) {
         VerifiedIdentity memory identity = verifiedUsers[user];
-        if (identity.expiresAt < block.timestamp * 1000) {
+        if (identity.expiresAt < _now()) {
+            return Tier.None;
+        }
+        if (revokedAttestations[userAttestations[user]]) {
             return Tier.None;
         }
         return identity.tier;
@@ -149,7 +196,40 @@ contract VeilVerifier is Ownable {
     /// @notice Check if user is verified
     function isVerified(address user) external view returns (bool) {
         VerifiedIdentity memory identity = verifiedUsers[user];
-        return identity.expiresAt > block.timestamp * 1000;
+        return identity.expiresAt > _now()
+            && !revokedAttestations[userAttestations[user]];
+    }
+
+    /// @notice ID of an attestation, as used by `usedAttestations` and `revokeAttestation`
+    function getAttestationId(bytes calldata attestation) external pure returns (bytes32) {
+        return _attestationId(attestation, _decodeAttestation(attestation).hashAlgorithmVersion);
+    }
+
+    /// @notice Check that an attestation commits to `nonce` under `userSecret`
+    function checkNonceCommitment(bytes calldata attestation, uint64 nonce, bytes32 userSecret)
+        external
+        pure
+        returns (bool)
+    {
+        bytes32 commitment = _decodeAttestation(attestation).nonceCommitment;
+        return commitment != bytes32(0)
+            && commitment == keccak256(abi.encodePacked(nonce, userSecret));
+    }
+
+    /// @notice Check that an attestation's claims hash folds exactly `claimDigests`, in order
+    /// @dev Each digest is keccak256(abi.encodePacked(casperAttestationId, uint8 claimType,
+    ///      claimData)), as signed by the claim provider; the fold starts from zero and takes
+    ///      keccak256(abi.encodePacked(claimsHash, digest)) per claim
+    function checkClaims(bytes calldata attestation, bytes32[] calldata claimDigests)
+        external
+        pure
+        returns (bool)
+    {
+        bytes32 claimsHash = bytes32(0);
+        for (uint256 i = 0; i < claimDigests.length; i++) {
+            claimsHash = keccak256(abi.encodePacked(claimsHash, claimDigests[i]));
+        }
+        return claimsHash == _decodeAttestation(attestation).claimsHash;
     }
 
     /// @notice Get full verified identity
@@ -187,9 +267,42 @@ contract VeilVerifier is Ownable {
             uint64 accountAgeDays,
             uint64 createdAt,
             uint64 expiresAt,
-            uint64 nonce
+            uint64 nonce,
+            bytes20 trustedVerifier,
+            uint64 casperEraId,
+            uint64 reputationScore,
+            string memory attestationType,
+            uint64 templateId,
+            bytes32 nonceCommitment,
+            uint8 hashAlgorithmVersion,
+            bytes32 claimsHash,
+            string memory regionHint,
+            uint8 stakeSource,
+            uint64 stakeEpoch
         ) = abi.decode(
-            attestation, (bytes32, string, string, uint256, uint8, uint64, uint64, uint64, uint64)
+            attestation,
+            (
+                bytes32,
+                string,
+                string,
+                uint256,
+                uint8,
+                uint64,
+                uint64,
+                uint64,
+                uint64,
+                bytes20,
+                uint64,
+                uint64,
+                string,
+                uint64,
+                bytes32,
+                uint8,
+                bytes32,
+                string,
+                uint8,
+                uint64
+            )
         );
 
         return AttestationData({
@@ -201,33 +314,105 @@ contract VeilVerifier is Ownable {
             accountAgeDays: accountAgeDays,
             createdAt: createdAt,
             expiresAt: expiresAt,
-            nonce: nonce
+            nonce: nonce,
+            trustedVerifier: trustedVerifier,
+            casperEraId: casperEraId,
+            reputationScore: reputationScore,
+            attestationType: attestationType,
+            templateId: templateId,
+            nonceCommitment: nonceCommitment,
+            hashAlgorithmVersion: hashAlgorithmVersion,
+            claimsHash: claimsHash,
+            regionHint: regionHint,
+            stakeSource: stakeSource,
+            stakeEpoch: stakeEpoch
         });
     }
 
+    /// @dev Current time in the unit payload timestamps are written in
+    function _now() internal view returns (uint256) {
+        return timeUnit == TIME_UNIT_SECONDS ? block.timestamp : block.timestamp * 1000;
+    }
+
     function _validateAttestation(bytes32 attestationId, uint64 expiresAt) internal view {
         if (usedAttestations[attestationId]) revert AttestationAlreadyUsed();
         if (revokedAttestations[attestationId]) revert AttestationIsRevoked();
-        if (block.timestamp * 1000 >= expiresAt) revert AttestationExpired();
+        if (_now() >= expiresAt) revert AttestationExpired();
     }
 
-    function _validateTargetAddress(string memory targetAddress) internal view {
-        if (!_compareStrings(targetAddress, _addressToString(msg.sender))) {
+    function _verifyAndStore(address user, bytes calldata attestation, bytes calldata signature)
+        internal
+        returns (bool)
+    {
+        AttestationData memory data = _decodeAttestation(attestation);
+        bytes32 attestationId = _attestationId(attestation, data.hashAlgorithmVersion);
+
+        _validateAttestation(attestationId, data.expiresAt);
+        _validateTargetAddress(data.targetAddress, user);
+        if (!_isIntendedVerifier(data.trustedVerifier)) revert UnintendedVerifier();
+        _validateSignature(attestationId, signature);
+
+        usedAttestations[attestationId] = true;
+        userAttestations[user] = attestationId;
+        userClaimsHashes[user] = data.claimsHash;
+
+        verifiedUsers[user] = VerifiedIdentity({
+            casperAddressHash: data.casperAddressHash,
+            tier: Tier(data.tier),
+            stake: data.stake,
+            accountAgeDays: data.accountAgeDays,
+            verifiedAt: uint64(block.timestamp),
+            expiresAt: data.expiresAt
+        });
+
+        emit IdentityVerified(user, data.casperAddressHash, Tier(data.tier), data.stake);
+
+        return true;
+    }
+
+    /// @dev Casper may issue EIP-55 checksummed addresses, so the case of the hex digits is ignored
+    function _validateTargetAddress(string memory targetAddress, address user) internal pure {
+        if (!_compareStrings(_toLowerCase(targetAddress), _addressToString(user))) {
             revert TargetAddressMismatch();
         }
     }
 
-    function _validateSignature(bytes calldata attestation, bytes calldata signature) internal view {
-        bytes32 messageHash = keccak256(attestation);
-        bytes32 ethSignedHash = messageHash.toEthSignedMessageHash();
+    function _validateSignature(bytes32 attestationId, bytes calldata signature) internal view {
+        bytes32 ethSignedHash = attestationId.toEthSignedMessageHash();
         address recovered = ethSignedHash.recover(signature);
         if (recovered != casperSigner) revert InvalidSignature();
     }
 
+    /// @dev The attestation ID is the hash of the encoding under the algorithm it names
+    function _attestationId(bytes calldata attestation, uint8 hashAlgorithmVersion)
+        internal
+        pure
+        returns (bytes32)
+    {
+        if (hashAlgorithmVersion == HASH_KECCAK256) return keccak256(attestation);
+        if (hashAlgorithmVersion == HASH_SHA3_256) return Sha3.sha3_256(attestation);
+        revert UnsupportedHashAlgorithm();
+    }
+
+    /// @dev A zero verifier means the attestation may be redeemed on any verifier
+    function _isIntendedVerifier(bytes20 trustedVerifier) internal view returns (bool) {
+        return trustedVerifier == bytes20(0) || trustedVerifier == bytes20(address(this));
+    }
+
     function _compareStrings(string memory a, string memory b) internal pure returns (bool) {
         return keccak256(bytes(a)) == keccak256(bytes(b));
     }
 
+    function _toLowerCase(string memory value) internal pure returns (string memory) {
+        bytes memory raw = bytes(value);
+        bytes memory result = new bytes(raw.length);
+        for (uint256 i = 0; i < raw.length; i++) {
+            bytes1 c = raw[i];
+            result[i] = c >= "A" && c <= "Z" ? bytes1(uint8(c) + 32) : c;
+        }
+        return string(result);
+    }
+
     function _addressToString(address addr) internal pure returns (string memory) {
         bytes memory alphabet = "0123456789abcdef";
         bytes memory data = abi.encodePacked(addr);
@@ -0,0 +1,38 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.24;
+
+import "forge-std/Test.sol";
+import "../src/Sha3.sol";
+
+/// @notice Known-answer tests for SHA3-256, digests from Python's hashlib.sha3_256
+contract Sha3Test is Test {
+    function test_sha3_256_empty() public pure {
+        assertEq(
+            Sha3.sha3_256(""),
+            0xa7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a
+        );
+    }
+
+    function test_sha3_256_abc() public pure {
+        assertEq(
+            Sha3.sha3_256("abc"),
+            0x3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532
+        );
+    }
+
+    function test_sha3_256_multipleBlocks() public pure {
+        // 200 bytes span two 136-byte blocks
+        bytes memory data = new bytes(200);
+        for (uint256 i = 0; i < data.length; i++) {
+            data[i] = bytes1(uint8(i));
+        }
+        assertEq(
+            Sha3.sha3_256(data),
+            0x5f728f63bf5ee48c77f453c0490398fa645b8d4c4e56be9a41cfec344d6ca899
+        );
+    }
+
+    function test_sha3_256_differsFromKeccak(bytes memory data) public pure {
+        assertNotEq(Sha3.sha3_256(data), keccak256(data));
+    }
+}
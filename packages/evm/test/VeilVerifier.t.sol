@@ -14,13 +14,14 @@ contract VeilVerifierTest is Test {
     address user = address(0x1234);
 
     function setUp() public {
-        verifier = new VeilVerifier(SIGNER_ADDRESS);
+        verifier = new VeilVerifier(SIGNER_ADDRESS, 0);
     }
 
     // ============ UNIT TESTS ============
 
     function test_constructor_setsSigner() public view {
         assertEq(verifier.casperSigner(), SIGNER_ADDRESS);
+        assertEq(verifier.timeUnit(), verifier.TIME_UNIT_MILLIS());
     }
 
     function test_unverifiedUser_returnsNone() public view {
@@ -52,7 +53,18 @@ contract VeilVerifierTest is Test {
             accountAgeDays,
             createdAt,
             expiresAt,
-            nonce
+            nonce,
+            bytes20(0),
+            uint64(0),
+            uint64(0),
+            "identity",
+            uint64(0),
+            bytes32(0),
+            uint8(0),
+            bytes32(0),
+            "",
+            uint8(0),
+            uint64(0)
         );
 
         // Sign with Ethereum personal_sign
@@ -86,7 +98,15 @@ contract VeilVerifierTest is Test {
 
         bytes memory attestation = abi.encode(
             casperAddressHash, targetChain, targetAddress,
-            stake, tier, accountAgeDays, createdAt, expiresAt, nonce
+            stake, tier, accountAgeDays, createdAt, expiresAt, nonce, bytes20(0), uint64(0), uint64(0),
+            "identity",
+            uint64(0),
+            bytes32(0),
+            uint8(0),
+            bytes32(0),
+            "",
+            uint8(0),
+            uint64(0)
         );
 
         bytes32 messageHash = keccak256(attestation);
@@ -103,14 +123,34 @@ contract VeilVerifierTest is Test {
         verifier.verifyAndStore(attestation, signature);
     }
 
+    function test_verifyAndStoreFor_relayerSubmitsForUser() public {
+        (bytes memory attestation, bytes memory signature) = _signAttestation(user, 1000 * 1e9, 2);
+
+        vm.prank(address(0x9999));
+        verifier.verifyAndStoreFor(user, attestation, signature);
+
+        assertTrue(verifier.isVerified(user));
+        assertEq(uint(verifier.getTier(user)), 2);
+        assertFalse(verifier.isVerified(address(0x9999)));
+    }
+
+    function test_verifyAndStoreFor_rejectsOtherUser() public {
+        (bytes memory attestation, bytes memory signature) = _signAttestation(user, 1000 * 1e9, 2);
+
+        vm.expectRevert(VeilVerifier.TargetAddressMismatch.selector);
+        verifier.verifyAndStoreFor(address(0x9999), attestation, signature);
+    }
+
     function test_verifyAndStore_rejectsInvalidSignature() public {
         bytes32 casperAddressHash = keccak256("casper-account-hash");
         string memory targetAddress = _addressToString(user);
         uint64 expiresAt = uint64((block.timestamp + 7 days) * 1000);
 
-        bytes memory attestation = abi.encode(
+        attestation = abi.encode(
             casperAddressHash, "base-sepolia", targetAddress,
-            1000 * 1e9, uint8(2), uint64(0), uint64(block.timestamp * 1000), expiresAt, uint64(0)
+            1000 * 1e9, uint8(2), uint64(0), uint64(block.timestamp * 1000), expiresAt, uint64(0), bytes20(0),
+            uint64(0), uint64(0), "identity", uint64(0), bytes32(0), uint8(0), bytes32(0), "",
+            uint8(0), uint64(0)
         );
 
         // Sign with wrong key
@@ -133,9 +173,17 @@ contract VeilVerifierTest is Test {
         uint64 createdAt = uint64(block.timestamp * 1000);
         uint64 expiresAt = uint64((block.timestamp - 1) * 1000); // Already expired
 
-        bytes memory attestation = abi.encode(
+        attestation = abi.encode(
             casperAddressHash, "base-sepolia", targetAddress,
-            1000 * 1e9, uint8(2), uint64(0), createdAt, expiresAt, uint64(0)
+            1000 * 1e9, uint8(2), uint64(0), createdAt, expiresAt, uint64(0), bytes20(0), uint64(0), uint64(0),
+            "identity",
+            uint64(0),
+            bytes32(0),
+            uint8(0),
+            bytes32(0),
+            "",
+            uint8(0),
+            uint64(0)
         );
 
         bytes32 messageHash = keccak256(attestation);
@@ -155,9 +203,11 @@ contract VeilVerifierTest is Test {
         string memory targetAddress = _addressToString(user);
         uint64 expiresAt = uint64((block.timestamp + 7 days) * 1000);
 
-        bytes memory attestation = abi.encode(
+        attestation = abi.encode(
             casperAddressHash, "base-sepolia", targetAddress,
-            1000 * 1e9, uint8(2), uint64(0), uint64(block.timestamp * 1000), expiresAt, uint64(0)
+            1000 * 1e9, uint8(2), uint64(0), uint64(block.timestamp * 1000), expiresAt, uint64(0), bytes20(0),
+            uint64(0), uint64(0), "identity", uint64(0), bytes32(0), uint8(0), bytes32(0), "",
+            uint8(0), uint64(0)
         );
 
         bytes32 messageHash = keccak256(attestation);
@@ -182,9 +232,11 @@ contract VeilVerifierTest is Test {
         string memory targetAddress = _addressToString(user);
         uint64 expiresAt = uint64((block.timestamp + 7 days) * 1000);
 
-        bytes memory attestation = abi.encode(
+        attestation = abi.encode(
             casperAddressHash, "base-sepolia", targetAddress,
-            10000 * 1e9, uint8(3), uint64(0), uint64(block.timestamp * 1000), expiresAt, uint64(0)
+            10000 * 1e9, uint8(3), uint64(0), uint64(block.timestamp * 1000), expiresAt, uint64(0), bytes20(0),
+            uint64(0), uint64(0), "identity", uint64(0), bytes32(0), uint8(0), bytes32(0), "",
+            uint8(0), uint64(0)
         );
 
         bytes32 messageHash = keccak256(attestation);
@@ -201,6 +253,130 @@ contract VeilVerifierTest is Test {
         assertEq(stake, 10000 * 1e9);
     }
 
+    function test_verifyAndStore_acceptsChecksummedTargetAddress() public {
+        // EIP-55 rendering, as issued by Casper when `require_checksum` is on
+        address checksummedUser = 0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266;
+        bytes memory attestation = abi.encode(
+            keccak256(abi.encodePacked(checksummedUser)), "base-sepolia",
+            "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266",
+            1000 * 1e9, uint8(2), uint64(0), uint64(block.timestamp * 1000),
+            uint64((block.timestamp + 7 days) * 1000), uint64(0), bytes20(0),
+            uint64(0), uint64(0), "identity", uint64(0), bytes32(0), uint8(0), bytes32(0), "",
+            uint8(0), uint64(0)
+        );
+
+        vm.prank(checksummedUser);
+        assertTrue(verifier.verifyAndStore(attestation, _signId(keccak256(attestation))));
+        assertTrue(verifier.isVerified(checksummedUser));
+    }
+
+    // ============ TRUSTED VERIFIER TESTS ============
+
+    function test_verifyAndStore_acceptsNamedVerifier() public {
+        (bytes memory attestation, bytes memory signature) =
+            _signAttestationFor(user, 1000 * 1e9, 2, bytes20(address(verifier)));
+
+        vm.prank(user);
+        assertTrue(verifier.verifyAndStore(attestation, signature));
+        assertTrue(verifier.isVerified(user));
+    }
+
+    function test_verifyAndStore_rejectsOtherVerifier() public {
+        (bytes memory attestation, bytes memory signature) =
+            _signAttestationFor(user, 1000 * 1e9, 2, bytes20(address(0xBEEF)));
+
+        (bool valid,,) = verifier.verify(attestation, signature);
+        assertFalse(valid);
+
+        vm.prank(user);
+        vm.expectRevert(VeilVerifier.UnintendedVerifier.selector);
+        verifier.verifyAndStore(attestation, signature);
+    }
+
+    function test_checkNonceCommitment() public view {
+        bytes32 userSecret = keccak256("user-secret");
+        bytes32 commitment = keccak256(abi.encodePacked(uint64(7), userSecret));
+        bytes memory attestation = abi.encode(
+            keccak256(abi.encodePacked(user)), "base-sepolia", _addressToString(user),
+            1000 * 1e9, uint8(2), uint64(0), uint64(block.timestamp * 1000), uint64(0), uint64(0), bytes20(0),
+            uint64(0), uint64(0), "identity", uint64(0), commitment, uint8(0), bytes32(0), "",
+            uint8(0), uint64(0)
+        );
+
+        assertTrue(verifier.checkNonceCommitment(attestation, 7, userSecret));
+        assertFalse(verifier.checkNonceCommitment(attestation, 8, userSecret));
+        assertFalse(verifier.checkNonceCommitment(attestation, 7, keccak256("other-secret")));
+
+        // Attestations with the nonce in the clear commit to nothing
+        (bytes memory clear,) = _signAttestationFor(user, 1000 * 1e9, 2, bytes20(0));
+        assertFalse(verifier.checkNonceCommitment(clear, 0, bytes32(0)));
+    }
+
+    // ============ CLAIM TESTS ============
+
+    function test_checkClaims_andStoresClaimsHash() public {
+        bytes32 casperId = keccak256("casper-attestation");
+        bytes32[] memory digests = new bytes32[](2);
+        digests[0] = keccak256(abi.encodePacked(casperId, uint8(2), "kyc-level-2"));
+        digests[1] = keccak256(abi.encodePacked(casperId, uint8(7), hex"00"));
+        bytes32 claimsHash = keccak256(abi.encodePacked(bytes32(0), digests[0]));
+        claimsHash = keccak256(abi.encodePacked(claimsHash, digests[1]));
+
+        bytes memory attestation = abi.encode(
+            keccak256(abi.encodePacked(user)), "base-sepolia", _addressToString(user),
+            1000 * 1e9, uint8(2), uint64(0), uint64(block.timestamp * 1000),
+            uint64((block.timestamp + 7 days) * 1000), uint64(0), bytes20(0),
+            uint64(0), uint64(0), "identity", uint64(0), bytes32(0), uint8(0), claimsHash, "",
+            uint8(0), uint64(0)
+        );
+        assertTrue(verifier.checkClaims(attestation, digests));
+
+        // Order and completeness both matter
+        bytes32[] memory reversed = new bytes32[](2);
+        reversed[0] = digests[1];
+        reversed[1] = digests[0];
+        assertFalse(verifier.checkClaims(attestation, reversed));
+        assertFalse(verifier.checkClaims(attestation, new bytes32[](0)));
+
+        vm.prank(user);
+        verifier.verifyAndStore(attestation, _signId(keccak256(attestation)));
+        assertEq(verifier.userClaimsHashes(user), claimsHash);
+    }
+
+    // ============ HASH ALGORITHM TESTS ============
+
+    function test_verifyAndStore_acceptsSha3Attestation() public {
+        bytes memory attestation = _encodeWithHashAlgorithm(user, verifier.HASH_SHA3_256());
+        bytes32 attestationId = Sha3.sha3_256(attestation);
+        assertEq(verifier.getAttestationId(attestation), attestationId);
+
+        // A signature over the keccak256 of a SHA3-256 attestation does not verify
+        bytes memory keccakSignature = _signId(keccak256(attestation));
+        (bool valid,,) = verifier.verify(attestation, keccakSignature);
+        assertFalse(valid);
+
+        bytes memory signature = _signId(attestationId);
+        (valid,,) = verifier.verify(attestation, signature);
+        assertTrue(valid);
+
+        vm.prank(user);
+        assertTrue(verifier.verifyAndStore(attestation, signature));
+        assertTrue(verifier.usedAttestations(attestationId));
+        assertEq(verifier.userAttestations(user), attestationId);
+    }
+
+    function test_verifyAndStore_rejectsUnknownHashAlgorithm() public {
+        bytes memory attestation = _encodeWithHashAlgorithm(user, 2);
+        bytes memory signature = _signId(keccak256(attestation));
+
+        (bool valid,,) = verifier.verify(attestation, signature);
+        assertFalse(valid);
+
+        vm.prank(user);
+        vm.expectRevert(VeilVerifier.UnsupportedHashAlgorithm.selector);
+        verifier.verifyAndStore(attestation, signature);
+    }
+
     // ============ ADMIN TESTS ============
 
     function test_updateSigner_onlyOwner() public {
@@ -229,6 +405,18 @@ contract VeilVerifierTest is Test {
         verifier.revokeAttestation(keccak256("other"));
     }
 
+    function test_revokeAttestation_unverifiesUser() public {
+        bytes memory attestation = _createAndVerifyAttestation(user, 1000 * 1e9, 2);
+        bytes32 attestationId = keccak256(attestation);
+        assertEq(verifier.userAttestations(user), attestationId);
+        assertTrue(verifier.isVerified(user));
+
+        verifier.revokeAttestation(attestationId);
+
+        assertFalse(verifier.isVerified(user));
+        assertEq(uint(verifier.getTier(user)), uint(VeilVerifier.Tier.None));
+    }
+
     // ============ TIER TESTS ============
 
     function test_allTiers() public {
@@ -247,16 +435,130 @@ contract VeilVerifierTest is Test {
         }
     }
 
+    // ============ TIME UNIT TESTS ============
+
+    function test_constructor_rejectsUnknownTimeUnit() public {
+        vm.expectRevert(VeilVerifier.UnsupportedTimeUnit.selector);
+        new VeilVerifier(SIGNER_ADDRESS, 2);
+    }
+
+    function test_expiry_inSeconds() public {
+        VeilVerifier secondsVerifier = new VeilVerifier(SIGNER_ADDRESS, 1);
+        bytes memory attestation = _encodeExpiringAt(
+            user, uint64(block.timestamp), uint64(block.timestamp + 1 hours)
+        );
+        bytes memory signature = _signId(keccak256(attestation));
+
+        (bool valid,,) = secondsVerifier.verify(attestation, signature);
+        assertTrue(valid);
+        vm.prank(user);
+        assertTrue(secondsVerifier.verifyAndStore(attestation, signature));
+        assertTrue(secondsVerifier.isVerified(user));
+
+        vm.warp(block.timestamp + 1 hours);
+        assertFalse(secondsVerifier.isVerified(user));
+        assertEq(uint256(secondsVerifier.getTier(user)), uint256(VeilVerifier.Tier.None));
+    }
+
+    function test_expiry_inMilliseconds() public {
+        bytes memory attestation = _encodeExpiringAt(
+            user, uint64(block.timestamp * 1000), uint64((block.timestamp + 1 hours) * 1000)
+        );
+        bytes memory signature = _signId(keccak256(attestation));
+
+        (bool valid,,) = verifier.verify(attestation, signature);
+        assertTrue(valid);
+        vm.prank(user);
+        assertTrue(verifier.verifyAndStore(attestation, signature));
+        assertTrue(verifier.isVerified(user));
+
+        vm.warp(block.timestamp + 1 hours);
+        assertFalse(verifier.isVerified(user));
+        assertEq(uint256(verifier.getTier(user)), uint256(VeilVerifier.Tier.None));
+    }
+
+    function test_expiry_secondsVerifierRejectsExpiredSecondsPayload() public {
+        VeilVerifier secondsVerifier = new VeilVerifier(SIGNER_ADDRESS, 1);
+        vm.warp(1_000_000);
+        bytes memory attestation = _encodeExpiringAt(
+            user, uint64(block.timestamp - 2 hours), uint64(block.timestamp - 1 hours)
+        );
+
+        vm.prank(user);
+        vm.expectRevert(VeilVerifier.AttestationExpired.selector);
+        secondsVerifier.verifyAndStore(attestation, _signId(keccak256(attestation)));
+    }
+
     // ============ HELPERS ============
 
-    function _createAndVerifyAttestation(address targetUser, uint256 stake, uint8 tier) internal {
+    function _createAndVerifyAttestation(address targetUser, uint256 stake, uint8 tier)
+        internal
+        returns (bytes memory attestation)
+    {
+        bytes memory signature;
+        (attestation, signature) = _signAttestation(targetUser, stake, tier);
+
+        vm.prank(targetUser);
+        verifier.verifyAndStore(attestation, signature);
+    }
+
+    function _signAttestation(address targetUser, uint256 stake, uint8 tier)
+        internal
+        view
+        returns (bytes memory attestation, bytes memory signature)
+    {
+        return _signAttestationFor(targetUser, stake, tier, bytes20(0));
+    }
+
+    function _encodeWithHashAlgorithm(address targetUser, uint8 hashAlgorithmVersion)
+        internal
+        view
+        returns (bytes memory)
+    {
+        return abi.encode(
+            keccak256(abi.encodePacked(targetUser)), "base-sepolia", _addressToString(targetUser),
+            1000 * 1e9, uint8(2), uint64(0), uint64(block.timestamp * 1000),
+            uint64((block.timestamp + 7 days) * 1000), uint64(0), bytes20(0),
+            uint64(0), uint64(0), "identity", uint64(0), bytes32(0), hashAlgorithmVersion, bytes32(0), "",
+            uint8(0), uint64(0)
+        );
+    }
+
+    function _encodeExpiringAt(address targetUser, uint64 createdAt, uint64 expiresAt)
+        internal
+        pure
+        returns (bytes memory)
+    {
+        return abi.encode(
+            keccak256(abi.encodePacked(targetUser)), "base-sepolia", _addressToString(targetUser),
+            1000 * 1e9, uint8(2), uint64(0), createdAt, expiresAt, uint64(0), bytes20(0),
+            uint64(0), uint64(0), "identity", uint64(0), bytes32(0), uint8(0), bytes32(0), "",
+            uint8(0), uint64(0)
+        );
+    }
+
+    function _signId(bytes32 attestationId) internal pure returns (bytes memory) {
+        bytes32 ethSignedHash = keccak256(
+            abi.encodePacked("\x19Ethereum Signed Message:\n32", attestationId)
+        );
+        (uint8 v, bytes32 r, bytes32 s) = vm.sign(SIGNER_PRIVATE_KEY, ethSignedHash);
+        return abi.encodePacked(r, s, v);
+    }
+
+    function _signAttestationFor(address targetUser, uint256 stake, uint8 tier, bytes20 trustedVerifier)
+        internal
+        view
+        returns (bytes memory attestation, bytes memory signature)
+    {
         bytes32 casperAddressHash = keccak256(abi.encodePacked(targetUser));
         string memory targetAddress = _addressToString(targetUser);
         uint64 expiresAt = uint64((block.timestamp + 7 days) * 1000);
 
-        bytes memory attestation = abi.encode(
+        attestation = abi.encode(
             casperAddressHash, "base-sepolia", targetAddress,
-            stake, tier, uint64(0), uint64(block.timestamp * 1000), expiresAt, uint64(0)
+            stake, tier, uint64(0), uint64(block.timestamp * 1000), expiresAt, uint64(0), trustedVerifier,
+            uint64(0), uint64(0), "identity", uint64(0), bytes32(0), uint8(0), bytes32(0), "",
+            uint8(0), uint64(0)
         );
 
         bytes32 messageHash = keccak256(attestation);
@@ -264,10 +566,7 @@ contract VeilVerifierTest is Test {
             abi.encodePacked("\x19Ethereum Signed Message:\n32", messageHash)
         );
         (uint8 v, bytes32 r, bytes32 s) = vm.sign(SIGNER_PRIVATE_KEY, ethSignedHash);
-        bytes memory signature = abi.encodePacked(r, s, v);
-
-        vm.prank(targetUser);
-        verifier.verifyAndStore(attestation, signature);
+        signature = abi.encodePacked(r, s, v);
     }
 
     function _addressToString(address addr) internal pure returns (string memory) {
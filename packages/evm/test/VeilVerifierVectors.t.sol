@@ -0,0 +1,75 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.24;
+
+import "forge-std/Test.sol";
+import "@openzeppelin/contracts/utils/cryptography/ECDSA.sol";
+import "@openzeppelin/contracts/utils/cryptography/MessageHashUtils.sol";
+import "../src/VeilVerifier.sol";
+
+/// @notice Checks the verifier against attestations produced by the Casper contract's own
+/// encoder and signer. Regenerate the fixture with `cargo run --bin veil_vectors` in packages/casper.
+contract VeilVerifierVectorsTest is Test {
+    VeilVerifier public verifier;
+
+    string json;
+    address signer;
+    uint256 count;
+
+    function setUp() public {
+        json = vm.readFile(string.concat(vm.projectRoot(), "/test/fixtures/attestation_vectors.json"));
+        signer = vm.parseJsonAddress(json, ".signer_address");
+        count = vm.parseJsonUint(json, ".count");
+        verifier = new VeilVerifier(signer, 0);
+    }
+
+    function test_vectors_hashesAndSignaturesMatch() public view {
+        assertGt(count, 0);
+        for (uint256 i = 0; i < count; i++) {
+            string memory path = _vector(i);
+            bytes memory encoded = vm.parseJsonBytes(json, string.concat(path, ".encoded"));
+            bytes32 attestationId = vm.parseJsonBytes32(json, string.concat(path, ".attestation_id"));
+            bytes32 ethSignedHash = vm.parseJsonBytes32(json, string.concat(path, ".eth_signed_hash"));
+            bytes memory signature = vm.parseJsonBytes(json, string.concat(path, ".signature"));
+
+            assertEq(keccak256(encoded), attestationId, path);
+            assertEq(MessageHashUtils.toEthSignedMessageHash(attestationId), ethSignedHash, path);
+            assertEq(ECDSA.recover(ethSignedHash, signature), signer, path);
+        }
+    }
+
+    function test_vectors_decodeAndVerify() public view {
+        for (uint256 i = 0; i < count; i++) {
+            string memory path = _vector(i);
+            bytes memory encoded = vm.parseJsonBytes(json, string.concat(path, ".encoded"));
+            bytes memory signature = vm.parseJsonBytes(json, string.concat(path, ".signature"));
+
+            (bool valid, VeilVerifier.Tier tier, uint256 stake) = verifier.verify(encoded, signature);
+
+            assertTrue(valid, path);
+            assertEq(uint256(tier), vm.parseJsonUint(json, string.concat(path, ".tier")), path);
+            assertEq(stake, vm.parseJsonUint(json, string.concat(path, ".stake_amount")), path);
+        }
+    }
+
+    function test_vectors_verifyAndStoreForTarget() public {
+        for (uint256 i = 0; i < count; i++) {
+            string memory path = _vector(i);
+            bytes memory encoded = vm.parseJsonBytes(json, string.concat(path, ".encoded"));
+            bytes memory signature = vm.parseJsonBytes(json, string.concat(path, ".signature"));
+            address target = vm.parseJsonAddress(json, string.concat(path, ".target_address"));
+
+            vm.prank(target);
+            verifier.verifyAndStore(encoded, signature);
+
+            assertEq(
+                uint256(verifier.getTier(target)),
+                vm.parseJsonUint(json, string.concat(path, ".tier")),
+                path
+            );
+        }
+    }
+
+    function _vector(uint256 i) internal pure returns (string memory) {
+        return string.concat(".vectors[", vm.toString(i), "]");
+    }
+}